@@ -0,0 +1,81 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use cross_path::security::PathSecurityChecker;
+use cross_path::{CrossPath, PathConfig, PathConverter, PathParser, PathStyle};
+use std::hint::black_box;
+use std::path::Path;
+
+const PATHS: [&str; 4] = [
+    r"C:\Users\alice\Documents\report.pdf",
+    "/mnt/d/projects/app/src/main.rs",
+    r"\\nas\share\media\film.mkv",
+    "relative/dir/file.txt",
+];
+
+fn construction(c: &mut Criterion) {
+    let config = PathConfig::default();
+    c.bench_function("PathConverter::new", |b| {
+        b.iter(|| PathConverter::new(black_box(&config)));
+    });
+    c.bench_function("PathParser::new", |b| b.iter(PathParser::new));
+    c.bench_function("PathSecurityChecker::new", |b| {
+        b.iter(PathSecurityChecker::new);
+    });
+}
+
+fn conversion(c: &mut Criterion) {
+    let config = PathConfig::default();
+    let converter = PathConverter::new(&config);
+    c.bench_function("convert batch", |b| {
+        b.iter(|| {
+            for path in PATHS {
+                let _ = converter.convert(black_box(path), PathStyle::Unix);
+                let _ = converter.convert(black_box(path), PathStyle::Windows);
+            }
+        });
+    });
+    c.bench_function("convert_cow batch", |b| {
+        b.iter(|| {
+            for path in PATHS {
+                let _ = converter.convert_cow(black_box(path), PathStyle::Unix);
+                let _ = converter.convert_cow(black_box(path), PathStyle::Windows);
+            }
+        });
+    });
+    c.bench_function("convert_batch", |b| {
+        b.iter(|| converter.convert_batch(black_box(&PATHS), PathStyle::Unix));
+    });
+    c.bench_function("CrossPath batch", |b| {
+        b.iter(|| {
+            for path in PATHS {
+                let path = CrossPath::with_config(black_box(path), config.clone()).unwrap();
+                let _ = path.to_unix();
+                let _ = path.is_safe();
+            }
+        });
+    });
+    c.bench_function("parse batch", |b| {
+        b.iter(|| {
+            for path in PATHS {
+                let _ = PathParser::parse(black_box(path));
+            }
+        });
+    });
+    c.bench_function("parse_ref batch", |b| {
+        b.iter(|| {
+            for path in PATHS {
+                let _ = PathParser::parse_ref(black_box(path));
+            }
+        });
+    });
+    let checker = PathSecurityChecker::new();
+    c.bench_function("security check batch", |b| {
+        b.iter(|| {
+            for path in PATHS {
+                let _ = checker.check(black_box(Path::new(path)));
+            }
+        });
+    });
+}
+
+criterion_group!(benches, construction, conversion);
+criterion_main!(benches);
@@ -28,8 +28,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         drive_mappings: vec![
             ("C:".to_string(), "/mnt/c".to_string()),
             ("D:".to_string(), "/mnt/data".to_string()),
-        ],
+        ]
+        .into(),
         normalize: true,
+        ..PathConfig::default()
     };
 
     let cp3 = CrossPath::with_config(windows_path, config)?;
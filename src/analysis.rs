@@ -0,0 +1,296 @@
+//! Path collection analysis
+//!
+//! Helpers for inspecting sets of paths, such as the entries of a config
+//! file or cache index, independent of the style they were written in.
+
+use crate::converter::WINDOWS_MAX_PATH;
+use crate::vfs::{FileSystem, OsFileSystem};
+use crate::{CaseSensitivity, CrossPath, CrossPathRef, PathResult, PathStyle, platform};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt;
+
+/// Style-independent identity of a path, for hashing and ordering
+///
+/// Paths that refer to the same target after drive mapping and lexical
+/// normalization get equal keys, so `C:\Foo\Bar`, `c:/foo/bar/` and
+/// `/mnt/c/Foo/Bar` share one `HashSet` or `BTreeMap` entry. Keys order
+/// by their text, the Unix form of the path.
+///
+/// Created by [`CrossPath::normalized_key`] and
+/// [`CrossPath::normalized_key_with`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NormalizedKey(String);
+
+impl NormalizedKey {
+    /// Compute the key of a path
+    ///
+    /// Without an explicit `case`, case is ignored for Windows paths, paths
+    /// under a mapped drive and paths configured as case-insensitive.
+    pub(crate) fn new(path: CrossPathRef<'_>, case: Option<CaseSensitivity>) -> PathResult<Self> {
+        let normalized = path.normalized_unix()?;
+        let normalized = path
+            .config()
+            .unicode_normalization
+            .fold(&normalized)
+            .into_owned();
+        let ignore_case = match case {
+            Some(case) => path.ignores_case(case),
+            None => {
+                path.ignores_case(path.config().case_sensitivity)
+                    || path.original_style == PathStyle::Windows
+                    || is_on_mapped_drive(path, &normalized)
+            }
+        };
+        Ok(Self(if ignore_case {
+            normalized.to_lowercase()
+        } else {
+            normalized
+        }))
+    }
+
+    /// Get the key text
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Convert into the key text
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Display for NormalizedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Paths in a collection that refer to the same target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// Style-independent form shared by all members
+    pub key: String,
+    /// Member chosen to represent the group
+    pub canonical: CrossPath,
+    /// All members, in input order (including the representative)
+    pub members: Vec<CrossPath>,
+}
+
+/// Find paths that refer to the same target
+///
+/// Paths are compared after drive mapping and lexical normalization, so
+/// `C:\Users\me\`, `/mnt/c/Users/me` and `C:/Users/./me` are duplicates.
+/// Windows paths, paths under a mapped drive and paths configured as
+/// case-insensitive are compared ignoring case.
+///
+/// The representative of each group prefers the current platform's style,
+/// then the shortest spelling, then the earliest entry. Only groups with
+/// more than one member are returned, ordered by first occurrence.
+///
+/// # Errors
+///
+/// Returns `PathError` if a path cannot be converted.
+pub fn find_duplicates<I>(paths: I) -> PathResult<Vec<DuplicateGroup>>
+where
+    I: IntoIterator<Item = CrossPath>,
+{
+    let mut groups: Vec<DuplicateGroup> = Vec::new();
+    // Key to index in `groups`, which keeps first-occurrence order
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for path in paths {
+        let key = NormalizedKey::new(path.as_cross_ref(), None)?.into_string();
+        match index.entry(key) {
+            Entry::Occupied(entry) => groups[*entry.get()].members.push(path),
+            Entry::Vacant(entry) => {
+                groups.push(DuplicateGroup {
+                    key: entry.key().clone(),
+                    canonical: path.clone(),
+                    members: vec![path],
+                });
+                entry.insert(groups.len() - 1);
+            }
+        }
+    }
+
+    groups.retain(|group| group.members.len() > 1);
+    for group in &mut groups {
+        if let Some(best) = group.members.iter().min_by_key(|p| representative_rank(p)) {
+            group.canonical = best.clone();
+        }
+    }
+
+    Ok(groups)
+}
+
+/// Check if a normalized Unix path lies under a drive mapping
+fn is_on_mapped_drive(path: CrossPathRef<'_>, canonical: &str) -> bool {
+    path.config().drive_mappings.iter().any(|(_, unix)| {
+        let unix = unix.trim_end_matches('/');
+        canonical
+            .get(..unix.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(unix))
+            && matches!(canonical.as_bytes().get(unix.len()), None | Some(b'/'))
+    })
+}
+
+/// Sort key for choosing a group representative (lower is better)
+fn representative_rank(path: &CrossPath) -> (bool, usize) {
+    (
+        path.original_style != platform::current_style(),
+        path.as_original().as_os_str().len(),
+    )
+}
+
+/// Longest Unix path (`PATH_MAX` minus the terminating NUL), in bytes
+const UNIX_MAX_PATH: usize = 4095;
+/// Longest single component on both platforms
+const MAX_COMPONENT: usize = 255;
+
+/// Remaining path length when nesting under a base directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LengthBudget {
+    /// Style the budget was computed for
+    pub target_style: PathStyle,
+    /// Length of the base directory in the target style
+    pub base_length: usize,
+    /// Maximum path length of the target platform
+    pub max_path: usize,
+    /// Maximum component length of the target platform
+    pub max_component: usize,
+    /// Characters left for a relative path below the base (excluding the
+    /// separator after the base)
+    pub remaining: usize,
+    /// Existing descendants that exceed a limit after conversion
+    pub overflows: Vec<LengthOverflow>,
+}
+
+/// Descendant that would exceed the target platform's limits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LengthOverflow {
+    /// Path relative to the base, `/`-separated
+    pub relative: String,
+    /// Full length after conversion
+    pub converted_length: usize,
+    /// Whether a single component is too long
+    pub component_too_long: bool,
+}
+
+impl LengthBudget {
+    /// Check if a relative path fits below the base
+    #[must_use]
+    pub fn fits(&self, relative: &str) -> bool {
+        let relative = relative.trim_matches(['/', '\\']);
+        let components = relative.split(['/', '\\']).filter(|c| !c.is_empty());
+        let mut length = 0;
+        for (i, component) in components.enumerate() {
+            let component_length = measure(component, self.target_style);
+            if component_length > self.max_component {
+                return false;
+            }
+            length += component_length + usize::from(i > 0);
+        }
+        length <= self.remaining
+    }
+}
+
+/// Compute how much path length remains below `base` on the target platform
+///
+/// If `base` exists locally, its descendants are walked (without following
+/// symlinks) and every entry whose converted path would exceed the limits is
+/// reported in [`LengthBudget::overflows`]. Windows lengths are measured in
+/// UTF-16 code units, Unix lengths in bytes.
+///
+/// # Errors
+///
+/// Returns `PathError` if the base cannot be converted or its descendants
+/// cannot be read.
+pub fn length_budget(base: &CrossPath, target_style: PathStyle) -> PathResult<LengthBudget> {
+    length_budget_in(&OsFileSystem, base, target_style)
+}
+
+/// Compute the length budget, walking descendants on the given filesystem
+///
+/// # Errors
+///
+/// Returns `PathError` if the base cannot be converted or its descendants
+/// cannot be read.
+pub fn length_budget_in(
+    fs: &dyn FileSystem,
+    base: &CrossPath,
+    target_style: PathStyle,
+) -> PathResult<LengthBudget> {
+    let target_style = match target_style {
+        PathStyle::Auto => platform::current_style(),
+        style => style,
+    };
+    let converted = base.to_style(target_style)?;
+    let base_length = measure(converted.trim_end_matches(['/', '\\']), target_style);
+    let max_path = match target_style {
+        PathStyle::Windows => WINDOWS_MAX_PATH,
+        _ => UNIX_MAX_PATH,
+    };
+
+    let mut budget = LengthBudget {
+        target_style,
+        base_length,
+        max_path,
+        max_component: MAX_COMPONENT,
+        remaining: max_path.saturating_sub(base_length + 1),
+        overflows: Vec::new(),
+    };
+
+    let native = std::path::PathBuf::from(base.to_style(fs.style())?);
+    if fs.is_dir(&native) {
+        collect_overflows(fs, &native, "", base_length, &mut budget)?;
+    }
+
+    Ok(budget)
+}
+
+/// Walk `dir`, recording descendants that exceed the budget
+fn collect_overflows(
+    fs: &dyn FileSystem,
+    dir: &std::path::Path,
+    prefix: &str,
+    length: usize,
+    budget: &mut LengthBudget,
+) -> PathResult<()> {
+    for entry in fs.read_dir(dir)? {
+        let name = entry.to_string_lossy().into_owned();
+        let relative = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}/{name}")
+        };
+        let component_length = measure(&name, budget.target_style);
+        let converted_length = length + 1 + component_length;
+        let component_too_long = component_length > budget.max_component;
+
+        if component_too_long || converted_length > budget.max_path {
+            budget.overflows.push(LengthOverflow {
+                relative: relative.clone(),
+                converted_length,
+                component_too_long,
+            });
+        }
+
+        let path = dir.join(&entry);
+        if fs.symlink_metadata(&path)?.is_dir() {
+            collect_overflows(fs, &path, &relative, converted_length, budget)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Length of a string as counted by the target platform
+fn measure(text: &str, style: PathStyle) -> usize {
+    match style {
+        PathStyle::Windows => text.encode_utf16().count(),
+        _ => text.len(),
+    }
+}
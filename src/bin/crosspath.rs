@@ -0,0 +1,221 @@
+//! `crosspath`: convert paths between Windows and Unix styles
+//!
+//! Behaves like `wslpath` and `cygpath`:
+//!
+//! ```text
+//! crosspath -u 'C:\Users\me'      # /mnt/c/Users/me
+//! crosspath -w /mnt/c/Users/me    # C:\Users\me
+//! crosspath --style cygwin 'C:\x' # /cygdrive/c/x
+//! find . | crosspath -w           # one path per line from stdin
+//! crosspath --check '../../etc/passwd'
+//! ```
+//!
+//! Settings come from `--config`, or from the file and variables named in
+//! the environment (`CROSS_PATH_CONFIG`, `CROSS_PATH_DRIVES`, ...).
+//!
+//! Errors that point at part of a path, such as a `..` rejected by
+//! `--check`, are shown with that part underlined.
+
+use cross_path::{CrossPath, PathConfig, PathError, PathResult, PathStyle};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: crosspath [OPTIONS] [PATH]...
+
+Convert paths between Windows and Unix styles. Without PATH arguments,
+paths are read from standard input, one per line.
+
+Options:
+  -u, --unix           Convert to Unix style
+  -w, --windows        Convert to Windows style
+  -m, --mixed          Convert to Windows style with forward slashes
+      --style STYLE    Convert to windows, unix, cygwin, msys or auto
+                       (default: auto, the style of this platform)
+  -c, --config FILE    Load settings from a .toml or .json file
+      --check          Check paths against the security policy instead of
+                       converting them; safe paths are printed unchanged
+  -h, --help           Print this help
+  -V, --version        Print the version
+";
+
+/// What to do with each path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Convert { style: PathStyle, mixed: bool },
+    Check,
+}
+
+/// Parsed command line
+struct Args {
+    mode: Mode,
+    config: Option<String>,
+    paths: Vec<String>,
+}
+
+/// Outcome of parsing the command line
+enum Command {
+    Run(Args),
+    Help,
+    Version,
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(args)) => args,
+        Ok(Command::Help) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Ok(Command::Version) => {
+            println!("crosspath {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("crosspath: {message}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let config = match &args.config {
+        Some(file) => PathConfig::from_file(file),
+        None => PathConfig::from_env(),
+    };
+    let config = match config {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("crosspath: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut failed = false;
+    let mut process = |path: &str| -> io::Result<()> {
+        match run(path, args.mode, &config) {
+            Ok(output) => writeln!(out, "{output}"),
+            Err(e) if e.span().is_some() => {
+                failed = true;
+                eprint!(
+                    "crosspath: {path}:\n{}",
+                    e.render(io::stderr().is_terminal())
+                );
+                Ok(())
+            }
+            Err(e) => {
+                failed = true;
+                eprintln!("crosspath: {path}: {e}");
+                Ok(())
+            }
+        }
+    };
+
+    let result = if args.paths.is_empty() {
+        io::stdin().lock().lines().try_for_each(|line| {
+            let line = line?;
+            let path = line.strip_suffix('\r').unwrap_or(&line);
+            if path.is_empty() {
+                Ok(())
+            } else {
+                process(path)
+            }
+        })
+    } else {
+        args.paths.iter().try_for_each(|path| process(path))
+    };
+
+    match result {
+        // A closed pipe (`crosspath ... | head`) is not an error
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => {
+            eprintln!("crosspath: {e}");
+            ExitCode::FAILURE
+        }
+        _ if failed => ExitCode::FAILURE,
+        _ => ExitCode::SUCCESS,
+    }
+}
+
+/// Convert or check a single path
+fn run(path: &str, mode: Mode, config: &PathConfig) -> PathResult<String> {
+    let cross_path = CrossPath::with_config(path, config.clone())?;
+    match mode {
+        Mode::Convert { style, mixed } => {
+            let converted = cross_path.to_style(style)?;
+            Ok(if mixed {
+                converted.replace('\\', "/")
+            } else {
+                converted
+            })
+        }
+        Mode::Check => {
+            if cross_path.is_safe()? {
+                Ok(path.to_string())
+            } else {
+                Err(PathError::security_error("Path is not safe"))
+            }
+        }
+    }
+}
+
+/// Parse the command line arguments after the program name
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, String> {
+    let mut mode = Mode::Convert {
+        style: PathStyle::Auto,
+        mixed: false,
+    };
+    let mut config = None;
+    let mut paths = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-u" | "--unix" => mode = convert(PathStyle::Unix, false),
+            "-w" | "--windows" => mode = convert(PathStyle::Windows, false),
+            "-m" | "--mixed" => mode = convert(PathStyle::Windows, true),
+            "--style" => {
+                let style = args.next().ok_or("--style requires a value")?;
+                mode = convert(parse_style(&style)?, false);
+            }
+            "-c" | "--config" => {
+                config = Some(args.next().ok_or("--config requires a file")?);
+            }
+            "--check" => mode = Mode::Check,
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            // Everything after `--` is a path, even if it starts with `-`
+            "--" => paths.extend(args.by_ref()),
+            _ => {
+                if let Some(style) = arg.strip_prefix("--style=") {
+                    mode = convert(parse_style(style)?, false);
+                } else if let Some(file) = arg.strip_prefix("--config=") {
+                    config = Some(file.to_string());
+                } else if arg.starts_with('-') && arg.len() > 1 {
+                    return Err(format!("unknown option '{arg}'"));
+                } else {
+                    paths.push(arg);
+                }
+            }
+        }
+    }
+
+    Ok(Command::Run(Args {
+        mode,
+        config,
+        paths,
+    }))
+}
+
+fn convert(style: PathStyle, mixed: bool) -> Mode {
+    Mode::Convert { style, mixed }
+}
+
+fn parse_style(style: &str) -> Result<PathStyle, String> {
+    match style.to_ascii_lowercase().as_str() {
+        "windows" => Ok(PathStyle::Windows),
+        "unix" => Ok(PathStyle::Unix),
+        "cygwin" => Ok(PathStyle::Cygwin),
+        "msys" => Ok(PathStyle::Msys),
+        "auto" => Ok(PathStyle::Auto),
+        _ => Err(format!("unknown style '{style}'")),
+    }
+}
@@ -0,0 +1,524 @@
+//! Loading [`PathConfig`] from configuration files and the environment
+//!
+//! Lets tools built on this crate take drive mappings and other settings
+//! from the user instead of hard-coding them. Files use the [`ConfigFile`]
+//! schema, written as TOML (with the `toml` feature) or JSON (with the
+//! `json` feature):
+//!
+//! ```toml
+//! style = "unix"                # windows, unix, cygwin, msys or auto
+//! case_sensitivity = "insensitive" # sensitive, insensitive, platform or probe
+//! unicode_normalization = "nfd" # exact, insensitive, nfc or nfd
+//! unmapped = "error"            # a drive letter, "error", "passthrough" or "wsl:<distro>"
+//! backend = "system"            # builtin, system or verify, optionally ":wslpath" or ":cygpath"
+//! mount_root = "/"              # built-in drives mount as /c, /d, /e
+//! trailing_slash = "preserve"   # strip, preserve or ensure
+//! wsl_distro = "Ubuntu"
+//!
+//! [drives]
+//! "P:" = "$HOME/projects"
+//! '\\nas\media' = "/mnt/media"
+//!
+//! [current_dirs]                # resolves drive-relative paths like D:foo
+//! "D:" = 'D:\work'
+//!
+//! [[rewrite]]                   # applied before every conversion
+//! kind = "prefix"
+//! from = "/builds/runner"
+//! to = '$USERPROFILE\src'
+//!
+//! [[rewrite]]
+//! kind = "regex"
+//! pattern = '/cargo/registry/src/[^/]+/([^/]+)'
+//! replacement = 'C:\deps\$1'
+//!
+//! [security]
+//! check = true
+//! max_path_length = 1024
+//! allowed_roots = ["/srv/uploads"]
+//! ```
+//!
+//! Every key is optional; missing keys keep their
+//! [default](PathConfig::default). Drive mappings listed under `drives`
+//! take precedence over the built-in ones and may reference environment
+//! variables.
+
+use crate::expand::{self, Environment};
+use crate::mapping::drives::DriveMappings;
+use crate::rewrite::RewriteRule;
+use crate::{
+    AdminSharePolicy, CaseSensitivity, ConversionBackend, DataStreamPolicy, DotDotPolicy,
+    NormalizeMode, PathConfig, PathError, PathResult, PathStyle, SystemTool, TrailingSlash,
+    UnicodeNormalization, UnmappedPolicy,
+};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Environment variable naming a configuration file for
+/// [`PathConfig::from_env`]
+pub const CONFIG_FILE_VAR: &str = "CROSS_PATH_CONFIG";
+
+/// Schema of a configuration file
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    /// Target style: `windows`, `unix`, `cygwin`, `msys` or `auto`
+    pub style: Option<String>,
+    /// Whether to normalize paths
+    pub normalize: Option<bool>,
+    /// `lexical`, `physical` or `lexical_then_verify`
+    pub normalize_mode: Option<String>,
+    /// `..` handling: `resolve`, `keep` or `error`
+    pub dot_dot: Option<String>,
+    /// NTFS alternate data streams on conversion: `preserve` or `strip`
+    pub data_streams: Option<String>,
+    /// Trailing separators on conversion: `preserve`, `strip` or `ensure`
+    pub trailing_slash: Option<String>,
+    /// Administrative shares on conversion to Unix: `unc`, `mount` or `smb`
+    pub admin_shares: Option<String>,
+    /// `sensitive`, `insensitive`, `platform` or `probe`
+    pub case_sensitivity: Option<String>,
+    /// `exact`, `insensitive`, `nfc` or `nfd`
+    pub unicode_normalization: Option<String>,
+    /// Drive letter for unmapped Unix paths, `error`, `passthrough` or
+    /// `wsl:<distro>`
+    pub unmapped: Option<String>,
+    /// `builtin`, `system` or `verify`, optionally followed by `:wslpath`
+    /// or `:cygpath`
+    pub backend: Option<String>,
+    /// Reject invalid paths at construction
+    pub strict: Option<bool>,
+    /// Emit `\\?\` for long Windows paths
+    pub extended_length_prefix: Option<bool>,
+    /// Expand environment variables in paths at construction
+    pub expand_env: Option<bool>,
+    /// Return paths as written when converting back to their style
+    pub preserve_roundtrip: Option<bool>,
+    /// WSL distribution Unix paths belong to
+    pub wsl_distro: Option<String>,
+    /// Root the built-in drives are mounted under (`/mnt` by default)
+    pub mount_root: Option<String>,
+    /// Windows drive or share ↔ Unix mount point mappings
+    pub drives: BTreeMap<String, String>,
+    /// Current directory of each Windows drive, keyed by drive (`D` or
+    /// `D:`)
+    pub current_dirs: BTreeMap<String, String>,
+    /// Rewrite rules, tried after the default ones
+    pub rewrite: Vec<RewriteRule>,
+    /// Security settings
+    pub security: SecuritySection,
+}
+
+/// `security` section of a configuration file
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SecuritySection {
+    /// Whether security checks run during conversion
+    pub check: Option<bool>,
+    /// Maximum path length in characters
+    pub max_path_length: Option<usize>,
+    /// Roots absolute paths are confined to
+    pub allowed_roots: Vec<String>,
+    /// Additional regular expressions paths must not match
+    pub dangerous_patterns: Vec<String>,
+    /// Reject paths addressing NTFS alternate data streams
+    pub reject_data_streams: Option<bool>,
+}
+
+impl ConfigFile {
+    /// Parse a TOML configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::ParseError` if the text is not valid TOML or does
+    /// not match the schema.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(text: &str) -> PathResult<Self> {
+        toml::from_str(text)
+            .map_err(|e| PathError::parse_error(format!("TOML config: {e}")).with_source(e))
+    }
+
+    /// Parse a JSON configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::ParseError` if the text is not valid JSON or does
+    /// not match the schema.
+    #[cfg(feature = "json")]
+    pub fn from_json_str(text: &str) -> PathResult<Self> {
+        serde_json::from_str(text)
+            .map_err(|e| PathError::parse_error(format!("JSON config: {e}")).with_source(e))
+    }
+
+    /// Read a configuration file, choosing the format by extension
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::IoError` if the file cannot be read,
+    /// `PathError::UnsupportedFormat` if the extension is neither `.toml`
+    /// nor `.json` or its feature is disabled, and `PathError::ParseError`
+    /// if the contents are invalid.
+    pub fn from_file(path: &Path) -> PathResult<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            PathError::io_error(format!("{}: {e}", path.display()))
+                .with_path(path.to_string_lossy())
+                .with_source(e)
+        })?;
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            #[cfg(feature = "toml")]
+            Some("toml") => Self::from_toml_str(&text),
+            #[cfg(feature = "json")]
+            Some("json") => Self::from_json_str(&text),
+            _ => {
+                let _ = text;
+                Err(PathError::unsupported_format(format!(
+                    "Unsupported config file: {}",
+                    path.display()
+                )))
+            }
+        }
+    }
+
+    /// Apply the settings on top of `config`
+    ///
+    /// Variables in drive mappings, current directories and prefix rewrite
+    /// rules are expanded from `env`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::ParseError` if a value is not recognized, and
+    /// `PathError` if a drive mapping references an undefined variable or a
+    /// dangerous or rewrite pattern is not a valid regular expression.
+    pub fn apply<E: Environment + ?Sized>(
+        &self,
+        config: &mut PathConfig,
+        env: &E,
+    ) -> PathResult<()> {
+        if let Some(style) = &self.style {
+            config.style = parse_style(style)?;
+        }
+        if let Some(normalize) = self.normalize {
+            config.normalize = normalize;
+        }
+        self.apply_policies(config)?;
+        if let Some(case) = &self.case_sensitivity {
+            config.case_sensitivity = match case.to_ascii_lowercase().as_str() {
+                "sensitive" => CaseSensitivity::Sensitive,
+                "insensitive" => CaseSensitivity::Insensitive,
+                "platform" => CaseSensitivity::Platform,
+                "probe" | "probe_filesystem" => CaseSensitivity::ProbeFilesystem,
+                _ => return Err(invalid_value("case_sensitivity", case)),
+            };
+        }
+        if let Some(unicode) = &self.unicode_normalization {
+            config.unicode_normalization = parse_unicode_normalization(unicode)?;
+        }
+        if let Some(unmapped) = &self.unmapped {
+            config.unmapped_policy = parse_unmapped(unmapped)?;
+        }
+        if let Some(backend) = &self.backend {
+            config.backend = parse_backend(backend)?;
+        }
+        if let Some(strict) = self.strict {
+            config.strict = strict;
+        }
+        if let Some(prefix) = self.extended_length_prefix {
+            config.extended_length_prefix = prefix;
+        }
+        if let Some(expand_env) = self.expand_env {
+            config.expand_env = expand_env;
+        }
+        if let Some(preserve) = self.preserve_roundtrip {
+            config.preserve_roundtrip = preserve;
+        }
+        if let Some(distro) = &self.wsl_distro {
+            config.wsl_distro = Some(distro.clone());
+        }
+        if let Some(root) = &self.mount_root {
+            config.drive_mappings = DriveMappings::under_root(root).into_vec().into();
+        }
+        if !self.drives.is_empty() {
+            let mut mappings = Vec::with_capacity(self.drives.len());
+            for (windows, unix) in &self.drives {
+                mappings.push((expand::expand(windows, env)?, expand::expand(unix, env)?));
+            }
+            // Mappings from the file take precedence over the defaults
+            mappings.extend(config.drive_mappings.as_slice().iter().cloned());
+            config.drive_mappings = mappings.into();
+        }
+        for (drive, dir) in &self.current_dirs {
+            let letter = match drive.strip_suffix(':').unwrap_or(drive).as_bytes() {
+                &[letter] if letter.is_ascii_alphabetic() => char::from(letter),
+                _ => return Err(invalid_value("current_dirs", drive)),
+            };
+            config
+                .drive_current_dirs
+                .insert(letter.to_ascii_uppercase(), expand::expand(dir, env)?);
+        }
+        for rule in &self.rewrite {
+            // Replacements of regex rules use `$` for captures
+            let rule = match rule {
+                RewriteRule::Prefix { from, to } => {
+                    RewriteRule::prefix(expand::expand(from, env)?, expand::expand(to, env)?)
+                }
+                RewriteRule::Regex { .. } => rule.clone(),
+            };
+            config.rewriter.push(rule)?;
+        }
+        self.security.apply(config)
+    }
+
+    /// Apply the settings that select a policy by name
+    fn apply_policies(&self, config: &mut PathConfig) -> PathResult<()> {
+        if let Some(mode) = &self.normalize_mode {
+            config.normalize_mode = match mode.to_ascii_lowercase().as_str() {
+                "lexical" => NormalizeMode::Lexical,
+                "physical" => NormalizeMode::Physical,
+                "lexical_then_verify" => NormalizeMode::LexicalThenVerify,
+                _ => return Err(invalid_value("normalize_mode", mode)),
+            };
+        }
+        if let Some(policy) = &self.dot_dot {
+            config.dot_dot_policy = match policy.to_ascii_lowercase().as_str() {
+                "resolve" => DotDotPolicy::Resolve,
+                "keep" => DotDotPolicy::Keep,
+                "error" => DotDotPolicy::Error,
+                _ => return Err(invalid_value("dot_dot", policy)),
+            };
+        }
+        if let Some(policy) = &self.data_streams {
+            config.data_streams = match policy.to_ascii_lowercase().as_str() {
+                "preserve" => DataStreamPolicy::Preserve,
+                "strip" => DataStreamPolicy::Strip,
+                _ => return Err(invalid_value("data_streams", policy)),
+            };
+        }
+        if let Some(policy) = &self.trailing_slash {
+            config.trailing_slash = match policy.to_ascii_lowercase().as_str() {
+                "preserve" => TrailingSlash::Preserve,
+                "strip" => TrailingSlash::Strip,
+                "ensure" => TrailingSlash::Ensure,
+                _ => return Err(invalid_value("trailing_slash", policy)),
+            };
+        }
+        if let Some(policy) = &self.admin_shares {
+            config.admin_shares = match policy.to_ascii_lowercase().as_str() {
+                "unc" => AdminSharePolicy::Unc,
+                "mount" | "mount_point" => AdminSharePolicy::MountPoint,
+                "smb" | "smb_url" => AdminSharePolicy::SmbUrl,
+                _ => return Err(invalid_value("admin_shares", policy)),
+            };
+        }
+        Ok(())
+    }
+
+    /// Build a configuration from the defaults and these settings
+    ///
+    /// Variables in drive mappings are expanded from the process
+    /// environment.
+    ///
+    /// # Errors
+    ///
+    /// See [`apply`](Self::apply).
+    pub fn into_config(self) -> PathResult<PathConfig> {
+        let mut config = PathConfig::default();
+        self.apply(&mut config, &expand::SystemEnvironment)?;
+        Ok(config)
+    }
+}
+
+impl SecuritySection {
+    /// Apply the security settings on top of `config`
+    #[cfg_attr(not(feature = "security"), allow(clippy::unnecessary_wraps))]
+    fn apply(&self, config: &mut PathConfig) -> PathResult<()> {
+        if let Some(check) = self.check {
+            config.security_check = check;
+        }
+
+        #[cfg(feature = "security")]
+        {
+            let mut builder =
+                crate::security::SecurityPolicyBuilder::from(config.security_policy.clone());
+            for root in &self.allowed_roots {
+                builder = builder.allow_root(root.clone());
+            }
+            for pattern in &self.dangerous_patterns {
+                builder = builder.add_dangerous_pattern(pattern.clone());
+            }
+            if let Some(max) = self.max_path_length {
+                builder = builder.max_path_length(max);
+            }
+            if let Some(reject) = self.reject_data_streams {
+                builder = builder.reject_data_streams(reject);
+            }
+            config.security_policy = builder.build()?;
+        }
+        Ok(())
+    }
+}
+
+impl PathConfig {
+    /// Load a configuration file on top of the defaults
+    ///
+    /// The format is chosen by extension (`.toml` or `.json`); see the
+    /// [`config`](crate::config) module for the schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the file cannot be read or is invalid.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> PathResult<Self> {
+        ConfigFile::from_file(path.as_ref())?.into_config()
+    }
+
+    /// Load the configuration named by the process environment
+    ///
+    /// See [`from_env_with`](Self::from_env_with).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the configuration file cannot be read or a
+    /// variable holds an invalid value.
+    pub fn from_env() -> PathResult<Self> {
+        Self::from_env_with(&expand::SystemEnvironment)
+    }
+
+    /// Load the configuration named by an environment
+    ///
+    /// Starts from the defaults, loads the file named by
+    /// `CROSS_PATH_CONFIG` if set, then applies these overrides:
+    ///
+    /// - `CROSS_PATH_STYLE`: target style
+    /// - `CROSS_PATH_MOUNT_ROOT`: root the built-in drives are mounted under
+    /// - `CROSS_PATH_DRIVES`: `;`-separated `windows=unix` mappings
+    /// - `CROSS_PATH_WSL_DISTRO`: WSL distribution
+    /// - `CROSS_PATH_SECURITY_CHECK`: `true` or `false`
+    /// - `CROSS_PATH_BACKEND`: conversion backend, as `backend` in a file
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the configuration file cannot be read or a
+    /// variable holds an invalid value.
+    pub fn from_env_with<E: Environment + ?Sized>(env: &E) -> PathResult<Self> {
+        let mut file = match env.var(CONFIG_FILE_VAR) {
+            Some(path) => ConfigFile::from_file(Path::new(&path))?,
+            None => ConfigFile::default(),
+        };
+
+        if let Some(style) = env.var("CROSS_PATH_STYLE") {
+            file.style = Some(style);
+        }
+        if let Some(root) = env.var("CROSS_PATH_MOUNT_ROOT") {
+            file.mount_root = Some(root);
+        }
+        if let Some(drives) = env.var("CROSS_PATH_DRIVES") {
+            for mapping in drives.split(';').filter(|m| !m.trim().is_empty()) {
+                let (windows, unix) = mapping
+                    .split_once('=')
+                    .ok_or_else(|| invalid_value("CROSS_PATH_DRIVES", mapping))?;
+                file.drives
+                    .insert(windows.trim().to_string(), unix.trim().to_string());
+            }
+        }
+        if let Some(distro) = env.var("CROSS_PATH_WSL_DISTRO") {
+            file.wsl_distro = Some(distro);
+        }
+        if let Some(check) = env.var("CROSS_PATH_SECURITY_CHECK") {
+            file.security.check = Some(match check.to_ascii_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => true,
+                "0" | "false" | "no" | "off" => false,
+                _ => return Err(invalid_value("CROSS_PATH_SECURITY_CHECK", &check)),
+            });
+        }
+
+        if let Some(backend) = env.var("CROSS_PATH_BACKEND") {
+            file.backend = Some(backend);
+        }
+
+        let mut config = Self::default();
+        file.apply(&mut config, env)?;
+        Ok(config)
+    }
+}
+
+/// Parse a style name
+fn parse_style(style: &str) -> PathResult<PathStyle> {
+    match style.to_ascii_lowercase().as_str() {
+        "windows" => Ok(PathStyle::Windows),
+        "unix" => Ok(PathStyle::Unix),
+        "cygwin" => Ok(PathStyle::Cygwin),
+        "msys" => Ok(PathStyle::Msys),
+        "auto" => Ok(PathStyle::Auto),
+        _ => Err(invalid_value("style", style)),
+    }
+}
+
+/// Parse a Unicode normalization behavior: `exact`, `insensitive`, `nfc`
+/// or `nfd`
+fn parse_unicode_normalization(unicode: &str) -> PathResult<UnicodeNormalization> {
+    match unicode.to_ascii_lowercase().as_str() {
+        "exact" => Ok(UnicodeNormalization::Exact),
+        "insensitive" => Ok(UnicodeNormalization::Insensitive),
+        "nfc" => Ok(UnicodeNormalization::Nfc),
+        "nfd" => Ok(UnicodeNormalization::Nfd),
+        _ => Err(invalid_value("unicode_normalization", unicode)),
+    }
+}
+
+/// Parse a conversion backend: `builtin`, `system` or `verify`, optionally
+/// followed by `:wslpath` or `:cygpath`
+fn parse_backend(backend: &str) -> PathResult<ConversionBackend> {
+    let lower = backend.to_ascii_lowercase();
+    let (kind, tool) = lower.split_once(':').unwrap_or((&lower, "detect"));
+    let tool = match tool {
+        "detect" => SystemTool::Detect,
+        "wslpath" => SystemTool::Wslpath,
+        "cygpath" => SystemTool::Cygpath,
+        _ => return Err(invalid_value("backend", backend)),
+    };
+    match kind {
+        "builtin" if tool == SystemTool::Detect => Ok(ConversionBackend::Builtin),
+        "system" => Ok(ConversionBackend::System(tool)),
+        "verify" => Ok(ConversionBackend::Verify(tool)),
+        _ => Err(invalid_value("backend", backend)),
+    }
+}
+
+/// Parse an unmapped policy: a drive letter, `error`, `passthrough` or
+/// `wsl:<distro>`
+fn parse_unmapped(unmapped: &str) -> PathResult<UnmappedPolicy> {
+    if let Some(distro) = unmapped
+        .get(..4)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("wsl:"))
+        .map(|_| &unmapped[4..])
+    {
+        return if distro.is_empty() {
+            Err(invalid_value("unmapped", unmapped))
+        } else {
+            Ok(UnmappedPolicy::WslUnc {
+                distro: distro.to_string(),
+            })
+        };
+    }
+    match unmapped.to_ascii_lowercase().as_str() {
+        "error" => Ok(UnmappedPolicy::Error),
+        "passthrough" => Ok(UnmappedPolicy::Passthrough),
+        drive => {
+            let mut chars = drive.trim_end_matches(':').chars();
+            match (chars.next(), chars.next()) {
+                (Some(letter), None) if letter.is_ascii_alphabetic() => {
+                    Ok(UnmappedPolicy::DefaultDrive(letter.to_ascii_uppercase()))
+                }
+                _ => Err(invalid_value("unmapped", unmapped)),
+            }
+        }
+    }
+}
+
+/// Build a parse error for an unrecognized value
+fn invalid_value(key: &str, value: &str) -> PathError {
+    PathError::parse_error(format!("Invalid value for {key}: {value}"))
+}
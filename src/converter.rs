@@ -1,28 +1,94 @@
-use crate::{PathConfig, PathError, PathResult, PathStyle};
+use crate::parser::PathKind;
+use crate::platform::RuntimeEnvironment;
+use crate::sanitize::Sanitizer;
+use crate::{
+    AdminSharePolicy, ConversionBackend, DataStreamPolicy, PathConfig, PathError, PathResult,
+    PathStyle, SystemTool, TrailingSlash, UnmappedPolicy, metrics, style, url,
+};
 use regex::Regex;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::LazyLock;
+use std::time::Instant;
+
+/// UNC host names under which Windows exposes WSL file systems
+const WSL_HOSTS: [&str; 2] = ["wsl$", "wsl.localhost"];
+
+/// Longest Windows path without the `\\?\` prefix (`MAX_PATH` minus the
+/// terminating NUL), in UTF-16 code units
+pub(crate) const WINDOWS_MAX_PATH: usize = 259;
+
+/// Windows drive path, absolute or drive-relative (`C:`, `C:\x`)
+static WINDOWS_PATH: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([a-zA-Z]:)([/\\].*)?$").unwrap());
+/// Absolute Unix path that is not a `//` network path
+static UNIX_PATH: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^/([^/].*)?$").unwrap());
+/// Bare drive letter (`C:`)
+static DRIVE_LETTER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[a-zA-Z]:$").unwrap());
+
+/// Adjustment made by [`PathConverter::convert_lossy`] to convert a path
+/// that would otherwise fail
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConversionWarning {
+    /// Absolute Unix path without a drive mapping was placed on `drive`
+    UnmappedDrive {
+        /// Upper-case drive letter the path was placed on
+        drive: char,
+    },
+    /// Rewrite rules failed and were skipped
+    RewriteSkipped(PathError),
+    /// Configured backend failed, so the built-in conversion was used
+    BackendMismatch(PathError),
+    /// Path could not be converted; only its separators were changed
+    Unconverted(PathError),
+    /// Character not allowed in the target style was replaced
+    ReplacedChar {
+        /// Character that was replaced
+        character: char,
+        /// Text that took its place
+        replacement: String,
+        /// Byte offset of the replacement in the converted path
+        index: usize,
+    },
+}
+
+impl fmt::Display for ConversionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnmappedDrive { drive } => {
+                write!(f, "No drive mapping, placed on drive {drive}:")
+            }
+            Self::RewriteSkipped(error) => write!(f, "Rewrite rules skipped: {error}"),
+            Self::BackendMismatch(error) => write!(f, "Used built-in conversion: {error}"),
+            Self::Unconverted(error) => write!(f, "Path left unconverted: {error}"),
+            Self::ReplacedChar {
+                character,
+                replacement,
+                index,
+            } => write!(
+                f,
+                "Replaced {character:?} with {replacement:?} at byte {index}"
+            ),
+        }
+    }
+}
 
 /// Path converter for Windows ↔ Unix conversion
 #[derive(Debug, Clone)]
 pub struct PathConverter {
     config: PathConfig,
-    windows_path_regex: Regex,
-    unix_path_regex: Regex,
-    drive_letter_regex: Regex,
 }
 
 impl PathConverter {
     /// Create new path converter
     ///
-    /// # Panics
-    ///
-    /// Panics if the internal regex patterns are invalid.
+    /// Cheap apart from cloning the configuration: the patterns used for
+    /// style detection are compiled once per process.
     #[must_use]
     pub fn new(config: &PathConfig) -> Self {
         Self {
             config: config.clone(),
-            windows_path_regex: Regex::new(r"^([a-zA-Z]:)([/\\].*)?$").unwrap(),
-            unix_path_regex: Regex::new(r"^/([^/].*)?$").unwrap(),
-            drive_letter_regex: Regex::new(r"^[a-zA-Z]:$").unwrap(),
         }
     }
 
@@ -32,23 +98,430 @@ impl PathConverter {
     ///
     /// Returns `PathError` if the path cannot be converted or the format is unsupported.
     pub fn convert(&self, path: &str, target_style: PathStyle) -> PathResult<String> {
+        self.convert_between(path, PathStyle::Auto, target_style)
+    }
+
+    /// Convert many paths to specified style
+    ///
+    /// Results are returned in input order, and a path that fails to
+    /// convert does not stop the batch. Cheaper than converting each path
+    /// through [`CrossPath`](crate::CrossPath), which clones the
+    /// configuration per path.
+    pub fn convert_batch<S: AsRef<str>>(
+        &self,
+        paths: &[S],
+        target_style: PathStyle,
+    ) -> Vec<PathResult<String>> {
+        let target_style = self.resolve_target(target_style);
+        let mut results = Vec::with_capacity(paths.len());
+        results.extend(
+            paths
+                .iter()
+                .map(|path| self.convert(path.as_ref(), target_style)),
+        );
+        results
+    }
+
+    /// Convert many paths to specified style on the rayon thread pool
+    ///
+    /// Behaves like [`convert_batch`](Self::convert_batch), including the
+    /// order of the results.
+    #[cfg(feature = "rayon")]
+    pub fn convert_batch_par<S: AsRef<str> + Sync>(
+        &self,
+        paths: &[S],
+        target_style: PathStyle,
+    ) -> Vec<PathResult<String>> {
+        use rayon::prelude::*;
+
+        let target_style = self.resolve_target(target_style);
+        paths
+            .par_iter()
+            .map(|path| self.convert(path.as_ref(), target_style))
+            .collect()
+    }
+
+    /// Convert path to specified style without failing
+    ///
+    /// Same as [`convert_lossy_with`](Self::convert_lossy_with) using the
+    /// default [`Sanitizer`], which replaces invalid characters with `_`.
+    #[must_use]
+    pub fn convert_lossy(
+        &self,
+        path: &str,
+        target_style: PathStyle,
+    ) -> (String, Vec<ConversionWarning>) {
+        self.convert_lossy_with(path, target_style, &Sanitizer::new())
+    }
+
+    /// Convert path to specified style without failing, reporting every
+    /// adjustment made on the way
+    ///
+    /// Meant for bulk processing, where one malformed path must not stop
+    /// the rest:
+    ///
+    /// - failing rewrite rules are skipped;
+    /// - when the configured backend fails, the built-in conversion is used;
+    /// - absolute Unix paths without a drive mapping are placed on the drive
+    ///   of [`UnmappedPolicy::DefaultDrive`], or on `C:` under
+    ///   [`UnmappedPolicy::Error`];
+    /// - paths that still cannot be converted are kept, with their
+    ///   separators switched to the target style;
+    /// - characters not allowed in the target style are replaced with the
+    ///   sanitizer's replacement, and characters outside ASCII follow its
+    ///   [`NonAscii`](crate::sanitize::NonAscii) setting.
+    ///
+    /// The sanitizer's target and length limits are not used. Paths that
+    /// [`convert`](Self::convert) accepts unchanged produce no warnings,
+    /// except for unmapped drives.
+    #[must_use]
+    pub fn convert_lossy_with(
+        &self,
+        path: &str,
+        target_style: PathStyle,
+        sanitizer: &Sanitizer,
+    ) -> (String, Vec<ConversionWarning>) {
+        let target_style = self.resolve_target(target_style);
+        let mut warnings = Vec::new();
+        let rewritten = self.config.rewriter.rewrite(path).unwrap_or_else(|error| {
+            warnings.push(ConversionWarning::RewriteSkipped(error));
+            Cow::Borrowed(path)
+        });
+        let source_style = self.detect_style(&rewritten).ok();
+
+        let mut relaxed = Cow::Borrowed(self);
+        if target_style == PathStyle::Windows
+            && source_style == Some(PathStyle::Unix)
+            && self.is_unmapped_unix_path(&rewritten)
+        {
+            let drive = match self.config.unmapped_policy {
+                UnmappedPolicy::DefaultDrive(drive) => Some(drive.to_ascii_uppercase()),
+                UnmappedPolicy::Error => {
+                    relaxed.to_mut().config.unmapped_policy = UnmappedPolicy::DefaultDrive('C');
+                    Some('C')
+                }
+                _ => None,
+            };
+            if let Some(drive) = drive {
+                warnings.push(ConversionWarning::UnmappedDrive { drive });
+            }
+        }
+
+        let result = match relaxed.convert_rewritten(&rewritten, PathStyle::Auto, target_style) {
+            Err(error) if self.config.backend != ConversionBackend::Builtin => {
+                relaxed.to_mut().config.backend = ConversionBackend::Builtin;
+                let result = relaxed.convert_rewritten(&rewritten, PathStyle::Auto, target_style);
+                if result.is_ok() {
+                    warnings.push(ConversionWarning::BackendMismatch(error));
+                }
+                result
+            }
+            result => result,
+        };
+        let converted = result.unwrap_or_else(|error| {
+            warnings.push(ConversionWarning::Unconverted(error));
+            if target_style == PathStyle::Windows {
+                rewritten.replace('/', "\\")
+            } else {
+                rewritten.replace('\\', "/")
+            }
+        });
+
+        // A colon in a Windows path that stays Windows names a data stream
+        let keep_streams = target_style == PathStyle::Windows
+            && source_style == Some(PathStyle::Windows)
+            && self.config.data_streams == DataStreamPolicy::Preserve;
+        let converted = replace_invalid_chars(
+            &converted,
+            target_style,
+            keep_streams,
+            sanitizer,
+            &mut warnings,
+        );
+        (converted, warnings)
+    }
+
+    /// Convert path to specified style, borrowing it if nothing changes
+    ///
+    /// Paths already in the canonical form of the target style (single
+    /// separators of the right kind, no trailing separator, upper-case drive
+    /// letter, no drive mapping that applies) are returned as
+    /// `Cow::Borrowed` without allocating. Other paths are converted as by
+    /// [`convert`](Self::convert).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted or the format is unsupported.
+    pub fn convert_cow<'a>(
+        &self,
+        path: &'a str,
+        target_style: PathStyle,
+    ) -> PathResult<Cow<'a, str>> {
+        let start = Instant::now();
         let source_style = self.detect_style(path)?;
+        let target_style = self.resolve_target(target_style);
+        if self.is_canonical(path, source_style, target_style) {
+            metrics::emit_for(&self.config, |m| {
+                m.record_conversion(source_style, target_style, start.elapsed());
+            });
+            return Ok(Cow::Borrowed(path));
+        }
+
+        let converted = self.convert_between(path, source_style, target_style)?;
+        Ok(if converted == path {
+            Cow::Borrowed(path)
+        } else {
+            Cow::Owned(converted)
+        })
+    }
 
-        if source_style == target_style {
-            // Even if styles match, we might want to normalize separators
-            match target_style {
-                PathStyle::Windows => return Ok(self.normalize_windows_path(path)),
-                PathStyle::Unix => return Ok(Self::normalize_unix_path(path)),
-                PathStyle::Auto => return Ok(path.to_string()),
+    /// Check if converting `path` would return it unchanged, without
+    /// converting it
+    ///
+    /// Only recognizes the common cases; `false` means "convert to find out".
+    fn is_canonical(&self, path: &str, source_style: PathStyle, target_style: PathStyle) -> bool {
+        let bytes = path.as_bytes();
+        if self.config.rewriter.matches(path) || self.config.trailing_slash == TrailingSlash::Ensure
+        {
+            return false;
+        }
+        match (source_style, target_style) {
+            (PathStyle::Unix, PathStyle::Unix) => {
+                !path.contains('\\')
+                    && !path.contains("//")
+                    && (path == "/" || !path.ends_with('/'))
+                    && !path.starts_with("/mnt/")
+                    && (!path.starts_with('/')
+                        || self.config.drive_mappings.find_unix(path).is_none())
+            }
+            (PathStyle::Windows, PathStyle::Windows) => {
+                let unc = path.starts_with(r"\\");
+                !path.contains('/')
+                    && !path[usize::from(unc) * 2..].contains(r"\\")
+                    && (path.len() <= 3 || !path.ends_with('\\'))
+                    && !bytes.first().is_some_and(u8::is_ascii_lowercase)
+                    && self.config.resolve_drive_relative(path).is_none()
+                    && self.strip_data_stream(path).is_none()
+                    && (!self.config.extended_length_prefix || path.len() <= WINDOWS_MAX_PATH)
             }
+            _ => false,
         }
+    }
 
+    /// Convert path written in a known style to specified style
+    ///
+    /// Needed for styles that are never auto-detected, such as
+    /// [`PathStyle::Msys`]. A `source_style` of `Auto` detects the style; a
+    /// `target_style` of `Auto` uses the configured style, or the current
+    /// platform if that is `Auto` as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted or the format is unsupported.
+    pub fn convert_between(
+        &self,
+        path: &str,
+        source_style: PathStyle,
+        target_style: PathStyle,
+    ) -> PathResult<String> {
+        // Rewrite rules run once, on the path as given; a rewritten path may
+        // be in another style
+        let rewritten = self.config.rewriter.rewrite(path)?;
+        let source_style = match rewritten {
+            Cow::Borrowed(_) => source_style,
+            Cow::Owned(_) => PathStyle::Auto,
+        };
+        self.convert_rewritten(&rewritten, source_style, target_style)
+    }
+
+    /// Convert path after the rewrite rules were applied
+    fn convert_rewritten(
+        &self,
+        path: &str,
+        source_style: PathStyle,
+        target_style: PathStyle,
+    ) -> PathResult<String> {
+        // SMB URLs stand for the UNC path they address
+        if url::is_smb_url(path) {
+            return self.convert_rewritten(
+                &url::smb_to_unc(path)?,
+                PathStyle::Windows,
+                target_style,
+            );
+        }
+
+        let start = Instant::now();
+        let source_style = match source_style {
+            PathStyle::Auto => self.detect_style(path)?,
+            style => style,
+        };
+        let target_style = self.resolve_target(target_style);
+        let windows = source_style == PathStyle::Windows;
+        // Device paths keep their separators, which can be significant
+        let trailing = (!windows || crate::parser::split_device_root(path).is_none()).then(|| {
+            let trailing = match source_style {
+                PathStyle::Windows => path.ends_with(['/', '\\']),
+                PathStyle::Custom(_) => false,
+                _ => path.ends_with('/'),
+            };
+            (trailing, crate::root_len(path, source_style) == path.len())
+        });
+        let stripped = windows.then(|| self.strip_data_stream(path)).flatten();
+        let path = stripped.as_deref().unwrap_or(path);
+        let resolved = windows
+            .then(|| self.config.resolve_drive_relative(path))
+            .flatten();
+        let path = resolved.as_deref().unwrap_or(path);
+        let mut result = self.convert_with_backend(path, source_style, target_style);
+        if let Some((trailing, root)) = trailing {
+            let policy = self.config.trailing_slash;
+            result = result.map(|converted| policy.apply(converted, target_style, trailing, root));
+        }
+        if target_style == PathStyle::Windows && self.config.extended_length_prefix {
+            result = result.map(add_extended_prefix);
+        }
+        metrics::emit_for(&self.config, |m| {
+            m.record_conversion(source_style, target_style, start.elapsed());
+        });
+        result
+    }
+
+    /// Convert path from a known source style through the configured
+    /// backend
+    fn convert_with_backend(
+        &self,
+        path: &str,
+        source_style: PathStyle,
+        target_style: PathStyle,
+    ) -> PathResult<String> {
+        match self.config.backend {
+            ConversionBackend::Builtin => self.convert_from(path, source_style, target_style),
+            ConversionBackend::System(tool) => {
+                match system_convert(tool, path, source_style, target_style) {
+                    Some((_, converted)) => Ok(converted),
+                    None => self.convert_from(path, source_style, target_style),
+                }
+            }
+            ConversionBackend::Verify(tool) => {
+                let builtin = self.convert_from(path, source_style, target_style)?;
+                match system_convert(tool, path, source_style, target_style) {
+                    Some((program, converted)) if converted != builtin => {
+                        Err(PathError::platform_error(format!(
+                            "{path} converts to {builtin}, but {program} gives {converted}"
+                        )))
+                    }
+                    _ => Ok(builtin),
+                }
+            }
+        }
+    }
+
+    /// Convert path from a known source style
+    ///
+    /// Covers every pair of styles. Identity conversions still normalize
+    /// separators and pass through drive mapping, so `c:/x` becomes `C:\x`
+    /// and a Unix path under a mapped root is rewritten to its configured
+    /// form.
+    fn convert_from(
+        &self,
+        path: &str,
+        source_style: PathStyle,
+        target_style: PathStyle,
+    ) -> PathResult<String> {
         match (source_style, target_style) {
+            (PathStyle::Auto, _) | (_, PathStyle::Auto) => {
+                self.convert_rewritten(path, source_style, target_style)
+            }
+            // Custom styles convert through Unix paths
+            (PathStyle::Custom(style), _) => {
+                let unix = style::to_unix(&*style::definition(style)?, path)?;
+                self.convert_from(&unix, PathStyle::Unix, target_style)
+            }
+            (_, PathStyle::Custom(style)) => {
+                let unix = self.convert_from(path, source_style, PathStyle::Unix)?;
+                style::from_unix(&*style::definition(style)?, &unix)
+            }
+            (PathStyle::Windows, PathStyle::Windows) => Ok(Self::windows_to_windows(path)),
             (PathStyle::Windows, PathStyle::Unix) => self.windows_to_unix(path),
-            (PathStyle::Unix, PathStyle::Windows) => Ok(self.unix_to_windows(path)),
-            _ => Err(PathError::UnsupportedFormat(format!(
-                "Unsupported conversion: {source_style:?} -> {target_style:?}"
-            ))),
+            (PathStyle::Unix, PathStyle::Windows) => self.unix_to_windows(path),
+            (PathStyle::Unix, PathStyle::Unix) => self.unix_to_unix(path),
+            (PathStyle::Windows, PathStyle::Cygwin | PathStyle::Msys) => {
+                self.windows_to_posix(path, target_style)
+            }
+            (PathStyle::Cygwin | PathStyle::Msys, PathStyle::Windows) => {
+                self.posix_to_windows(path, source_style)
+            }
+            (PathStyle::Unix, PathStyle::Cygwin | PathStyle::Msys) => {
+                let windows = self.unix_to_windows(path)?;
+                self.windows_to_posix(&windows, target_style)
+            }
+            (
+                PathStyle::Cygwin | PathStyle::Msys,
+                PathStyle::Unix | PathStyle::Cygwin | PathStyle::Msys,
+            ) => {
+                // Only drive paths leave the emulation layer's own namespace
+                let normalized = Self::normalize_unix_path(path);
+                if split_posix_drive(&normalized, source_style).is_some() {
+                    let windows = self.posix_to_windows(&normalized, source_style)?;
+                    self.convert_from(&windows, PathStyle::Windows, target_style)
+                } else {
+                    Ok(normalized)
+                }
+            }
+        }
+    }
+
+    /// Drop the alternate data stream of a Windows path if the
+    /// configuration says so
+    ///
+    /// Returns `None` if the path is kept as is.
+    fn strip_data_stream(&self, path: &str) -> Option<String> {
+        if self.config.data_streams != DataStreamPolicy::Strip {
+            return None;
+        }
+        let parsed = crate::PathParser::parse_ref(path);
+        let (stream, span) = (parsed.stream?, parsed.spans.last()?);
+        Some(format!(
+            "{}{}",
+            &path[..span.end],
+            &path[span.end + 1 + stream.len()..]
+        ))
+    }
+
+    /// Resolve `Auto` to the configured style, or the current platform
+    pub(crate) fn resolve_target(&self, style: PathStyle) -> PathStyle {
+        match (style, self.config.style) {
+            (PathStyle::Auto, PathStyle::Auto) => super::platform::current_style(),
+            (PathStyle::Auto, configured) => configured,
+            (style, _) => style,
+        }
+    }
+
+    /// Normalize a Windows path, upper-casing the drive letter
+    ///
+    /// Device paths only get their separators unified, since a trailing
+    /// separator or repeated separator can be significant to the device.
+    fn windows_to_windows(path: &str) -> String {
+        if crate::parser::split_device_root(path).is_some() {
+            return path.replace('/', "\\");
+        }
+        let normalized = Self::normalize_windows_path(path);
+        match Self::split_drive_path(&normalized) {
+            Some((drive, rest)) => format!("{}{rest}", drive.to_ascii_uppercase()),
+            None => normalized,
+        }
+    }
+
+    /// Normalize a Unix path, routing mapped paths through their drive
+    fn unix_to_unix(&self, path: &str) -> PathResult<String> {
+        let normalized = Self::normalize_unix_path(path);
+        if normalized.starts_with("//") {
+            return Ok(normalized);
+        }
+
+        match self.map_unix_path(&normalized) {
+            Some(windows) => Ok(Self::normalize_unix_path(&self.windows_to_unix(&windows)?)),
+            None => Ok(normalized),
         }
     }
 
@@ -58,13 +531,28 @@ impl PathConverter {
     ///
     /// Returns `PathError` if detection fails (though currently it always succeeds or returns default).
     pub fn detect_style(&self, path: &str) -> PathResult<PathStyle> {
-        // Check for Windows path
-        if self.windows_path_regex.is_match(path) {
+        // Registered custom styles are recognized by their root
+        if let Some(style) = style::detect(path) {
+            return Ok(style);
+        }
+
+        // Check for Windows path, including SMB URLs, device paths and the
+        // drive- and root-relative forms `C:foo` and `\foo`
+        if WINDOWS_PATH.is_match(path)
+            || url::is_smb_url(path)
+            || crate::parser::split_device_root(path).is_some()
+            || crate::parser::is_drive_relative(path)
+            || crate::parser::is_root_relative(path)
+        {
             return Ok(PathStyle::Windows);
         }
 
+        if split_posix_drive(path, PathStyle::Cygwin).is_some() {
+            return Ok(PathStyle::Cygwin);
+        }
+
         // Check for Unix path
-        if self.unix_path_regex.is_match(path) {
+        if UNIX_PATH.is_match(path) {
             return Ok(PathStyle::Unix);
         }
 
@@ -88,17 +576,26 @@ impl PathConverter {
 
     /// Convert Windows path to Unix
     fn windows_to_unix(&self, path: &str) -> PathResult<String> {
-        let normalized = self.normalize_windows_path(path);
+        reject_device_path(path)?;
+        let normalized = Self::normalize_windows_path(&strip_extended_prefix(path));
 
-        // Handle UNC paths
+        // Handle UNC paths, preferring configured share mappings
         if normalized.starts_with(r"\\") {
-            return Self::convert_unc_path(&normalized);
+            if let Some(unix_path) = self
+                .map_windows_to_unix(&normalized)
+                .or_else(|| self.map_wsl_to_unix(&normalized))
+            {
+                return Ok(unix_path);
+            }
+            return self.convert_unc_path(&normalized);
         }
 
-        // Handle drive letter paths
-        if let Some((drive, rest)) = self.split_drive_path(&normalized) {
-            let unix_path = self.map_drive_to_unix(&drive, &rest);
-            return Ok(unix_path);
+        // Handle drive letter paths, preferring configured drive and
+        // directory mappings
+        if let Some((drive, rest)) = Self::split_drive_path(&normalized) {
+            return Ok(self
+                .map_windows_to_unix(&normalized)
+                .unwrap_or_else(|| Self::default_drive_mount(&drive, &rest)));
         }
 
         // Handle relative paths
@@ -107,62 +604,151 @@ impl PathConverter {
     }
 
     /// Convert Unix path to Windows
-    fn unix_to_windows(&self, path: &str) -> String {
+    fn unix_to_windows(&self, path: &str) -> PathResult<String> {
         let normalized = Self::normalize_unix_path(path);
 
+        if let Some(mapped) = self.map_unix_path(&normalized) {
+            return Ok(mapped);
+        }
+
+        if normalized.starts_with('/') {
+            // Paths inside the configured WSL distribution
+            if let Some(distro) = &self.config.wsl_distro {
+                return Ok(wsl_unc(distro, &normalized));
+            }
+
+            // Absolute paths without a mapping follow the configured policy
+            return match &self.config.unmapped_policy {
+                UnmappedPolicy::DefaultDrive(drive) => {
+                    metrics::emit_for(&self.config, |m| {
+                        m.record_lossy_conversion(PathStyle::Unix, PathStyle::Windows);
+                    });
+                    Ok(format!(
+                        "{}:{}",
+                        drive.to_ascii_uppercase(),
+                        normalized.replace('/', "\\")
+                    ))
+                }
+                UnmappedPolicy::Error => Err(PathError::drive_mapping_error(format!(
+                    "No drive mapping for {normalized}"
+                ))
+                .with_path(normalized)),
+                UnmappedPolicy::Passthrough => Ok(normalized),
+                UnmappedPolicy::WslUnc { distro } => Ok(wsl_unc(distro, &normalized)),
+            };
+        }
+
+        // Relative paths
+        Ok(normalized.replace('/', "\\"))
+    }
+
+    /// Convert Windows path to Cygwin or MSYS form
+    fn windows_to_posix(&self, path: &str, style: PathStyle) -> PathResult<String> {
+        reject_device_path(path)?;
+        let normalized = Self::normalize_windows_path(&strip_extended_prefix(path));
+
+        if normalized.starts_with(r"\\") {
+            return self.convert_unc_path(&normalized);
+        }
+
+        if let Some((drive, rest)) = Self::split_drive_path(&normalized) {
+            let drive_letter = drive.chars().next().unwrap().to_ascii_lowercase();
+            return Ok(format!(
+                "{}{drive_letter}{}",
+                posix_drive_prefix(style),
+                rest.replace('\\', "/")
+            ));
+        }
+
+        Ok(normalized.replace('\\', "/"))
+    }
+
+    /// Convert Cygwin or MSYS path to Windows
+    ///
+    /// Paths outside the drive prefix are treated like Unix paths.
+    fn posix_to_windows(&self, path: &str, style: PathStyle) -> PathResult<String> {
+        let normalized = Self::normalize_unix_path(path);
+
+        match split_posix_drive(&normalized, style) {
+            Some((drive, rest)) => Ok(format!(
+                "{}:{}",
+                drive.to_ascii_uppercase(),
+                if rest.is_empty() {
+                    "\\".to_string()
+                } else {
+                    rest.replace('/', "\\")
+                }
+            )),
+            None => self.unix_to_windows(&normalized),
+        }
+    }
+
+    /// Map a normalized Unix path through UNC, configured and mount point rules
+    ///
+    /// Returns `None` if no rule applies.
+    fn map_unix_path(&self, normalized: &str) -> Option<String> {
         // Check for UNC paths (Unix style //server/share)
         if normalized.starts_with("//") {
-            return normalized.replace('/', "\\");
+            return Some(normalized.replace('/', "\\"));
         }
 
-        // Check for mapped drive paths
-        // Fix: Tuple is (Windows, Unix), so we must destructure as (windows_drive, unix_prefix)
-        for (windows_drive, unix_prefix) in &self.config.drive_mappings {
-            if normalized.starts_with(unix_prefix) {
-                let rest = &normalized[unix_prefix.len()..];
-                return format!("{}{}", windows_drive, rest.replace('/', "\\"));
-            }
+        // Check for mapped roots, preferring the most specific one so that
+        // `/Volumes/Data/x` beats a mapping for `/Volumes`
+        if let Some((windows_root, rest)) = self.config.drive_mappings.find_unix(normalized) {
+            let windows_root = windows_root.trim_end_matches(['\\', '/']);
+            let rest = if rest.is_empty() && DRIVE_LETTER.is_match(windows_root) {
+                "\\".to_string()
+            } else {
+                rest.replace('/', "\\")
+            };
+            return Some(format!("{windows_root}{rest}"));
+        }
+
+        // Per-server mount points of administrative shares
+        if self.config.admin_shares == AdminSharePolicy::MountPoint
+            && let Some(share) = admin_share_mount(normalized)
+        {
+            return Some(share);
         }
 
         // Handle regular Unix paths
         #[cfg(not(target_os = "windows"))]
         if normalized.starts_with("/mnt/")
-            && let Some((drive, rest)) = crate::platform::unix::parse_unix_mount_point(&normalized)
+            && let Some((drive, rest)) = crate::platform::unix::parse_unix_mount_point(normalized)
         {
             let drive_str: String = drive.to_ascii_uppercase().clone();
             let rest_str: String = rest.replace('/', "\\");
-            return format!(
+            return Some(format!(
                 "{}:{}{}",
                 drive_str,
                 rest_str,
                 if rest.is_empty() { "\\" } else { "" }
-            );
+            ));
         }
 
-        if normalized.starts_with('/') {
-            // For absolute paths, map to default drive
-            return format!("C:{}", normalized.replace('/', "\\"));
-        }
+        None
+    }
 
-        // Relative paths
-        normalized.replace('/', "\\")
+    /// Check if an absolute Unix path has no drive mapping
+    ///
+    /// Such paths are handled by the configured [`UnmappedPolicy`] when
+    /// converted to Windows. With a WSL distribution configured, directly
+    /// or through [`UnmappedPolicy::WslUnc`], every path is mapped.
+    pub(crate) fn is_unmapped_unix_path(&self, path: &str) -> bool {
+        let normalized = Self::normalize_unix_path(path);
+        normalized.starts_with('/')
+            && self.config.wsl_distro.is_none()
+            && !matches!(self.config.unmapped_policy, UnmappedPolicy::WslUnc { .. })
+            && self.map_unix_path(&normalized).is_none()
     }
 
     /// Normalize Windows path
-    fn normalize_windows_path(&self, path: &str) -> String {
-        let mut result = path.to_string();
-
-        // Unify separators
-        result = result.replace('/', "\\");
-
-        // Remove duplicate separators
-        while result.contains("\\\\") && !result.starts_with(r"\\") {
-            result = result.replace("\\\\", "\\");
-        }
+    fn normalize_windows_path(path: &str) -> String {
+        // Unify separators and remove duplicates, keeping UNC paths intact
+        let mut result = collapse_separators(path, '\\', '/');
 
         // Remove trailing separator (unless root path)
-        if result.ends_with('\\') && result.len() > 3 && !self.drive_letter_regex.is_match(&result)
-        {
+        if result.ends_with('\\') && result.len() > 3 && !DRIVE_LETTER.is_match(&result) {
             result.pop();
         }
 
@@ -171,15 +757,8 @@ impl PathConverter {
 
     /// Normalize Unix path
     fn normalize_unix_path(path: &str) -> String {
-        let mut result = path.to_string();
-
-        // Unify separators
-        result = result.replace('\\', "/");
-
-        // Remove duplicate separators
-        while result.contains("//") && !result.starts_with("//") {
-            result = result.replace("//", "/");
-        }
+        // Unify separators and remove duplicates, keeping `//` paths intact
+        let mut result = collapse_separators(path, '/', '\\');
 
         // Remove trailing separator (unless root path)
         if result.ends_with('/') && result != "/" {
@@ -190,47 +769,323 @@ impl PathConverter {
     }
 
     /// Split drive letter from path
-    fn split_drive_path(&self, path: &str) -> Option<(String, String)> {
-        if path.len() >= 2 {
-            let drive = &path[..2];
-            if self.drive_letter_regex.is_match(drive) {
-                let rest = if path.len() > 2 { &path[2..] } else { "" };
-                return Some((drive.to_string(), rest.to_string()));
-            }
+    ///
+    /// Drive-relative paths (`C:foo`) are not split: those with a
+    /// configured current directory were resolved before conversion, and
+    /// the rest have no absolute form, so they convert as relative paths.
+    fn split_drive_path(path: &str) -> Option<(String, String)> {
+        // `get` fails when the path starts with a multibyte character
+        let drive = path.get(..2)?;
+        if !crate::parser::is_drive_relative(path) && DRIVE_LETTER.is_match(drive) {
+            return Some((drive.to_string(), path[2..].to_string()));
         }
         None
     }
 
-    /// Map Windows drive letter to Unix path
-    fn map_drive_to_unix(&self, drive: &str, rest: &str) -> String {
-        // Look for mapping configuration
-        for (windows_drive, unix_mount) in &self.config.drive_mappings {
-            if windows_drive == drive {
-                return format!("{}{}", unix_mount, rest.replace('\\', "/"));
-            }
-        }
-
-        // Default mapping
+    /// Map an unmapped Windows drive to its default mount point under `/mnt`
+    fn default_drive_mount(drive: &str, rest: &str) -> String {
         let drive_letter = drive.chars().next().unwrap().to_ascii_lowercase();
         format!("/mnt/{}{}", drive_letter, rest.replace('\\', "/"))
     }
 
+    /// Map a Windows path through the most specific drive, directory or
+    /// share mapping, such as `C:\Users\me` ↔ `/home/me` or `\\nas\data` ↔
+    /// `/srv/data`
+    fn map_windows_to_unix(&self, path: &str) -> Option<String> {
+        self.config
+            .drive_mappings
+            .find_windows(path)
+            .map(|(unix_root, rest)| {
+                let unix_path = format!(
+                    "{}{}",
+                    unix_root.trim_end_matches('/'),
+                    rest.replace('\\', "/")
+                );
+                if unix_path.is_empty() {
+                    "/".to_string()
+                } else {
+                    unix_path
+                }
+            })
+    }
+
+    /// Map a WSL UNC path such as `\\wsl$\Ubuntu\home` to the in-distro
+    /// path `/home`
+    ///
+    /// Only the configured distribution matches, if one is set.
+    fn map_wsl_to_unix(&self, path: &str) -> Option<String> {
+        let (host, rest) = path.strip_prefix(r"\\")?.split_once('\\')?;
+        if !WSL_HOSTS.iter().any(|wsl| wsl.eq_ignore_ascii_case(host)) {
+            return None;
+        }
+
+        let (distro, rest) = rest.split_once('\\').unwrap_or((rest, ""));
+        if let Some(expected) = &self.config.wsl_distro
+            && !expected.eq_ignore_ascii_case(distro)
+        {
+            return None;
+        }
+        Some(format!("/{}", rest.replace('\\', "/")))
+    }
+
     /// Convert UNC path
-    fn convert_unc_path(path: &str) -> PathResult<String> {
+    ///
+    /// Accepts a bare server (`\\server`); administrative shares follow the
+    /// configured [`AdminSharePolicy`].
+    fn convert_unc_path(&self, path: &str) -> PathResult<String> {
         // UNC path format: \\server\share\path
-        let parts: Vec<&str> = path.split('\\').collect();
-        if parts.len() >= 4 {
-            let server = parts[2];
-            let share = parts[3];
-            let rest = if parts.len() > 4 {
-                parts[4..].join("/")
-            } else {
-                String::new()
-            };
-            let unix_path = format!("//{server}/{share}/{rest}");
-            return Ok(unix_path.trim_end_matches('/').to_string());
+        let mut parts = path
+            .strip_prefix(r"\\")
+            .unwrap_or(path)
+            .split('\\')
+            .filter(|part| !part.is_empty());
+        let Some(server) = parts.next() else {
+            return Err(PathError::parse_error(format!("Invalid UNC path: {path}")));
+        };
+        let share = parts.next();
+        let rest: Vec<&str> = parts.collect();
+        Ok(unc_to_unix(server, share, &rest, self.config.admin_shares))
+    }
+}
+
+/// Replace `other` with `separator` and collapse runs of separators in one
+/// pass
+///
+/// Paths starting with two separators (UNC and `//` network paths) are only
+/// unified, not collapsed.
+fn collapse_separators(path: &str, separator: char, other: char) -> String {
+    let is_separator = |c: char| c == separator || c == other;
+    let mut chars = path.chars();
+    let network = chars.next().is_some_and(is_separator) && chars.next().is_some_and(is_separator);
+
+    let mut result = String::with_capacity(path.len());
+    let mut previous_separator = false;
+    for c in path.chars() {
+        if is_separator(c) {
+            if network || !previous_separator {
+                result.push(separator);
+            }
+            previous_separator = true;
+        } else {
+            result.push(c);
+            previous_separator = false;
+        }
+    }
+    result
+}
+
+/// Write a UNC path in Unix form
+///
+/// Administrative shares (`C$`) are rewritten as `policy` says; other
+/// shares and bare servers become `//server/share` network paths.
+pub(crate) fn unc_to_unix<S: AsRef<str>>(
+    server: &str,
+    share: Option<&str>,
+    components: &[S],
+    policy: AdminSharePolicy,
+) -> String {
+    let mut result = match (share, share.and_then(crate::parser::admin_share_drive)) {
+        (_, Some(drive)) if policy == AdminSharePolicy::MountPoint => {
+            format!("/mnt/{server}/{}", drive.to_ascii_lowercase())
+        }
+        (Some(share), Some(_)) if policy == AdminSharePolicy::SmbUrl => {
+            let mut segments = vec![share];
+            segments.extend(components.iter().map(AsRef::as_ref));
+            return url::smb_url(server, &segments);
+        }
+        (Some(share), _) => format!("//{server}/{share}"),
+        (None, _) => format!("//{server}"),
+    };
+    for component in components {
+        result.push('/');
+        result.push_str(component.as_ref());
+    }
+    result
+}
+
+/// Map a per-server mount point (`/mnt/server/c/Users`) back to its
+/// administrative share (`\\server\C$\Users`)
+///
+/// Single-letter directories under `/mnt` are drives, not servers.
+fn admin_share_mount(path: &str) -> Option<String> {
+    let (server, rest) = path.strip_prefix("/mnt/")?.split_once('/')?;
+    let (drive, rest) = rest.split_once('/').unwrap_or((rest, ""));
+    let drive = match drive.as_bytes() {
+        &[letter] if letter.is_ascii_alphabetic() => char::from(letter.to_ascii_uppercase()),
+        _ => return None,
+    };
+    if server.len() < 2 {
+        return None;
+    }
+    let mut share = format!(r"\\{server}\{drive}$");
+    if !rest.is_empty() {
+        share.push('\\');
+        share.push_str(&rest.replace('/', "\\"));
+    }
+    Some(share)
+}
+
+/// Prefix placed before the drive letter by `Cygwin` and `Msys` styles
+pub(crate) fn posix_drive_prefix(style: PathStyle) -> &'static str {
+    match style {
+        PathStyle::Cygwin => "/cygdrive/",
+        _ => "/",
+    }
+}
+
+/// Split `/cygdrive/c/rest` (Cygwin) or `/c/rest` (MSYS) into the drive
+/// letter and the remainder
+pub(crate) fn split_posix_drive(path: &str, style: PathStyle) -> Option<(char, &str)> {
+    let rest = path.strip_prefix(posix_drive_prefix(style))?;
+    let mut chars = rest.chars();
+    let drive = chars.next().filter(char::is_ascii_alphabetic)?;
+    let rest = chars.as_str();
+    (rest.is_empty() || rest.starts_with('/')).then_some((drive, rest))
+}
+
+/// Convert an absolute path between Windows style and a Unix-like style
+/// with `wslpath` or `cygpath`
+///
+/// Returns the program and its output, or `None` if the tool does not
+/// cover the conversion, is not installed or fails.
+fn system_convert(
+    tool: SystemTool,
+    path: &str,
+    source_style: PathStyle,
+    target_style: PathStyle,
+) -> Option<(&'static str, String)> {
+    let program = match tool {
+        SystemTool::Wslpath => "wslpath",
+        SystemTool::Cygpath => "cygpath",
+        SystemTool::Detect => match crate::platform::detect_environment() {
+            RuntimeEnvironment::Wsl1 | RuntimeEnvironment::Wsl2 => "wslpath",
+            RuntimeEnvironment::Cygwin | RuntimeEnvironment::Msys => "cygpath",
+            _ => return None,
+        },
+    };
+    // `wslpath` only knows the Linux side; `cygpath` writes the POSIX form
+    // of its own installation
+    let posix = |style: PathStyle| match program {
+        "wslpath" => style == PathStyle::Unix,
+        _ => matches!(style, PathStyle::Unix | PathStyle::Cygwin | PathStyle::Msys),
+    };
+    let flag = match (source_style, target_style) {
+        (PathStyle::Windows, style) if posix(style) => "-u",
+        (style, PathStyle::Windows) if posix(style) => "-w",
+        _ => return None,
+    };
+    if !matches!(
+        crate::PathParser::parse_ref(path).kind,
+        PathKind::Absolute | PathKind::Unc
+    ) {
+        return None;
+    }
+
+    let output = std::process::Command::new(program)
+        .arg(flag)
+        .arg(path)
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let converted = String::from_utf8(output.stdout).ok()?;
+    let converted = converted.trim_end_matches(['\r', '\n']);
+    (!converted.is_empty()).then(|| (program, converted.to_string()))
+}
+
+/// Replace the characters of a converted path that `target_style` does not
+/// allow, or the sanitizer's non-ASCII policy rewrites, recording each
+fn replace_invalid_chars(
+    path: &str,
+    target_style: PathStyle,
+    keep_streams: bool,
+    sanitizer: &Sanitizer,
+    warnings: &mut Vec<ConversionWarning>,
+) -> String {
+    let root_len = crate::root_len(path, target_style);
+    let mut result = String::with_capacity(path.len());
+    result.push_str(&path[..root_len]);
+    for character in path[root_len..].chars() {
+        let separator =
+            character == '/' || (target_style == PathStyle::Windows && character == '\\');
+        let invalid = crate::parser::is_invalid_component_char(character, target_style)
+            && !(keep_streams && character == ':');
+        match (!separator)
+            .then(|| sanitizer.substitute(character, invalid))
+            .flatten()
+        {
+            Some(replacement) => {
+                result.push_str(&replacement);
+                warnings.push(ConversionWarning::ReplacedChar {
+                    character,
+                    index: result.len() - replacement.len(),
+                    replacement,
+                });
+            }
+            None => result.push(character),
         }
+    }
+    result
+}
 
-        Err(PathError::ParseError(format!("Invalid UNC path: {path}")))
+/// Fail with `PathError::UnsupportedFormat` if `path` is a Windows device
+/// or NT object path, which has no Unix equivalent
+pub(crate) fn reject_device_path(path: &str) -> PathResult<()> {
+    match crate::parser::split_device_root(path) {
+        Some((kind, root_len)) => Err(PathError::unsupported_format(format!(
+            "{path} is a Windows {} path ({}) and has no Unix equivalent",
+            if kind == crate::parser::PathKind::NtObject {
+                "NT object"
+            } else {
+                "device"
+            },
+            &path[..root_len]
+        ))
+        .with_path(path)
+        .with_span(0..root_len)),
+        None => Ok(()),
     }
 }
+
+/// Remove the extended-length prefix: `\\?\C:\x` becomes `C:\x` and
+/// `\\?\UNC\server\share` becomes `\\server\share`
+pub(crate) fn strip_extended_prefix(path: &str) -> Cow<'_, str> {
+    let Some(rest) = path.strip_prefix(r"\\?\") else {
+        return Cow::Borrowed(path);
+    };
+    match rest.get(..4) {
+        Some(unc) if unc.eq_ignore_ascii_case(r"UNC\") => Cow::Owned(format!(r"\\{}", &rest[4..])),
+        _ => Cow::Borrowed(rest),
+    }
+}
+
+/// Add the extended-length prefix to an absolute Windows path longer than
+/// [`WINDOWS_MAX_PATH`]
+///
+/// Paths that are relative, already prefixed, device paths (`\\.\`) or
+/// contain `.`/`..` components (which the prefix disables) are returned
+/// unchanged.
+pub(crate) fn add_extended_prefix(path: String) -> String {
+    if path.encode_utf16().count() <= WINDOWS_MAX_PATH
+        || path.starts_with(r"\\?\")
+        || path.starts_with(r"\\.\")
+        || path.split(['\\', '/']).any(|c| c == "." || c == "..")
+    {
+        return path;
+    }
+
+    if let Some(unc) = path.strip_prefix(r"\\") {
+        return format!(r"\\?\UNC\{unc}");
+    }
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && bytes[2] == b'\\' {
+        return format!(r"\\?\{path}");
+    }
+    path
+}
+
+/// Address an absolute Unix path inside a WSL distribution
+fn wsl_unc(distro: &str, path: &str) -> String {
+    let rest = if path == "/" { "" } else { path };
+    format!(r"\\wsl$\{distro}{}", rest.replace('/', "\\"))
+}
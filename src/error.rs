@@ -1,78 +1,404 @@
+use std::error::Error;
 use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
 
 /// Path error type
+///
+/// Every variant carries an [`ErrorDetail`] with the message and, where
+/// known, the offending path, the byte span of the offending part of it and
+/// the underlying error, which [`Error::source`] returns.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum PathError {
     /// Invalid path
-    InvalidPath(String),
+    InvalidPath(Box<ErrorDetail>),
 
     /// Encoding error
-    EncodingError(String),
+    EncodingError(Box<ErrorDetail>),
 
     /// Security error
-    SecurityError(String),
+    SecurityError(Box<ErrorDetail>),
 
     /// Platform-specific error
-    PlatformError(String),
+    PlatformError(Box<ErrorDetail>),
 
     /// Path normalization error
-    NormalizationError(String),
+    NormalizationError(Box<ErrorDetail>),
 
     /// Path parsing error
-    ParseError(String),
+    ParseError(Box<ErrorDetail>),
 
     /// IO error
-    IoError(String),
+    IoError(Box<ErrorDetail>),
 
     /// Unsupported path format
-    UnsupportedFormat(String),
+    UnsupportedFormat(Box<ErrorDetail>),
 
     /// Drive mapping error
-    DriveMappingError(String),
+    DriveMappingError(Box<ErrorDetail>),
+
+    /// Paths are on different drives or shares
+    CrossDriveError(Box<ErrorDetail>),
+}
+
+/// Category of a [`PathError`], without its details
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Invalid path
+    InvalidPath,
+    /// Encoding error
+    Encoding,
+    /// Security error
+    Security,
+    /// Platform-specific error
+    Platform,
+    /// Path normalization error
+    Normalization,
+    /// Path parsing error
+    Parse,
+    /// IO error
+    Io,
+    /// Unsupported path format
+    UnsupportedFormat,
+    /// Drive mapping error
+    DriveMapping,
+    /// Paths are on different drives or shares
+    CrossDrive,
+}
+
+/// Message and context of a [`PathError`]
+///
+/// Details compare equal when their message, path and span match; the
+/// sources are not compared.
+#[derive(Debug, Clone, Default)]
+pub struct ErrorDetail {
+    message: String,
+    path: Option<String>,
+    span: Option<Range<usize>>,
+    source: Option<Arc<dyn Error + Send + Sync>>,
+}
+
+impl ErrorDetail {
+    /// Error message
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Path the error is about
+    #[must_use]
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Byte range of the offending part of [`path`](Self::path)
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    /// Underlying error
+    #[must_use]
+    pub fn source(&self) -> Option<&(dyn Error + Send + Sync + 'static)> {
+        self.source.as_deref()
+    }
+}
+
+impl PartialEq for ErrorDetail {
+    fn eq(&self, other: &Self) -> bool {
+        self.message == other.message && self.path == other.path && self.span == other.span
+    }
+}
+
+impl Eq for ErrorDetail {}
+
+impl fmt::Display for ErrorDetail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
 }
 
 impl PathError {
+    /// Create new error of a kind
+    pub fn new(kind: ErrorKind, msg: impl Into<String>) -> Self {
+        let detail = Box::new(ErrorDetail {
+            message: msg.into(),
+            ..ErrorDetail::default()
+        });
+        match kind {
+            ErrorKind::InvalidPath => Self::InvalidPath(detail),
+            ErrorKind::Encoding => Self::EncodingError(detail),
+            ErrorKind::Security => Self::SecurityError(detail),
+            ErrorKind::Platform => Self::PlatformError(detail),
+            ErrorKind::Normalization => Self::NormalizationError(detail),
+            ErrorKind::Parse => Self::ParseError(detail),
+            ErrorKind::Io => Self::IoError(detail),
+            ErrorKind::UnsupportedFormat => Self::UnsupportedFormat(detail),
+            ErrorKind::DriveMapping => Self::DriveMappingError(detail),
+            ErrorKind::CrossDrive => Self::CrossDriveError(detail),
+        }
+    }
+
     /// Create new `InvalidPath` error
     pub fn invalid_path(msg: impl Into<String>) -> Self {
-        Self::InvalidPath(msg.into())
+        Self::new(ErrorKind::InvalidPath, msg)
     }
 
     /// Create new `EncodingError`
     pub fn encoding_error(msg: impl Into<String>) -> Self {
-        Self::EncodingError(msg.into())
+        Self::new(ErrorKind::Encoding, msg)
     }
 
     /// Create new `SecurityError`
     pub fn security_error(msg: impl Into<String>) -> Self {
-        Self::SecurityError(msg.into())
+        Self::new(ErrorKind::Security, msg)
     }
 
     /// Create new `PlatformError`
     pub fn platform_error(msg: impl Into<String>) -> Self {
-        Self::PlatformError(msg.into())
+        Self::new(ErrorKind::Platform, msg)
+    }
+
+    /// Create new `NormalizationError`
+    pub fn normalization_error(msg: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Normalization, msg)
+    }
+
+    /// Create new `ParseError`
+    pub fn parse_error(msg: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Parse, msg)
+    }
+
+    /// Create new `IoError`
+    pub fn io_error(msg: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Io, msg)
+    }
+
+    /// Create new `UnsupportedFormat` error
+    pub fn unsupported_format(msg: impl Into<String>) -> Self {
+        Self::new(ErrorKind::UnsupportedFormat, msg)
+    }
+
+    /// Create new `DriveMappingError`
+    pub fn drive_mapping_error(msg: impl Into<String>) -> Self {
+        Self::new(ErrorKind::DriveMapping, msg)
+    }
+
+    /// Create new `CrossDriveError`
+    pub fn cross_drive_error(msg: impl Into<String>) -> Self {
+        Self::new(ErrorKind::CrossDrive, msg)
+    }
+
+    /// Attach the path the error is about
+    #[must_use]
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.detail_mut().path = Some(path.into());
+        self
+    }
+
+    /// Attach the byte range of the offending part of the path
+    #[must_use]
+    pub fn with_span(mut self, span: Range<usize>) -> Self {
+        self.detail_mut().span = Some(span);
+        self
+    }
+
+    /// Attach the underlying error
+    #[must_use]
+    pub fn with_source(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        self.detail_mut().source = Some(Arc::new(source));
+        self
+    }
+
+    /// Prefix the message with `context`, keeping the kind and details
+    #[must_use]
+    pub fn with_context(mut self, context: impl fmt::Display) -> Self {
+        let detail = self.detail_mut();
+        detail.message = format!("{context}: {}", detail.message);
+        self
+    }
+
+    /// Category of the error
+    #[must_use]
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::InvalidPath(_) => ErrorKind::InvalidPath,
+            Self::EncodingError(_) => ErrorKind::Encoding,
+            Self::SecurityError(_) => ErrorKind::Security,
+            Self::PlatformError(_) => ErrorKind::Platform,
+            Self::NormalizationError(_) => ErrorKind::Normalization,
+            Self::ParseError(_) => ErrorKind::Parse,
+            Self::IoError(_) => ErrorKind::Io,
+            Self::UnsupportedFormat(_) => ErrorKind::UnsupportedFormat,
+            Self::DriveMappingError(_) => ErrorKind::DriveMapping,
+            Self::CrossDriveError(_) => ErrorKind::CrossDrive,
+        }
+    }
+
+    /// Message and context of the error
+    #[must_use]
+    pub fn detail(&self) -> &ErrorDetail {
+        match self {
+            Self::InvalidPath(detail)
+            | Self::EncodingError(detail)
+            | Self::SecurityError(detail)
+            | Self::PlatformError(detail)
+            | Self::NormalizationError(detail)
+            | Self::ParseError(detail)
+            | Self::IoError(detail)
+            | Self::UnsupportedFormat(detail)
+            | Self::DriveMappingError(detail)
+            | Self::CrossDriveError(detail) => detail,
+        }
+    }
+
+    fn detail_mut(&mut self) -> &mut ErrorDetail {
+        match self {
+            Self::InvalidPath(detail)
+            | Self::EncodingError(detail)
+            | Self::SecurityError(detail)
+            | Self::PlatformError(detail)
+            | Self::NormalizationError(detail)
+            | Self::ParseError(detail)
+            | Self::IoError(detail)
+            | Self::UnsupportedFormat(detail)
+            | Self::DriveMappingError(detail)
+            | Self::CrossDriveError(detail) => detail,
+        }
+    }
+
+    /// Error message, without the category prefix of `Display`
+    #[must_use]
+    pub fn message(&self) -> &str {
+        self.detail().message()
+    }
+
+    /// Path the error is about
+    #[must_use]
+    pub fn path(&self) -> Option<&str> {
+        self.detail().path()
+    }
+
+    /// Byte range of the offending part of [`path`](Self::path)
+    #[must_use]
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.detail().span()
+    }
+
+    /// Kind of the underlying `io::Error`, if there is one
+    #[must_use]
+    pub fn io_error_kind(&self) -> Option<std::io::ErrorKind> {
+        self.detail()
+            .source()?
+            .downcast_ref::<std::io::Error>()
+            .map(std::io::Error::kind)
     }
 }
 
 impl fmt::Display for PathError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::InvalidPath(msg) => write!(f, "Invalid path: {msg}"),
-            Self::EncodingError(msg) => write!(f, "Encoding error: {msg}"),
-            Self::SecurityError(msg) => write!(f, "Security error: {msg}"),
-            Self::PlatformError(msg) => write!(f, "Platform error: {msg}"),
-            Self::NormalizationError(msg) => write!(f, "Normalization error: {msg}"),
-            Self::ParseError(msg) => write!(f, "Parse error: {msg}"),
-            Self::IoError(msg) => write!(f, "IO error: {msg}"),
-            Self::UnsupportedFormat(msg) => write!(f, "Unsupported format: {msg}"),
-            Self::DriveMappingError(msg) => write!(f, "Drive mapping error: {msg}"),
-        }
+        let prefix = match self.kind() {
+            ErrorKind::InvalidPath => "Invalid path",
+            ErrorKind::Encoding => "Encoding error",
+            ErrorKind::Security => "Security error",
+            ErrorKind::Platform => "Platform error",
+            ErrorKind::Normalization => "Normalization error",
+            ErrorKind::Parse => "Parse error",
+            ErrorKind::Io => "IO error",
+            ErrorKind::UnsupportedFormat => "Unsupported format",
+            ErrorKind::DriveMapping => "Drive mapping error",
+            ErrorKind::CrossDrive => "Cross-drive error",
+        };
+        write!(f, "{prefix}: {}", self.message())
     }
 }
 
-impl std::error::Error for PathError {}
+impl Error for PathError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.detail()
+            .source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
 
 impl From<std::io::Error> for PathError {
     fn from(err: std::io::Error) -> Self {
-        Self::IoError(err.to_string())
+        Self::io_error(err.to_string()).with_source(err)
+    }
+}
+
+/// Annotated rendering of errors, underlining the offending part of the path
+#[cfg(feature = "diagnostics")]
+impl PathError {
+    /// Render the error as a diagnostic with the path and the offending
+    /// span underlined, followed by the chain of underlying errors
+    ///
+    /// `color` adds ANSI colors for terminals. Errors without a path render
+    /// as their message.
+    #[must_use]
+    pub fn render(&self, color: bool) -> String {
+        use miette::{GraphicalReportHandler, GraphicalTheme};
+
+        let theme = if color {
+            GraphicalTheme::unicode()
+        } else {
+            GraphicalTheme::unicode_nocolor()
+        };
+        let mut rendered = String::new();
+        if GraphicalReportHandler::new_themed(theme)
+            .render_report(&mut rendered, self)
+            .is_err()
+        {
+            return self.to_string();
+        }
+        rendered
+    }
+
+    /// Label under the offending span
+    fn label(&self) -> &'static str {
+        match self.kind() {
+            ErrorKind::Security => "rejected here",
+            ErrorKind::InvalidPath | ErrorKind::Parse => "invalid here",
+            ErrorKind::UnsupportedFormat => "not supported",
+            ErrorKind::Normalization => "cannot be normalized",
+            _ => "here",
+        }
+    }
+}
+
+#[cfg(feature = "diagnostics")]
+impl miette::Diagnostic for PathError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let code = match self.kind() {
+            ErrorKind::InvalidPath => "cross_path::invalid_path",
+            ErrorKind::Encoding => "cross_path::encoding",
+            ErrorKind::Security => "cross_path::security",
+            ErrorKind::Platform => "cross_path::platform",
+            ErrorKind::Normalization => "cross_path::normalization",
+            ErrorKind::Parse => "cross_path::parse",
+            ErrorKind::Io => "cross_path::io",
+            ErrorKind::UnsupportedFormat => "cross_path::unsupported_format",
+            ErrorKind::DriveMapping => "cross_path::drive_mapping",
+            ErrorKind::CrossDrive => "cross_path::cross_drive",
+        };
+        Some(Box::new(code))
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        self.detail()
+            .path
+            .as_ref()
+            .map(|path| path as &dyn miette::SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let span = self.span()?;
+        Some(Box::new(std::iter::once(miette::LabeledSpan::at(
+            span,
+            self.label(),
+        ))))
     }
 }
@@ -0,0 +1,120 @@
+//! Environment variable and tilde expansion
+//!
+//! Expands `%VAR%`, `${VAR}`, `$VAR` and a leading `~` in path strings. The
+//! variables come from an [`Environment`], which is either the process
+//! environment ([`SystemEnvironment`]) or any `Fn(&str) -> Option<String>`
+//! closure, so expansion can be tested without touching the real
+//! environment.
+
+use crate::{PathError, PathResult};
+
+/// Source of environment variables
+pub trait Environment {
+    /// Look up a variable
+    fn var(&self, name: &str) -> Option<String>;
+
+    /// Home directory used for `~`
+    ///
+    /// Defaults to `HOME`, falling back to `USERPROFILE`.
+    fn home_dir(&self) -> Option<String> {
+        self.var("HOME").or_else(|| self.var("USERPROFILE"))
+    }
+}
+
+/// The process environment
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemEnvironment;
+
+impl Environment for SystemEnvironment {
+    fn var(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok()
+    }
+}
+
+impl<F> Environment for F
+where
+    F: Fn(&str) -> Option<String>,
+{
+    fn var(&self, name: &str) -> Option<String> {
+        self(name)
+    }
+}
+
+/// Expand variables and a leading `~` in a path string
+///
+/// A `%` or `$` that does not start a well-formed reference is kept as is;
+/// `~user` forms are not expanded.
+///
+/// # Errors
+///
+/// Returns `PathError` if a referenced variable (or the home directory for
+/// `~`) is not defined.
+pub fn expand<E: Environment + ?Sized>(text: &str, env: &E) -> PathResult<String> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    if let Some(after) = text.strip_prefix('~')
+        && (after.is_empty() || after.starts_with(['/', '\\']))
+    {
+        let home = env
+            .home_dir()
+            .ok_or_else(|| PathError::invalid_path("Cannot expand ~: home directory unknown"))?;
+        result.push_str(&home);
+        rest = after;
+    }
+
+    while let Some(pos) = rest.find(['%', '$']) {
+        result.push_str(&rest[..pos]);
+        let Some((name, consumed)) = variable_reference(&rest[pos..]) else {
+            result.push_str(&rest[pos..=pos]);
+            rest = &rest[pos + 1..];
+            continue;
+        };
+        let value = env.var(name).ok_or_else(|| {
+            PathError::invalid_path(format!("Undefined environment variable: {name}"))
+        })?;
+        result.push_str(&value);
+        rest = &rest[pos + consumed..];
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+/// Parse a variable reference at the start of `text`
+///
+/// Returns the variable name and the length of the reference.
+fn variable_reference(text: &str) -> Option<(&str, usize)> {
+    if let Some(body) = text.strip_prefix('%') {
+        let end = body.find('%')?;
+        let name = &body[..end];
+        return is_windows_name(name).then_some((name, end + 2));
+    }
+
+    let body = text.strip_prefix('$')?;
+    if let Some(braced) = body.strip_prefix('{') {
+        let end = braced.find('}')?;
+        let name = &braced[..end];
+        return is_unix_name(name).then_some((name, end + 3));
+    }
+
+    let end = body
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(body.len());
+    let name = &body[..end];
+    is_unix_name(name).then_some((name, end + 1))
+}
+
+/// Check for a POSIX variable name
+fn is_unix_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Check for a Windows variable name such as `ProgramFiles(x86)`
+fn is_windows_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '(' | ')'))
+}
@@ -0,0 +1,211 @@
+//! File extension helpers
+//!
+//! Extension handling that works on the path as written rather than the
+//! native `Path`, so Windows paths behave the same on every platform:
+//! backslash separators are honoured, trailing dots and spaces (which
+//! Windows strips) are ignored, and compound extensions such as `.tar.gz`
+//! are recognized.
+
+use crate::PathStyle;
+
+/// Extensions made of several dot-separated parts, the default of
+/// [`PathConfig::compound_extensions`](crate::PathConfig::compound_extensions)
+pub const COMPOUND_EXTENSIONS: [&str; 9] = [
+    "tar.gz", "tar.bz2", "tar.xz", "tar.zst", "tar.lz", "tar.lzma", "tar.z", "tar.br", "d.ts",
+];
+
+/// Last component of a path written in `style`
+#[must_use]
+pub fn file_name(path: &str, style: PathStyle) -> Option<&str> {
+    let trimmed = match style {
+        PathStyle::Windows => path.trim_end_matches(['/', '\\']),
+        _ => path.trim_end_matches('/'),
+    };
+    let name = match style {
+        PathStyle::Windows => trimmed.rsplit(['/', '\\']).next(),
+        _ => trimmed.rsplit('/').next(),
+    }?;
+    if style == PathStyle::Windows && name.len() == 2 && name.ends_with(':') {
+        return None;
+    }
+
+    (!name.is_empty() && name != "." && name != "..").then_some(name)
+}
+
+/// Last extension of a file name, without the dot
+///
+/// Leading dots (hidden files such as `.bashrc`) do not start an extension.
+/// For Windows names, trailing dots and spaces are ignored.
+#[must_use]
+pub fn extension(name: &str, style: PathStyle) -> Option<&str> {
+    let body = stored_name(name, style).trim_start_matches('.');
+    let (stem, extension) = body.rsplit_once('.')?;
+    (!stem.is_empty() && !extension.is_empty()).then_some(extension)
+}
+
+/// File name without its last extension
+///
+/// For Windows names, trailing dots and spaces are ignored.
+#[must_use]
+pub fn file_stem(name: &str, style: PathStyle) -> Option<&str> {
+    let stored = stored_name(name, style);
+    let stem = match extension(name, style) {
+        Some(extension) => &stored[..stored.len() - extension.len() - 1],
+        None => stored,
+    };
+    (!stem.is_empty()).then_some(stem)
+}
+
+/// Full extension of a file name, without the leading dot
+///
+/// Recognizes [`COMPOUND_EXTENSIONS`] (case-insensitively), so
+/// `archive.tar.gz` yields `tar.gz` while `v1.2.zip` yields `zip`.
+#[must_use]
+pub fn extension_full(name: &str, style: PathStyle) -> Option<&str> {
+    extension_full_with(name, style, &COMPOUND_EXTENSIONS)
+}
+
+/// Full extension of a file name, recognizing the given compound
+/// extensions
+///
+/// Compound extensions may include a leading dot and match
+/// case-insensitively; the longest one that fits wins.
+#[must_use]
+pub fn extension_full_with<'n, S: AsRef<str>>(
+    name: &'n str,
+    style: PathStyle,
+    compound: &[S],
+) -> Option<&'n str> {
+    let body = stored_name(name, style).trim_start_matches('.');
+    let lower = body.to_ascii_lowercase();
+
+    let longest = compound
+        .iter()
+        .map(|compound| {
+            compound
+                .as_ref()
+                .trim_start_matches('.')
+                .to_ascii_lowercase()
+        })
+        .filter(|compound| {
+            body.len()
+                .checked_sub(compound.len() + 1)
+                .is_some_and(|dot| {
+                    dot > 0 && lower.ends_with(compound.as_str()) && lower.as_bytes()[dot] == b'.'
+                })
+        })
+        .map(|compound| compound.len())
+        .max();
+    match longest {
+        Some(len) => Some(&body[body.len() - len..]),
+        None => extension(name, style),
+    }
+}
+
+/// Stems of a file name, from the name without its last extension to the
+/// name without its full extension
+///
+/// `backup.tar.gz` yields `backup.tar` and `backup`; names with a simple
+/// extension yield one stem, and names without one yield themselves.
+pub fn stems<'n, S: AsRef<str>>(
+    name: &'n str,
+    style: PathStyle,
+    compound: &[S],
+) -> impl Iterator<Item = &'n str> + use<'n, S> {
+    let stored = stored_name(name, style);
+    let stem = file_stem(name, style);
+    let full = extension_full_with(name, style, compound)
+        .map(|extension| &stored[..stored.len() - extension.len() - 1])
+        .filter(|full| Some(*full) != stem);
+    stem.into_iter().chain(full)
+}
+
+/// Compare two extensions (with or without leading dot)
+///
+/// Windows compares case-insensitively, Unix case-sensitively.
+#[must_use]
+pub fn extensions_match(a: &str, b: &str, style: PathStyle) -> bool {
+    let (a, b) = (a.trim_start_matches('.'), b.trim_start_matches('.'));
+    match style {
+        PathStyle::Windows => a.eq_ignore_ascii_case(b),
+        _ => a == b,
+    }
+}
+
+/// Name as stored by the file system (Windows drops trailing dots and spaces)
+fn stored_name(name: &str, style: PathStyle) -> &str {
+    match style {
+        PathStyle::Windows => name.trim_end_matches(['.', ' ']),
+        _ => name,
+    }
+}
+
+/// Look up the MIME type for an extension (case-insensitive)
+///
+/// Uses a small built-in table of common types; compound extensions are
+/// looked up as a whole first, then by their last part.
+#[cfg(feature = "mime")]
+#[must_use]
+pub fn mime_type(extension: &str) -> Option<&'static str> {
+    let extension = extension.trim_start_matches('.').to_ascii_lowercase();
+    let lookup = |ext: &str| {
+        MIME_TYPES
+            .iter()
+            .find(|(e, _)| *e == ext)
+            .map(|(_, mime)| *mime)
+    };
+    lookup(&extension).or_else(|| lookup(extension.rsplit('.').next()?))
+}
+
+/// Built-in extension to MIME type table
+#[cfg(feature = "mime")]
+const MIME_TYPES: [(&str, &str); 42] = [
+    ("7z", "application/x-7z-compressed"),
+    ("avif", "image/avif"),
+    ("bmp", "image/bmp"),
+    ("bz2", "application/x-bzip2"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("doc", "application/msword"),
+    (
+        "docx",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    ),
+    ("exe", "application/vnd.microsoft.portable-executable"),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("ico", "image/vnd.microsoft.icon"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("md", "text/markdown"),
+    ("mjs", "text/javascript"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("ppt", "application/vnd.ms-powerpoint"),
+    (
+        "pptx",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    ),
+    ("rar", "application/vnd.rar"),
+    ("svg", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("tar.gz", "application/gzip"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("toml", "application/toml"),
+    ("ts", "text/typescript"),
+    ("txt", "text/plain"),
+    ("wasm", "application/wasm"),
+    ("wav", "audio/wav"),
+    ("webp", "image/webp"),
+    ("xls", "application/vnd.ms-excel"),
+    ("xml", "application/xml"),
+    ("xz", "application/x-xz"),
+    ("zip", "application/zip"),
+];
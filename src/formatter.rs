@@ -1,8 +1,39 @@
-use crate::parser::ParsedPath;
-use crate::{PathConfig, PathResult, PathStyle};
+use crate::converter::{add_extended_prefix, posix_drive_prefix, unc_to_unix};
+use crate::parser::{ParsedPath, ParsedPathRef, PathKind};
+use crate::{
+    DataStreamPolicy, PathConfig, PathError, PathParser, PathResult, PathStyle, UnmappedPolicy,
+    style,
+};
+use std::borrow::Cow;
 use std::fmt;
 use std::fmt::Write;
 
+/// Shell or command interpreter a path is quoted for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Shell {
+    /// POSIX shells: bash, zsh, dash and `sh`
+    Bash,
+    /// PowerShell and `pwsh`
+    PowerShell,
+    /// `cmd.exe` batch files, with delayed expansion disabled (the default)
+    Cmd,
+    /// The fish shell
+    Fish,
+}
+
+/// Build file format a path is escaped for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum BuildSystem {
+    /// Makefile targets and prerequisites
+    Make,
+    /// Ninja build files
+    Ninja,
+    /// `CMakeLists.txt` and other `CMake` scripts
+    CMake,
+    /// `MSBuild` project files (`.vcxproj`, `.props`, ...)
+    MsBuild,
+}
+
 /// Path formatter for generating styled path strings
 #[derive(Debug, Clone)]
 pub struct PathFormatter {
@@ -20,24 +51,288 @@ impl PathFormatter {
 
     /// Format parsed path with specified style
     ///
+    /// Drive-relative paths (`C:foo`) are resolved against the current
+    /// directory configured for their drive in
+    /// [`PathConfig::drive_current_dirs`]; without one they keep their
+    /// drive prefix (`C:foo/bar` in Unix style).
+    ///
     /// # Errors
     ///
     /// Returns `PathError` if formatting fails (e.g., invalid components).
     pub fn format(&self, parsed: &ParsedPath, target_style: PathStyle) -> PathResult<String> {
+        let parts = Parts {
+            components: &parsed.components,
+            kind: parsed.kind,
+            is_absolute: parsed.is_absolute,
+            has_drive: parsed.has_drive,
+            drive_letter: parsed.drive_letter,
+            is_unc: parsed.is_unc,
+            server: parsed.server.as_deref(),
+            share: parsed.share.as_deref(),
+            device: parsed.device.as_deref(),
+            stream: parsed.stream.as_deref(),
+            trailing: parsed.original.ends_with(['/', '\\']),
+        };
+        self.format_parts(&parts, target_style)
+    }
+
+    /// Format borrowed parsed path with specified style
+    ///
+    /// Produces the same output as [`format`](Self::format) without
+    /// requiring owned components, see
+    /// [`PathParser::parse_ref`](crate::PathParser::parse_ref).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if formatting fails (e.g., invalid components).
+    pub fn format_ref(
+        &self,
+        parsed: &ParsedPathRef<'_>,
+        target_style: PathStyle,
+    ) -> PathResult<String> {
+        let parts = Parts {
+            components: &parsed.components,
+            kind: parsed.kind,
+            is_absolute: parsed.is_absolute,
+            has_drive: parsed.has_drive,
+            drive_letter: parsed.drive_letter,
+            is_unc: parsed.is_unc,
+            server: parsed.server,
+            share: parsed.share,
+            device: parsed.device,
+            stream: parsed.stream,
+            trailing: parsed.original.ends_with(['/', '\\']),
+        };
+        self.format_parts(&parts, target_style)
+    }
+
+    /// Quote a path so `shell` passes it to a command as a single, literal
+    /// argument
+    ///
+    /// Paths made only of characters the shell never interprets are
+    /// returned as they are. Otherwise Bash paths are put in single quotes,
+    /// with `'` written as `'\''`; fish paths also in single quotes, with
+    /// `'` and `\` backslash-escaped; PowerShell paths in single quotes,
+    /// with quotes doubled; and `cmd.exe` paths in double quotes, with `%`
+    /// doubled so it is not expanded in batch files.
+    ///
+    /// ```rust
+    /// use cross_path::{PathFormatter, Shell};
+    ///
+    /// let quote = PathFormatter::quote_for;
+    /// assert_eq!(quote("/usr/bin", Shell::Bash), "/usr/bin");
+    /// assert_eq!(quote("/tmp/it's $HOME!", Shell::Bash), r"'/tmp/it'\''s $HOME!'");
+    /// assert_eq!(quote(r"C:\Program Files (x86)", Shell::PowerShell), r"'C:\Program Files (x86)'");
+    /// assert_eq!(quote(r"C:\100% done", Shell::Cmd), r#""C:\100%% done""#);
+    /// ```
+    #[must_use]
+    pub fn quote_for(path: &str, shell: Shell) -> Cow<'_, str> {
+        if !path.is_empty()
+            && path
+                .chars()
+                .enumerate()
+                .all(|(i, c)| is_literal(shell, i, c))
+        {
+            return Cow::Borrowed(path);
+        }
+
+        let mut quoted = String::with_capacity(path.len() + 2);
+        match shell {
+            Shell::Bash => {
+                quoted.push('\'');
+                quoted.push_str(&path.replace('\'', r"'\''"));
+                quoted.push('\'');
+            }
+            Shell::Fish => {
+                quoted.push('\'');
+                for c in path.chars() {
+                    if matches!(c, '\'' | '\\') {
+                        quoted.push('\\');
+                    }
+                    quoted.push(c);
+                }
+                quoted.push('\'');
+            }
+            Shell::PowerShell => {
+                quoted.push('\'');
+                for c in path.chars() {
+                    // PowerShell also accepts typographic single quotes
+                    if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+                        quoted.push(c);
+                    }
+                    quoted.push(c);
+                }
+                quoted.push('\'');
+            }
+            Shell::Cmd => {
+                quoted.push('"');
+                for c in path.chars() {
+                    match c {
+                        '%' => quoted.push_str("%%"),
+                        '"' => quoted.push_str("\"\""),
+                        c => quoted.push(c),
+                    }
+                }
+                quoted.push('"');
+            }
+        }
+        Cow::Owned(quoted)
+    }
+
+    /// Escape a path for use in a build file
+    ///
+    /// - `Make` escapes spaces and `#` with `\` and doubles `$`.
+    /// - `Ninja` writes `$`, spaces and `:` as `$$`, `$ ` and `$:`.
+    /// - `CMake` switches to forward slashes, which `CMake` expects even on
+    ///   Windows, and quotes paths containing spaces or other special
+    ///   characters, escaping `"`, `$` and the `;` list separator inside.
+    /// - `MsBuild` `%`-encodes the characters `MSBuild` treats specially
+    ///   (`$ @ ; ' ? * %`) and writes `&`, `<`, `>` and `"` as XML
+    ///   entities.
+    ///
+    /// ```rust
+    /// use cross_path::{BuildSystem, PathFormatter};
+    ///
+    /// let escape = PathFormatter::escape_for;
+    /// assert_eq!(escape("/src/my app", BuildSystem::Make), r"/src/my\ app");
+    /// assert_eq!(escape(r"C:\my app", BuildSystem::Ninja), r"C$:\my$ app");
+    /// assert_eq!(escape(r"C:\my app", BuildSystem::CMake), r#""C:/my app""#);
+    /// assert_eq!(escape(r"C:\R&D\50%", BuildSystem::MsBuild), r"C:\R&amp;D\50%25");
+    /// ```
+    #[must_use]
+    pub fn escape_for(path: &str, build: BuildSystem) -> Cow<'_, str> {
+        match build {
+            BuildSystem::Make => escape_chars(path, |c| match c {
+                ' ' => Some(r"\ "),
+                '#' => Some(r"\#"),
+                '$' => Some("$$"),
+                _ => None,
+            }),
+            BuildSystem::Ninja => escape_chars(path, |c| match c {
+                ' ' => Some("$ "),
+                ':' => Some("$:"),
+                '$' => Some("$$"),
+                _ => None,
+            }),
+            BuildSystem::CMake => {
+                let path = path.replace('\\', "/");
+                let plain = !path.is_empty()
+                    && path.chars().all(|c| {
+                        c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '/' | ':' | '-' | '+')
+                    });
+                if plain {
+                    return Cow::Owned(path);
+                }
+                let escaped = escape_chars(&path, |c| match c {
+                    '"' => Some(r#"\""#),
+                    '$' => Some(r"\$"),
+                    ';' => Some(r"\;"),
+                    _ => None,
+                });
+                Cow::Owned(format!("\"{escaped}\""))
+            }
+            BuildSystem::MsBuild => escape_chars(path, |c| match c {
+                '%' => Some("%25"),
+                '$' => Some("%24"),
+                '@' => Some("%40"),
+                ';' => Some("%3B"),
+                '\'' => Some("%27"),
+                '?' => Some("%3F"),
+                '*' => Some("%2A"),
+                '&' => Some("&amp;"),
+                '<' => Some("&lt;"),
+                '>' => Some("&gt;"),
+                '"' => Some("&quot;"),
+                _ => None,
+            }),
+        }
+    }
+
+    fn format_parts<S: AsRef<str>>(
+        &self,
+        parsed: &Parts<'_, S>,
+        target_style: PathStyle,
+    ) -> PathResult<String> {
+        if parsed.kind == PathKind::DriveRelative
+            && let Some(drive) = parsed.drive_letter
+            && let Some(mut resolved) = self.config.drive_current_dir(drive)
+        {
+            if !resolved.ends_with('\\') {
+                resolved.push('\\');
+            }
+            join_into(&mut resolved, parsed.components, '\\');
+            return self.format_ref(&PathParser::parse_ref(&resolved), target_style);
+        }
+
+        if parsed.kind.is_device() && !matches!(target_style, PathStyle::Windows | PathStyle::Auto)
+        {
+            return Err(PathError::unsupported_format(format!(
+                "Windows device path {} has no {target_style:?} equivalent",
+                parsed.device.unwrap_or_default()
+            )));
+        }
+
         match target_style {
-            PathStyle::Windows => Ok(self.format_windows(parsed)),
-            PathStyle::Unix => Ok(self.format_unix(parsed)),
+            PathStyle::Windows if self.config.extended_length_prefix => Ok(add_extended_prefix(
+                self.finish(self.format_windows(parsed)?, target_style, parsed),
+            )),
+            PathStyle::Windows => {
+                Ok(self.finish(self.format_windows(parsed)?, target_style, parsed))
+            }
+            PathStyle::Unix => Ok(self.finish(self.format_unix(parsed), target_style, parsed)),
+            PathStyle::Cygwin | PathStyle::Msys => Ok(self.finish(
+                self.format_posix(parsed, target_style),
+                target_style,
+                parsed,
+            )),
+            PathStyle::Custom(custom) => {
+                style::from_unix(&*style::definition(custom)?, &self.format_unix(parsed))
+            }
             PathStyle::Auto => {
                 let current_style = super::platform::current_style();
-                self.format(parsed, current_style)
+                self.format_parts(parsed, current_style)
             }
         }
     }
 
+    /// Apply the trailing separator policy to a formatted path
+    ///
+    /// Device paths keep their separators, which can be significant.
+    fn finish<S: AsRef<str>>(
+        &self,
+        result: String,
+        target_style: PathStyle,
+        parsed: &Parts<'_, S>,
+    ) -> String {
+        if parsed.device.is_some() {
+            return result;
+        }
+        let root = parsed.components.is_empty()
+            && (parsed.is_absolute || parsed.is_unc || parsed.kind == PathKind::RootRelative);
+        self.config
+            .trailing_slash
+            .apply(result, target_style, parsed.trailing, root)
+    }
+
     /// Format as Windows path
-    fn format_windows(&self, parsed: &ParsedPath) -> String {
+    ///
+    /// Absolute paths without a drive follow the configured
+    /// [`UnmappedPolicy`]; `Passthrough` leaves them without a drive.
+    fn format_windows<S: AsRef<str>>(&self, parsed: &Parts<'_, S>) -> PathResult<String> {
         if parsed.is_unc {
-            return Self::format_unc_windows(parsed);
+            let mut result = Self::format_unc_windows(parsed);
+            self.push_stream(&mut result, parsed.stream);
+            return Ok(result);
+        }
+
+        // Device roots are kept as written, apart from the separators
+        if let Some(device) = parsed.device {
+            let mut result = device.replace('/', "\\");
+            for component in parsed.components {
+                result.push('\\');
+                result.push_str(component.as_ref());
+            }
+            return Ok(result);
         }
 
         let mut result = String::new();
@@ -45,63 +340,90 @@ impl PathFormatter {
         // Add drive letter
         if let Some(drive) = parsed.drive_letter {
             let _ = write!(result, "{drive}:");
+        } else if let (true, Some(distro)) = (parsed.is_absolute, &self.config.wsl_distro) {
+            let _ = write!(result, r"\\wsl$\{distro}");
         } else if parsed.is_absolute {
             // Default drive
-            result.push_str("C:");
+            match &self.config.unmapped_policy {
+                UnmappedPolicy::DefaultDrive(drive) => {
+                    let _ = write!(result, "{}:", drive.to_ascii_uppercase());
+                }
+                UnmappedPolicy::WslUnc { distro } => {
+                    let _ = write!(result, r"\\wsl$\{distro}");
+                }
+                UnmappedPolicy::Error => {
+                    let mut unix = String::from("/");
+                    join_into(&mut unix, parsed.components, '/');
+                    return Err(PathError::drive_mapping_error(format!(
+                        "No drive mapping for {unix}"
+                    ))
+                    .with_path(unix));
+                }
+                UnmappedPolicy::Passthrough => {}
+            }
         }
 
         // Add separator
-        if parsed.is_absolute && !parsed.is_unc {
+        if parsed.is_absolute || parsed.kind == PathKind::RootRelative {
             result.push('\\');
         }
 
         // Add components
-        for (i, component) in parsed.components.iter().enumerate() {
-            if i > 0 {
-                result.push('\\');
-            }
-            result.push_str(component);
-        }
+        join_into(&mut result, parsed.components, '\\');
+        self.push_stream(&mut result, parsed.stream);
 
         // Normalize if requested
         if self.config.normalize {
             result = Self::normalize_windows_path(&result);
         }
 
-        result
+        Ok(result)
     }
 
     /// Format as Unix path
-    fn format_unix(&self, parsed: &ParsedPath) -> String {
+    fn format_unix<S: AsRef<str>>(&self, parsed: &Parts<'_, S>) -> String {
         if parsed.is_unc {
-            return Self::format_unc_unix(parsed);
+            let mut result = self.format_unc_unix(parsed);
+            self.push_stream(&mut result, parsed.stream);
+            return result;
         }
 
         let mut result = String::new();
+        let mut components = parsed.components;
 
         // UNC path handling
         if parsed.is_unc {
-            if let (Some(server), Some(share)) = (&parsed.server, &parsed.share) {
+            if let (Some(server), Some(share)) = (parsed.server, parsed.share) {
                 let _ = write!(result, "//{server}/{share}");
             }
         } else if parsed.is_absolute {
             if parsed.has_drive {
-                // Map drive letter to Unix mount point
+                // Map drive letter, or a directory on the drive, to its
+                // Unix mount point
                 if let Some(drive) = parsed.drive_letter {
-                    let drive_lower = drive.to_ascii_lowercase();
-                    result.push_str(&self.map_drive_to_unix(&format!("{drive_lower}:")));
+                    let (mount, consumed) = self.map_drive_to_unix(drive, components);
+                    result.push_str(&mount);
+                    components = &components[consumed..];
                 }
             } else {
                 result.push('/');
             }
+        } else if parsed.kind == PathKind::RootRelative {
+            result.push('/');
         }
 
         // Add components
-        for component in &parsed.components {
+        for component in components {
             if !result.ends_with('/') && !result.is_empty() {
                 result.push('/');
             }
-            result.push_str(component);
+            result.push_str(component.as_ref());
+        }
+        self.push_stream(&mut result, parsed.stream);
+
+        // Drive-relative paths keep their drive (`C:foo/bar`)
+        if let (PathKind::DriveRelative, Some(drive)) = (parsed.kind, parsed.drive_letter) {
+            result.insert_str(0, &format!("{drive}:"));
         }
 
         // Normalize if requested
@@ -112,61 +434,109 @@ impl PathFormatter {
         result
     }
 
-    /// Format UNC path as Windows format
-    fn format_unc_windows(parsed: &ParsedPath) -> String {
-        let mut result = String::from(r"\\");
-
-        if let Some(server) = &parsed.server {
-            result.push_str(server);
+    /// Format as Cygwin or MSYS path
+    fn format_posix<S: AsRef<str>>(&self, parsed: &Parts<'_, S>, style: PathStyle) -> String {
+        if parsed.is_unc {
+            let mut result = self.format_unc_unix(parsed);
+            self.push_stream(&mut result, parsed.stream);
+            return result;
         }
 
-        result.push('\\');
+        let mut result = String::new();
 
-        if let Some(share) = &parsed.share {
-            result.push_str(share);
+        // Drive letters live under the emulation layer's drive prefix;
+        // drive-relative paths keep their drive (`C:foo/bar`)
+        match (parsed.kind, parsed.drive_letter) {
+            (PathKind::DriveRelative, Some(drive)) => {
+                let _ = write!(result, "{drive}:");
+            }
+            (_, Some(drive)) => {
+                let _ = write!(
+                    result,
+                    "{}{}",
+                    posix_drive_prefix(style),
+                    drive.to_ascii_lowercase()
+                );
+            }
+            (_, None) => {}
         }
 
-        for component in &parsed.components {
-            result.push('\\');
-            result.push_str(component);
+        if parsed.is_absolute || parsed.kind == PathKind::RootRelative {
+            result.push('/');
+        }
+        join_into(&mut result, parsed.components, '/');
+        self.push_stream(&mut result, parsed.stream);
+
+        // Normalize if requested
+        if self.config.normalize {
+            result = Self::normalize_unix_path(&result);
         }
 
         result
     }
 
-    /// Format UNC path as Unix format
-    fn format_unc_unix(parsed: &ParsedPath) -> String {
-        let mut result = String::from("//");
+    /// Append an alternate data stream suffix unless the configuration
+    /// strips streams
+    fn push_stream(&self, result: &mut String, stream: Option<&str>) {
+        if let Some(stream) = stream
+            && self.config.data_streams == DataStreamPolicy::Preserve
+        {
+            result.push(':');
+            result.push_str(stream);
+        }
+    }
+
+    /// Format UNC path as Windows format
+    fn format_unc_windows<S: AsRef<str>>(parsed: &Parts<'_, S>) -> String {
+        let mut result = String::from(r"\\");
 
-        if let Some(server) = &parsed.server {
+        if let Some(server) = parsed.server {
             result.push_str(server);
         }
 
-        result.push('/');
-
-        if let Some(share) = &parsed.share {
+        if let Some(share) = parsed.share {
+            result.push('\\');
             result.push_str(share);
         }
 
-        for component in &parsed.components {
-            result.push('/');
-            result.push_str(component);
+        for component in parsed.components {
+            result.push('\\');
+            result.push_str(component.as_ref());
         }
 
         result
     }
 
-    /// Map Windows drive letter to Unix path
-    fn map_drive_to_unix(&self, drive: &str) -> String {
-        for (windows_drive, unix_mount) in &self.config.drive_mappings {
-            if windows_drive == drive {
-                return unix_mount.clone();
+    /// Format UNC path as Unix format
+    fn format_unc_unix<S: AsRef<str>>(&self, parsed: &Parts<'_, S>) -> String {
+        unc_to_unix(
+            parsed.server.unwrap_or_default(),
+            parsed.share,
+            parsed.components,
+            self.config.admin_shares,
+        )
+    }
+
+    /// Map a path on a Windows drive to the Unix directory of the most
+    /// specific mapping covering it
+    ///
+    /// Returns the directory and the number of leading components it
+    /// stands for.
+    fn map_drive_to_unix<S: AsRef<str>>(&self, drive: char, components: &[S]) -> (String, usize) {
+        let mut windows = format!("{drive}:\\");
+        join_into(&mut windows, components, '\\');
+        match self.config.drive_mappings.find_windows(&windows) {
+            Some((unix_mount, rest)) => {
+                let remaining = rest.split('\\').filter(|c| !c.is_empty()).count();
+                let unix_mount = match unix_mount.trim_end_matches('/') {
+                    "" => "/",
+                    trimmed => trimmed,
+                };
+                (unix_mount.to_string(), components.len() - remaining)
             }
+            // Default mapping
+            None => (format!("/mnt/{}", drive.to_ascii_lowercase()), 0),
         }
-
-        // Default mapping
-        let drive_letter = drive.chars().next().unwrap().to_ascii_lowercase();
-        format!("/mnt/{drive_letter}")
     }
 
     /// Normalize Windows path string
@@ -210,6 +580,61 @@ impl PathFormatter {
     }
 }
 
+/// Fields of [`ParsedPath`] or [`ParsedPathRef`] the formatter reads
+#[allow(clippy::struct_excessive_bools)]
+struct Parts<'p, S> {
+    components: &'p [S],
+    kind: PathKind,
+    is_absolute: bool,
+    has_drive: bool,
+    drive_letter: Option<char>,
+    is_unc: bool,
+    server: Option<&'p str>,
+    share: Option<&'p str>,
+    device: Option<&'p str>,
+    stream: Option<&'p str>,
+    trailing: bool,
+}
+
+/// Check whether `shell` leaves the character at `index` of an argument
+/// as it is
+fn is_literal(shell: Shell, index: usize, c: char) -> bool {
+    match c {
+        'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '.' | '/' | ':' => true,
+        '\\' => matches!(shell, Shell::PowerShell | Shell::Cmd),
+        // A leading `-` turns a PowerShell argument into a parameter name
+        '-' => shell != Shell::PowerShell || index > 0,
+        '+' | '@' | ',' | '=' => matches!(shell, Shell::Bash | Shell::Fish),
+        _ => false,
+    }
+}
+
+/// Replace the characters `escape` has a replacement for, borrowing the
+/// text if there are none
+fn escape_chars(text: &str, escape: impl Fn(char) -> Option<&'static str>) -> Cow<'_, str> {
+    if !text.chars().any(|c| escape(c).is_some()) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 8);
+    for c in text.chars() {
+        match escape(c) {
+            Some(replacement) => escaped.push_str(replacement),
+            None => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Append components joined with `separator`
+fn join_into<S: AsRef<str>>(result: &mut String, components: &[S], separator: char) {
+    for (i, component) in components.iter().enumerate() {
+        if i > 0 {
+            result.push(separator);
+        }
+        result.push_str(component.as_ref());
+    }
+}
+
 impl fmt::Display for PathFormatter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "PathFormatter(config: {:?})", self.config)
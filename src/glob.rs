@@ -0,0 +1,424 @@
+//! Glob patterns that convert between path styles
+//!
+//! A [`CrossGlob`] converts like a path, except that its wildcards are left
+//! alone: the literal directories in front of the first wildcard go through
+//! the usual conversion (drive mappings included), and the rest only has
+//! its separators swapped. `*`, `?`, `**`, `[...]` classes and `{a,b}`
+//! alternatives are supported. In Unix-style patterns `\` escapes the next
+//! character; Windows-style patterns have no escapes, since `\` is a
+//! separator there, so escaped characters are written as one-character
+//! classes (`\*` becomes `[*]`) when converting to Windows.
+//!
+//! ```rust
+//! use cross_path::{CrossGlob, CrossPath, DriveMap, PathConfig};
+//!
+//! let config = PathConfig {
+//!     drive_mappings: DriveMap::from_pairs([("C:", "/mnt/c")])?,
+//!     ..PathConfig::default()
+//! };
+//! let glob = CrossGlob::with_config(r"C:\src\**\*.{rs,toml}", config.clone())?;
+//! assert_eq!(glob.to_unix()?, "/mnt/c/src/**/*.{rs,toml}");
+//! assert!(glob.matches(&CrossPath::with_config("/mnt/c/src/app/main.rs", config)?)?);
+//! # Ok::<(), cross_path::PathError>(())
+//! ```
+
+use crate::{
+    CaseSensitivity, CrossPath, PathConfig, PathConverter, PathError, PathParser, PathResult,
+    PathStyle,
+};
+use regex::{Regex, RegexBuilder};
+use std::fmt;
+
+/// Characters that make a pattern component a wildcard
+const WILDCARDS: [char; 4] = ['*', '?', '[', '{'];
+
+/// Glob pattern written in one path style, convertible to the others
+#[derive(Debug, Clone)]
+pub struct CrossGlob {
+    pattern: String,
+    style: PathStyle,
+    config: PathConfig,
+    case_sensitivity: CaseSensitivity,
+    sensitive: Regex,
+    insensitive: Regex,
+}
+
+impl CrossGlob {
+    /// Create glob with the [current](PathConfig::current) configuration
+    ///
+    /// # Errors
+    ///
+    /// See [`with_config`](Self::with_config).
+    pub fn new<P: AsRef<str>>(pattern: P) -> PathResult<Self> {
+        Self::with_config(pattern, PathConfig::current())
+    }
+
+    /// Create glob, detecting its style as [`CrossPath`] does
+    ///
+    /// Matching uses the configuration's `case_sensitivity` until changed
+    /// with [`case_sensitivity`](Self::case_sensitivity).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::ParseError` if a `{` is not closed, or
+    /// `PathError` if the literal part of the pattern cannot be converted.
+    pub fn with_config<P: AsRef<str>>(pattern: P, config: PathConfig) -> PathResult<Self> {
+        let pattern = pattern.as_ref();
+        Self::build(pattern, PathParser::detect_style(pattern), config)
+    }
+
+    /// Create glob written in a known style
+    ///
+    /// `Auto` detects the style as [`new`](Self::new) does.
+    ///
+    /// # Errors
+    ///
+    /// See [`with_config`](Self::with_config).
+    pub fn with_style<P: AsRef<str>>(pattern: P, style: PathStyle) -> PathResult<Self> {
+        let pattern = pattern.as_ref();
+        let style = match style {
+            PathStyle::Auto => PathParser::detect_style(pattern),
+            style => style,
+        };
+        Self::build(pattern, style, PathConfig::current())
+    }
+
+    fn build(pattern: &str, style: PathStyle, config: PathConfig) -> PathResult<Self> {
+        let unix = convert_pattern(pattern, style, PathStyle::Unix, &config)?;
+        let body = unix_regex(&unix)?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            style,
+            case_sensitivity: config.case_sensitivity,
+            config,
+            sensitive: compile(&body, false)?,
+            insensitive: compile(&body, true)?,
+        })
+    }
+
+    /// Set how [`matches`](Self::matches) treats case
+    #[must_use]
+    pub fn case_sensitivity(mut self, case: CaseSensitivity) -> Self {
+        self.case_sensitivity = case;
+        self
+    }
+
+    /// Get the pattern as written
+    #[must_use]
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Style the pattern is written in
+    #[must_use]
+    pub fn style(&self) -> PathStyle {
+        self.style
+    }
+
+    /// Convert the pattern to `style`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the literal part of the pattern cannot be
+    /// converted.
+    pub fn to_style(&self, style: PathStyle) -> PathResult<String> {
+        convert_pattern(&self.pattern, self.style, style, &self.config)
+    }
+
+    /// Convert the pattern to Windows style
+    ///
+    /// # Errors
+    ///
+    /// See [`to_style`](Self::to_style).
+    pub fn to_windows(&self) -> PathResult<String> {
+        self.to_style(PathStyle::Windows)
+    }
+
+    /// Convert the pattern to Unix style
+    ///
+    /// # Errors
+    ///
+    /// See [`to_style`](Self::to_style).
+    pub fn to_unix(&self) -> PathResult<String> {
+        self.to_style(PathStyle::Unix)
+    }
+
+    /// Check if a path matches the pattern
+    ///
+    /// Both are compared in their Unix form, so a Windows pattern matches
+    /// paths written in any style. `*` and `?` do not match separators;
+    /// `**` matches any number of directories.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted.
+    pub fn matches(&self, path: &CrossPath) -> PathResult<bool> {
+        let cross_ref = path.as_cross_ref();
+        let unix = cross_ref.normalized_unix()?;
+        let regex = if cross_ref.ignores_case(self.case_sensitivity) {
+            &self.insensitive
+        } else {
+            &self.sensitive
+        };
+        Ok(regex.is_match(&unix))
+    }
+}
+
+impl fmt::Display for CrossGlob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+/// Convert a pattern written in `source` to `target`
+fn convert_pattern(
+    pattern: &str,
+    source: PathStyle,
+    target: PathStyle,
+    config: &PathConfig,
+) -> PathResult<String> {
+    let converter = PathConverter::new(config);
+    let target = converter.resolve_target(target);
+    let windows = source == PathStyle::Windows;
+
+    let (prefix, tail) = split_literal(pattern, windows);
+    let prefix = if prefix.is_empty() {
+        String::new()
+    } else {
+        converter.convert_between(prefix, source, target)?
+    };
+    let tail = convert_wildcards(tail, windows, target == PathStyle::Windows);
+
+    let separator = if target == PathStyle::Windows {
+        '\\'
+    } else {
+        '/'
+    };
+    Ok(if prefix.is_empty() || tail.is_empty() {
+        prefix + &tail
+    } else if prefix.ends_with(['/', '\\']) {
+        format!("{prefix}{tail}")
+    } else {
+        format!("{prefix}{separator}{tail}")
+    })
+}
+
+/// Split a pattern before the first component containing a wildcard
+///
+/// In Unix patterns an escape also ends the literal part.
+fn split_literal(pattern: &str, windows: bool) -> (&str, &str) {
+    let Some(wildcard) = pattern.find(|c| WILDCARDS.contains(&c) || (!windows && c == '\\')) else {
+        return (pattern, "");
+    };
+    let start = pattern[..wildcard]
+        .rfind(|c| c == '/' || (windows && c == '\\'))
+        .map_or(0, |i| i + 1);
+    (&pattern[..start], &pattern[start..])
+}
+
+/// Swap the separators of the wildcard part of a pattern
+fn convert_wildcards(tail: &str, from_windows: bool, to_windows: bool) -> String {
+    let separator = if to_windows { '\\' } else { '/' };
+    let mut converted = String::with_capacity(tail.len());
+    let mut chars = tail.chars().peekable();
+    let mut in_class = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if !from_windows => {
+                let Some(escaped) = chars.next() else {
+                    converted.push(c);
+                    break;
+                };
+                if !to_windows {
+                    converted.push(c);
+                    converted.push(escaped);
+                } else if "*?[]{},".contains(escaped) {
+                    converted.push('[');
+                    converted.push(escaped);
+                    converted.push(']');
+                } else {
+                    converted.push(escaped);
+                }
+            }
+            '[' if !in_class => {
+                in_class = true;
+                converted.push(c);
+                // `]` right after the opening bracket is a member
+                if chars.peek() == Some(&'!') || chars.peek() == Some(&'^') {
+                    converted.extend(chars.next());
+                }
+                if chars.peek() == Some(&']') {
+                    converted.extend(chars.next());
+                }
+            }
+            ']' if in_class => {
+                in_class = false;
+                converted.push(c);
+            }
+            '/' | '\\' if !in_class => converted.push(separator),
+            c => converted.push(c),
+        }
+    }
+    converted
+}
+
+/// Translate a Unix-style glob into an anchored regular expression
+fn unix_regex(glob: &str) -> PathResult<String> {
+    let mut glob = glob;
+    while let Some(rest) = glob.strip_prefix("./") {
+        glob = rest;
+    }
+    if glob.len() > 1 {
+        glob = glob.strip_suffix('/').unwrap_or(glob);
+    }
+
+    let alternatives: Vec<String> = expand_braces(glob)?
+        .iter()
+        .map(|alternative| glob_to_regex(alternative))
+        .collect();
+    Ok(format!("^(?:{})$", alternatives.join("|")))
+}
+
+/// Expand `{a,b}` alternatives into one pattern per combination
+fn expand_braces(glob: &str) -> PathResult<Vec<String>> {
+    let chars: Vec<(usize, char)> = glob.char_indices().collect();
+    let mut open = None;
+    let mut depth = 0;
+    let mut commas = Vec::new();
+    let mut in_class = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (index, c) = chars[i];
+        match c {
+            '\\' => i += 1,
+            '[' if !in_class => in_class = true,
+            ']' if in_class => in_class = false,
+            '{' if !in_class => {
+                if depth == 0 {
+                    open = Some(index);
+                }
+                depth += 1;
+            }
+            ',' if !in_class && depth == 1 => commas.push(index),
+            '}' if !in_class && depth > 0 => {
+                depth -= 1;
+                if depth == 0
+                    && let Some(open) = open
+                {
+                    let (head, tail) = (&glob[..open], &glob[index + 1..]);
+                    let mut bounds = vec![open];
+                    bounds.extend(&commas);
+                    bounds.push(index);
+
+                    let mut expanded = Vec::new();
+                    for pair in bounds.windows(2) {
+                        let alternative = &glob[pair[0] + 1..pair[1]];
+                        expanded.extend(expand_braces(&format!("{head}{alternative}{tail}"))?);
+                    }
+                    return Ok(expanded);
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if depth > 0 {
+        return Err(PathError::parse_error(format!(
+            "Unclosed '{{' in glob pattern {glob}"
+        )));
+    }
+    Ok(vec![glob.to_string()])
+}
+
+/// Compile the regular expression of a pattern
+fn compile(pattern: &str, case_insensitive: bool) -> PathResult<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map_err(|e| PathError::parse_error(format!("Invalid glob pattern: {e}")).with_source(e))
+}
+
+/// Translate a `/`-separated glob into a regular expression body
+pub(crate) fn glob_to_regex(glob: &str) -> String {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut regex = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                let at_start = i == 0 || chars[i - 1] == '/';
+                match chars.get(i + 2) {
+                    Some('/') if at_start => {
+                        regex.push_str("(?:.*/)?");
+                        i += 3;
+                    }
+                    None if at_start => {
+                        regex.push_str(".*");
+                        i += 2;
+                    }
+                    _ => {
+                        regex.push_str("[^/]*");
+                        i += 2;
+                    }
+                }
+                continue;
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                if let Some((class, len)) = bracket_class(&chars[i..]) {
+                    regex.push_str(&class);
+                    i += len;
+                    continue;
+                }
+                regex.push_str(r"\[");
+            }
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                regex.push_str(&regex::escape(&chars[i].to_string()));
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+
+    regex
+}
+
+/// Translate a `[...]` class, returning it and the number of chars consumed
+fn bracket_class(chars: &[char]) -> Option<(String, usize)> {
+    let mut class = String::from("[");
+    let mut i = 1;
+    if matches!(chars.get(i), Some('!' | '^')) {
+        class.push('^');
+        i += 1;
+    }
+    let start = i;
+
+    while let Some(&c) = chars.get(i) {
+        match c {
+            ']' if i > start => {
+                class.push(']');
+                return Some((class, i + 1));
+            }
+            '\\' => {
+                i += 1;
+                let escaped = chars.get(i)?;
+                class.push('\\');
+                class.push(*escaped);
+            }
+            '[' | ']' | '&' | '~' => {
+                class.push('\\');
+                class.push(c);
+            }
+            c => class.push(c),
+        }
+        i += 1;
+    }
+
+    None
+}
@@ -0,0 +1,295 @@
+//! `.gitignore`-style pattern matching
+//!
+//! Patterns always use `/`, while the matched paths may be written in any
+//! style: they are compared in their style-independent form (see
+//! [`CrossPath::stable_hash`](crate::CrossPath::stable_hash)), so
+//! `src\target\x.o` and `src/target/x.o` match the same rules.
+//!
+//! Supported syntax follows git: comments, escaped `\#`/`\!`, `!` negation,
+//! trailing `/` for directory-only rules, anchoring with a leading or inner
+//! `/`, `*`, `?`, `[...]` and `**`. As in git, a path inside an ignored
+//! directory cannot be re-included by a negated rule.
+//!
+//! `.dockerignore` files use [`IgnoreSyntax::Docker`] instead: every
+//! pattern is anchored at the root, a trailing `/` is ignored, and a
+//! negated rule can re-include a path inside an ignored directory.
+
+use crate::glob::glob_to_regex;
+use crate::{CrossPathRef, PathError, PathResult};
+use regex::{Regex, RegexBuilder};
+
+/// Dialect of an ignore file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum IgnoreSyntax {
+    /// `.gitignore` rules
+    #[default]
+    Git,
+    /// `.dockerignore` rules
+    Docker,
+}
+
+/// Compiled set of ignore rules
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreMatcher {
+    rules: Vec<Rule>,
+    root: Option<String>,
+    case_insensitive: bool,
+    syntax: IgnoreSyntax,
+}
+
+/// Single compiled pattern
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: String,
+    regex: Regex,
+    negated: bool,
+    dir_only: bool,
+}
+
+impl IgnoreMatcher {
+    /// Create an empty matcher
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty matcher for rules in `syntax`
+    #[must_use]
+    pub fn with_syntax(syntax: IgnoreSyntax) -> Self {
+        Self {
+            syntax,
+            ..Self::default()
+        }
+    }
+
+    /// Compile the lines of a `.gitignore` file
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if a pattern cannot be compiled.
+    pub fn parse(content: &str) -> PathResult<Self> {
+        Self::parse_with(content, IgnoreSyntax::Git)
+    }
+
+    /// Compile the lines of an ignore file written in `syntax`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if a pattern cannot be compiled.
+    pub fn parse_with(content: &str, syntax: IgnoreSyntax) -> PathResult<Self> {
+        let mut matcher = Self::with_syntax(syntax);
+        for line in content.lines() {
+            matcher.add_line(line)?;
+        }
+        Ok(matcher)
+    }
+
+    /// Get the dialect of the rules
+    #[must_use]
+    pub fn syntax(&self) -> IgnoreSyntax {
+        self.syntax
+    }
+
+    /// Add one line of an ignore file, in the matcher's syntax
+    ///
+    /// Blank lines and comments are accepted and ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the pattern cannot be compiled.
+    pub fn add_line(&mut self, line: &str) -> PathResult<()> {
+        let rule = match self.syntax {
+            IgnoreSyntax::Git => Rule::parse(line, self.case_insensitive)?,
+            IgnoreSyntax::Docker => Rule::parse_docker(line, self.case_insensitive)?,
+        };
+        if let Some(rule) = rule {
+            self.rules.push(rule);
+        }
+        Ok(())
+    }
+
+    /// Set the directory the patterns are relative to
+    ///
+    /// Absolute paths outside the root are never ignored. Without a root,
+    /// paths are matched as given (and should be relative).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the root cannot be converted.
+    pub fn set_root<'p>(&mut self, root: impl Into<CrossPathRef<'p>>) -> PathResult<()> {
+        self.root = Some(root.into().canonical_form()?);
+        Ok(())
+    }
+
+    /// Match ignoring case (as git does with `core.ignorecase`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if a pattern cannot be recompiled.
+    pub fn set_case_insensitive(&mut self, case_insensitive: bool) -> PathResult<()> {
+        self.case_insensitive = case_insensitive;
+        self.rules = self
+            .rules
+            .iter()
+            .map(|rule| {
+                Ok(Rule {
+                    regex: compile(&rule.pattern, case_insensitive)?,
+                    ..rule.clone()
+                })
+            })
+            .collect::<PathResult<_>>()?;
+        Ok(())
+    }
+
+    /// Check if a path is ignored
+    ///
+    /// `is_dir` tells whether the path is a directory, which matters for
+    /// directory-only rules of `.gitignore` files. Plain strings are matched
+    /// without copying them into a [`CrossPath`](crate::CrossPath).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted.
+    pub fn is_ignored<'p>(
+        &self,
+        path: impl Into<CrossPathRef<'p>>,
+        is_dir: bool,
+    ) -> PathResult<bool> {
+        let canonical = path.into().canonical_form()?;
+        let Some(relative) = self.relative(&canonical) else {
+            return Ok(false);
+        };
+        // Docker rules match directories together with their contents, and
+        // the last matching rule decides even inside an ignored directory
+        if self.syntax == IgnoreSyntax::Docker {
+            return Ok(self.matches(relative.trim_end_matches('/'), is_dir));
+        }
+        let components: Vec<&str> = relative.split('/').filter(|c| !c.is_empty()).collect();
+
+        // A path is ignored if it, or any of its parent directories, is
+        let mut prefix = String::new();
+        for (i, component) in components.iter().enumerate() {
+            if i > 0 {
+                prefix.push('/');
+            }
+            prefix.push_str(component);
+            let last = i + 1 == components.len();
+            if self.matches(&prefix, !last || is_dir) {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Evaluate the rules for one path; the last matching rule wins
+    fn matches(&self, relative: &str, is_dir: bool) -> bool {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (is_dir || !rule.dir_only) && rule.regex.is_match(relative))
+            .is_some_and(|rule| !rule.negated)
+    }
+
+    /// Path relative to the root, if it lies under it
+    fn relative<'a>(&self, canonical: &'a str) -> Option<&'a str> {
+        let Some(root) = &self.root else {
+            return Some(canonical.trim_start_matches('/'));
+        };
+        if !canonical.starts_with('/') {
+            return Some(canonical);
+        }
+
+        let root = root.trim_end_matches('/');
+        let head = canonical.get(..root.len())?;
+        let same = if self.case_insensitive {
+            head.eq_ignore_ascii_case(root)
+        } else {
+            head == root
+        };
+        let rest = &canonical[root.len()..];
+        (same && (rest.is_empty() || rest.starts_with('/') || root.is_empty()))
+            .then(|| rest.trim_start_matches('/'))
+    }
+}
+
+impl Rule {
+    fn parse(line: &str, case_insensitive: bool) -> PathResult<Option<Self>> {
+        let line = trim_trailing_spaces(line);
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        if line.is_empty() {
+            return Ok(None);
+        }
+
+        let anchored = line.contains('/');
+        let body = line.strip_prefix('/').unwrap_or(line);
+        let pattern = if anchored {
+            format!("^{}$", glob_to_regex(body))
+        } else {
+            format!("^(?:.*/)?{}$", glob_to_regex(body))
+        };
+
+        Ok(Some(Self {
+            regex: compile(&pattern, case_insensitive)?,
+            pattern,
+            negated,
+            dir_only,
+        }))
+    }
+
+    fn parse_docker(line: &str, case_insensitive: bool) -> PathResult<Option<Self>> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, line),
+        };
+        // Patterns are cleaned like file paths: `/a//./b/` is `a/b`
+        let body = line
+            .split('/')
+            .filter(|c| !c.is_empty() && *c != ".")
+            .collect::<Vec<_>>()
+            .join("/");
+        if body.is_empty() {
+            return Ok(None);
+        }
+
+        let pattern = format!("^{}(?:/.*)?$", glob_to_regex(&body));
+        Ok(Some(Self {
+            regex: compile(&pattern, case_insensitive)?,
+            pattern,
+            negated,
+            dir_only: false,
+        }))
+    }
+}
+
+/// Compile a regular expression for a rule
+fn compile(pattern: &str, case_insensitive: bool) -> PathResult<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map_err(|e| PathError::parse_error(format!("Invalid ignore pattern: {e}")).with_source(e))
+}
+
+/// Remove trailing spaces that are not escaped with a backslash
+fn trim_trailing_spaces(line: &str) -> &str {
+    let mut end = line.len();
+    while line[..end].ends_with(' ') && !line[..end - 1].ends_with('\\') {
+        end -= 1;
+    }
+    &line[..end]
+}
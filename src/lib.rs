@@ -28,46 +28,371 @@
 #![allow(clippy::module_name_repetitions)]
 extern crate alloc;
 
+/// Path collection analysis module
+pub mod analysis;
+/// Configuration file module
+pub mod config;
 /// Path converter module
 pub mod converter;
 /// Error handling module
 pub mod error;
+/// Environment variable expansion module
+pub mod expand;
+/// File extension module
+pub mod extension;
 /// Path formatter module
 pub mod formatter;
+/// Glob pattern module
+pub mod glob;
+#[cfg(feature = "gitignore")]
+/// Ignore file pattern matching module
+pub mod ignore;
+/// `PATH`-style path list module
+pub mod list;
+/// Host ↔ guest path mapping module
+pub mod mapping;
+/// Conversion metrics module
+pub mod metrics;
+/// File operations module
+pub mod ops;
 /// Path parser module
 pub mod parser;
 /// Platform-specific operations module
 pub mod platform;
+/// Global mapping registry module
+pub mod registry;
+/// Path rewrite rules module
+pub mod rewrite;
+/// File name sanitizer module
+pub mod sanitize;
+/// Path scanning module
+pub mod scan;
 #[cfg(feature = "security")]
 /// Security verification module
 pub mod security;
+/// Serde support module
+pub mod serde;
+/// Streaming path list conversion module
+pub mod stream;
+/// Custom path style module
+pub mod style;
+#[cfg(feature = "text")]
+/// Embedded path conversion module
+pub mod text;
 #[cfg(feature = "unicode")]
 /// Unicode handling module
 pub mod unicode;
+/// File URL module
+pub mod url;
+/// Cross-platform name validation module
+pub mod validation;
+/// Virtual filesystem module
+pub mod vfs;
+#[cfg(feature = "walk")]
+/// Directory walking module
+pub mod walk;
 
+pub use analysis::NormalizedKey;
 pub use converter::PathConverter;
 pub use error::PathError;
-pub use formatter::PathFormatter;
+pub use formatter::{BuildSystem, PathFormatter, Shell};
+pub use glob::CrossGlob;
+pub use mapping::drive_map::DriveMap;
 pub use parser::PathParser;
+pub use validation::{PortabilityFix, ValidationTarget};
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::path::{Path, PathBuf};
 
 /// Cross-platform path result type
 pub type PathResult<T> = Result<T, PathError>;
 
 /// Path style enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize)]
 pub enum PathStyle {
     /// Windows path style (C:\Users\name)
     Windows,
     /// Unix/Linux path style (/home/name)
     Unix,
+    /// Cygwin path style (/cygdrive/c/Users/name)
+    Cygwin,
+    /// MSYS2 and Git Bash path style (/c/Users/name)
+    ///
+    /// Never auto-detected, since `/c/...` is also a valid Unix path; create
+    /// such paths with [`CrossPath::with_style`].
+    Msys,
+    /// Style defined by a registered [`StyleDefinition`](style::StyleDefinition)
+    ///
+    /// Custom styles are not serializable.
+    #[serde(skip)]
+    Custom(style::CustomStyle),
     /// Auto-detect based on current platform
     Auto,
 }
 
+impl PathStyle {
+    /// Refer to the custom style registered under `name`
+    #[must_use]
+    pub const fn custom(name: &'static str) -> Self {
+        Self::Custom(style::CustomStyle::new(name))
+    }
+}
+
+/// Case sensitivity policy for path comparison
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+)]
+pub enum CaseSensitivity {
+    /// Paths differing only in case are different (Unix semantics)
+    #[default]
+    Sensitive,
+    /// Paths differing only in case are the same (Windows semantics)
+    Insensitive,
+    /// The default of the current platform: insensitive on Windows and
+    /// macOS, sensitive elsewhere
+    Platform,
+    /// Ask the file system holding the path, falling back to `Platform` if
+    /// it cannot be probed
+    ProbeFilesystem,
+}
+
+impl CaseSensitivity {
+    /// Resolve the policy to `Sensitive` or `Insensitive` for a native path
+    ///
+    /// Only `ProbeFilesystem` touches the file system, probing the nearest
+    /// existing ancestor of `path`.
+    #[must_use]
+    pub fn resolve(self, path: &Path) -> Self {
+        match self {
+            Self::Sensitive | Self::Insensitive => self,
+            Self::Platform if cfg!(any(target_os = "windows", target_os = "macos")) => {
+                Self::Insensitive
+            }
+            Self::Platform => Self::Sensitive,
+            Self::ProbeFilesystem => {
+                platform::case_sensitivity(path).unwrap_or_else(|_| Self::Platform.resolve(path))
+            }
+        }
+    }
+}
+
+/// Treatment of names that differ only in Unicode normalization
+///
+/// `é` may be written precomposed (NFC, `U+00E9`) or decomposed (NFD, `e`
+/// followed by `U+0301`). Under every behavior but `Exact` the two
+/// spellings are the same name, and paths are compared and hashed in NFC;
+/// this needs the `unicode` feature.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+)]
+pub enum UnicodeNormalization {
+    /// Names are compared code point by code point, so the spellings are
+    /// different names (ext4, NTFS)
+    #[default]
+    Exact,
+    /// Names keep their spelling, but either spelling finds the same file
+    /// (APFS, ZFS with `normalization` set)
+    Insensitive,
+    /// Names are stored in NFC
+    Nfc,
+    /// Names are stored in NFD (HFS+)
+    Nfd,
+}
+
+impl UnicodeNormalization {
+    /// Spelling of `name` that comparisons under this behavior see
+    pub(crate) fn fold(self, name: &str) -> Cow<'_, str> {
+        #[cfg(feature = "unicode")]
+        if self != Self::Exact && !unicode::UnicodeHandler::is_nfc(name) {
+            return Cow::Owned(unicode::UnicodeHandler::to_nfc(name));
+        }
+        #[cfg(not(feature = "unicode"))]
+        let _ = self;
+        Cow::Borrowed(name)
+    }
+}
+
+/// Normalization strategy for `..` components
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+)]
+pub enum NormalizeMode {
+    /// Collapse `..` purely on the path string
+    #[default]
+    Lexical,
+    /// Resolve symlinks in the existing prefix first (`pwd -P` semantics)
+    Physical,
+    /// Collapse `..` on the path string, but fail if the file system
+    /// resolves the result differently (a `..` stepped out of a symlink)
+    LexicalThenVerify,
+}
+
+/// Handling of `..` components during normalization
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+)]
+pub enum DotDotPolicy {
+    /// Collapse `..` as the normalization mode prescribes
+    #[default]
+    Resolve,
+    /// Leave `..` in place and only drop `.` and redundant separators
+    ///
+    /// Has no effect on [`NormalizeMode::Physical`], which asks the file
+    /// system.
+    Keep,
+    /// Fail with `PathError::NormalizationError` if the path contains `..`
+    Error,
+}
+
+/// Handling of NTFS alternate data streams (`file.txt:Zone.Identifier`)
+/// when converting Windows paths
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+)]
+pub enum DataStreamPolicy {
+    /// Keep the stream suffix (`/mnt/c/file.txt:Zone.Identifier`)
+    #[default]
+    Preserve,
+    /// Drop the stream suffix, addressing the file itself
+    /// (`/mnt/c/file.txt`)
+    Strip,
+}
+
+/// Trailing separators on converted paths
+///
+/// Roots (`/`, `C:\`, `\\server\share\`) always keep their separator.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+)]
+pub enum TrailingSlash {
+    /// End in a separator if the source path did (`docs/` → `docs\`), as
+    /// rsync-style tools expect
+    Preserve,
+    /// Drop trailing separators, except from paths converted from a root
+    /// (`C:\` → `/mnt/c/`)
+    #[default]
+    Strip,
+    /// Always end in a separator, for paths known to be directories
+    Ensure,
+}
+
+impl TrailingSlash {
+    /// Apply the policy to a path converted to `style`
+    ///
+    /// `source_trailing` and `source_root` tell if the source path ended in
+    /// a separator and if it was a root. Custom styles are left as they are.
+    pub(crate) fn apply(
+        self,
+        mut path: String,
+        style: PathStyle,
+        source_trailing: bool,
+        source_root: bool,
+    ) -> String {
+        let separator = match style {
+            PathStyle::Windows => '\\',
+            PathStyle::Unix | PathStyle::Cygwin | PathStyle::Msys => '/',
+            PathStyle::Custom(_) | PathStyle::Auto => return path,
+        };
+        if path.is_empty() || root_len(&path, style) == path.len() {
+            return path;
+        }
+        let ends = path.ends_with(separator);
+        let wanted = match self {
+            Self::Preserve => source_trailing,
+            Self::Strip => source_root && ends,
+            Self::Ensure => true,
+        };
+        if wanted && !ends {
+            path.push(separator);
+        } else if !wanted && ends {
+            path.pop();
+        }
+        path
+    }
+}
+
+/// Form of Windows administrative shares (`\\server\C$\...`) when
+/// converting to Unix
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+)]
+pub enum AdminSharePolicy {
+    /// Keep the share as a network path (`//server/C$/Users`)
+    #[default]
+    Unc,
+    /// Place the drive under a per-server mount point
+    /// (`/mnt/server/c/Users`); such paths convert back to the share
+    MountPoint,
+    /// Address the share with an SMB URL (`smb://server/C$/Users`)
+    SmbUrl,
+}
+
+/// Handling of absolute Unix paths that have no drive mapping when
+/// converting to Windows
+#[derive(Debug, Clone, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize)]
+pub enum UnmappedPolicy {
+    /// Place the path on the given drive (`/var/log` → `C:\var\log`)
+    DefaultDrive(char),
+    /// Fail with `PathError::DriveMappingError`
+    Error,
+    /// Leave the path unchanged (`/var/log`)
+    Passthrough,
+    /// Address the path inside a WSL distribution
+    /// (`/var/log` → `\\wsl$\Ubuntu\var\log`)
+    WslUnc {
+        /// Name of the WSL distribution
+        distro: String,
+    },
+}
+
+impl Default for UnmappedPolicy {
+    fn default() -> Self {
+        Self::DefaultDrive('C')
+    }
+}
+
+/// System path conversion tool consulted by [`ConversionBackend`]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+)]
+pub enum SystemTool {
+    /// `wslpath` under WSL, `cygpath` under Cygwin and MSYS2, none
+    /// elsewhere (see [`platform::detect_environment`])
+    #[default]
+    Detect,
+    /// `wslpath`, converting between Windows and Unix paths
+    Wslpath,
+    /// `cygpath`, converting between Windows paths and the POSIX form of
+    /// the Cygwin or MSYS2 installation it belongs to
+    Cygpath,
+}
+
+/// How conversions between Windows and Unix-like paths are carried out
+///
+/// The system tools know the real mount table, including mounts the
+/// built-in logic cannot discover. They are only consulted for absolute
+/// paths converted between Windows style and a Unix-like style, and run
+/// once per conversion, which is far slower than the built-in logic.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize,
+)]
+pub enum ConversionBackend {
+    /// Convert with the built-in logic only
+    #[default]
+    Builtin,
+    /// Use the tool's output, falling back to the built-in logic if the
+    /// tool is missing or fails
+    System(SystemTool),
+    /// Convert with the built-in logic and fail with
+    /// `PathError::PlatformError` if the tool gives a different result
+    Verify(SystemTool),
+}
+
 /// Path conversion configuration
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct PathConfig {
     /// Target path style
     pub style: PathStyle,
@@ -75,10 +400,71 @@ pub struct PathConfig {
     pub preserve_encoding: bool,
     /// Whether to perform security checks
     pub security_check: bool,
-    /// Windows drive letter mappings (e.g., "C:" -> "/mnt/c")
-    pub drive_mappings: Vec<(String, String)>,
+    /// Windows drive, directory or share ↔ Unix directory mappings (e.g.,
+    /// `C:` ↔ `/mnt/c`, `D:\data` ↔ `/srv/data`); the most specific mapping
+    /// covering a path wins
+    pub drive_mappings: DriveMap,
     /// Whether to normalize paths (remove redundant components)
     pub normalize: bool,
+    /// How `normalize` collapses `..` components
+    pub normalize_mode: NormalizeMode,
+    /// Whether `normalize` collapses, keeps or rejects `..` components
+    pub dot_dot_policy: DotDotPolicy,
+    /// Whether conversions keep or drop NTFS alternate data streams
+    pub data_streams: DataStreamPolicy,
+    /// Whether converted paths keep, drop or gain a trailing separator
+    pub trailing_slash: TrailingSlash,
+    /// Extensions of several parts that [`CrossPath::extension_full`] and
+    /// [`CrossPath::set_extension`] treat as one (`tar.gz`, `d.ts`)
+    pub compound_extensions: Vec<String>,
+    /// How administrative shares (`\\server\C$`) convert to Unix
+    pub admin_shares: AdminSharePolicy,
+    /// Case sensitivity used when comparing and hashing paths
+    pub case_sensitivity: CaseSensitivity,
+    /// Unicode normalization behavior assumed when comparing and hashing
+    /// paths
+    pub unicode_normalization: UnicodeNormalization,
+    /// How absolute Unix paths without a drive mapping convert to Windows
+    pub unmapped_policy: UnmappedPolicy,
+    /// Reject paths that are invalid for their detected style at construction
+    pub strict: bool,
+    /// Emit the `\\?\` prefix for Windows paths longer than `MAX_PATH`
+    pub extended_length_prefix: bool,
+    /// Expand environment variables and `~` from the process environment at
+    /// construction
+    pub expand_env: bool,
+    /// WSL distribution whose file system Unix paths belong to
+    ///
+    /// When set, unmapped absolute Unix paths convert to `\\wsl$\<distro>\...`
+    /// and only that distribution's WSL UNC paths convert back to Unix; when
+    /// unset, WSL UNC paths of any distribution convert back.
+    pub wsl_distro: Option<String>,
+    /// Current directory of each Windows drive, keyed by upper-case drive
+    /// letter (`'D'` → `D:\work`)
+    ///
+    /// Drive-relative paths such as `D:foo` resolve against it on
+    /// conversion and in [`CrossPath::resolve`]. The drive may be omitted
+    /// from the directory (`\work`).
+    pub drive_current_dirs: BTreeMap<char, String>,
+    /// Rewrite rules applied to every path before it is converted
+    pub rewriter: rewrite::PathRewriter,
+    /// Whether conversions consult `wslpath` or `cygpath`
+    pub backend: ConversionBackend,
+    /// Return paths exactly as written when converting them back to the
+    /// style they were written in
+    ///
+    /// Covers a path's own style and, for paths created by
+    /// [`CrossPath::convert`], the style of the path they were converted
+    /// from, so trailing separators, drive letter case and share names
+    /// survive a round trip. Only the unmodified path is remembered.
+    pub preserve_roundtrip: bool,
+    /// Rules applied by [`CrossPath::is_safe`]
+    #[cfg(feature = "security")]
+    pub security_policy: security::SecurityPolicy,
+    /// Metrics sink for paths using this configuration, in place of the
+    /// one installed in the [`registry`]
+    #[serde(skip)]
+    pub metrics: metrics::MetricsSink,
 }
 
 impl Default for PathConfig {
@@ -87,32 +473,166 @@ impl Default for PathConfig {
             style: PathStyle::Auto,
             preserve_encoding: true,
             security_check: true,
-            drive_mappings: default_drive_mappings(),
+            drive_mappings: registry::drive_mappings()
+                .unwrap_or_else(registry::default_drive_mappings)
+                .into(),
             normalize: true,
+            normalize_mode: NormalizeMode::default(),
+            dot_dot_policy: DotDotPolicy::default(),
+            data_streams: DataStreamPolicy::default(),
+            trailing_slash: TrailingSlash::default(),
+            compound_extensions: extension::COMPOUND_EXTENSIONS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            admin_shares: AdminSharePolicy::default(),
+            case_sensitivity: CaseSensitivity::Sensitive,
+            unicode_normalization: UnicodeNormalization::default(),
+            unmapped_policy: UnmappedPolicy::default(),
+            strict: false,
+            extended_length_prefix: false,
+            expand_env: false,
+            wsl_distro: None,
+            drive_current_dirs: BTreeMap::new(),
+            rewriter: rewrite::PathRewriter::new(),
+            backend: ConversionBackend::default(),
+            preserve_roundtrip: false,
+            #[cfg(feature = "security")]
+            security_policy: security::SecurityPolicy::default(),
+            metrics: metrics::MetricsSink::default(),
         }
     }
 }
 
-/// Default drive letter mappings
-fn default_drive_mappings() -> Vec<(String, String)> {
-    vec![
-        ("C:".to_string(), "/mnt/c".to_string()),
-        ("D:".to_string(), "/mnt/d".to_string()),
-        ("E:".to_string(), "/mnt/e".to_string()),
-    ]
+impl PathConfig {
+    /// Install this configuration as the process default
+    ///
+    /// See [`registry::set_global_config`].
+    pub fn set_global(self) {
+        registry::set_global_config(self);
+    }
+
+    /// Run `f` with this configuration as the default of the current thread
+    ///
+    /// See [`registry::with_config`].
+    pub fn scope<R, F: FnOnce() -> R>(self, f: F) -> R {
+        registry::with_config(self, f)
+    }
+
+    /// Get the configuration used when none is given
+    ///
+    /// See [`registry::current_config`].
+    #[must_use]
+    pub fn current() -> Self {
+        registry::current_config()
+    }
+
+    /// Return the configuration with the name comparison rules of a probed
+    /// file system
+    ///
+    /// Sets `case_sensitivity` and `unicode_normalization`, so comparisons
+    /// and hashes agree with the volume the behavior was probed on; see
+    /// [`platform::probe_filesystem`].
+    #[must_use]
+    pub fn with_fs_behavior(mut self, behavior: platform::FsBehavior) -> Self {
+        self.case_sensitivity = behavior.case_sensitivity;
+        self.unicode_normalization = behavior.unicode_normalization;
+        self
+    }
+
+    /// Get the configured current directory of a Windows drive as an
+    /// absolute path (`D:\work`)
+    pub(crate) fn drive_current_dir(&self, drive: char) -> Option<String> {
+        let dir = self.drive_current_dirs.get(&drive.to_ascii_uppercase())?;
+        let dir = dir
+            .strip_prefix(|c: char| c.is_ascii_alphabetic())
+            .and_then(|rest| rest.strip_prefix(':'))
+            .unwrap_or(dir)
+            .trim_matches(['\\', '/']);
+        Some(format!(
+            "{}:\\{}",
+            drive.to_ascii_uppercase(),
+            dir.replace('/', "\\")
+        ))
+    }
+
+    /// Resolve a drive-relative Windows path (`D:foo`) against the
+    /// configured current directory of its drive
+    ///
+    /// Returns `None` for other paths and drives without a current
+    /// directory.
+    pub(crate) fn resolve_drive_relative(&self, path: &str) -> Option<String> {
+        if !parser::is_drive_relative(path) {
+            return None;
+        }
+        let mut resolved = self.drive_current_dir(path.chars().next()?)?;
+        if !resolved.ends_with('\\') {
+            resolved.push('\\');
+        }
+        resolved.push_str(&path[2..]);
+        Some(resolved)
+    }
+
+    /// Expand environment variables and `~` in drive mappings
+    ///
+    /// Lets one configuration work across machines, e.g.
+    /// `("P:", "$HOME/projects")` or `("%USERPROFILE%\\data", "/data")`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if a mapping references an undefined variable.
+    pub fn expand_mappings<E: expand::Environment + ?Sized>(&mut self, env: &E) -> PathResult<()> {
+        let mut expanded = Vec::with_capacity(self.drive_mappings.len());
+        for (windows, unix) in self.drive_mappings.iter() {
+            expanded.push((expand::expand(windows, env)?, expand::expand(unix, env)?));
+        }
+        self.drive_mappings = expanded.into();
+        Ok(())
+    }
+
+    /// Return the configuration with expanded drive mappings
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if a mapping references an undefined variable.
+    pub fn with_expanded_mappings<E: expand::Environment + ?Sized>(
+        mut self,
+        env: &E,
+    ) -> PathResult<Self> {
+        self.expand_mappings(env)?;
+        Ok(self)
+    }
 }
 
 /// Main cross-platform path structure
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+///
+/// Serializes as a plain path string; see the [`serde`](mod@crate::serde)
+/// module.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CrossPath {
     inner: PathBuf,
     original_style: PathStyle,
     config: PathConfig,
+    /// Path this one was converted from, kept with `preserve_roundtrip`
+    origin: Option<Box<Origin>>,
+}
+
+/// Path a [`CrossPath`] was converted from
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Origin {
+    /// Path as written before the conversion
+    path: String,
+    /// Style it was written in
+    style: PathStyle,
+    /// Text the conversion produced, to tell if the path changed since
+    converted: String,
 }
 
 impl CrossPath {
     /// Create a cross-platform path from a string
     ///
+    /// Uses the [current](PathConfig::current) default configuration.
+    ///
     /// # Arguments
     ///
     /// * `path` - The path string to parse
@@ -121,15 +641,93 @@ impl CrossPath {
     ///
     /// Returns `PathError` if the path is invalid
     pub fn new<P: AsRef<str>>(path: P) -> PathResult<Self> {
-        let path_str = path.as_ref();
-        let _ = PathParser::parse(path_str)?;
-        let style = PathParser::detect_style(path_str);
+        Self::with_config(path, PathConfig::current())
+    }
 
-        Ok(Self {
-            inner: PathBuf::from(path_str),
-            original_style: style,
-            config: PathConfig::default(),
-        })
+    /// Create a cross-platform path, rejecting invalid input up front
+    ///
+    /// In addition to the checks of [`new`](Self::new), the path must be
+    /// valid for its detected style: no `<>:"|?*` or control characters in
+    /// Windows components, no NUL in Unix paths, no empty drive specs and no
+    /// UNC roots missing the server or share.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::InvalidPath` if the path is invalid
+    pub fn new_strict<P: AsRef<str>>(path: P) -> PathResult<Self> {
+        Self::with_config(
+            path,
+            PathConfig {
+                strict: true,
+                ..PathConfig::current()
+            },
+        )
+    }
+
+    /// Create a cross-platform path written in a known style
+    ///
+    /// Skips style detection, which is required for [`PathStyle::Msys`]
+    /// paths such as `/c/Users/name`. `Auto` detects the style as
+    /// [`new`](Self::new) does.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path is invalid
+    pub fn with_style<P: AsRef<str>>(path: P, style: PathStyle) -> PathResult<Self> {
+        let mut cross_path = Self::new(path)?;
+        if style != PathStyle::Auto {
+            cross_path.original_style = style;
+        }
+        Ok(cross_path)
+    }
+
+    /// Create a cross-platform path from a `file://` URL
+    ///
+    /// Percent-escapes are decoded; `file:///C:/...` yields a Windows drive
+    /// path and `file://server/share/...` a UNC path.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the URL is not a valid file URL
+    pub fn from_file_url(url: &str) -> PathResult<Self> {
+        Self::new(url::to_path(url)?)
+    }
+
+    /// Create a cross-platform path from an `smb://` URL
+    ///
+    /// Percent-escapes are decoded and the URL becomes the UNC path it
+    /// addresses: `smb://server/share/dir` yields `\\server\share\dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the URL is not a valid SMB URL
+    pub fn from_smb_url(url: &str) -> PathResult<Self> {
+        Self::new(url::smb_to_unc(url)?)
+    }
+
+    /// Create a cross-platform path from an OS string without loss
+    ///
+    /// Unlike [`new`](Self::new), this accepts paths that are not valid
+    /// Unicode, such as non-UTF-8 Unix file names or Windows names with
+    /// unpaired surrogates. They are stored exactly as given (bytes on Unix,
+    /// WTF-8 on Windows) and round-trip through
+    /// [`as_os_str`](Self::as_os_str); string conversions of such paths fail
+    /// unless the lossy variants are used.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path is invalid
+    pub fn from_os_str<S: AsRef<std::ffi::OsStr>>(path: S) -> PathResult<Self> {
+        let path = path.as_ref();
+        if let Some(path) = path.to_str() {
+            return Self::new(path);
+        }
+
+        let config = PathConfig::current();
+        let lossy = path.to_string_lossy();
+        let mut cross_path = Self::build(&lossy, config)?;
+        cross_path.inner = PathBuf::from(path);
+        Ok(cross_path)
     }
 
     /// Create path with custom configuration
@@ -143,9 +741,46 @@ impl CrossPath {
     ///
     /// Returns `PathError` if the path is invalid
     pub fn with_config<P: AsRef<str>>(path: P, config: PathConfig) -> PathResult<Self> {
-        let mut cross_path = Self::new(path)?;
-        cross_path.config = config;
-        Ok(cross_path)
+        if config.expand_env {
+            return Self::with_env(path, config, &expand::SystemEnvironment);
+        }
+        Self::build(path.as_ref(), config)
+    }
+
+    /// Create path after expanding variables and `~` from `env`
+    ///
+    /// Resolves `%VAR%`, `${VAR}`, `$VAR` and a leading `~` before the path
+    /// is parsed, regardless of `expand_env`. Any
+    /// `Fn(&str) -> Option<String>` closure can serve as the environment.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if a referenced variable is undefined or the
+    /// expanded path is invalid
+    pub fn with_env<P, E>(path: P, config: PathConfig, env: &E) -> PathResult<Self>
+    where
+        P: AsRef<str>,
+        E: expand::Environment + ?Sized,
+    {
+        let expanded = expand::expand(path.as_ref(), env)?;
+        Self::build(&expanded, config)
+    }
+
+    /// Parse and validate a path string
+    fn build(path_str: &str, config: PathConfig) -> PathResult<Self> {
+        let _ = PathParser::parse(path_str)?;
+        let style = PathParser::detect_style(path_str);
+
+        if config.strict {
+            validation::validate_strict(path_str, style)?;
+        }
+
+        Ok(Self {
+            inner: PathBuf::from(path_str),
+            original_style: style,
+            config,
+            origin: None,
+        })
     }
 
     /// Convert to path string with specified style
@@ -158,8 +793,53 @@ impl CrossPath {
     ///
     /// Returns `PathError` if conversion fails
     pub fn to_style(&self, style: PathStyle) -> PathResult<String> {
-        let converter = PathConverter::new(&self.config);
-        converter.convert(self.inner.to_string_lossy().as_ref(), style)
+        self.as_cross_ref().to_style(style)
+    }
+
+    /// Convert to path string with specified style, replacing anything that
+    /// is not valid Unicode with `U+FFFD`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if conversion fails
+    pub fn to_style_lossy(&self, style: PathStyle) -> PathResult<String> {
+        self.as_cross_ref().to_style_lossy(style)
+    }
+
+    /// Convert to a path in the specified style
+    ///
+    /// The result keeps this path's configuration. With
+    /// [`preserve_roundtrip`](PathConfig::preserve_roundtrip) it remembers
+    /// the path it was converted from, so converting it back restores the
+    /// original string exactly.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if conversion fails
+    pub fn convert(&self, style: PathStyle) -> PathResult<Self> {
+        let style = PathConverter::new(&self.config).resolve_target(style);
+        let converted = self.to_style(style)?;
+        let origin = if self.config.preserve_roundtrip {
+            let (path, source_style) = match self.as_cross_ref().origin() {
+                Some(origin) => (origin.path.clone(), origin.style),
+                None => (self.to_string_lossy().into_owned(), self.original_style),
+            };
+            (source_style != style).then(|| {
+                Box::new(Origin {
+                    path,
+                    style: source_style,
+                    converted: converted.clone(),
+                })
+            })
+        } else {
+            None
+        };
+        Ok(Self {
+            inner: PathBuf::from(converted),
+            original_style: style,
+            config: self.config.clone(),
+            origin,
+        })
     }
 
     /// Convert to platform-appropriate path
@@ -171,11 +851,7 @@ impl CrossPath {
     ///
     /// Returns `PathError` if conversion fails
     pub fn to_platform(&self) -> PathResult<String> {
-        let target_style = match self.config.style {
-            PathStyle::Auto => platform::current_style(),
-            style => style,
-        };
-        self.to_style(target_style)
+        self.as_cross_ref().to_platform()
     }
 
     /// Convert to Windows path
@@ -200,68 +876,1581 @@ impl CrossPath {
         self.to_style(PathStyle::Unix)
     }
 
-    /// Get original path
-    #[must_use]
-    pub fn as_original(&self) -> &Path {
-        &self.inner
+    /// Convert to Cygwin path
+    ///
+    /// Forces conversion to Cygwin style (e.g., `/cygdrive/c/path/to/file`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if conversion fails
+    pub fn to_cygwin(&self) -> PathResult<String> {
+        self.to_style(PathStyle::Cygwin)
     }
 
-    /// Update configuration
-    pub fn set_config(&mut self, config: PathConfig) {
-        self.config = config;
+    /// Convert to MSYS2/Git Bash path
+    ///
+    /// Forces conversion to MSYS style (e.g., `/c/path/to/file`)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if conversion fails
+    pub fn to_msys(&self) -> PathResult<String> {
+        self.to_style(PathStyle::Msys)
     }
 
-    /// Get configuration reference
-    #[must_use]
-    pub fn config(&self) -> &PathConfig {
-        &self.config
+    /// Convert to a `file://` URL for the path in the given style
+    ///
+    /// The style decides the path inside the URL: `/mnt/c/x` becomes
+    /// `file:///C:/x` for `Windows` and `file:///mnt/c/x` for `Unix`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if conversion fails or the path is not absolute
+    pub fn to_file_url(&self, style: PathStyle) -> PathResult<String> {
+        let style = match style {
+            PathStyle::Auto => platform::current_style(),
+            style => style,
+        };
+        url::from_path(&self.to_style(style)?, style)
     }
 
-    /// Check if path is safe
+    /// Convert to an `smb://` URL
     ///
-    /// Performs security checks including:
-    /// - Path traversal detection
-    /// - Dangerous pattern detection
-    /// - System directory access check
+    /// The path must be a UNC path, or a Unix path that converts to one
+    /// such as `//server/share/dir`.
     ///
     /// # Errors
     ///
-    /// Returns `PathError` if security check fails
-    pub fn is_safe(&self) -> PathResult<bool> {
-        security::PathSecurityChecker::check_path_security(&self.inner)
+    /// Returns `PathError` if conversion fails or the path is not on a
+    /// network share
+    pub fn to_smb_url(&self) -> PathResult<String> {
+        url::unc_to_smb(&self.to_windows()?)
     }
 
-    /// Normalize path
+    /// Convert to path string with specified style, refusing lossy output
+    ///
+    /// Unlike [`to_style`](Self::to_style), which always produces a
+    /// best-effort result, this fails when the conversion would lose or
+    /// invent information, which makes it the safe choice for code that
+    /// persists paths. The conversion is rejected when:
+    ///
+    /// - the path is not valid UTF-8
+    /// - a component contains characters that are invalid in the target style
+    /// - an absolute Unix path has no drive mapping (regardless of `unmapped_policy`)
+    /// - the path fails the security check (if `security_check` is enabled
+    ///   and the `security` feature is on)
+    ///
+    /// # Arguments
     ///
-    /// Removes redundant components like `.` and `..`
+    /// * `style` - The target path style
     ///
     /// # Errors
     ///
-    /// Returns `PathError` if normalization fails
-    pub fn normalize(&mut self) -> PathResult<()> {
-        let normalized = PathParser::normalize_path(&self.inner)?;
-        self.inner = normalized;
-        Ok(())
-    }
-}
+    /// Returns `PathError` describing the first rule the conversion violates
+    pub fn to_string_checked(&self, style: PathStyle) -> PathResult<String> {
+        let path_str = self.inner.to_str().ok_or_else(|| {
+            PathError::encoding_error(format!("Path is not valid UTF-8: {}", self.inner.display()))
+        })?;
+        let target_style = match style {
+            PathStyle::Auto => platform::current_style(),
+            style => style,
+        };
 
-impl From<&Path> for CrossPath {
-    fn from(path: &Path) -> Self {
-        Self {
-            inner: path.to_path_buf(),
-            original_style: PathStyle::Auto,
-            config: PathConfig::default(),
+        let parsed = PathParser::parse(path_str)?;
+        if let Some(component) = parsed.components.iter().find(|c| {
+            c.chars()
+                .any(|ch| parser::is_invalid_component_char(ch, target_style))
+        }) {
+            return Err(PathError::invalid_path(format!(
+                "Component '{component}' cannot be represented as a {target_style:?} path"
+            )));
         }
-    }
-}
 
-impl From<PathBuf> for CrossPath {
-    fn from(path: PathBuf) -> Self {
-        Self {
-            inner: path,
-            original_style: PathStyle::Auto,
-            config: PathConfig::default(),
+        let converter = PathConverter::new(&self.config);
+        if target_style == PathStyle::Windows
+            && converter.detect_style(path_str)? == PathStyle::Unix
+            && converter.is_unmapped_unix_path(path_str)
+        {
+            return Err(
+                PathError::drive_mapping_error(format!("No drive mapping for {path_str}"))
+                    .with_path(path_str),
+            );
         }
+
+        #[cfg(feature = "security")]
+        if self.config.security_check {
+            self.is_safe()?;
+        }
+
+        converter.convert(path_str, target_style)
+    }
+
+    /// Compute a style-invariant 64-bit hash of the path
+    ///
+    /// The hash is computed from the canonical form of the path (Unix style
+    /// after drive mapping, with `.` and `..` collapsed, trailing separators
+    /// removed and case folded per `case_sensitivity`), so `C:\Foo\bar` and
+    /// `/mnt/c/Foo/bar` hash identically on every platform. Drives are always
+    /// mapped with [`DriveMappings::builtin`](mapping::drives::DriveMappings::builtin)
+    /// and the built-in conversion, never with configured or discovered
+    /// mappings. The algorithm (FNV-1a) is fixed, making the value suitable
+    /// as a cache key shared between machines.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted to its canonical form
+    pub fn stable_hash(&self) -> PathResult<u64> {
+        self.as_cross_ref().stable_hash()
+    }
+
+    /// Compute a style-invariant 128-bit hash of the path
+    ///
+    /// Same canonical form as [`stable_hash`](Self::stable_hash), using
+    /// 128-bit FNV-1a for a lower collision probability.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted to its canonical form
+    pub fn stable_hash128(&self) -> PathResult<u128> {
+        self.as_cross_ref().stable_hash128()
+    }
+
+    /// Compute a style-insensitive key for hashing and ordering
+    ///
+    /// `C:\Foo\Bar`, `c:/foo/bar/` and `/mnt/c/Foo/Bar` get the same key.
+    /// Case is ignored for Windows paths, paths under a mapped drive and
+    /// paths configured as case-insensitive, as in
+    /// [`analysis::find_duplicates`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted to its Unix form
+    pub fn normalized_key(&self) -> PathResult<NormalizedKey> {
+        NormalizedKey::new(self.as_cross_ref(), None)
+    }
+
+    /// Compute a style-insensitive key with an explicit case policy
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted to its Unix form
+    pub fn normalized_key_with(&self, case: CaseSensitivity) -> PathResult<NormalizedKey> {
+        NormalizedKey::new(self.as_cross_ref(), Some(case))
+    }
+
+    /// Iterate over the typed components of the path
+    ///
+    /// The path is split according to the style it was written in, so
+    /// `C:\data\logs` and `C:/data/logs` yield the same components.
+    #[must_use]
+    pub fn components(&self) -> parser::Components {
+        self.as_cross_ref().components()
+    }
+
+    /// Get the last component of the path as written
+    ///
+    /// Windows paths are split on both separators on every platform.
+    #[must_use]
+    pub fn file_name(&self) -> Option<&str> {
+        self.as_cross_ref().file_name()
+    }
+
+    /// Get the file name without its last extension
+    #[must_use]
+    pub fn file_stem(&self) -> Option<&str> {
+        self.as_cross_ref().file_stem()
+    }
+
+    /// Get the last extension, without the dot
+    #[must_use]
+    pub fn extension(&self) -> Option<&str> {
+        self.as_cross_ref().extension()
+    }
+
+    /// Get the full extension, without the leading dot
+    ///
+    /// Compound extensions listed in
+    /// [`compound_extensions`](PathConfig::compound_extensions) are kept
+    /// whole, so `backup.tar.gz` yields `tar.gz`.
+    #[must_use]
+    pub fn extension_full(&self) -> Option<&str> {
+        self.as_cross_ref().extension_full()
+    }
+
+    /// Get the file name without its last extension, then without its full
+    /// extension if that is longer
+    ///
+    /// `backup.tar.gz` yields `backup.tar` and `backup`.
+    pub fn stems(&self) -> impl Iterator<Item = &str> {
+        self.as_cross_ref().stems()
+    }
+
+    /// Check if the path has an extension, as the target platform sees it
+    ///
+    /// `ext` may include a leading dot and may be a compound extension. The
+    /// comparison ignores case for Windows targets (`Auto` uses the current
+    /// platform) and for paths configured as case-insensitive.
+    #[must_use]
+    pub fn has_extension(&self, ext: &str, target: PathStyle) -> bool {
+        let style = match target {
+            _ if self
+                .as_cross_ref()
+                .ignores_case(self.config.case_sensitivity) =>
+            {
+                PathStyle::Windows
+            }
+            PathStyle::Auto => platform::current_style(),
+            style => style,
+        };
+        [self.extension_full(), self.extension()]
+            .into_iter()
+            .flatten()
+            .any(|candidate| extension::extensions_match(candidate, ext, style))
+    }
+
+    /// Look up the MIME type from the file extension
+    #[cfg(feature = "mime")]
+    #[must_use]
+    pub fn mime_type(&self) -> Option<&'static str> {
+        extension::mime_type(self.extension_full()?)
+    }
+
+    /// List components that would be illegal on the target, with proposed
+    /// replacements
+    ///
+    /// The drive and UNC server/share are not checked.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be parsed.
+    pub fn portability_fixes(&self, target: ValidationTarget) -> PathResult<Vec<PortabilityFix>> {
+        let parsed =
+            PathParser::parse_with_style(&self.inner.to_string_lossy(), self.written_style())?;
+        Ok(validation::portability_fixes(&parsed.components, target))
+    }
+
+    /// List everything that prevents the path from being created on a file
+    /// system
+    ///
+    /// Checks component and total path length, forbidden characters and
+    /// reserved names; the path is measured as written. The drive and UNC
+    /// server/share are not checked as components.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be parsed.
+    pub fn validate_for(
+        &self,
+        fs: validation::Filesystem,
+    ) -> PathResult<Vec<validation::FilesystemIssue>> {
+        let path = self.inner.to_string_lossy();
+        let parsed = PathParser::parse_with_style(&path, self.written_style())?;
+        Ok(validation::filesystem_issues(&parsed.components, &path, fs))
+    }
+
+    /// Apply fixes returned by [`portability_fixes`](Self::portability_fixes)
+    ///
+    /// Each fix replaces its component; the path keeps its original style
+    /// and configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if a fix does not match the path's components or
+    /// the fixed path cannot be formatted.
+    pub fn apply_fixes(&self, fixes: &[PortabilityFix]) -> PathResult<Self> {
+        let mut parsed =
+            PathParser::parse_with_style(&self.inner.to_string_lossy(), self.written_style())?;
+        for fix in fixes {
+            match parsed.components.get_mut(fix.index) {
+                Some(component) if *component == fix.component => {
+                    component.clone_from(&fix.replacement);
+                }
+                _ => {
+                    return Err(PathError::invalid_path(format!(
+                        "Fix does not apply to component {}: {}",
+                        fix.index, fix.component
+                    )));
+                }
+            }
+        }
+
+        let formatted =
+            formatter::PathFormatter::new(&self.config).format(&parsed, self.written_style())?;
+        Ok(Self {
+            inner: PathBuf::from(formatted),
+            original_style: self.original_style,
+            config: self.config.clone(),
+            origin: None,
+        })
+    }
+
+    /// Create a new path with `other` appended
+    ///
+    /// `other` is appended with the separator of the style this path was
+    /// written in, so `C:\data` joined with `logs/app.log` yields
+    /// `C:\data\logs\app.log`. If `other` is absolute in either style
+    /// it replaces the path, as with [`Path::join`]. The configuration is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the joined path cannot be parsed.
+    pub fn join<P: AsRef<str>>(&self, other: P) -> PathResult<Self> {
+        let mut joined = self.clone();
+        joined.push(other)?;
+        Ok(joined)
+    }
+
+    /// Join an untrusted relative path onto this path without escaping it
+    ///
+    /// `untrusted` must be relative; its `.` and `..` components are
+    /// collapsed lexically and may not climb above this path. With
+    /// [`NormalizeMode::Physical`] or [`NormalizeMode::LexicalThenVerify`]
+    /// as the configured `normalize_mode`, symlinks are resolved as well and
+    /// the physical result must stay inside the physical root. Use this before writing archive entries or
+    /// user-supplied names below a directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::SecurityError` if `untrusted` is absolute, has a
+    /// drive or UNC prefix, or resolves to a location outside this path.
+    pub fn safe_join<P: AsRef<str>>(&self, untrusted: P) -> PathResult<Self> {
+        let untrusted = untrusted.as_ref();
+        let escape =
+            || PathError::security_error(format!("{untrusted} escapes {}", self.inner.display()));
+        if root_len(untrusted, PathStyle::Windows) > 0 || untrusted.starts_with('/') {
+            return Err(escape());
+        }
+
+        let mut parts = Vec::new();
+        for part in untrusted.split(['/', '\\']) {
+            match part {
+                "" | "." => {}
+                ".." => {
+                    parts.pop().ok_or_else(escape)?;
+                }
+                name => parts.push(name),
+            }
+        }
+        let joined = self.join(parts.join(&separator_for(self.written_style()).to_string()))?;
+
+        if self.config.normalize_mode != NormalizeMode::Lexical {
+            let root = PathParser::normalize_physical(&self.inner)?;
+            if !PathParser::normalize_physical(&joined.inner)?.starts_with(&root) {
+                return Err(escape());
+            }
+        }
+        Ok(joined)
+    }
+
+    /// Append `other` to the path in place
+    ///
+    /// See [`join`](Self::join) for how separators and absolute paths are
+    /// handled. The path is left unchanged on error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the joined path cannot be parsed.
+    pub fn push<P: AsRef<str>>(&mut self, other: P) -> PathResult<()> {
+        let other = other.as_ref();
+        if self.inner.to_str().is_none() {
+            // Non-Unicode paths come from the OS, so its rules apply
+            PathParser::parse(other)?;
+            self.inner.push(other);
+            return Ok(());
+        }
+        let other_style = PathParser::detect_style(other);
+        if root_len(other, other_style) > 0 {
+            *self = Self::with_config(other, self.config.clone())?;
+            return Ok(());
+        }
+
+        let base = self.inner.to_string_lossy().into_owned();
+        // A single bare component says nothing about its style
+        let style = if base.contains(['/', '\\']) {
+            self.written_style()
+        } else {
+            other_style
+        };
+        let separator = separator_for(style);
+        let other = match style {
+            PathStyle::Windows => other.replace('/', "\\"),
+            _ if other_style == PathStyle::Windows => other.replace('\\', "/"),
+            _ => other.to_string(),
+        };
+
+        let joined = if base.is_empty() || base.ends_with(separator) {
+            format!("{base}{other}")
+        } else if other.is_empty() {
+            base
+        } else {
+            format!("{base}{separator}{other}")
+        };
+        PathParser::parse(&joined)?;
+
+        self.inner = PathBuf::from(joined);
+        self.original_style = style;
+        Ok(())
+    }
+
+    /// Truncate the path to its parent
+    ///
+    /// Returns `false` and leaves the path unchanged if it has no parent.
+    pub fn pop(&mut self) -> bool {
+        match self.parent() {
+            Some(parent) => {
+                *self = parent;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the parent path, keeping style and configuration
+    ///
+    /// Returns `None` for a root (`/`, `C:\`, `\\server\share`) or a
+    /// single relative component.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        if self.inner.to_str().is_none() {
+            // Non-Unicode paths come from the OS, so its rules apply
+            return self
+                .inner
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .map(|parent| Self {
+                    inner: parent.to_path_buf(),
+                    original_style: self.original_style,
+                    config: self.config.clone(),
+                    origin: None,
+                });
+        }
+
+        let style = self.written_style();
+        let path = self.inner.to_string_lossy();
+        let root = root_len(&path, style);
+        let trimmed = trim_separators(&path, root, style);
+        if trimmed.len() <= root {
+            return None;
+        }
+
+        let parent = match trimmed[root..].rfind(|c| is_separator(c, style)) {
+            Some(index) => trim_separators(&trimmed[..root + index], root, style),
+            None => &trimmed[..root],
+        };
+        (!parent.is_empty()).then(|| Self {
+            inner: PathBuf::from(parent),
+            original_style: self.original_style,
+            config: self.config.clone(),
+            origin: None,
+        })
+    }
+
+    /// Replace the full extension (an empty `ext` removes it)
+    ///
+    /// Compound extensions are replaced whole, so `backup.tar.gz` becomes
+    /// `backup.zip`. `ext` may include a leading dot. Returns `false` and
+    /// leaves the path unchanged if it has no file name.
+    pub fn set_extension(&mut self, ext: &str) -> bool {
+        if self.inner.to_str().is_none() {
+            return self.inner.set_extension(ext.trim_start_matches('.'));
+        }
+        let style = self.written_style();
+        let Some((name, stem)) = self.file_name().zip(self.stems().last()) else {
+            return false;
+        };
+
+        let path = self.inner.to_string_lossy();
+        let trimmed = trim_separators(&path, 0, style);
+        let prefix = &trimmed[..trimmed.len() - name.len()];
+        let ext = ext.trim_start_matches('.');
+        let updated = if ext.is_empty() {
+            format!("{prefix}{stem}")
+        } else {
+            format!("{prefix}{stem}.{ext}")
+        };
+
+        self.inner = PathBuf::from(updated);
+        true
+    }
+
+    /// Make the path absolute against `base` or the current directory
+    ///
+    /// Absolute paths are returned unchanged and other relative paths are
+    /// [joined](Self::join) onto the base. Windows drive-relative paths
+    /// (`C:foo`) resolve against the base if it lies on the same drive,
+    /// then against the current directory configured for the drive in
+    /// [`PathConfig::drive_current_dirs`], and against the drive root
+    /// (`C:\foo`) otherwise. Root-relative paths
+    /// (`\foo`) resolve against the drive or UNC share of the base, or `/`
+    /// for a Unix base. If `base` is relative, so is the result. The
+    /// configuration of this path is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the current directory cannot be determined or
+    /// the joined path cannot be parsed.
+    pub fn resolve(&self, base: Option<&Self>) -> PathResult<Self> {
+        let current;
+        let base = if let Some(base) = base {
+            base
+        } else {
+            current = Self::from(std::env::current_dir()?);
+            &current
+        };
+
+        let Some(path) = self.inner.to_str() else {
+            // Non-Unicode paths come from the OS, so its rules apply
+            return Ok(Self {
+                inner: base.inner.join(&self.inner),
+                original_style: self.original_style,
+                config: self.config.clone(),
+                origin: None,
+            });
+        };
+        let style = self.written_style();
+        let windows = style == PathStyle::Windows;
+
+        let (mut resolved, rest) = if windows && parser::is_drive_relative(path) {
+            let drive = &path[..2];
+            let on_drive = (base.written_style() == PathStyle::Windows)
+                .then(|| base.to_windows())
+                .transpose()?
+                .filter(|b| b.get(..2).is_some_and(|d| d.eq_ignore_ascii_case(drive)))
+                .or_else(|| {
+                    let letter = drive.chars().next()?;
+                    self.config.drive_current_dir(letter)
+                });
+            let root = on_drive.unwrap_or_else(|| format!("{}\\", drive.to_ascii_uppercase()));
+            (Self::build(&root, self.config.clone())?, &path[2..])
+        } else if windows && parser::is_root_relative(path) {
+            let base_path = base.inner.to_string_lossy();
+            let root = &base_path[..root_len(&base_path, base.written_style())];
+            let mut root = Self::build(root, self.config.clone())?;
+            root.original_style = base.written_style();
+            (root, &path[1..])
+        } else if root_len(path, style) > 0 {
+            return Ok(self.clone());
+        } else {
+            let mut base = base.clone();
+            base.config = self.config.clone();
+            (base, path)
+        };
+
+        resolved.push(rest)?;
+        Ok(resolved)
+    }
+
+    /// Compute the relative path from `base` to this path
+    ///
+    /// Both paths are compared in their Unix form after drive mapping and
+    /// lexical normalization, so a Windows path can be related to a Unix
+    /// one. The result uses `..` segments as needed, is written in this
+    /// path's style and converts to any target with [`to_style`](Self::to_style).
+    /// Components compare case-insensitively if either path is Windows style
+    /// or configured as case-insensitive.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::CrossDriveError` if the paths are on different
+    /// Windows drives or UNC shares (in either spelling), and `PathError::InvalidPath` if one path
+    /// is absolute and the other relative or `base` escapes above its start.
+    pub fn relative_to(&self, base: &Self) -> PathResult<Self> {
+        let windows = self.written_style() == PathStyle::Windows
+            || base.written_style() == PathStyle::Windows;
+        if windows || self.is_unix_unc() || base.is_unix_unc() {
+            let (own_root, base_root) = (self.windows_root()?, base.windows_root()?);
+            if own_root != base_root {
+                return Err(PathError::cross_drive_error(format!(
+                    "{} and {} do not share a drive",
+                    self.inner.display(),
+                    base.inner.display()
+                )));
+            }
+        }
+
+        let (own, base_components) = (self.unix_components()?, base.unix_components()?);
+        let is_absolute = |c: &[parser::Component]| c.first() == Some(&parser::Component::Root);
+        if is_absolute(&own) != is_absolute(&base_components) {
+            return Err(PathError::invalid_path(format!(
+                "Cannot relate absolute and relative paths: {} and {}",
+                self.inner.display(),
+                base.inner.display()
+            )));
+        }
+
+        let ignore_case = windows
+            || self
+                .as_cross_ref()
+                .ignores_case(self.config.case_sensitivity)
+            || base
+                .as_cross_ref()
+                .ignores_case(base.config.case_sensitivity);
+        let unicode = self.config.unicode_normalization;
+        let common = own
+            .iter()
+            .zip(&base_components)
+            .take_while(|(a, b)| same_component(a, b, ignore_case, unicode))
+            .count();
+
+        let mut relative = Vec::new();
+        for component in &base_components[common..] {
+            if *component == parser::Component::ParentDir {
+                return Err(PathError::invalid_path(format!(
+                    "Cannot compute a path relative to {}",
+                    base.inner.display()
+                )));
+            }
+            relative.push("..");
+        }
+        for component in &own[common..] {
+            match component {
+                parser::Component::Normal(name) => relative.push(name),
+                _ => relative.push(".."),
+            }
+        }
+        if relative.is_empty() {
+            relative.push(".");
+        }
+
+        let style = self.written_style();
+        Ok(Self {
+            inner: PathBuf::from(relative.join(&separator_for(style).to_string())),
+            original_style: style,
+            config: self.config.clone(),
+            origin: None,
+        })
+    }
+
+    /// Check if two paths refer to the same location under a case policy
+    ///
+    /// Both paths are compared in their Unix form after drive mapping and
+    /// lexical normalization, so `C:\Data\` equals `/mnt/c/data` when case
+    /// is ignored. `ProbeFilesystem` probes the file system holding this
+    /// path. Names differing only in Unicode normalization are equal unless
+    /// this path's `unicode_normalization` is `Exact`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if either path cannot be converted.
+    pub fn eq_with_case(&self, other: &Self, case: CaseSensitivity) -> PathResult<bool> {
+        let ignore_case = self.as_cross_ref().ignores_case(case);
+        let unicode = self.config.unicode_normalization;
+        let (own, other) = (self.unix_components()?, other.unix_components()?);
+        Ok(own.len() == other.len()
+            && own
+                .iter()
+                .zip(&other)
+                .all(|(a, b)| same_component(a, b, ignore_case, unicode)))
+    }
+
+    /// Check if two paths refer to the same location, ignoring case
+    ///
+    /// Shorthand for [`eq_with_case`](Self::eq_with_case) with
+    /// [`CaseSensitivity::Insensitive`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if either path cannot be converted.
+    pub fn eq_ignore_case(&self, other: &Self) -> PathResult<bool> {
+        self.eq_with_case(other, CaseSensitivity::Insensitive)
+    }
+
+    /// Check if the path starts with `base`, comparing whole components
+    ///
+    /// Paths are compared as in [`eq_with_case`](Self::eq_with_case), so
+    /// `C:\Users\me` starts with `/mnt/c/users` when case is ignored, but
+    /// not with `/mnt/c/Use`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if either path cannot be converted.
+    pub fn starts_with(&self, base: &Self, case: CaseSensitivity) -> PathResult<bool> {
+        Ok(self.strip_components(base, case)?.is_some())
+    }
+
+    /// Remove `base` from the start of the path
+    ///
+    /// Returns the rest as a relative path in this path's style, spelled as
+    /// in this path, or `None` if the path does not start with `base`. The
+    /// rest is empty if both paths are equal.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if either path cannot be converted.
+    pub fn strip_prefix(&self, base: &Self, case: CaseSensitivity) -> PathResult<Option<Self>> {
+        let Some(rest) = self.strip_components(base, case)? else {
+            return Ok(None);
+        };
+        let names: Vec<&str> = rest
+            .iter()
+            .filter_map(|component| match component {
+                parser::Component::Normal(name) => Some(name.as_str()),
+                parser::Component::ParentDir => Some(".."),
+                _ => None,
+            })
+            .collect();
+
+        let style = self.written_style();
+        Ok(Some(Self {
+            inner: PathBuf::from(names.join(&separator_for(style).to_string())),
+            original_style: style,
+            config: self.config.clone(),
+            origin: None,
+        }))
+    }
+
+    /// Check if the path is a proper ancestor of `other`
+    ///
+    /// A path is not its own ancestor. `ProbeFilesystem` probes the file
+    /// system holding `other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if either path cannot be converted.
+    pub fn is_ancestor_of(&self, other: &Self, case: CaseSensitivity) -> PathResult<bool> {
+        Ok(other
+            .strip_components(self, case)?
+            .is_some_and(|rest| !rest.is_empty()))
+    }
+
+    /// Components left after removing `base`, if the path starts with it
+    fn strip_components(
+        &self,
+        base: &Self,
+        case: CaseSensitivity,
+    ) -> PathResult<Option<Vec<parser::Component>>> {
+        let ignore_case = self.as_cross_ref().ignores_case(case);
+        let unicode = self.config.unicode_normalization;
+        let (mut own, base) = (self.unix_components()?, base.unix_components()?);
+        if base.len() > own.len()
+            || !own
+                .iter()
+                .zip(&base)
+                .all(|(a, b)| same_component(a, b, ignore_case, unicode))
+        {
+            return Ok(None);
+        }
+        Ok(Some(own.split_off(base.len())))
+    }
+
+    /// Drive letter or UNC share of the path in Windows form
+    fn windows_root(&self) -> PathResult<Vec<parser::Component>> {
+        let windows = self.to_windows()?;
+        Ok(PathParser::components(&windows, PathStyle::Windows)
+            .take_while(|c| {
+                matches!(
+                    c,
+                    parser::Component::Drive(_)
+                        | parser::Component::UncServer(_)
+                        | parser::Component::UncShare(_)
+                )
+            })
+            .map(|c| match c {
+                parser::Component::UncServer(name) => {
+                    parser::Component::UncServer(name.to_lowercase())
+                }
+                parser::Component::UncShare(name) => {
+                    parser::Component::UncShare(name.to_lowercase())
+                }
+                other => other,
+            })
+            .collect())
+    }
+
+    /// Whether the path is a UNC path written in Unix form (`//server/share`)
+    fn is_unix_unc(&self) -> bool {
+        let text = self.inner.to_string_lossy();
+        self.written_style() != PathStyle::Windows
+            && text.starts_with("//")
+            && !text.starts_with("///")
+    }
+
+    /// Lexically normalized components of the path in Unix form
+    fn unix_components(&self) -> PathResult<Vec<parser::Component>> {
+        let mut components = Vec::new();
+        for component in PathParser::components(&self.to_unix()?, PathStyle::Unix) {
+            match component {
+                parser::Component::CurDir => {}
+                parser::Component::ParentDir => match components.last() {
+                    Some(parser::Component::Normal(_)) => {
+                        components.pop();
+                    }
+                    Some(parser::Component::Root) => {}
+                    _ => components.push(component),
+                },
+                other => components.push(other),
+            }
+        }
+        Ok(components)
+    }
+
+    /// Style the path was written in (`Auto` resolved to the current platform)
+    fn written_style(&self) -> PathStyle {
+        self.as_cross_ref().written_style()
+    }
+
+    /// Get original path
+    #[must_use]
+    pub fn as_original(&self) -> &Path {
+        &self.inner
+    }
+
+    /// Borrow the path as a [`CrossPathRef`]
+    #[must_use]
+    pub fn as_cross_ref(&self) -> CrossPathRef<'_> {
+        CrossPathRef {
+            inner: &self.inner,
+            original_style: self.original_style,
+            config: Some(&self.config),
+            origin: self.origin.as_deref(),
+        }
+    }
+
+    /// Get the path exactly as stored, including non-Unicode data
+    #[must_use]
+    pub fn as_os_str(&self) -> &std::ffi::OsStr {
+        self.inner.as_os_str()
+    }
+
+    /// Get the path as written if it is valid Unicode
+    #[must_use]
+    pub fn to_str(&self) -> Option<&str> {
+        self.inner.to_str()
+    }
+
+    /// Get the path as written, replacing anything that is not valid
+    /// Unicode with `U+FFFD`
+    #[must_use]
+    pub fn to_string_lossy(&self) -> std::borrow::Cow<'_, str> {
+        self.inner.to_string_lossy()
+    }
+
+    /// Update configuration
+    pub fn set_config(&mut self, config: PathConfig) {
+        self.config = config;
+    }
+
+    /// Get configuration reference
+    #[must_use]
+    pub fn config(&self) -> &PathConfig {
+        &self.config
+    }
+
+    /// Check if path is safe
+    ///
+    /// Performs security checks including:
+    /// - Path traversal detection
+    /// - Dangerous pattern detection
+    /// - System directory access check
+    ///
+    /// The rules come from the configured `security_policy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if security check fails
+    #[cfg(feature = "security")]
+    pub fn is_safe(&self) -> PathResult<bool> {
+        self.as_cross_ref().is_safe()
+    }
+
+    /// Resolve the path to its canonical location through the file system
+    ///
+    /// The path is converted to its native form and resolved by the OS like
+    /// [`std::fs::canonicalize`], following symlinks and junctions. The
+    /// `\\?\` prefix Windows adds is removed, so the result converts to any
+    /// style with [`to_style`](Self::to_style); long Windows paths get it
+    /// back if `extended_length_prefix` is set. The configuration is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted to its native
+    /// form or does not exist.
+    pub fn canonicalize(&self) -> PathResult<Self> {
+        self.canonicalize_in(&vfs::OsFileSystem)
+    }
+
+    /// Resolve the path to its canonical location through the given
+    /// filesystem
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted to the native
+    /// form of `fs` or does not exist.
+    pub fn canonicalize_in(&self, fs: &dyn vfs::FileSystem) -> PathResult<Self> {
+        let canonical = fs.canonicalize(&ops::native_path(fs, self)?)?;
+        let Some(text) = canonical.to_str() else {
+            return Ok(Self {
+                inner: canonical,
+                original_style: PathStyle::Auto,
+                config: self.config.clone(),
+                origin: None,
+            });
+        };
+        let mut resolved =
+            Self::build(&converter::strip_extended_prefix(text), self.config.clone())?;
+        // Canonical paths are native, even where they look like Cygwin paths
+        resolved.original_style = fs.style();
+        Ok(resolved)
+    }
+
+    /// Check if two paths refer to the same physical file
+    ///
+    /// Unlike [`eq_with_case`](Self::eq_with_case) this asks the file
+    /// system: both paths are converted to their native form and compared
+    /// by device and inode on Unix, or volume serial number and file index
+    /// on Windows. Symlinks are followed, and hard links to one file count
+    /// as the same file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if either path cannot be converted to its native
+    /// form or does not exist.
+    pub fn is_same_file(&self, other: &Self) -> PathResult<bool> {
+        let fs = vfs::OsFileSystem;
+        let own = platform::file_id(&ops::native_path(&fs, self)?)?;
+        let other = platform::file_id(&ops::native_path(&fs, other)?)?;
+        Ok(own == other)
+    }
+
+    /// Normalize path
+    ///
+    /// Removes redundant components like `.` and `..`, using the configured
+    /// `normalize_mode` and `dot_dot_policy`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if normalization fails
+    pub fn normalize(&mut self) -> PathResult<()> {
+        self.normalize_with(self.config.normalize_mode)
+    }
+
+    /// Normalize path with an explicit mode
+    ///
+    /// [`NormalizeMode::Physical`] consults the filesystem and always yields
+    /// an absolute path. `..` components are handled per the configured
+    /// `dot_dot_policy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::NormalizationError` if the path contains `..`
+    /// under [`DotDotPolicy::Error`] or a verified lexical result does not
+    /// match the file system, and `PathError` if normalization fails
+    pub fn normalize_with(&mut self, mode: NormalizeMode) -> PathResult<()> {
+        let dot_dot = self.config.dot_dot_policy;
+        if dot_dot == DotDotPolicy::Error
+            && self.components().any(|c| c == parser::Component::ParentDir)
+        {
+            let path = self.inner.to_string_lossy();
+            let error = PathError::normalization_error(format!("{path} contains '..'"));
+            let is_separator = |c: char| c == '/' || c == '\\';
+            let span = path
+                .match_indices("..")
+                .map(|(i, _)| i..i + 2)
+                .find(|span| {
+                    path[..span.start]
+                        .chars()
+                        .next_back()
+                        .is_none_or(is_separator)
+                        && path[span.end..].chars().next().is_none_or(is_separator)
+                });
+            return Err(match span {
+                Some(span) => error.with_path(path.as_ref()).with_span(span),
+                None => error.with_path(path.as_ref()),
+            });
+        }
+
+        let normalized = match (mode, dot_dot) {
+            (NormalizeMode::Physical, _) => PathParser::normalize_physical(&self.inner)?,
+            (_, DotDotPolicy::Keep) => self.inner.components().collect(),
+            (NormalizeMode::Lexical, _) => PathParser::normalize_path(&self.inner)?,
+            (NormalizeMode::LexicalThenVerify, _) => {
+                let lexical = PathParser::normalize_path(&self.inner)?;
+                if PathParser::normalize_physical(&lexical)?
+                    != PathParser::normalize_physical(&self.inner)?
+                {
+                    return Err(PathError::normalization_error(format!(
+                        "{} resolves differently through symlinks than {}",
+                        self.inner.display(),
+                        lexical.display()
+                    )));
+                }
+                lexical
+            }
+        };
+        self.inner = normalized;
+        Ok(())
+    }
+}
+
+/// Compare two components, ignoring the case of names if `ignore_case`
+fn same_component(
+    a: &parser::Component,
+    b: &parser::Component,
+    ignore_case: bool,
+    unicode: UnicodeNormalization,
+) -> bool {
+    match (a, b) {
+        (parser::Component::Normal(a), parser::Component::Normal(b))
+        | (parser::Component::UncServer(a), parser::Component::UncServer(b))
+        | (parser::Component::UncShare(a), parser::Component::UncShare(b)) => {
+            let (a, b) = (unicode.fold(a), unicode.fold(b));
+            if ignore_case {
+                a.to_lowercase() == b.to_lowercase()
+            } else {
+                a == b
+            }
+        }
+        _ => a == b,
+    }
+}
+
+/// Separator written between components in `style`
+fn separator_for(style: PathStyle) -> char {
+    match style {
+        PathStyle::Windows => '\\',
+        _ => '/',
+    }
+}
+
+/// Check if `c` separates components in `style`
+fn is_separator(c: char, style: PathStyle) -> bool {
+    c == '/' || (style == PathStyle::Windows && c == '\\')
+}
+
+/// Length of the root prefix (`/`, `C:`, `C:\`, `\\server\share\`) of a
+/// path written in `style`
+pub(crate) fn root_len(path: &str, style: PathStyle) -> usize {
+    let bytes = path.as_bytes();
+    let separator = |i: usize| {
+        bytes
+            .get(i)
+            .is_some_and(|&b| is_separator(char::from(b), style))
+    };
+
+    if style != PathStyle::Windows {
+        return usize::from(separator(0));
+    }
+
+    // Extended-length UNC root: `\\?\UNC\server\share\`
+    let unc_start = match path.get(..8) {
+        Some(prefix) if prefix.eq_ignore_ascii_case(r"\\?\UNC\") => Some(8),
+        _ => (separator(0) && separator(1)).then_some(2),
+    };
+    if let Some(start) = unc_start {
+        // UNC root: separators, server, separator, share, separator
+        let mut end = start;
+        for _ in 0..2 {
+            while end < bytes.len() && !separator(end) {
+                end += 1;
+            }
+            end = (end + 1).min(bytes.len());
+        }
+        return end;
+    }
+
+    if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+        return 2 + usize::from(separator(2));
+    }
+
+    usize::from(separator(0))
+}
+
+/// Strip trailing separators, never cutting into the first `keep` bytes
+fn trim_separators(path: &str, keep: usize, style: PathStyle) -> &str {
+    let trimmed = path.trim_end_matches(|c| is_separator(c, style));
+    if trimmed.len() < keep {
+        &path[..keep]
+    } else {
+        trimmed
+    }
+}
+
+impl From<&Path> for CrossPath {
+    fn from(path: &Path) -> Self {
+        Self {
+            inner: path.to_path_buf(),
+            original_style: PathStyle::Auto,
+            config: PathConfig::current(),
+            origin: None,
+        }
+    }
+}
+
+impl From<PathBuf> for CrossPath {
+    fn from(path: PathBuf) -> Self {
+        Self {
+            inner: path,
+            original_style: PathStyle::Auto,
+            config: PathConfig::current(),
+            origin: None,
+        }
+    }
+}
+
+impl TryFrom<&std::ffi::OsStr> for CrossPath {
+    type Error = PathError;
+
+    fn try_from(path: &std::ffi::OsStr) -> PathResult<Self> {
+        Self::from_os_str(path)
+    }
+}
+
+impl std::str::FromStr for CrossPath {
+    type Err = PathError;
+
+    fn from_str(path: &str) -> PathResult<Self> {
+        Self::new(path)
+    }
+}
+
+impl AsRef<Path> for CrossPath {
+    fn as_ref(&self) -> &Path {
+        &self.inner
+    }
+}
+
+/// Renders the path in the configured `style`, as written for `Auto` or if
+/// the conversion fails
+impl fmt::Display for CrossPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.as_cross_ref(), f)
+    }
+}
+
+/// Borrowed cross-platform path
+///
+/// Relates to [`CrossPath`] as [`Path`] does to [`PathBuf`]: it wraps a
+/// `&str` or `&Path` without copying it and converts and compares like the
+/// owned path. Borrow one from a `CrossPath` with
+/// [`as_cross_ref`](CrossPath::as_cross_ref); functions taking
+/// `impl Into<CrossPathRef>` accept both forms as well as plain strings.
+///
+/// Unless one is given, the [current](PathConfig::current) configuration
+/// is looked up when a method needs it rather than at construction.
+/// Borrowed paths are not validated; [`into_owned`](Self::into_owned)
+/// keeps them as they are.
+#[derive(Debug, Clone, Copy)]
+pub struct CrossPathRef<'a> {
+    inner: &'a Path,
+    original_style: PathStyle,
+    config: Option<&'a PathConfig>,
+    origin: Option<&'a Origin>,
+}
+
+impl<'a> CrossPathRef<'a> {
+    /// Borrow a path string, detecting its style
+    #[must_use]
+    pub fn new(path: &'a str) -> Self {
+        Self::with_style(path, PathParser::detect_style(path))
+    }
+
+    /// Borrow a path string written in a known style
+    ///
+    /// `Auto` detects the style as [`new`](Self::new) does.
+    #[must_use]
+    pub fn with_style(path: &'a str, style: PathStyle) -> Self {
+        let style = match style {
+            PathStyle::Auto => PathParser::detect_style(path),
+            style => style,
+        };
+        Self {
+            inner: Path::new(path),
+            original_style: style,
+            config: None,
+            origin: None,
+        }
+    }
+
+    /// Borrow a path string with custom configuration
+    #[must_use]
+    pub fn with_config(path: &'a str, config: &'a PathConfig) -> Self {
+        Self {
+            config: Some(config),
+            ..Self::new(path)
+        }
+    }
+
+    /// Borrow an OS path, in the style of the current platform
+    #[must_use]
+    pub fn from_path(path: &'a Path) -> Self {
+        Self {
+            inner: path,
+            original_style: PathStyle::Auto,
+            config: None,
+            origin: None,
+        }
+    }
+
+    /// Copy into an owned [`CrossPath`] with the same style and
+    /// configuration
+    #[must_use]
+    pub fn into_owned(self) -> CrossPath {
+        CrossPath {
+            inner: self.inner.to_path_buf(),
+            original_style: self.original_style,
+            config: self.config().into_owned(),
+            origin: self.origin.cloned().map(Box::new),
+        }
+    }
+
+    /// Path this one was converted from, if it is unmodified since
+    fn origin(&self) -> Option<&'a Origin> {
+        self.origin
+            .filter(|origin| self.inner.to_str() == Some(origin.converted.as_str()))
+    }
+
+    /// Configuration of the path, falling back to the current one
+    #[must_use]
+    pub fn config(&self) -> Cow<'a, PathConfig> {
+        match self.config {
+            Some(config) => Cow::Borrowed(config),
+            None => Cow::Owned(PathConfig::current()),
+        }
+    }
+
+    /// Get original path
+    #[must_use]
+    pub fn as_original(&self) -> &'a Path {
+        self.inner
+    }
+
+    /// Get the path as written if it is valid Unicode
+    #[must_use]
+    pub fn to_str(&self) -> Option<&'a str> {
+        self.inner.to_str()
+    }
+
+    /// Convert to path string with specified style
+    ///
+    /// See [`CrossPath::to_style`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if conversion fails
+    pub fn to_style(&self, style: PathStyle) -> PathResult<String> {
+        if self.inner.to_str().is_none() {
+            return Err(PathError::encoding_error(format!(
+                "Path is not valid Unicode: {}",
+                self.inner.display()
+            )));
+        }
+        self.to_style_lossy(style)
+    }
+
+    /// Convert to path string with specified style, replacing anything that
+    /// is not valid Unicode with `U+FFFD`
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if conversion fails
+    pub fn to_style_lossy(&self, style: PathStyle) -> PathResult<String> {
+        let config = self.config();
+        let converter = PathConverter::new(&config);
+        let path = self.inner.to_string_lossy();
+        if config.preserve_roundtrip {
+            let target = converter.resolve_target(style);
+            if target == self.original_style {
+                return Ok(path.into_owned());
+            }
+            if let Some(origin) = self.origin().filter(|origin| origin.style == target) {
+                return Ok(origin.path.clone());
+            }
+        }
+        match self.original_style {
+            PathStyle::Cygwin | PathStyle::Msys | PathStyle::Custom(_) => {
+                converter.convert_between(&path, self.original_style, style)
+            }
+            _ => converter.convert(&path, style),
+        }
+    }
+
+    /// Convert to platform-appropriate path
+    ///
+    /// See [`CrossPath::to_platform`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if conversion fails
+    pub fn to_platform(&self) -> PathResult<String> {
+        let target_style = match self.config().style {
+            PathStyle::Auto => platform::current_style(),
+            style => style,
+        };
+        self.to_style(target_style)
+    }
+
+    /// Convert to Windows path
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if conversion fails
+    pub fn to_windows(&self) -> PathResult<String> {
+        self.to_style(PathStyle::Windows)
+    }
+
+    /// Convert to Unix path
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if conversion fails
+    pub fn to_unix(&self) -> PathResult<String> {
+        self.to_style(PathStyle::Unix)
+    }
+
+    /// Compute a style-invariant 64-bit hash of the path
+    ///
+    /// See [`CrossPath::stable_hash`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted to its canonical form
+    pub fn stable_hash(&self) -> PathResult<u64> {
+        const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let canonical = self.stable_form()?;
+        Ok(canonical.bytes().fold(OFFSET, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        }))
+    }
+
+    /// Compute a style-invariant 128-bit hash of the path
+    ///
+    /// See [`CrossPath::stable_hash128`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path cannot be converted to its canonical form
+    pub fn stable_hash128(&self) -> PathResult<u128> {
+        const OFFSET: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+        const PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+        let canonical = self.stable_form()?;
+        Ok(canonical.bytes().fold(OFFSET, |hash, byte| {
+            (hash ^ u128::from(byte)).wrapping_mul(PRIME)
+        }))
+    }
+
+    /// Canonical, style-independent form of the path
+    ///
+    /// Unix style after drive mapping, lexically normalized, without trailing
+    /// separators, in NFC unless `unicode_normalization` is `Exact` and case
+    /// folded per `case_sensitivity`.
+    pub(crate) fn canonical_form(&self) -> PathResult<String> {
+        let config = self.config();
+        let normalized = self.normalized_unix()?;
+        let canonical = config.unicode_normalization.fold(&normalized).into_owned();
+        Ok(if self.ignores_case(config.case_sensitivity) {
+            canonical.to_lowercase()
+        } else {
+            canonical
+        })
+    }
+
+    /// Canonical form used by the stable hashes
+    ///
+    /// Drive mappings and the conversion backend depend on the machine
+    /// (mount table, `wsl.conf`, installed tools), so they are replaced by
+    /// the built-in ones.
+    fn stable_form(&self) -> PathResult<String> {
+        let config = PathConfig {
+            drive_mappings: mapping::drives::DriveMappings::builtin().into(),
+            backend: ConversionBackend::Builtin,
+            ..self.config().into_owned()
+        };
+        CrossPathRef {
+            config: Some(&config),
+            ..*self
+        }
+        .canonical_form()
+    }
+
+    /// Check if `case` resolves to `Insensitive` for this path
+    ///
+    /// `ProbeFilesystem` probes the native form of the path.
+    pub(crate) fn ignores_case(&self, case: CaseSensitivity) -> bool {
+        let resolved = match case {
+            CaseSensitivity::ProbeFilesystem => match self.to_style(platform::current_style()) {
+                Ok(native) => case.resolve(Path::new(&native)),
+                Err(_) => CaseSensitivity::Platform.resolve(self.inner),
+            },
+            case => case.resolve(self.inner),
+        };
+        resolved == CaseSensitivity::Insensitive
+    }
+
+    /// Unix form after drive mapping, lexically normalized and without
+    /// trailing separators, in its original case
+    pub(crate) fn normalized_unix(&self) -> PathResult<String> {
+        let unix = self.to_unix()?;
+        let (root, rest) = if let Some(rest) = unix.strip_prefix("//") {
+            ("//", rest)
+        } else if let Some(rest) = unix.strip_prefix('/') {
+            ("/", rest)
+        } else {
+            ("", unix.as_str())
+        };
+
+        let mut components: Vec<&str> = Vec::new();
+        for component in rest.split('/') {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    if components.last().is_some_and(|c| *c != "..") {
+                        components.pop();
+                    } else if root.is_empty() {
+                        components.push(component);
+                    }
+                }
+                name => components.push(name),
+            }
+        }
+
+        Ok(format!("{root}{}", components.join("/")))
+    }
+
+    /// Iterate over the typed components of the path
+    #[must_use]
+    pub fn components(&self) -> parser::Components {
+        PathParser::components(&self.inner.to_string_lossy(), self.written_style())
+    }
+
+    /// Get the last component of the path as written
+    #[must_use]
+    pub fn file_name(&self) -> Option<&'a str> {
+        extension::file_name(self.inner.to_str()?, self.written_style())
+    }
+
+    /// Get the file name without its last extension
+    #[must_use]
+    pub fn file_stem(&self) -> Option<&'a str> {
+        extension::file_stem(self.file_name()?, self.written_style())
+    }
+
+    /// Get the last extension, without the dot
+    #[must_use]
+    pub fn extension(&self) -> Option<&'a str> {
+        extension::extension(self.file_name()?, self.written_style())
+    }
+
+    /// Get the full extension, without the leading dot
+    #[must_use]
+    pub fn extension_full(&self) -> Option<&'a str> {
+        extension::extension_full_with(
+            self.file_name()?,
+            self.written_style(),
+            &self.config().compound_extensions,
+        )
+    }
+
+    /// Get the stems of the file name
+    ///
+    /// See [`CrossPath::stems`].
+    pub fn stems(&self) -> impl Iterator<Item = &'a str> + use<'a> {
+        let stems = self.file_name().map(|name| {
+            extension::stems(
+                name,
+                self.written_style(),
+                &self.config().compound_extensions,
+            )
+        });
+        stems.into_iter().flatten()
+    }
+
+    /// Check if path is safe
+    ///
+    /// See [`CrossPath::is_safe`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if security check fails
+    #[cfg(feature = "security")]
+    pub fn is_safe(&self) -> PathResult<bool> {
+        security::PathSecurityChecker::with_policy(&self.config().security_policy)
+            .and_then(|checker| checker.check(self.inner))
+            .inspect_err(|e| {
+                metrics::emit_for(&self.config(), |m| m.record_security_rejection(e));
+            })
+    }
+
+    /// Style the path was written in (`Auto` resolved to the current platform)
+    fn written_style(&self) -> PathStyle {
+        match self.original_style {
+            PathStyle::Auto => platform::current_style(),
+            style => style,
+        }
+    }
+}
+
+impl AsRef<Path> for CrossPathRef<'_> {
+    fn as_ref(&self) -> &Path {
+        self.inner
+    }
+}
+
+/// Renders the path like [`CrossPath`] does
+impl fmt::Display for CrossPathRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.config().style {
+            PathStyle::Auto => {}
+            style => {
+                if let Ok(converted) = self.to_style(style) {
+                    return f.write_str(&converted);
+                }
+            }
+        }
+        f.write_str(&self.inner.to_string_lossy())
+    }
+}
+
+impl PartialEq for CrossPathRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+            && self.original_style == other.original_style
+            && match (self.config, other.config) {
+                (None, None) => true,
+                (Some(a), Some(b)) => a == b,
+                _ => self.config() == other.config(),
+            }
+            && self.origin == other.origin
+    }
+}
+
+impl Eq for CrossPathRef<'_> {}
+
+impl PartialEq<CrossPathRef<'_>> for CrossPath {
+    fn eq(&self, other: &CrossPathRef<'_>) -> bool {
+        self.as_cross_ref() == *other
+    }
+}
+
+impl PartialEq<CrossPath> for CrossPathRef<'_> {
+    fn eq(&self, other: &CrossPath) -> bool {
+        *self == other.as_cross_ref()
+    }
+}
+
+impl<'a> From<&'a str> for CrossPathRef<'a> {
+    fn from(path: &'a str) -> Self {
+        Self::new(path)
+    }
+}
+
+impl<'a> From<&'a Path> for CrossPathRef<'a> {
+    fn from(path: &'a Path) -> Self {
+        Self::from_path(path)
+    }
+}
+
+impl<'a> From<&'a CrossPath> for CrossPathRef<'a> {
+    fn from(path: &'a CrossPath) -> Self {
+        path.as_cross_ref()
+    }
+}
+
+impl From<CrossPathRef<'_>> for CrossPath {
+    fn from(path: CrossPathRef<'_>) -> Self {
+        path.into_owned()
     }
 }
 
@@ -0,0 +1,227 @@
+//! `PATH`-style path lists
+//!
+//! Windows joins the entries of `PATH`, `PSModulePath` and similar
+//! variables with `;` and quotes entries that contain one; Unix, Cygwin and
+//! MSYS join them with `:`. [`PathList`] splits either form, converts every
+//! entry and joins the result with the separator of the target style, which
+//! is what launching a process across the WSL boundary needs.
+//!
+//! ```rust
+//! use cross_path::list::PathList;
+//! use cross_path::{DriveMap, PathConfig, PathStyle};
+//!
+//! let config = PathConfig {
+//!     drive_mappings: DriveMap::from_pairs([("C:", "/mnt/c")])?,
+//!     ..PathConfig::default()
+//! };
+//! let list = PathList::parse(r#"C:\Windows;"C:\Tools;old";C:\bin"#, PathStyle::Auto);
+//! assert_eq!(list.len(), 3);
+//! let unix = list.convert(PathStyle::Unix, &config)?;
+//! assert_eq!(unix.join()?, "/mnt/c/Windows:/mnt/c/Tools;old:/mnt/c/bin");
+//! # Ok::<(), cross_path::PathError>(())
+//! ```
+
+use crate::{PathConfig, PathConverter, PathError, PathResult, PathStyle};
+use std::fmt::Write;
+
+/// Entries of a `PATH`-style list
+///
+/// Empty entries are dropped when parsing: Windows ignores them, and on
+/// Unix they stand for the current directory, which is rarely intended.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct PathList {
+    entries: Vec<String>,
+    style: PathStyle,
+}
+
+impl PathList {
+    /// Create empty list joined in `style`
+    #[must_use]
+    pub fn new(style: PathStyle) -> Self {
+        Self {
+            entries: Vec::new(),
+            style,
+        }
+    }
+
+    /// Split a list written in `style`
+    ///
+    /// `Windows` splits on `;` outside double quotes and removes the
+    /// quotes; the other styles split on `:`. `Auto` detects the style with
+    /// [`detect_style`](Self::detect_style).
+    #[must_use]
+    pub fn parse(list: &str, style: PathStyle) -> Self {
+        let style = match style {
+            PathStyle::Auto => Self::detect_style(list),
+            style => style,
+        };
+        let entries = if style == PathStyle::Windows {
+            split_windows(list)
+        } else {
+            list.split(':')
+                .filter(|entry| !entry.is_empty())
+                .map(ToString::to_string)
+                .collect()
+        };
+        Self { entries, style }
+    }
+
+    /// Guess whether a list is joined Windows-style or Unix-style
+    ///
+    /// Lists containing `;` or starting with a drive letter are Windows
+    /// lists; everything else is a Unix list.
+    #[must_use]
+    pub fn detect_style(list: &str) -> PathStyle {
+        let bytes = list.trim_start_matches('"').as_bytes();
+        let drive = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+        if list.contains(';') || (drive && matches!(bytes.get(2), None | Some(b'\\' | b'/' | b';')))
+        {
+            PathStyle::Windows
+        } else {
+            PathStyle::Unix
+        }
+    }
+
+    /// Style the list is joined in
+    #[must_use]
+    pub fn style(&self) -> PathStyle {
+        self.style
+    }
+
+    /// Get the entries in order
+    #[must_use]
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Iterate over the entries in order
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    /// Number of entries
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the list has no entries
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Append an entry, unless it is empty
+    pub fn push(&mut self, entry: impl Into<String>) {
+        let entry = entry.into();
+        if !entry.is_empty() {
+            self.entries.push(entry);
+        }
+    }
+
+    /// Convert every entry to `style`, giving a list joined in that style
+    ///
+    /// Entries of a Cygwin or MSYS list are read in that style; the others
+    /// are detected one by one.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `PathError` of the first entry that fails to convert,
+    /// with the entry's position (counted from 1) prefixed to its message.
+    pub fn convert(&self, style: PathStyle, config: &PathConfig) -> PathResult<Self> {
+        let converter = PathConverter::new(config);
+        let target = converter.resolve_target(style);
+        let source = match self.style {
+            PathStyle::Cygwin | PathStyle::Msys => self.style,
+            _ => PathStyle::Auto,
+        };
+
+        let mut list = Self::new(target);
+        for (index, entry) in self.entries.iter().enumerate() {
+            let entry = converter
+                .convert_between(entry, source, target)
+                .map_err(|e| e.with_context(format_args!("Entry {}", index + 1)))?;
+            list.push(entry);
+        }
+        Ok(list)
+    }
+
+    /// Join the entries with the separator of the list's style
+    ///
+    /// Windows entries containing `;` are quoted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::InvalidPath` if an entry of a `:`-joined list
+    /// contains `:`, which such lists cannot represent.
+    pub fn join(&self) -> PathResult<String> {
+        let mut joined = String::new();
+        for (index, entry) in self.entries.iter().enumerate() {
+            if self.style == PathStyle::Windows {
+                if index > 0 {
+                    joined.push(';');
+                }
+                if entry.contains(';') {
+                    let _ = write!(joined, "\"{entry}\"");
+                } else {
+                    joined.push_str(entry);
+                }
+            } else {
+                if entry.contains(':') {
+                    return Err(PathError::invalid_path(format!(
+                        "'{entry}' cannot be part of a ':'-separated list"
+                    )));
+                }
+                if index > 0 {
+                    joined.push(':');
+                }
+                joined.push_str(entry);
+            }
+        }
+        Ok(joined)
+    }
+}
+
+impl<S: Into<String>> Extend<S> for PathList {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, entries: I) {
+        for entry in entries {
+            self.push(entry);
+        }
+    }
+}
+
+/// Convert a `PATH`-style list to `style`
+///
+/// Shorthand for [`PathList::parse`] with auto-detection,
+/// [`PathList::convert`] and [`PathList::join`].
+///
+/// # Errors
+///
+/// Returns `PathError` if an entry fails to convert or cannot be joined.
+pub fn convert(list: &str, style: PathStyle, config: &PathConfig) -> PathResult<String> {
+    PathList::parse(list, PathStyle::Auto)
+        .convert(style, config)?
+        .join()
+}
+
+/// Split a Windows list on `;` outside double quotes, removing the quotes
+fn split_windows(list: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut entry = String::new();
+    let mut quoted = false;
+    for c in list.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                if !entry.is_empty() {
+                    entries.push(std::mem::take(&mut entry));
+                }
+            }
+            c => entry.push(c),
+        }
+    }
+    if !entry.is_empty() {
+        entries.push(entry);
+    }
+    entries
+}
@@ -0,0 +1,526 @@
+//! Development container mount loaders
+//!
+//! Reads `devcontainer.json` and docker-compose volume definitions and turns
+//! their bind mounts into a [`MappingSet`], so that editor tooling can
+//! translate file links and breakpoints between the host and the container.
+//!
+//! Only bind mounts produce mappings; named volumes and tmpfs mounts have no
+//! host-side path and are skipped.
+
+use super::{MappingSet, PathMapping, is_windows_host, parse_error, resolve_against};
+use crate::PathResult;
+use std::path::Path;
+
+/// Parse a single docker-style volume specification (`host:container[:mode]`)
+///
+/// Relative host paths are resolved against `base_dir`. Returns `None` for
+/// named volumes and anonymous volumes, which have no host path.
+#[must_use]
+pub fn parse_volume_spec(spec: &str, base_dir: &str) -> Option<PathMapping> {
+    let spec = unquote(spec.trim());
+
+    // Skip a leading drive letter so `C:\proj:/app` splits correctly
+    let search_from = if is_windows_host(spec) && !spec.starts_with(r"\\") {
+        2
+    } else {
+        0
+    };
+    let split = spec[search_from..].find(':')? + search_from;
+    let host = &spec[..split];
+    let rest = &spec[split + 1..];
+    let guest = rest.split(':').next().unwrap_or(rest);
+
+    if !is_host_path(host) || !guest.starts_with('/') {
+        return None;
+    }
+
+    Some(PathMapping::new(resolve_against(base_dir, host), guest))
+}
+
+/// Parse the volume entries of a docker-compose file
+///
+/// When `service` is given only that service's volumes are used, otherwise
+/// the bind mounts of every service are collected. Relative host paths are
+/// resolved against `base_dir`, normally the directory holding the compose file.
+///
+/// # Errors
+///
+/// Returns `PathError` if a long-syntax volume entry is missing its target.
+pub fn parse_compose_volumes(
+    content: &str,
+    service: Option<&str>,
+    base_dir: &str,
+) -> PathResult<MappingSet> {
+    let mut set = MappingSet::new();
+    let mut top_level_services: Option<usize> = None;
+    let mut current_service: Option<usize> = None;
+    let mut service_selected = false;
+    let mut volumes_block: Option<usize> = None;
+    let mut long_entry: Option<LongVolume> = None;
+
+    for raw in content.lines() {
+        let line = strip_yaml_comment(raw);
+        let text = line.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+
+        // Continuation lines of a long-syntax volume entry
+        if let Some(entry) = long_entry.as_mut() {
+            if indent > entry.indent && !text.starts_with('-') {
+                if let Some((key, value)) = split_key_value(text) {
+                    entry.set(key, value);
+                }
+                continue;
+            }
+            if let Some(entry) = long_entry.take()
+                && let Some(mapping) = entry.into_mapping(base_dir)?
+            {
+                set.push(mapping);
+            }
+        }
+
+        if let Some(volumes) = volumes_block {
+            if text.starts_with('-') && indent >= volumes {
+                if service_selected {
+                    let item = text[1..].trim();
+                    if let Some((key, value)) = split_key_value(item) {
+                        let mut entry = LongVolume::new(indent);
+                        entry.set(key, value);
+                        long_entry = Some(entry);
+                    } else if let Some(mapping) = parse_volume_spec(item, base_dir) {
+                        set.push(mapping);
+                    }
+                }
+                continue;
+            }
+            if indent <= volumes {
+                volumes_block = None;
+            } else {
+                continue;
+            }
+        }
+
+        if current_service.is_some_and(|i| indent <= i) {
+            current_service = None;
+        }
+        if top_level_services.is_some_and(|i| indent <= i) {
+            top_level_services = None;
+        }
+
+        match (top_level_services, current_service) {
+            (None, _) => {
+                if indent == 0 && text == "services:" {
+                    top_level_services = Some(indent);
+                }
+            }
+            (Some(_), None) => {
+                if let Some(name) = text.strip_suffix(':') {
+                    let name = unquote(name.trim());
+                    current_service = Some(indent);
+                    service_selected = service.is_none_or(|s| s == name);
+                }
+            }
+            (Some(_), Some(_)) => {
+                if text == "volumes:" {
+                    volumes_block = Some(indent);
+                }
+            }
+        }
+    }
+
+    if let Some(entry) = long_entry
+        && let Some(mapping) = entry.into_mapping(base_dir)?
+    {
+        set.push(mapping);
+    }
+
+    Ok(set)
+}
+
+/// Load the bind mounts of a docker-compose file
+///
+/// # Errors
+///
+/// Returns `PathError` if the file cannot be read or parsed.
+pub fn load_compose_file(path: &Path, service: Option<&str>) -> PathResult<MappingSet> {
+    let content = std::fs::read_to_string(path)?;
+    let base_dir = super::parent_dir(path);
+    parse_compose_volumes(&content, service, &base_dir)
+}
+
+/// Parse a `devcontainer.json` document
+///
+/// `local_workspace` is the host folder opened in the editor; it is used to
+/// expand `${localWorkspaceFolder}` and to build the default workspace mount.
+/// Compose-based configurations are only resolved by [`load_devcontainer`],
+/// since they reference other files.
+///
+/// # Errors
+///
+/// Returns `PathError` if the document is not valid JSON (comments and
+/// trailing commas are accepted).
+#[cfg(feature = "json")]
+pub fn parse_devcontainer(content: &str, local_workspace: &str) -> PathResult<MappingSet> {
+    let config = DevContainerConfig::parse(content)?;
+    Ok(config.mappings(local_workspace))
+}
+
+/// Load a `devcontainer.json` file, including referenced compose files
+///
+/// # Errors
+///
+/// Returns `PathError` if any of the files cannot be read or parsed.
+#[cfg(feature = "json")]
+pub fn load_devcontainer(path: &Path, local_workspace: &str) -> PathResult<MappingSet> {
+    let content = std::fs::read_to_string(path)?;
+    let config = DevContainerConfig::parse(&content)?;
+    let mut set = config.mappings(local_workspace);
+
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    for compose_file in &config.compose_files {
+        let compose_path = dir.join(compose_file);
+        set.extend(load_compose_file(&compose_path, config.service.as_deref())?);
+    }
+
+    Ok(set)
+}
+
+/// Relevant parts of a `devcontainer.json` document
+#[cfg(feature = "json")]
+struct DevContainerConfig {
+    workspace_mount: Option<String>,
+    mounts: Vec<MountSpec>,
+    compose_files: Vec<String>,
+    service: Option<String>,
+}
+
+/// Mount entry of a `devcontainer.json` document
+#[cfg(feature = "json")]
+enum MountSpec {
+    /// `source=...,target=...,type=bind` string
+    Text(String),
+    /// `{ "source": ..., "target": ..., "type": ... }` object
+    Fields {
+        source: Option<String>,
+        target: Option<String>,
+        kind: Option<String>,
+    },
+}
+
+#[cfg(feature = "json")]
+impl DevContainerConfig {
+    fn parse(content: &str) -> PathResult<Self> {
+        let cleaned = strip_jsonc(content);
+        let value: serde_json::Value =
+            serde_json::from_str(&cleaned).map_err(|e| parse_error("devcontainer.json", e))?;
+
+        let text = |key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+
+        let mounts = value
+            .get("mounts")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| match item {
+                        serde_json::Value::String(s) => Some(MountSpec::Text(s.clone())),
+                        serde_json::Value::Object(obj) => {
+                            let field =
+                                |k: &str| obj.get(k).and_then(|v| v.as_str()).map(String::from);
+                            Some(MountSpec::Fields {
+                                source: field("source"),
+                                target: field("target"),
+                                kind: field("type"),
+                            })
+                        }
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let compose_files = match value.get("dockerComposeFile") {
+            Some(serde_json::Value::String(s)) => vec![s.clone()],
+            Some(serde_json::Value::Array(items)) => items
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(Self {
+            workspace_mount: text("workspaceMount"),
+            mounts,
+            compose_files,
+            service: text("service"),
+        })
+    }
+
+    fn mappings(&self, local_workspace: &str) -> MappingSet {
+        let vars = Variables::new(local_workspace);
+        let mut set = MappingSet::new();
+
+        if let Some(mount) = &self.workspace_mount {
+            if let Some(mapping) = mount_from_text(&vars.expand(mount), local_workspace) {
+                set.push(mapping);
+            }
+        } else if self.compose_files.is_empty() {
+            let target = format!("/workspaces/{}", vars.basename);
+            set.push(PathMapping::new(local_workspace, target));
+        }
+
+        for mount in &self.mounts {
+            let mapping = match mount {
+                MountSpec::Text(text) => mount_from_text(&vars.expand(text), local_workspace),
+                MountSpec::Fields {
+                    source,
+                    target,
+                    kind,
+                } => mount_from_parts(
+                    source.as_deref().map(|s| vars.expand(s)).as_deref(),
+                    target.as_deref().map(|s| vars.expand(s)).as_deref(),
+                    kind.as_deref(),
+                    local_workspace,
+                ),
+            };
+            if let Some(mapping) = mapping {
+                set.push(mapping);
+            }
+        }
+
+        set
+    }
+}
+
+/// Variables available for substitution in `devcontainer.json`
+#[cfg(feature = "json")]
+struct Variables {
+    workspace: String,
+    basename: String,
+}
+
+#[cfg(feature = "json")]
+impl Variables {
+    fn new(local_workspace: &str) -> Self {
+        let basename = local_workspace
+            .trim_end_matches(['/', '\\'])
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        Self {
+            workspace: local_workspace.to_string(),
+            basename,
+        }
+    }
+
+    /// Expand `${...}` variables, leaving unknown ones untouched
+    fn expand(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+
+        while let Some(start) = rest.find("${") {
+            result.push_str(&rest[..start]);
+            let Some(end) = rest[start..].find('}') else {
+                rest = &rest[start..];
+                break;
+            };
+            let name = &rest[start + 2..start + end];
+            match self.lookup(name) {
+                Some(value) => result.push_str(&value),
+                None => result.push_str(&rest[start..=start + end]),
+            }
+            rest = &rest[start + end + 1..];
+        }
+
+        result.push_str(rest);
+        result
+    }
+
+    fn lookup(&self, name: &str) -> Option<String> {
+        match name {
+            "localWorkspaceFolder" => Some(self.workspace.clone()),
+            "localWorkspaceFolderBasename" => Some(self.basename.clone()),
+            "containerWorkspaceFolder" => Some(format!("/workspaces/{}", self.basename)),
+            _ => {
+                let spec = name.strip_prefix("localEnv:")?;
+                let (var, default) = spec.split_once(':').unwrap_or((spec, ""));
+                Some(std::env::var(var).unwrap_or_else(|_| default.to_string()))
+            }
+        }
+    }
+}
+
+/// Build a mapping from a `source=...,target=...` mount string
+#[cfg(feature = "json")]
+fn mount_from_text(text: &str, base_dir: &str) -> Option<PathMapping> {
+    let mut source = None;
+    let mut target = None;
+    let mut kind = None;
+
+    for part in text.split(',') {
+        let (key, value) = part.split_once('=')?;
+        match key.trim() {
+            "source" | "src" => source = Some(value.trim()),
+            "target" | "destination" | "dst" => target = Some(value.trim()),
+            "type" => kind = Some(value.trim()),
+            _ => {}
+        }
+    }
+
+    mount_from_parts(source, target, kind, base_dir)
+}
+
+#[cfg(feature = "json")]
+fn mount_from_parts(
+    source: Option<&str>,
+    target: Option<&str>,
+    kind: Option<&str>,
+    base_dir: &str,
+) -> Option<PathMapping> {
+    if kind.is_some_and(|k| k != "bind") {
+        return None;
+    }
+    let (source, target) = (source?, target?);
+    if !is_host_path(source) || !target.starts_with('/') {
+        return None;
+    }
+    Some(PathMapping::new(resolve_against(base_dir, source), target))
+}
+
+/// Strip `//` and `/* */` comments and trailing commas from JSONC text
+#[cfg(feature = "json")]
+fn strip_jsonc(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            result.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    result.push(escaped);
+                }
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                result.push(c);
+            }
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        result.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = '\0';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            (']' | '}', _) => {
+                let trimmed = result.trim_end().len();
+                if result[..trimmed].ends_with(',') {
+                    result.truncate(trimmed - 1);
+                }
+                result.push(c);
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Long-syntax compose volume entry
+struct LongVolume {
+    indent: usize,
+    kind: Option<String>,
+    source: Option<String>,
+    target: Option<String>,
+}
+
+impl LongVolume {
+    fn new(indent: usize) -> Self {
+        Self {
+            indent,
+            kind: None,
+            source: None,
+            target: None,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        let value = Some(unquote(value).to_string());
+        match key {
+            "type" => self.kind = value,
+            "source" => self.source = value,
+            "target" => self.target = value,
+            _ => {}
+        }
+    }
+
+    fn into_mapping(self, base_dir: &str) -> PathResult<Option<PathMapping>> {
+        if self.kind.as_deref().is_some_and(|k| k != "bind") {
+            return Ok(None);
+        }
+        let Some(target) = self.target else {
+            return Err(parse_error(
+                "docker-compose",
+                "volume entry is missing a target",
+            ));
+        };
+        Ok(self
+            .source
+            .filter(|source| is_host_path(source))
+            .map(|source| PathMapping::new(resolve_against(base_dir, &source), target)))
+    }
+}
+
+/// Check if a volume source refers to a host path rather than a named volume
+fn is_host_path(source: &str) -> bool {
+    source.starts_with(['.', '/', '~']) || is_windows_host(source)
+}
+
+/// Remove a trailing YAML comment from a line
+fn strip_yaml_comment(line: &str) -> &str {
+    if line.trim_start().starts_with('#') {
+        return "";
+    }
+    line.find(" #").map_or(line, |pos| &line[..pos])
+}
+
+/// Split a YAML `key: value` pair
+fn split_key_value(text: &str) -> Option<(&str, &str)> {
+    if text.starts_with(['"', '\'']) {
+        return None;
+    }
+    let (key, value) = text.split_once(": ")?;
+    Some((key.trim(), value.trim()))
+}
+
+/// Remove surrounding quotes from a scalar
+fn unquote(text: &str) -> &str {
+    let text = text.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = text.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    text
+}
@@ -0,0 +1,230 @@
+//! Drive mapping table
+//!
+//! [`DriveMap`] holds the Windows drive or share ↔ Unix directory pairs used
+//! by [`PathConfig`](crate::PathConfig). Both sides are indexed, so lookups
+//! cost one ordered-map probe per path component instead of a scan over
+//! every mapping, and the most specific mapping always wins regardless of
+//! the order mappings were added in: with `/mnt/c → C:` and
+//! `/mnt/c/Users → U:`, `/mnt/c/Users/me` maps to `U:\me`.
+//!
+//! Windows roots are compared case-insensitively and with either separator,
+//! so `c:`, `C:\` and `C:/` are the same key.
+
+use crate::{PathError, PathResult};
+use std::collections::BTreeMap;
+
+/// Table of Windows ↔ Unix root mappings with longest-prefix lookup
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(from = "Vec<(String, String)>", into = "Vec<(String, String)>")]
+pub struct DriveMap {
+    /// Mappings as written, in insertion order
+    entries: Vec<(String, String)>,
+    /// Windows key → index into `entries`
+    windows_index: BTreeMap<String, usize>,
+    /// Unix key → index into `entries`
+    unix_index: BTreeMap<String, usize>,
+}
+
+impl DriveMap {
+    /// Create empty drive map
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a drive map, rejecting duplicates
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::DriveMappingError` if a Windows or Unix root is
+    /// mapped twice.
+    pub fn from_pairs<I, W, U>(pairs: I) -> PathResult<Self>
+    where
+        I: IntoIterator<Item = (W, U)>,
+        W: Into<String>,
+        U: Into<String>,
+    {
+        let mut map = Self::new();
+        for (windows, unix) in pairs {
+            map.insert(windows, unix)?;
+        }
+        Ok(map)
+    }
+
+    /// Add a mapping
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::DriveMappingError` if the Windows or the Unix
+    /// root is already mapped.
+    pub fn insert(
+        &mut self,
+        windows: impl Into<String>,
+        unix: impl Into<String>,
+    ) -> PathResult<()> {
+        let (windows, unix) = (windows.into(), unix.into());
+        if let Some(existing) = self.get(&windows) {
+            return Err(PathError::drive_mapping_error(format!(
+                "{windows} is already mapped to {existing}"
+            )));
+        }
+        if let Some(&index) = self.unix_index.get(unix_key(&unix)) {
+            return Err(PathError::drive_mapping_error(format!(
+                "{unix} is already mapped to {}",
+                self.entries[index].0
+            )));
+        }
+        self.push(windows, unix);
+        Ok(())
+    }
+
+    /// Remove the mapping of a Windows root, returning its Unix root
+    pub fn remove(&mut self, windows: &str) -> Option<String> {
+        let index = *self.windows_index.get(&windows_key(windows))?;
+        let (_, unix) = self.entries.remove(index);
+        self.reindex();
+        Some(unix)
+    }
+
+    /// Keep only the mappings for which `keep` returns `true`
+    pub fn retain<F: FnMut(&str, &str) -> bool>(&mut self, mut keep: F) {
+        self.entries.retain(|(windows, unix)| keep(windows, unix));
+        self.reindex();
+    }
+
+    /// Get the Unix root a Windows root is mapped to
+    #[must_use]
+    pub fn get(&self, windows: &str) -> Option<&str> {
+        let index = *self.windows_index.get(&windows_key(windows))?;
+        Some(&self.entries[index].1)
+    }
+
+    /// Find the most specific mapping covering a Unix path
+    ///
+    /// Returns the Windows root and the rest of the path after the Unix
+    /// root, which is empty or starts with `/`. Roots match whole
+    /// components only, so `/mnt/c` does not cover `/mnt/cd`.
+    #[must_use]
+    pub fn find_unix<'a>(&self, path: &'a str) -> Option<(&str, &'a str)> {
+        let mut prefix = path.trim_end_matches('/');
+        loop {
+            if let Some(&index) = self.unix_index.get(prefix) {
+                return Some((&self.entries[index].0, &path[prefix.len()..]));
+            }
+            prefix = &prefix[..prefix.rfind('/')?];
+        }
+    }
+
+    /// Find the most specific mapping covering a Windows path
+    ///
+    /// Returns the Unix root and the rest of the path after the Windows
+    /// root, which is empty or starts with a separator. Roots match whole
+    /// components only and ignore ASCII case.
+    #[must_use]
+    pub fn find_windows<'a>(&self, path: &'a str) -> Option<(&str, &'a str)> {
+        let key = windows_key(path);
+        let mut prefix = key.as_str();
+        while !prefix.is_empty() {
+            if let Some(&index) = self.windows_index.get(prefix) {
+                // Keys keep the byte length of the path they were made from
+                return Some((&self.entries[index].1, &path[prefix.len()..]));
+            }
+            prefix = &prefix[..prefix.rfind('\\')?];
+        }
+        None
+    }
+
+    /// Iterate over the mappings as (Windows, Unix) pairs in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(w, u)| (w.as_str(), u.as_str()))
+    }
+
+    /// Get the mappings as (Windows, Unix) pairs
+    #[must_use]
+    pub fn as_slice(&self) -> &[(String, String)] {
+        &self.entries
+    }
+
+    /// Convert into (Windows, Unix) pairs
+    #[must_use]
+    pub fn into_vec(self) -> Vec<(String, String)> {
+        self.entries
+    }
+
+    /// Number of mappings
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if there are no mappings
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Append a mapping, unless either root is already mapped
+    fn push(&mut self, windows: String, unix: String) {
+        let index = self.entries.len();
+        let windows_key = windows_key(&windows);
+        let unix_key = unix_key(&unix).to_string();
+        if self.windows_index.contains_key(&windows_key) || self.unix_index.contains_key(&unix_key)
+        {
+            return;
+        }
+        self.windows_index.insert(windows_key, index);
+        self.unix_index.insert(unix_key, index);
+        self.entries.push((windows, unix));
+    }
+
+    /// Rebuild both indexes after entries were removed
+    fn reindex(&mut self) {
+        let entries = std::mem::take(&mut self.entries);
+        self.windows_index.clear();
+        self.unix_index.clear();
+        for (windows, unix) in entries {
+            self.push(windows, unix);
+        }
+    }
+}
+
+/// Collects pairs, keeping the first mapping of a duplicated root
+impl From<Vec<(String, String)>> for DriveMap {
+    fn from(pairs: Vec<(String, String)>) -> Self {
+        pairs.into_iter().collect()
+    }
+}
+
+impl From<DriveMap> for Vec<(String, String)> {
+    fn from(map: DriveMap) -> Self {
+        map.into_vec()
+    }
+}
+
+/// Collects pairs, keeping the first mapping of a duplicated root
+impl FromIterator<(String, String)> for DriveMap {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (windows, unix) in iter {
+            map.push(windows, unix);
+        }
+        map
+    }
+}
+
+/// Index key of a Windows root: upper case, `\` separators, no trailing
+/// separator
+///
+/// ASCII case folding and the separator swap keep the byte length, so
+/// prefixes of the key line up with prefixes of the original path.
+fn windows_key(path: &str) -> String {
+    path.replace('/', "\\")
+        .to_ascii_uppercase()
+        .trim_end_matches('\\')
+        .to_string()
+}
+
+/// Index key of a Unix root: no trailing separator (`/` becomes empty)
+fn unix_key(path: &str) -> &str {
+    path.trim_end_matches('/')
+}
@@ -0,0 +1,253 @@
+//! Drive mapping discovery
+//!
+//! [`DriveMappings::discover`] builds the Windows drive ↔ Unix mount point
+//! table from the running system instead of assuming `/mnt/<letter>`. On
+//! Linux and WSL it reads the mount table for `drvfs` and CIFS/SMB mounts
+//! and `/etc/wsl.conf` for the automount root, so drives mounted under a
+//! custom root such as `/c` are mapped correctly. On Windows the logical
+//! drives are enumerated and each one is mapped to `/mnt/<letter>`.
+//!
+//! [`PathConfig::default`](crate::PathConfig) uses the discovered mappings
+//! unless mappings were registered in the [`registry`](crate::registry), or
+//! the [environment defaults](crate::registry::set_environment_defaults)
+//! were enabled, which use [`DriveMappings::for_environment`].
+
+use super::drive_map::DriveMap;
+use crate::platform::{LogicalDrive, RuntimeEnvironment};
+use std::sync::OnceLock;
+
+/// Drive letters that are always mapped, even if not mounted
+const FALLBACK_DRIVES: [char; 3] = ['c', 'd', 'e'];
+/// Mount root used by WSL unless `/etc/wsl.conf` says otherwise
+const DEFAULT_AUTOMOUNT_ROOT: &str = "/mnt/";
+
+/// Cached result of the first discovery
+static DISCOVERED: OnceLock<DriveMappings> = OnceLock::new();
+
+/// Table of Windows drive or share ↔ Unix mount point mappings
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DriveMappings {
+    mappings: Vec<(String, String)>,
+}
+
+impl DriveMappings {
+    /// Built-in mappings: `C:`, `D:` and `E:` under `/mnt`
+    #[must_use]
+    pub fn builtin() -> Self {
+        Self::with_fallbacks(Vec::new(), DEFAULT_AUTOMOUNT_ROOT)
+    }
+
+    /// Built-in mappings under a custom mount root (`/` yields `/c`, `/d`
+    /// and `/e`)
+    #[must_use]
+    pub fn under_root(root: &str) -> Self {
+        Self::with_fallbacks(Vec::new(), root)
+    }
+
+    /// Discover mappings from the running system
+    ///
+    /// Mounted drives and shares come first; `C:`, `D:` and `E:` are added
+    /// under the automount root if they are not mounted, so the result is
+    /// never worse than [`builtin`](Self::builtin). Sources that cannot be
+    /// read are skipped.
+    #[must_use]
+    #[cfg(not(target_os = "windows"))]
+    pub fn discover() -> Self {
+        let mounts = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+        let wsl_conf = std::fs::read_to_string("/etc/wsl.conf").ok();
+        Self::from_sources(&mounts, wsl_conf.as_deref())
+    }
+
+    /// Discover mappings from the running system
+    ///
+    /// Every logical drive (fixed, removable, network, ...) is mapped to
+    /// `/mnt/<letter>`; `C:`, `D:` and `E:` are always included.
+    #[must_use]
+    #[cfg(target_os = "windows")]
+    pub fn discover() -> Self {
+        Self::from_drives(&crate::platform::windows::logical_drives())
+    }
+
+    /// Build mappings for enumerated Windows drives
+    ///
+    /// Each drive is mapped to `/mnt/<letter>`, the layout WSL uses, followed
+    /// by the built-in drives that are missing.
+    #[must_use]
+    pub fn from_drives(drives: &[LogicalDrive]) -> Self {
+        let mounted = drives
+            .iter()
+            .map(|drive| {
+                let letter = drive.letter.to_ascii_lowercase();
+                (
+                    format!("{}:", letter.to_ascii_uppercase()),
+                    format!("{DEFAULT_AUTOMOUNT_ROOT}{letter}"),
+                )
+            })
+            .collect();
+        Self::with_fallbacks(mounted, DEFAULT_AUTOMOUNT_ROOT)
+    }
+
+    /// Build mappings from a mount table in `/proc/mounts` format and the
+    /// contents of `/etc/wsl.conf`
+    #[must_use]
+    #[cfg(not(target_os = "windows"))]
+    pub fn from_sources(mounts: &str, wsl_conf: Option<&str>) -> Self {
+        let entries = crate::platform::unix::parse_mount_table(mounts);
+        let root = wsl_conf
+            .and_then(automount_root)
+            .unwrap_or_else(|| DEFAULT_AUTOMOUNT_ROOT.to_string());
+        Self::with_fallbacks(mount_drive_mappings(&entries), &root)
+    }
+
+    /// Mappings discovered once per process
+    ///
+    /// The first call runs [`discover`](Self::discover); later calls return
+    /// the cached result. Use
+    /// [`MappingWatcher`](super::watcher::MappingWatcher) to follow mount
+    /// changes.
+    #[must_use]
+    pub fn cached() -> Self {
+        if let Some(mappings) = DISCOVERED.get() {
+            crate::metrics::emit(|m| m.record_cache_hit("drive_mappings"));
+            return mappings.clone();
+        }
+        crate::metrics::emit(|m| m.record_cache_miss("drive_mappings"));
+        DISCOVERED.get_or_init(Self::discover).clone()
+    }
+
+    /// Mappings suited to the environment the process runs in
+    ///
+    /// Cygwin mounts drives under `/cygdrive` and MSYS2 under `/`, so
+    /// drives [discovered](Self::cached) under `/mnt` are moved there. WSL
+    /// keeps its automount root, and native systems and containers use the
+    /// discovered mappings as they are.
+    #[must_use]
+    pub fn for_environment(environment: RuntimeEnvironment) -> Self {
+        let mappings = Self::cached();
+        match environment {
+            RuntimeEnvironment::Cygwin => mappings.moved_under("/cygdrive/"),
+            RuntimeEnvironment::Msys => mappings.moved_under("/"),
+            _ => mappings,
+        }
+    }
+
+    /// Move drives mapped to `/mnt/<letter>` under `root`
+    fn moved_under(mut self, root: &str) -> Self {
+        for (windows, unix) in &mut self.mappings {
+            let letter = windows
+                .strip_suffix(':')
+                .filter(|letter| letter.len() == 1)
+                .map(str::to_ascii_lowercase);
+            if let Some(letter) = letter
+                && *unix == format!("{DEFAULT_AUTOMOUNT_ROOT}{letter}")
+            {
+                *unix = format!("{root}{letter}");
+            }
+        }
+        self
+    }
+
+    /// Get the mappings as (Windows, Unix) pairs
+    #[must_use]
+    pub fn as_slice(&self) -> &[(String, String)] {
+        &self.mappings
+    }
+
+    /// Convert into (Windows, Unix) pairs
+    #[must_use]
+    pub fn into_vec(self) -> Vec<(String, String)> {
+        self.mappings
+    }
+
+    /// Add fallback drives under `root` that are not mapped yet
+    fn with_fallbacks(mut mappings: Vec<(String, String)>, root: &str) -> Self {
+        let root = format!("{}/", root.trim_end_matches('/'));
+        for letter in FALLBACK_DRIVES {
+            let drive = format!("{}:", letter.to_ascii_uppercase());
+            if !mappings.iter().any(|(w, _)| w.eq_ignore_ascii_case(&drive)) {
+                mappings.push((drive, format!("{root}{letter}")));
+            }
+        }
+        Self { mappings }
+    }
+}
+
+impl From<DriveMappings> for Vec<(String, String)> {
+    fn from(mappings: DriveMappings) -> Self {
+        mappings.into_vec()
+    }
+}
+
+/// For [`PathConfig::drive_mappings`](crate::PathConfig::drive_mappings)
+impl From<DriveMappings> for DriveMap {
+    fn from(mappings: DriveMappings) -> Self {
+        mappings.into_vec().into()
+    }
+}
+
+/// Read the automount root from `/etc/wsl.conf` contents
+///
+/// Returns the `root` key of the `[automount]` section, e.g. `/` when drives
+/// are mounted as `/c`.
+#[must_use]
+pub fn automount_root(wsl_conf: &str) -> Option<String> {
+    let mut in_automount = false;
+    for line in wsl_conf.lines() {
+        let line = line.split(['#', ';']).next().unwrap_or_default().trim();
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_automount = section.trim().eq_ignore_ascii_case("automount");
+            continue;
+        }
+        if !in_automount {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=')
+            && key.trim().eq_ignore_ascii_case("root")
+        {
+            let value = value.trim().trim_matches(['"', '\'']);
+            return (!value.is_empty()).then(|| value.to_string());
+        }
+    }
+    None
+}
+
+/// Drive mappings for `drvfs` and CIFS/SMB entries of a mount table
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn mount_drive_mappings(
+    entries: &[crate::platform::unix::MountEntry],
+) -> Vec<(String, String)> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let windows = match entry.fs_type.as_str() {
+                "drvfs" => drvfs_drive(&entry.device)?,
+                "9p" if entry.options.iter().any(|o| o.starts_with("aname=drvfs")) => {
+                    drvfs_drive(&entry.device)?
+                }
+                "cifs" | "smb3" | "smbfs" => {
+                    let share = entry.device.strip_prefix("//")?;
+                    // macOS names the user in the source (`//user@nas/share`)
+                    let share = match share.split_once('@') {
+                        Some((user, share)) if !user.contains('/') => share,
+                        _ => share,
+                    };
+                    format!(r"\\{}", share.replace('/', "\\"))
+                }
+                _ => return None,
+            };
+            Some((windows, entry.mount_point.clone()))
+        })
+        .collect()
+}
+
+/// Extract the drive (`C:`) from a drvfs device such as `C:\`
+#[cfg(not(target_os = "windows"))]
+fn drvfs_drive(device: &str) -> Option<String> {
+    let mut chars = device.chars();
+    match (chars.next(), chars.next()) {
+        (Some(letter), Some(':')) if letter.is_ascii_alphabetic() => {
+            Some(format!("{}:", letter.to_ascii_uppercase()))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,273 @@
+//! Host ↔ guest path mappings
+//!
+//! This module provides a small mapping table for translating paths between a
+//! host machine and a guest environment such as a development container or a
+//! virtual machine.
+//!
+//! Both sides may be written in either Windows or Unix style; Windows-style
+//! prefixes are compared case-insensitively. Lookups use longest-prefix
+//! matching on whole path components, so `/workspace` never matches
+//! `/workspaces`.
+
+pub mod devcontainer;
+pub mod drive_map;
+pub mod drives;
+pub mod samba;
+pub mod sandbox;
+pub mod vagrant;
+pub mod volume;
+pub mod watcher;
+
+use crate::PathError;
+
+/// A single host ↔ guest path mapping
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PathMapping {
+    /// Path on the host side (e.g., `C:\Users\name\project`)
+    pub host: String,
+    /// Path on the guest side (e.g., `/workspace`)
+    pub guest: String,
+}
+
+impl PathMapping {
+    /// Create new path mapping
+    pub fn new(host: impl Into<String>, guest: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            guest: guest.into(),
+        }
+    }
+}
+
+/// Ordered set of host ↔ guest path mappings
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MappingSet {
+    mappings: Vec<PathMapping>,
+}
+
+impl MappingSet {
+    /// Create empty mapping set
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a mapping to the set
+    ///
+    /// Mappings with an identical host and guest pair are only stored once.
+    pub fn push(&mut self, mapping: PathMapping) {
+        if !self.mappings.contains(&mapping) {
+            self.mappings.push(mapping);
+        }
+    }
+
+    /// Append all mappings from another set
+    pub fn extend(&mut self, other: MappingSet) {
+        for mapping in other.mappings {
+            self.push(mapping);
+        }
+    }
+
+    /// Iterate over the mappings in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = &PathMapping> {
+        self.mappings.iter()
+    }
+
+    /// Number of mappings in the set
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    /// Check if the set contains no mappings
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// Translate a host path to the guest view
+    ///
+    /// Returns `None` if no mapping covers the path.
+    #[must_use]
+    pub fn to_guest(&self, host_path: &str) -> Option<String> {
+        let (mapping, rest) = self
+            .mappings
+            .iter()
+            .filter_map(|m| strip_path_prefix(&m.host, host_path).map(|rest| (m, rest)))
+            .max_by_key(|(m, _)| trim_separators(&m.host).len())?;
+
+        Some(join_with(
+            &mapping.guest,
+            &rest,
+            host_separator(&mapping.guest),
+        ))
+    }
+
+    /// Translate a guest path to the host view
+    ///
+    /// Returns `None` if no mapping covers the path.
+    #[must_use]
+    pub fn to_host(&self, guest_path: &str) -> Option<String> {
+        let (mapping, rest) = self
+            .mappings
+            .iter()
+            .filter_map(|m| strip_path_prefix(&m.guest, guest_path).map(|rest| (m, rest)))
+            .max_by_key(|(m, _)| trim_separators(&m.guest).len())?;
+
+        Some(join_with(
+            &mapping.host,
+            &rest,
+            host_separator(&mapping.host),
+        ))
+    }
+}
+
+impl FromIterator<PathMapping> for MappingSet {
+    fn from_iter<I: IntoIterator<Item = PathMapping>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for mapping in iter {
+            set.push(mapping);
+        }
+        set
+    }
+}
+
+impl<'a> IntoIterator for &'a MappingSet {
+    type Item = &'a PathMapping;
+    type IntoIter = core::slice::Iter<'a, PathMapping>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.mappings.iter()
+    }
+}
+
+/// Check whether a mapping path is written in Windows style
+fn is_windows_host(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with(r"\\")
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+/// Separator used by a mapping path
+fn host_separator(path: &str) -> char {
+    if is_windows_host(path) { '\\' } else { '/' }
+}
+
+/// Split a path into its non-empty components, accepting both separators
+fn split_components(path: &str) -> Vec<&str> {
+    path.split(['/', '\\']).filter(|s| !s.is_empty()).collect()
+}
+
+/// Remove trailing separators (keeping a lone root)
+fn trim_separators(path: &str) -> &str {
+    let trimmed = path.trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() { path } else { trimmed }
+}
+
+/// Strip a mapping prefix from a path, comparing whole components
+///
+/// Windows-style prefixes are compared case-insensitively.
+fn strip_path_prefix(prefix: &str, path: &str) -> Option<Vec<String>> {
+    let windows = is_windows_host(prefix);
+    if windows != is_windows_host(path) || prefix.starts_with('/') != path.starts_with('/') {
+        return None;
+    }
+    strip_components(prefix, path, windows)
+}
+
+fn strip_components(prefix: &str, path: &str, case_insensitive: bool) -> Option<Vec<String>> {
+    let prefix_parts = split_components(prefix);
+    let path_parts = split_components(path);
+
+    if prefix_parts.len() > path_parts.len() {
+        return None;
+    }
+
+    let matches = prefix_parts.iter().zip(&path_parts).all(|(a, b)| {
+        if case_insensitive {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    });
+
+    matches.then(|| {
+        path_parts[prefix_parts.len()..]
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    })
+}
+
+/// Append components to a base path using the given separator
+fn join_with(base: &str, rest: &[String], separator: char) -> String {
+    let mut result = trim_separators(base).to_string();
+    for component in rest {
+        if !result.ends_with(['/', '\\']) {
+            result.push(separator);
+        }
+        result.push_str(component);
+    }
+    result
+}
+
+/// Lexically join a relative path onto a base directory
+///
+/// Absolute relative parts replace the base. `.` and `..` components are
+/// resolved using the separator style of the resulting root. Paths starting
+/// with `~` are returned unchanged.
+fn resolve_against(base: &str, relative: &str) -> String {
+    if relative.starts_with('~') {
+        return relative.to_string();
+    }
+    let (base, relative) = if relative.starts_with('/') || is_windows_host(relative) {
+        (relative, "")
+    } else {
+        (base, relative)
+    };
+
+    let separator = host_separator(base);
+    let root_len = usize::from(is_windows_host(base) && !base.starts_with(r"\\"));
+    let mut parts: Vec<&str> = Vec::new();
+
+    for component in split_components(base)
+        .into_iter()
+        .chain(split_components(relative))
+    {
+        match component {
+            "." => {}
+            ".." => {
+                if parts.len() > root_len {
+                    parts.pop();
+                }
+            }
+            name => parts.push(name),
+        }
+    }
+
+    let mut result = if base.starts_with(r"\\") {
+        String::from(r"\\")
+    } else if base.starts_with('/') {
+        String::from("/")
+    } else {
+        String::new()
+    };
+    result.push_str(&parts.join(&separator.to_string()));
+    if root_len == 1 && parts.len() == 1 {
+        result.push(separator);
+    }
+    result
+}
+
+/// Directory containing a file, as a string
+fn parent_dir(path: &std::path::Path) -> String {
+    path.parent()
+        .map(|p| p.to_string_lossy().into_owned())
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| ".".to_string())
+}
+
+/// Build a parse error for a mapping source
+fn parse_error(source: &str, msg: impl core::fmt::Display) -> PathError {
+    PathError::parse_error(format!("{source}: {msg}"))
+}
@@ -0,0 +1,132 @@
+//! Samba share mappings
+//!
+//! Parses `smb.conf` share sections (`[share]` with `path = /srv/data`) into
+//! mappings between the UNC path clients use (`\\server\share\...`) and the
+//! server-local Unix path, so admin tools can translate client-reported
+//! Windows paths into server paths.
+//!
+//! In the produced [`MappingSet`] the server is the host: `host` holds the
+//! server-local path and `guest` the UNC path seen by clients.
+
+use super::{MappingSet, PathMapping};
+use crate::PathResult;
+use std::path::Path;
+
+/// Sections that never describe a file share
+const SPECIAL_SECTIONS: [&str; 3] = ["global", "printers", "print$"];
+
+/// Parse the shares of an `smb.conf` document
+///
+/// `server` is the host name clients use to reach the server.
+/// Shares whose path uses substitution variables (such as `%U` in `[homes]`)
+/// and shares marked `available = no` are skipped.
+///
+/// # Errors
+///
+/// Returns `PathError` if a section header is malformed.
+pub fn parse_smb_conf(content: &str, server: &str) -> PathResult<MappingSet> {
+    let mut set = MappingSet::new();
+    let mut current: Option<Share> = None;
+
+    for line in logical_lines(content) {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(['#', ';']) {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) else {
+                return Err(super::parse_error(
+                    "smb.conf",
+                    format!("malformed section header: {line}"),
+                ));
+            };
+            if let Some(share) = current.take() {
+                share.push_into(&mut set, server);
+            }
+            current = Some(Share::new(name.trim()));
+            continue;
+        }
+
+        let (Some(share), Some((key, value))) = (current.as_mut(), line.split_once('=')) else {
+            continue;
+        };
+        match key.trim().to_ascii_lowercase().as_str() {
+            "path" | "directory" => share.path = Some(value.trim().to_string()),
+            "available" => share.available = !is_false(value.trim()),
+            _ => {}
+        }
+    }
+
+    if let Some(share) = current {
+        share.push_into(&mut set, server);
+    }
+
+    Ok(set)
+}
+
+/// Load the shares of an `smb.conf` file
+///
+/// # Errors
+///
+/// Returns `PathError` if the file cannot be read or parsed.
+pub fn load_smb_conf(path: &Path, server: &str) -> PathResult<MappingSet> {
+    let content = std::fs::read_to_string(path)?;
+    parse_smb_conf(&content, server)
+}
+
+/// Share section being parsed
+struct Share {
+    name: String,
+    path: Option<String>,
+    available: bool,
+}
+
+impl Share {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            path: None,
+            available: true,
+        }
+    }
+
+    fn push_into(self, set: &mut MappingSet, server: &str) {
+        let is_special = SPECIAL_SECTIONS
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(&self.name));
+        if is_special || !self.available {
+            return;
+        }
+        if let Some(path) = self.path.filter(|p| p.starts_with('/') && !p.contains('%')) {
+            set.push(PathMapping::new(path, format!(r"\\{server}\{}", self.name)));
+        }
+    }
+}
+
+/// Join lines ending with a backslash continuation
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut pending = String::new();
+
+    for line in content.lines() {
+        if let Some(continued) = line.strip_suffix('\\') {
+            pending.push_str(continued);
+        } else {
+            pending.push_str(line);
+            lines.push(core::mem::take(&mut pending));
+        }
+    }
+    if !pending.is_empty() {
+        lines.push(pending);
+    }
+
+    lines
+}
+
+/// Check if an `smb.conf` boolean is false
+fn is_false(value: &str) -> bool {
+    ["no", "false", "0"]
+        .iter()
+        .any(|v| v.eq_ignore_ascii_case(value))
+}
@@ -0,0 +1,267 @@
+//! Sandbox path remapping
+//!
+//! Sandboxed applications see parts of the host file system under other
+//! names: a Flatpak app finds the host's `/usr` and `/etc` under
+//! `/run/host`, a strictly confined Snap reaches the host root through
+//! `/var/lib/snapd/hostfs`, and containers often bind-mount the host root at
+//! `/host` or `/run/host`. Files handed over through the document portal
+//! appear as `/run/user/<uid>/doc/<id>/<name>`.
+//!
+//! [`SandboxMap`] translates paths between the sandbox and the host. It
+//! starts from the built-in remappings of a [`Sandbox`] kind, or from
+//! [`SandboxMap::detect`], and takes further entries such as
+//! [documents](SandboxMap::insert_document). Paths no entry covers are
+//! shared unchanged, which is also true of document portal paths, whose
+//! FUSE mount is visible on the host as well.
+//!
+//! ```rust
+//! use cross_path::mapping::sandbox::{Sandbox, SandboxMap};
+//!
+//! let snap = SandboxMap::for_sandbox(Sandbox::Snap);
+//! assert_eq!(snap.to_host("/var/lib/snapd/hostfs/etc/hosts"), "/etc/hosts");
+//! assert_eq!(snap.to_sandbox("/etc/hosts"), "/var/lib/snapd/hostfs/etc/hosts");
+//! assert_eq!(snap.to_sandbox("/home/me/a.txt"), "/home/me/a.txt");
+//! ```
+
+use super::{MappingSet, PathMapping, trim_separators};
+use crate::expand::{Environment, SystemEnvironment};
+use crate::platform::{RuntimeEnvironment, detect_environment};
+use crate::{PathError, PathResult};
+
+/// Kind of application sandbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sandbox {
+    /// Flatpak app (`/.flatpak-info` exists)
+    Flatpak,
+    /// Snap package (`SNAP_NAME` is set)
+    Snap,
+    /// Other container
+    Container,
+}
+
+impl Sandbox {
+    /// Classify from the presence of `/.flatpak-info`, the environment
+    /// variables and the detected runtime environment
+    #[must_use]
+    pub fn from_sources(
+        flatpak_info: bool,
+        env: &dyn Environment,
+        runtime: RuntimeEnvironment,
+    ) -> Option<Self> {
+        if flatpak_info || env.var("FLATPAK_ID").is_some_and(|id| !id.is_empty()) {
+            Some(Self::Flatpak)
+        } else if env.var("SNAP_NAME").is_some_and(|name| !name.is_empty()) {
+            Some(Self::Snap)
+        } else if runtime == RuntimeEnvironment::Container {
+            Some(Self::Container)
+        } else {
+            None
+        }
+    }
+}
+
+/// Detect the sandbox the process runs in, if any
+#[must_use]
+pub fn detect_sandbox() -> Option<Sandbox> {
+    Sandbox::from_sources(
+        std::path::Path::new("/.flatpak-info").exists(),
+        &SystemEnvironment,
+        detect_environment(),
+    )
+}
+
+/// Host directories a Flatpak app sees under `/run/host`
+const FLATPAK_HOST_DIRS: &[&str] = &["/usr", "/etc"];
+
+/// Root of the host file system inside a strictly confined Snap
+const SNAP_HOSTFS: &str = "/var/lib/snapd/hostfs";
+
+/// Host directories a Snap shares at the same path
+const SNAP_SHARED_DIRS: &[&str] = &["/home", "/media", "/run/media", "/mnt"];
+
+/// Places containers commonly bind-mount the host root at
+const CONTAINER_HOST_ROOTS: &[&str] = &["/host", "/run/host"];
+
+/// Table of host ↔ sandbox path remappings
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(from = "MappingSet", into = "MappingSet")]
+pub struct SandboxMap {
+    mappings: MappingSet,
+}
+
+impl SandboxMap {
+    /// Create empty sandbox map, which shares every path unchanged
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Built-in remappings of a sandbox kind
+    ///
+    /// Containers have none, since where the host root is mounted differs
+    /// between tools; add it with [`insert`](Self::insert) or use
+    /// [`detect`](Self::detect).
+    #[must_use]
+    pub fn for_sandbox(sandbox: Sandbox) -> Self {
+        let mappings = match sandbox {
+            Sandbox::Flatpak => FLATPAK_HOST_DIRS
+                .iter()
+                .map(|dir| PathMapping::new(*dir, format!("/run/host{dir}")))
+                .collect(),
+            Sandbox::Snap => std::iter::once(PathMapping::new("/", SNAP_HOSTFS))
+                .chain(
+                    SNAP_SHARED_DIRS
+                        .iter()
+                        .map(|dir| PathMapping::new(*dir, *dir)),
+                )
+                .collect(),
+            Sandbox::Container => MappingSet::new(),
+        };
+        Self { mappings }
+    }
+
+    /// Remappings of the sandbox the process runs in
+    ///
+    /// Inside a container the host root is mapped if it is mounted at
+    /// `/host` or `/run/host`. Outside any sandbox the map is empty.
+    #[must_use]
+    pub fn detect() -> Self {
+        let Some(sandbox) = detect_sandbox() else {
+            return Self::new();
+        };
+        let mut map = Self::for_sandbox(sandbox);
+        if sandbox == Sandbox::Container
+            && let Some(root) = CONTAINER_HOST_ROOTS
+                .iter()
+                .find(|root| std::path::Path::new(root).is_dir())
+        {
+            map.mappings.push(PathMapping::new("/", *root));
+        }
+        map
+    }
+
+    /// Add a remapping of a host directory or file
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::InvalidPath` if either side is not an absolute
+    /// Unix path.
+    pub fn insert(
+        &mut self,
+        host: impl Into<String>,
+        sandbox: impl Into<String>,
+    ) -> PathResult<()> {
+        let (host, sandbox) = (host.into(), sandbox.into());
+        for path in [&host, &sandbox] {
+            if !path.starts_with('/') {
+                return Err(PathError::invalid_path(format!(
+                    "Sandbox mapping path must be an absolute Unix path: {path}"
+                )));
+            }
+        }
+        self.mappings.push(PathMapping::new(host, sandbox));
+        Ok(())
+    }
+
+    /// Add a file exported through the document portal
+    ///
+    /// The sandbox sees `host_path` as `<runtime_dir>/doc/<id>/<name>`,
+    /// where `runtime_dir` is the app's `XDG_RUNTIME_DIR`
+    /// (`/run/user/<uid>`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::InvalidPath` if `id` is empty or contains a
+    /// separator, or `host_path` is not an absolute file path.
+    pub fn insert_document(
+        &mut self,
+        runtime_dir: &str,
+        id: &str,
+        host_path: &str,
+    ) -> PathResult<()> {
+        if id.is_empty() || id.contains('/') {
+            return Err(PathError::invalid_path(format!(
+                "Invalid document portal ID: {id}"
+            )));
+        }
+        let name = trim_separators(host_path)
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| {
+                PathError::invalid_path(format!("Document path has no file name: {host_path}"))
+            })?;
+        let sandbox = format!("{}/doc/{id}/{name}", trim_separators(runtime_dir));
+        self.insert(host_path, sandbox)
+    }
+
+    /// Iterate over the remappings in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = &PathMapping> {
+        self.mappings.iter()
+    }
+
+    /// Number of remappings
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    /// Check if there are no remappings
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// Translate a path seen inside the sandbox to the host
+    ///
+    /// The most specific remapping wins; uncovered paths are returned
+    /// unchanged.
+    #[must_use]
+    pub fn to_host(&self, sandbox_path: &str) -> String {
+        self.mappings
+            .to_host(sandbox_path)
+            .unwrap_or_else(|| sandbox_path.to_string())
+    }
+
+    /// Translate a host path to the path the sandbox sees
+    ///
+    /// The most specific remapping wins; uncovered paths are returned
+    /// unchanged.
+    #[must_use]
+    pub fn to_sandbox(&self, host_path: &str) -> String {
+        self.mappings
+            .to_guest(host_path)
+            .unwrap_or_else(|| host_path.to_string())
+    }
+}
+
+impl From<MappingSet> for SandboxMap {
+    fn from(mappings: MappingSet) -> Self {
+        Self { mappings }
+    }
+}
+
+impl From<SandboxMap> for MappingSet {
+    fn from(map: SandboxMap) -> Self {
+        map.mappings
+    }
+}
+
+/// Document ID of a document portal path
+///
+/// Recognizes `/run/user/<uid>/doc/<id>/...` and the `/run/flatpak/doc/<id>`
+/// view.
+#[must_use]
+pub fn document_id(path: &str) -> Option<&str> {
+    let rest = if let Some(rest) = path.strip_prefix("/run/flatpak/doc/") {
+        rest
+    } else {
+        let (uid, rest) = path.strip_prefix("/run/user/")?.split_once('/')?;
+        if uid.is_empty() || !uid.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        rest.strip_prefix("doc/")?
+    };
+    rest.split('/').next().filter(|id| !id.is_empty())
+}
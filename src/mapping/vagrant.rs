@@ -0,0 +1,107 @@
+//! Vagrant, `VirtualBox` and Hyper-V shared folder mappings
+//!
+//! Provides presets for the usual shared folder conventions and a small parser
+//! for `config.vm.synced_folder` entries in a `Vagrantfile`, so paths can be
+//! converted between the host and guest views of a synced folder.
+
+use super::{MappingSet, PathMapping, resolve_against};
+use crate::PathResult;
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// `config.vm.synced_folder "host", "guest", options...`
+static SYNCED_FOLDER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\.vm\.synced_folder\s*\(?\s*(["'])([^"']*)["']\s*,\s*(["'])([^"']*)["'](.*)$"#)
+        .unwrap()
+});
+/// `disabled: true` in the synced folder options
+static DISABLED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(disabled:|:disabled\s*=>)\s*true").unwrap());
+
+/// Guest operating system of a virtual machine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum GuestOs {
+    /// Linux guest, shared folders are mounted into the Unix tree
+    Linux,
+    /// Windows guest, shared folders are reached through UNC paths
+    Windows,
+}
+
+/// Default Vagrant synced folder (project directory ↔ `/vagrant`)
+pub fn vagrant_default(project_dir: impl Into<String>) -> PathMapping {
+    PathMapping::new(project_dir, "/vagrant")
+}
+
+/// `VirtualBox` shared folder mapping
+///
+/// Linux guests with the guest additions automount shares at
+/// `/media/sf_<share>`, Windows guests reach them at `\\vboxsvr\<share>`.
+pub fn virtualbox_share(host_dir: impl Into<String>, share: &str, guest: GuestOs) -> PathMapping {
+    let guest_path = match guest {
+        GuestOs::Linux => format!("/media/sf_{share}"),
+        GuestOs::Windows => format!(r"\\vboxsvr\{share}"),
+    };
+    PathMapping::new(host_dir, guest_path)
+}
+
+/// Hyper-V SMB shared folder mapping as seen from a Windows guest
+///
+/// Hyper-V synced folders are exported by the host over SMB and reached from
+/// the guest at `\\<host_name>\<share>`. Linux guests mount the share at the
+/// synced folder's guest path, which [`parse_vagrantfile`] already covers.
+pub fn hyperv_share(host_dir: impl Into<String>, host_name: &str, share: &str) -> PathMapping {
+    PathMapping::new(host_dir, format!(r"\\{host_name}\{share}"))
+}
+
+/// Parse the synced folders of a `Vagrantfile`
+///
+/// The implicit `.` ↔ `/vagrant` folder is included unless the Vagrantfile
+/// disables it. Relative host paths are resolved against `project_dir`.
+#[must_use]
+pub fn parse_vagrantfile(content: &str, project_dir: &str) -> MappingSet {
+    let mut folders = Vec::new();
+    let mut default_enabled = true;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let Some(caps) = SYNCED_FOLDER.captures(line) else {
+            continue;
+        };
+
+        let (host, guest, options) = (&caps[2], &caps[4], &caps[5]);
+        if DISABLED.is_match(options) {
+            if guest == "/vagrant" {
+                default_enabled = false;
+            }
+            continue;
+        }
+
+        folders.push(PathMapping::new(resolve_against(project_dir, host), guest));
+    }
+
+    let mut set = MappingSet::new();
+    if default_enabled && !folders.iter().any(|m| m.guest == "/vagrant") {
+        set.push(vagrant_default(resolve_against(project_dir, ".")));
+    }
+    for folder in folders {
+        set.push(folder);
+    }
+
+    set
+}
+
+/// Load the synced folders of a `Vagrantfile`
+///
+/// The directory holding the Vagrantfile is used as the project directory.
+///
+/// # Errors
+///
+/// Returns `PathError` if the file cannot be read.
+pub fn load_vagrantfile(path: &Path) -> PathResult<MappingSet> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(parse_vagrantfile(&content, &super::parent_dir(path)))
+}
@@ -0,0 +1,258 @@
+//! Container volume mount tables
+//!
+//! [`VolumeMap`] holds the bind mounts of a container, such as `C:\proj` ↔
+//! `/workspace`, and converts paths across the mount boundary in either
+//! direction. It generalizes the drive mappings of
+//! [`PathConfig`](crate::PathConfig) to arbitrary host directories: the
+//! most specific mount wins, and a path outside every mount is an error
+//! rather than a guess.
+//!
+//! Host paths do not have to be written like the mount: when no mount
+//! matches literally, the path is converted to the other style with the
+//! [current](crate::PathConfig::current) drive mappings and looked up
+//! again, so `/mnt/c/proj/src` finds the `C:\proj` mount from inside WSL.
+//!
+//! ```rust
+//! use cross_path::mapping::volume::VolumeMap;
+//!
+//! let volumes = VolumeMap::from_specs([r"C:\proj:/workspace", "cargo:/usr/local/cargo"], "/")?;
+//! assert_eq!(volumes.len(), 1);
+//! assert_eq!(volumes.to_container(r"C:\proj\src\main.rs")?, "/workspace/src/main.rs");
+//! assert_eq!(volumes.to_host("/workspace/Cargo.toml")?, r"C:\proj\Cargo.toml");
+//! # Ok::<(), cross_path::PathError>(())
+//! ```
+
+use super::devcontainer::{parse_compose_volumes, parse_volume_spec};
+use super::{
+    MappingSet, PathMapping, host_separator, is_windows_host, join_with, parse_error,
+    strip_path_prefix, trim_separators,
+};
+use crate::{PathConfig, PathConverter, PathError, PathResult, PathStyle, UnmappedPolicy};
+
+/// Table of host ↔ container bind mounts
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(from = "MappingSet", into = "MappingSet")]
+pub struct VolumeMap {
+    mounts: Vec<PathMapping>,
+}
+
+impl VolumeMap {
+    /// Create empty volume map
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a volume map from docker-style `host:container[:mode]` specs
+    ///
+    /// Relative host paths are resolved against `base_dir`. Named and
+    /// anonymous volumes have no host path and are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::DriveMappingError` if two specs mount at the same
+    /// container path.
+    pub fn from_specs<I, S>(specs: I, base_dir: &str) -> PathResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut map = Self::new();
+        for spec in specs {
+            if let Some(mapping) = parse_volume_spec(spec.as_ref(), base_dir) {
+                map.insert(mapping.host, mapping.guest)?;
+            }
+        }
+        Ok(map)
+    }
+
+    /// Build a volume map from the bind mounts of a docker-compose file
+    ///
+    /// See [`parse_compose_volumes`] for how `service` and `base_dir` are
+    /// used. When several services mount the same container path, the
+    /// first mount is kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if a long-syntax volume entry is missing its target.
+    pub fn from_compose(content: &str, service: Option<&str>, base_dir: &str) -> PathResult<Self> {
+        Ok(parse_compose_volumes(content, service, base_dir)?.into())
+    }
+
+    /// Add a mount
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::InvalidPath` if either side is not absolute or
+    /// the container side is not a Unix path, and
+    /// `PathError::DriveMappingError` if the container path is already
+    /// mounted.
+    pub fn insert(
+        &mut self,
+        host: impl Into<String>,
+        container: impl Into<String>,
+    ) -> PathResult<()> {
+        let (host, container) = (host.into(), container.into());
+        if !is_windows_host(&host) && !host.starts_with('/') {
+            return Err(PathError::invalid_path(format!(
+                "Volume host path must be absolute: {host}"
+            )));
+        }
+        if !container.starts_with('/') {
+            return Err(PathError::invalid_path(format!(
+                "Volume container path must be an absolute Unix path: {container}"
+            )));
+        }
+        if let Some(existing) = self.host_of(&container) {
+            return Err(PathError::drive_mapping_error(format!(
+                "{container} is already mounted from {existing}"
+            )));
+        }
+        self.mounts.push(PathMapping::new(host, container));
+        Ok(())
+    }
+
+    /// Add a mount from a docker-style `host:container[:mode]` spec
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::ParseError` if the spec is not a bind mount, and
+    /// the errors of [`insert`](Self::insert).
+    pub fn insert_spec(&mut self, spec: &str, base_dir: &str) -> PathResult<()> {
+        let mapping = parse_volume_spec(spec, base_dir)
+            .ok_or_else(|| parse_error("volume spec", format!("not a bind mount: {spec}")))?;
+        self.insert(mapping.host, mapping.guest)
+    }
+
+    /// Remove the mount at a container path, returning its host path
+    pub fn remove(&mut self, container: &str) -> Option<String> {
+        let index = self
+            .mounts
+            .iter()
+            .position(|m| trim_separators(&m.guest) == trim_separators(container))?;
+        Some(self.mounts.remove(index).host)
+    }
+
+    /// Get the host path mounted at a container path
+    #[must_use]
+    pub fn host_of(&self, container: &str) -> Option<&str> {
+        self.mounts
+            .iter()
+            .find(|m| trim_separators(&m.guest) == trim_separators(container))
+            .map(|m| m.host.as_str())
+    }
+
+    /// Iterate over the mounts in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = &PathMapping> {
+        self.mounts.iter()
+    }
+
+    /// Number of mounts
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.mounts.len()
+    }
+
+    /// Check if there are no mounts
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mounts.is_empty()
+    }
+
+    /// Convert a host path to the path it has inside the container
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::DriveMappingError` if no mount covers the path.
+    pub fn to_container(&self, host_path: &str) -> PathResult<String> {
+        let lookup = |path: &str| {
+            let (mount, rest) = longest_match(&self.mounts, path, |m| &m.host)?;
+            Some(join_with(&mount.guest, &rest, '/'))
+        };
+        lookup(host_path)
+            .or_else(|| lookup(&other_style(host_path)?))
+            .ok_or_else(|| {
+                PathError::drive_mapping_error(format!(
+                    "{host_path} is not inside a mounted volume"
+                ))
+            })
+    }
+
+    /// Convert a container path to the path it has on the host
+    ///
+    /// `\` separators are accepted, so `\workspace\src` works too.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::DriveMappingError` if no mount covers the path.
+    pub fn to_host(&self, container_path: &str) -> PathResult<String> {
+        let path = container_path.replace('\\', "/");
+        let (mount, rest) = longest_match(&self.mounts, &path, |m| &m.guest).ok_or_else(|| {
+            PathError::drive_mapping_error(format!(
+                "{container_path} is not inside a mounted volume"
+            ))
+        })?;
+        Ok(join_with(&mount.host, &rest, host_separator(&mount.host)))
+    }
+}
+
+/// Keeps the first mount of a duplicated container path
+impl From<MappingSet> for VolumeMap {
+    fn from(set: MappingSet) -> Self {
+        let mut map = Self::new();
+        for mapping in &set {
+            if map.host_of(&mapping.guest).is_none() {
+                map.mounts.push(mapping.clone());
+            }
+        }
+        map
+    }
+}
+
+impl From<VolumeMap> for MappingSet {
+    fn from(map: VolumeMap) -> Self {
+        map.mounts.into_iter().collect()
+    }
+}
+
+/// Find the mount whose `side` is the longest prefix of `path`
+///
+/// Ties go to the mount added first.
+fn longest_match<'a, F>(
+    mounts: &'a [PathMapping],
+    path: &str,
+    side: F,
+) -> Option<(&'a PathMapping, Vec<String>)>
+where
+    F: Fn(&PathMapping) -> &str,
+{
+    let mut best: Option<(&PathMapping, Vec<String>)> = None;
+    for mount in mounts {
+        let Some(rest) = strip_path_prefix(side(mount), path) else {
+            continue;
+        };
+        if best.as_ref().is_none_or(|(b, _)| {
+            trim_separators(side(mount)).len() > trim_separators(side(b)).len()
+        }) {
+            best = Some((mount, rest));
+        }
+    }
+    best
+}
+
+/// Convert a host path to the other style with the current drive mappings
+///
+/// Unix paths without a drive mapping have no Windows form here, so they
+/// cannot match a mount by accident.
+fn other_style(path: &str) -> Option<String> {
+    let target = if is_windows_host(path) {
+        PathStyle::Unix
+    } else {
+        PathStyle::Windows
+    };
+    let config = PathConfig {
+        unmapped_policy: UnmappedPolicy::Error,
+        ..PathConfig::current()
+    };
+    PathConverter::new(&config).convert(path, target).ok()
+}
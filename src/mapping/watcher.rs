@@ -0,0 +1,190 @@
+//! Live mapping refresh from the mount table
+//!
+//! [`MappingWatcher`] polls the system mount table (`/proc/self/mounts` on
+//! Linux, `getmntinfo` on macOS, the logical drive list on Windows) and keeps
+//! the global drive mappings in [`registry`](crate::registry) in sync as
+//! drives and shares are mounted and unmounted. Applications that cache
+//! converted paths can register a callback to re-validate them.
+//!
+//! Windows is polled as well: volume arrival is only announced through
+//! `WM_DEVICECHANGE` window messages, which would need a hidden window and
+//! message loop, and polling `GetLogicalDrives` every few seconds is cheap.
+
+use crate::{PathResult, registry};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Granularity used when sleeping between polls, so `stop` stays responsive
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Drive mappings added and removed between two mount table snapshots
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MappingChange {
+    /// Newly mounted drive mappings (Windows drive or share, Unix mount point)
+    pub added: Vec<(String, String)>,
+    /// Drive mappings that are no longer mounted
+    pub removed: Vec<(String, String)>,
+}
+
+impl MappingChange {
+    /// Compute the change between two snapshots
+    #[must_use]
+    pub fn between(old: &[(String, String)], new: &[(String, String)]) -> Self {
+        Self {
+            added: new.iter().filter(|m| !old.contains(m)).cloned().collect(),
+            removed: old.iter().filter(|m| !new.contains(m)).cloned().collect(),
+        }
+    }
+
+    /// Check if nothing changed
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Apply the change to a list of drive mappings
+    ///
+    /// Added mappings replace existing entries for the same Windows drive.
+    pub fn apply(&self, mappings: &mut Vec<(String, String)>) {
+        mappings.retain(|m| !self.removed.contains(m));
+        for (windows, unix) in &self.added {
+            mappings.retain(|(w, _)| !w.eq_ignore_ascii_case(windows));
+            mappings.push((windows.clone(), unix.clone()));
+        }
+    }
+}
+
+/// Background watcher keeping the global drive mappings up to date
+///
+/// The watcher stops when dropped.
+#[derive(Debug)]
+pub struct MappingWatcher {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MappingWatcher {
+    /// Start watching the mount table
+    ///
+    /// The registered mappings (the discovered ones, including unmounted
+    /// fallback drives, if none are registered yet) are reconciled with the
+    /// current mounts immediately, so the registry mirrors the mount table
+    /// and entries that are not mounted are reported as removed. Afterwards
+    /// the mount table is polled every `interval`. `on_change` is called
+    /// after each change has been applied, starting with the initial one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the mount table cannot be read or the watcher
+    /// thread cannot be spawned.
+    pub fn start<F>(interval: Duration, mut on_change: F) -> PathResult<Self>
+    where
+        F: FnMut(&MappingChange) + Send + 'static,
+    {
+        let mut current = mounted_drive_mappings()?;
+        let mut initial = MappingChange::default();
+        registry::update_drive_mappings(|mappings| {
+            initial = MappingChange::between(mappings, &current);
+            initial.apply(mappings);
+        });
+        if !initial.is_empty() {
+            on_change(&initial);
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_flag = Arc::clone(&stop);
+
+        let handle = thread::Builder::new()
+            .name("cross-path-mapping-watcher".to_string())
+            .spawn(move || {
+                while sleep_unless_stopped(interval, &stop_flag) {
+                    let Ok(snapshot) = mounted_drive_mappings() else {
+                        continue;
+                    };
+                    let change = MappingChange::between(&current, &snapshot);
+                    if change.is_empty() {
+                        continue;
+                    }
+                    registry::update_drive_mappings(|mappings| change.apply(mappings));
+                    current = snapshot;
+                    on_change(&change);
+                }
+            })?;
+
+        Ok(Self {
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    /// Stop the watcher and wait for its thread to finish
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MappingWatcher {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Sleep for `interval`, returning `false` as soon as a stop is requested
+fn sleep_unless_stopped(interval: Duration, stop: &AtomicBool) -> bool {
+    let mut remaining = interval;
+    while !remaining.is_zero() {
+        if stop.load(Ordering::Relaxed) {
+            return false;
+        }
+        let chunk = remaining.min(STOP_CHECK_INTERVAL);
+        thread::sleep(chunk);
+        remaining -= chunk;
+    }
+    !stop.load(Ordering::Relaxed)
+}
+
+/// Drive mappings derived from the currently mounted filesystems
+///
+/// On Linux this covers WSL `drvfs` mounts (`C:` ↔ `/mnt/c`) and CIFS/SMB
+/// mounts (`\\server\share` ↔ mount point), on macOS SMB mounts. On
+/// Windows every logical drive is mapped to `/mnt/<letter>`.
+///
+/// # Errors
+///
+/// Returns `PathError` if the mount table cannot be read.
+#[cfg(not(target_os = "windows"))]
+pub fn mounted_drive_mappings() -> PathResult<Vec<(String, String)>> {
+    let entries = crate::platform::unix::read_mount_table()?;
+    Ok(super::drives::mount_drive_mappings(&entries))
+}
+
+/// Drive mappings derived from the currently mounted filesystems
+///
+/// On Linux this covers WSL `drvfs` mounts (`C:` ↔ `/mnt/c`) and CIFS/SMB
+/// mounts (`\\server\share` ↔ mount point), on macOS SMB mounts. On
+/// Windows every logical drive is mapped to `/mnt/<letter>`.
+///
+/// # Errors
+///
+/// Returns `PathError` if the mount table cannot be read.
+#[cfg(target_os = "windows")]
+pub fn mounted_drive_mappings() -> PathResult<Vec<(String, String)>> {
+    Ok(crate::platform::windows::logical_drive_letters()
+        .into_iter()
+        .map(|letter| {
+            (
+                format!("{letter}:"),
+                format!("/mnt/{}", letter.to_ascii_lowercase()),
+            )
+        })
+        .collect())
+}
@@ -0,0 +1,249 @@
+//! Conversion metrics hooks
+//!
+//! Implement [`Metrics`] to forward conversion statistics to a monitoring
+//! system such as Prometheus or statsd, then install it with
+//! [`registry::set_metrics`](crate::registry::set_metrics). All hooks have
+//! empty default implementations, so only the interesting ones need to be
+//! provided.
+//!
+//! A sink can also be attached to a single configuration through
+//! [`PathConfig::metrics`](crate::PathConfig::metrics); conversions and
+//! security rejections of paths using that configuration then go to it
+//! instead of the registry's sink. Events not tied to a configuration,
+//! such as drive discovery cache hits, always go to the registry's sink.
+//!
+//! [`ConversionStats`] is a ready-made in-memory implementation that
+//! counts events and keeps a latency histogram of conversions.
+
+use crate::{PathConfig, PathError, PathStyle};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// Metrics sink for path conversions
+pub trait Metrics: Send + Sync {
+    /// Called after every conversion with the source and target style and
+    /// the time it took
+    fn record_conversion(&self, from: PathStyle, to: PathStyle, elapsed: Duration) {
+        let _ = (from, to, elapsed);
+    }
+
+    /// Called when a conversion had to invent or drop information, such as
+    /// mapping an unmapped Unix path onto the default drive
+    fn record_lossy_conversion(&self, from: PathStyle, to: PathStyle) {
+        let _ = (from, to);
+    }
+
+    /// Called when a path is rejected by the security checker
+    fn record_security_rejection(&self, error: &PathError) {
+        let _ = error;
+    }
+
+    /// Called when the security checker accepts a path but reports a
+    /// problem, such as an invisible character under a warn-only policy
+    fn record_security_warning(&self, warning: &PathError) {
+        let _ = warning;
+    }
+
+    /// Called when a cached value (such as discovered drive mappings) is
+    /// reused
+    fn record_cache_hit(&self, cache: &str) {
+        let _ = cache;
+    }
+
+    /// Called when a cached value has to be computed
+    fn record_cache_miss(&self, cache: &str) {
+        let _ = cache;
+    }
+}
+
+/// Metrics sink attached to a [`PathConfig`]
+///
+/// Compares by identity and is skipped when the configuration is
+/// serialized. The default sink is empty, which defers to the registry.
+#[derive(Clone, Default)]
+pub struct MetricsSink(Option<Arc<dyn Metrics>>);
+
+impl MetricsSink {
+    /// Create a sink forwarding to `metrics`
+    #[must_use]
+    pub fn new(metrics: Arc<dyn Metrics>) -> Self {
+        Self(Some(metrics))
+    }
+
+    /// Get the attached metrics, if any
+    #[must_use]
+    pub fn get(&self) -> Option<&Arc<dyn Metrics>> {
+        self.0.as_ref()
+    }
+}
+
+impl From<Arc<dyn Metrics>> for MetricsSink {
+    fn from(metrics: Arc<dyn Metrics>) -> Self {
+        Self::new(metrics)
+    }
+}
+
+impl fmt::Debug for MetricsSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.0.is_some() {
+            "MetricsSink(attached)"
+        } else {
+            "MetricsSink(registry)"
+        })
+    }
+}
+
+impl PartialEq for MetricsSink {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for MetricsSink {}
+
+/// Upper bounds of the [`StatsSnapshot::latency_histogram`] buckets
+///
+/// The histogram has one more bucket for conversions slower than the last
+/// bound.
+pub const LATENCY_BUCKETS: [Duration; 5] = [
+    Duration::from_micros(1),
+    Duration::from_micros(10),
+    Duration::from_micros(100),
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+];
+
+/// In-memory conversion statistics
+#[derive(Debug, Default)]
+pub struct ConversionStats {
+    conversions: Mutex<HashMap<(PathStyle, PathStyle), u64>>,
+    lossy_conversions: AtomicU64,
+    security_rejections: AtomicU64,
+    security_warnings: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    total_nanos: AtomicU64,
+    latency: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+}
+
+/// Point-in-time copy of [`ConversionStats`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    /// Number of conversions per (source, target) style pair
+    pub conversions: HashMap<(PathStyle, PathStyle), u64>,
+    /// Number of lossy conversions
+    pub lossy_conversions: u64,
+    /// Number of security rejections
+    pub security_rejections: u64,
+    /// Number of security warnings
+    pub security_warnings: u64,
+    /// Number of cache hits
+    pub cache_hits: u64,
+    /// Number of cache misses
+    pub cache_misses: u64,
+    /// Total time spent converting
+    pub total_time: Duration,
+    /// Number of conversions per latency bucket: entry `i` counts those
+    /// that took at most [`LATENCY_BUCKETS`]`[i]` (and more than the
+    /// previous bound), the last entry those slower than every bound
+    pub latency_histogram: [u64; LATENCY_BUCKETS.len() + 1],
+}
+
+impl StatsSnapshot {
+    /// Total number of conversions across all style pairs
+    #[must_use]
+    pub fn total_conversions(&self) -> u64 {
+        self.conversions.values().sum()
+    }
+}
+
+impl ConversionStats {
+    /// Create empty statistics
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a snapshot of the current counters
+    #[must_use]
+    pub fn snapshot(&self) -> StatsSnapshot {
+        StatsSnapshot {
+            conversions: self
+                .conversions
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone(),
+            lossy_conversions: self.lossy_conversions.load(Ordering::Relaxed),
+            security_rejections: self.security_rejections.load(Ordering::Relaxed),
+            security_warnings: self.security_warnings.load(Ordering::Relaxed),
+            cache_hits: self.cache_hits.load(Ordering::Relaxed),
+            cache_misses: self.cache_misses.load(Ordering::Relaxed),
+            total_time: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            latency_histogram: self
+                .latency
+                .each_ref()
+                .map(|bucket| bucket.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl Metrics for ConversionStats {
+    fn record_conversion(&self, from: PathStyle, to: PathStyle, elapsed: Duration) {
+        *self
+            .conversions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry((from, to))
+            .or_default() += 1;
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| elapsed <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.latency[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_lossy_conversion(&self, _from: PathStyle, _to: PathStyle) {
+        self.lossy_conversions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_security_rejection(&self, _error: &PathError) {
+        self.security_rejections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_security_warning(&self, _warning: &PathError) {
+        self.security_warnings.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_cache_hit(&self, _cache: &str) {
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record_cache_miss(&self, _cache: &str) {
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Forward an event to the installed metrics sink, if any
+pub(crate) fn emit<F: FnOnce(&dyn Metrics)>(event: F) {
+    if let Some(metrics) = crate::registry::metrics() {
+        event(metrics.as_ref());
+    }
+}
+
+/// Forward an event to the sink attached to `config`, falling back to the
+/// installed one
+pub(crate) fn emit_for<F: FnOnce(&dyn Metrics)>(config: &PathConfig, event: F) {
+    match config.metrics.get() {
+        Some(metrics) => event(metrics.as_ref()),
+        None => emit(event),
+    }
+}
@@ -0,0 +1,416 @@
+//! Asynchronous file operations on tokio
+//!
+//! The functions mirror those of [`ops`](super) and take the same
+//! `CrossPath` arguments. Single-step operations such as [`copy`],
+//! [`read_link`] or [`write_atomic`] use `tokio::fs` directly. Operations
+//! that walk a tree or call platform APIs ([`copy_to`],
+//! [`copy_dir_recursive`], [`link_kind`], ...) run their synchronous
+//! counterpart on tokio's blocking pool in one piece: `tokio::fs` would
+//! hop to that pool for every entry anyway, and behavior stays identical.
+//!
+//! [`AsyncPathExt`] gives `CrossPath` non-blocking versions of the
+//! [`PathExt`] queries.
+//!
+//! ```rust,no_run
+//! use cross_path::CrossPath;
+//! use cross_path::ops::r#async::{self as ops, AsyncPathExt};
+//!
+//! # async fn run() -> cross_path::PathResult<()> {
+//! let config = CrossPath::new(r"C:\app\config.toml")?;
+//! ops::write_atomic(&config, b"debug = true\n").await?;
+//! let size = config.get_attributes().await.map_or(0, |a| a.size);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{OpOptions, PlannedOp, REMOVE_ATTEMPTS, is_transient, native_path, temp_path};
+use crate::platform::{self, AccessMode, DiskInfo, FileAttributes, FileTimes, LinkKind, PathExt};
+use crate::vfs::OsFileSystem;
+use crate::{CrossPath, PathError, PathResult};
+use std::future::Future;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Copy a file or directory tree to a destination
+///
+/// See [`ops::copy_to`](super::copy_to).
+///
+/// # Errors
+///
+/// Returns `PathError` if planning fails, the plan contains conflicts
+/// (unless `overwrite` is set), or an operation fails during execution.
+pub async fn copy_to(
+    source: &CrossPath,
+    destination: &CrossPath,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    let (source, destination, options) = (source.clone(), destination.clone(), options.clone());
+    blocking(move || super::copy_to(&source, &destination, &options)).await
+}
+
+/// Move a file or directory to a destination
+///
+/// See [`ops::move_to`](super::move_to).
+///
+/// # Errors
+///
+/// Returns `PathError` if planning fails, the plan contains conflicts
+/// (unless `overwrite` is set), or the move fails during execution.
+pub async fn move_to(
+    source: &CrossPath,
+    destination: &CrossPath,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    let (source, destination, options) = (source.clone(), destination.clone(), options.clone());
+    blocking(move || super::move_to(&source, &destination, &options)).await
+}
+
+/// Copy a directory tree, renaming entries whose names are invalid for the
+/// target style
+///
+/// See [`ops::convert_tree`](super::convert_tree).
+///
+/// # Errors
+///
+/// Returns `PathError` if planning fails, the plan contains conflicts
+/// (unless `overwrite` is set), or an operation fails during execution.
+pub async fn convert_tree(
+    source: &CrossPath,
+    destination: &CrossPath,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    let (source, destination, options) = (source.clone(), destination.clone(), options.clone());
+    blocking(move || super::convert_tree(&source, &destination, &options)).await
+}
+
+/// Copy a file, replacing the destination, and return the bytes copied
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the copy
+/// fails.
+pub async fn copy(source: &CrossPath, destination: &CrossPath) -> PathResult<u64> {
+    let (src, dst) = native_pair(source, destination)?;
+    Ok(tokio::fs::copy(src, dst).await?)
+}
+
+/// Copy a directory tree, merging into existing directories and replacing
+/// existing files
+///
+/// See [`ops::copy_dir_recursive`](super::copy_dir_recursive).
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or an entry
+/// fails to copy.
+pub async fn copy_dir_recursive(source: &CrossPath, destination: &CrossPath) -> PathResult<u64> {
+    let (source, destination) = (source.clone(), destination.clone());
+    blocking(move || super::copy_dir_recursive(&source, &destination)).await
+}
+
+/// Rename a file or directory, copying and deleting it when the
+/// destination is on another device
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the move
+/// fails.
+pub async fn rename_across_devices(source: &CrossPath, destination: &CrossPath) -> PathResult<()> {
+    let (src, dst) = native_pair(source, destination)?;
+    match tokio::fs::rename(&src, &dst).await {
+        Err(err) if err.kind() == io::ErrorKind::CrossesDevices => {
+            let (source, destination) = (source.clone(), destination.clone());
+            blocking(move || super::rename_across_devices(&source, &destination)).await
+        }
+        result => Ok(result?),
+    }
+}
+
+/// Remove a directory tree, retrying while it is in use
+///
+/// Retries like [`ops::remove_dir_all_robust`](super::remove_dir_all_robust),
+/// but waits between attempts without blocking the runtime.
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or the tree is
+/// still not removable after the last attempt.
+pub async fn remove_dir_all_robust(path: &CrossPath) -> PathResult<()> {
+    let native = native_path(&OsFileSystem, path)?;
+    let mut delay = Duration::from_millis(10);
+    let mut attempt = 1;
+    loop {
+        match tokio::fs::remove_dir_all(&native).await {
+            Ok(()) => return Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) if attempt < REMOVE_ATTEMPTS && is_transient(&err) => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Write a file atomically
+///
+/// See [`ops::write_atomic`](super::write_atomic).
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or the file cannot
+/// be written; the temporary file is removed on failure.
+pub async fn write_atomic(path: &CrossPath, contents: &[u8]) -> PathResult<()> {
+    let native = native_path(&OsFileSystem, path)?;
+    let temp = temp_path(&native)?;
+
+    let result = async {
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp)
+            .await?;
+        file.write_all(contents).await?;
+        if let Ok(existing) = tokio::fs::metadata(&native).await {
+            file.set_permissions(existing.permissions()).await?;
+        }
+        file.sync_all().await?;
+        drop(file);
+        let (temp, native) = (temp.clone(), native.clone());
+        tokio::task::spawn_blocking(move || platform::replace_file(&temp, &native))
+            .await
+            .map_err(io::Error::other)?
+    }
+    .await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&temp).await;
+    }
+    Ok(result?)
+}
+
+/// Replace `destination` with `source` in one atomic step
+///
+/// See [`ops::replace_file`](super::replace_file).
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the file
+/// cannot be moved.
+pub async fn replace_file(source: &CrossPath, destination: &CrossPath) -> PathResult<()> {
+    let (source, destination) = (source.clone(), destination.clone());
+    blocking(move || super::replace_file(&source, &destination)).await
+}
+
+/// Create a symbolic link at `link` pointing to `target`
+///
+/// See [`ops::symlink`](super::symlink).
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the link
+/// cannot be created.
+pub async fn symlink(target: &CrossPath, link: &CrossPath) -> PathResult<()> {
+    let (target, link) = native_pair(target, link)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let resolved = match link.parent() {
+            Some(parent) if target.is_relative() => parent.join(&target),
+            _ => target.clone(),
+        };
+        if tokio::fs::metadata(&resolved)
+            .await
+            .is_ok_and(|m| m.is_dir())
+        {
+            tokio::fs::symlink_dir(&target, &link).await?;
+        } else {
+            tokio::fs::symlink_file(&target, &link).await?;
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    tokio::fs::symlink(&target, &link).await?;
+
+    Ok(())
+}
+
+/// Create a directory junction at `link` pointing to the directory `target`
+///
+/// See [`ops::junction`](super::junction).
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the junction
+/// cannot be created, and `PathError::PlatformError` on other platforms.
+pub async fn junction(target: &CrossPath, link: &CrossPath) -> PathResult<()> {
+    let (target, link) = (target.clone(), link.clone());
+    blocking(move || super::junction(&target, &link)).await
+}
+
+/// Create a hard link at `link` to the file `target`
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the link
+/// cannot be created.
+pub async fn hard_link(target: &CrossPath, link: &CrossPath) -> PathResult<()> {
+    let (target, link) = native_pair(target, link)?;
+    Ok(tokio::fs::hard_link(target, link).await?)
+}
+
+/// Read the target of a symbolic link or junction
+///
+/// The target is returned with the link's configuration.
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or is not a link.
+pub async fn read_link(link: &CrossPath) -> PathResult<CrossPath> {
+    let native = native_path(&OsFileSystem, link)?;
+    let target = tokio::fs::read_link(native).await?;
+    CrossPath::with_config(target.to_string_lossy(), link.config().clone())
+}
+
+/// Kind of link at `path`, without following it
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or does not exist.
+pub async fn link_kind(path: &CrossPath) -> PathResult<Option<LinkKind>> {
+    let path = path.clone();
+    blocking(move || super::link_kind(&path)).await
+}
+
+/// Set the timestamps of a file or directory, following links
+///
+/// See [`ops::set_times`](super::set_times).
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or does not exist,
+/// or the times cannot be set.
+pub async fn set_times(path: &CrossPath, times: &FileTimes) -> PathResult<()> {
+    let (path, times) = (path.clone(), *times);
+    blocking(move || super::set_times(&path, &times)).await
+}
+
+/// Move a file or directory to the trash instead of deleting it
+///
+/// See [`ops::move_to_trash`](super::move_to_trash).
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted, does not exist, or
+/// cannot be moved to the trash.
+#[cfg(feature = "trash")]
+pub async fn move_to_trash(path: &CrossPath) -> PathResult<()> {
+    let path = path.clone();
+    blocking(move || super::move_to_trash(&path)).await
+}
+
+/// Move a file or directory to the trash, or only plan it
+///
+/// See [`ops::move_to_trash_with`](super::move_to_trash_with).
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted, does not exist, or
+/// cannot be moved to the trash.
+#[cfg(feature = "trash")]
+pub async fn move_to_trash_with(
+    path: &CrossPath,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    let (path, options) = (path.clone(), options.clone());
+    blocking(move || super::move_to_trash_with(&path, &options)).await
+}
+
+/// Non-blocking versions of the [`PathExt`] queries
+///
+/// Implemented for `CrossPath`, which is converted to its native form
+/// first; a path that cannot be converted has no attributes and is not
+/// accessible.
+pub trait AsyncPathExt {
+    /// Get file attributes (platform-specific)
+    fn get_attributes(&self) -> impl Future<Output = Option<FileAttributes>> + Send;
+
+    /// Check if path exists and is accessible
+    fn is_accessible(&self) -> impl Future<Output = bool> + Send;
+
+    /// Check if the current user may access the path in the given ways
+    fn access(&self, mode: AccessMode) -> impl Future<Output = bool> + Send;
+
+    /// Get disk information for path
+    fn get_disk_info(&self) -> impl Future<Output = Option<DiskInfo>> + Send;
+}
+
+impl AsyncPathExt for CrossPath {
+    fn get_attributes(&self) -> impl Future<Output = Option<FileAttributes>> + Send {
+        query(self, |ext| ext.get_attributes())
+    }
+
+    fn is_accessible(&self) -> impl Future<Output = bool> + Send {
+        let accessible = query(self, |ext| Some(ext.is_accessible()));
+        async { accessible.await.unwrap_or(false) }
+    }
+
+    fn access(&self, mode: AccessMode) -> impl Future<Output = bool> + Send {
+        let allowed = query(self, move |ext| Some(ext.access(mode)));
+        async { allowed.await.unwrap_or(false) }
+    }
+
+    fn get_disk_info(&self) -> impl Future<Output = Option<DiskInfo>> + Send {
+        query(self, |ext| ext.get_disk_info())
+    }
+}
+
+/// Run a `PathExt` query for a path on the blocking pool
+fn query<T, F>(path: &CrossPath, f: F) -> impl Future<Output = Option<T>> + Send + use<T, F>
+where
+    T: Send + 'static,
+    F: FnOnce(&dyn PathExt) -> Option<T> + Send + 'static,
+{
+    let native = native_path(&OsFileSystem, path).ok();
+    async move {
+        let native = native?;
+        tokio::task::spawn_blocking(move || f(&native_ext(native)))
+            .await
+            .ok()
+            .flatten()
+    }
+}
+
+/// Platform `PathExt` implementation for a native path
+fn native_ext(path: PathBuf) -> impl PathExt {
+    #[cfg(target_os = "windows")]
+    {
+        platform::windows::WindowsPathExt::new(path)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        platform::unix::UnixPathExt::new(path)
+    }
+}
+
+/// Native forms of a source and destination path
+fn native_pair(source: &CrossPath, destination: &CrossPath) -> PathResult<(PathBuf, PathBuf)> {
+    Ok((
+        native_path(&OsFileSystem, source)?,
+        native_path(&OsFileSystem, destination)?,
+    ))
+}
+
+/// Run a synchronous operation on tokio's blocking pool
+async fn blocking<T, F>(f: F) -> PathResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> PathResult<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| PathError::io_error(format!("Blocking task failed: {e}")).with_source(e))?
+}
@@ -0,0 +1,277 @@
+//! Name encoding repair
+//!
+//! Copies a directory tree while fixing entry names along the way:
+//! decomposed (NFD) names, as produced by macOS, are composed to NFC, and
+//! names stored in a legacy code page are decoded to UTF-8 using
+//! [`UnicodeHandler`]'s detection.
+
+use super::{OpKind, OpOptions, PlannedOp};
+use crate::unicode::UnicodeHandler;
+use crate::vfs::{FileSystem, OsFileSystem};
+use crate::{CrossPath, PathError, PathResult, platform};
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// What to do when a fixed name collides with another entry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum CollisionStrategy {
+    /// Leave the colliding entry out of the copy
+    Skip,
+    /// Replace the existing destination
+    Overwrite,
+    /// Append a counter to the name (`name (1).ext`)
+    #[default]
+    Suffix,
+    /// Abort the operation
+    Error,
+}
+
+/// Why an entry was renamed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RenameReason {
+    /// The name was valid UTF-8 but not in NFC
+    Nfc,
+    /// The name was stored in a legacy encoding
+    LegacyEncoding,
+    /// The fixed name collided and was given a suffix
+    Collision,
+}
+
+/// A rename performed (or planned) by [`fix_name_encoding`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Rename {
+    /// Native source path
+    pub source: PathBuf,
+    /// Native destination path
+    pub destination: PathBuf,
+    /// Original name (lossily decoded for display)
+    pub from: String,
+    /// New name
+    pub to: String,
+    /// Reason for the rename
+    pub reason: RenameReason,
+}
+
+/// Result of [`fix_name_encoding`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FixNamesReport {
+    /// Every rename, in traversal order
+    pub renames: Vec<Rename>,
+    /// Source entries left out because of a collision
+    pub skipped: Vec<PathBuf>,
+    /// Underlying operation plan
+    pub plan: Vec<PlannedOp>,
+}
+
+/// Copy a tree, converting NFD and legacy-encoded names to NFC UTF-8
+///
+/// Symlinks are followed like in [`copy_to`](super::copy_to); a link back
+/// to a directory being copied is rejected.
+///
+/// # Errors
+///
+/// Returns `PathError` if the tree cannot be read or contains a symlink
+/// loop, a name cannot be decoded,
+/// a collision occurs with [`CollisionStrategy::Error`], or an operation
+/// fails during execution.
+pub fn fix_name_encoding(
+    source: &CrossPath,
+    destination: &CrossPath,
+    strategy: CollisionStrategy,
+    options: &OpOptions,
+) -> PathResult<FixNamesReport> {
+    fix_name_encoding_in(&OsFileSystem, source, destination, strategy, options)
+}
+
+/// Copy a tree on the given filesystem, converting names to NFC UTF-8
+///
+/// # Errors
+///
+/// Returns `PathError` if the tree cannot be read, a name cannot be decoded,
+/// a collision occurs with [`CollisionStrategy::Error`], or an operation
+/// fails during execution.
+pub fn fix_name_encoding_in(
+    fs: &dyn FileSystem,
+    source: &CrossPath,
+    destination: &CrossPath,
+    strategy: CollisionStrategy,
+    options: &OpOptions,
+) -> PathResult<FixNamesReport> {
+    let src = super::native_path(fs, source)?;
+    let dst = super::native_path(fs, destination)?;
+
+    let mut report = FixNamesReport::default();
+    let mut fixer = Fixer {
+        fs,
+        strategy,
+        case_insensitive: options.resolved_style() == crate::PathStyle::Windows,
+        taken: HashSet::new(),
+        ancestors: Vec::new(),
+        report: &mut report,
+    };
+    fixer.visit(&src, &dst)?;
+
+    let options = OpOptions {
+        overwrite: options.overwrite || strategy == CollisionStrategy::Overwrite,
+        ..options.clone()
+    };
+    report.plan = super::finish(fs, core::mem::take(&mut report.plan), &options)?;
+    Ok(report)
+}
+
+/// Tree walker building the plan and rename report
+struct Fixer<'a> {
+    fs: &'a dyn FileSystem,
+    strategy: CollisionStrategy,
+    case_insensitive: bool,
+    taken: HashSet<String>,
+    /// Canonical paths of the directories being visited, to detect loops
+    ancestors: Vec<PathBuf>,
+    report: &'a mut FixNamesReport,
+}
+
+impl Fixer<'_> {
+    fn visit(&mut self, src: &Path, dst: &Path) -> PathResult<()> {
+        self.claim(dst);
+
+        if !self.fs.metadata(src)?.is_dir() {
+            self.report
+                .plan
+                .push(PlannedOp::new(OpKind::Copy, src, Some(dst)));
+            return Ok(());
+        }
+
+        let canonical = self.fs.canonicalize(src)?;
+        if self.ancestors.contains(&canonical) {
+            return Err(PathError::io_error(format!(
+                "Symlink loop: {} leads back to {}",
+                src.display(),
+                canonical.display()
+            ))
+            .with_path(src.to_string_lossy()));
+        }
+        self.report
+            .plan
+            .push(PlannedOp::new(OpKind::CreateDir, src, Some(dst)));
+        self.ancestors.push(canonical);
+
+        let mut entries = self
+            .fs
+            .read_dir(src)?
+            .into_iter()
+            .map(|entry| {
+                let (name, reason) = fixed_name(&entry)?;
+                Ok((entry, name, reason))
+            })
+            .collect::<PathResult<Vec<_>>>()?;
+        // Names that are already correct claim their destination first
+        entries.sort_by(|(a, _, a_reason), (b, _, b_reason)| {
+            (a_reason.is_some(), a).cmp(&(b_reason.is_some(), b))
+        });
+
+        for (entry, mut name, mut reason) in entries {
+            let original = entry.to_string_lossy().into_owned();
+            let entry_path = src.join(&entry);
+
+            let mut child_dst = dst.join(&name);
+            let is_dir = self.fs.symlink_metadata(&entry_path)?.is_dir();
+            if self.collides(&child_dst, is_dir) {
+                match self.strategy {
+                    CollisionStrategy::Skip => {
+                        self.report.skipped.push(entry_path);
+                        continue;
+                    }
+                    CollisionStrategy::Overwrite => {}
+                    CollisionStrategy::Suffix => {
+                        name = self.unique_name(dst, &name, is_dir);
+                        child_dst = dst.join(&name);
+                        reason = Some(RenameReason::Collision);
+                    }
+                    CollisionStrategy::Error => {
+                        return Err(PathError::io_error(format!(
+                            "Name collision: {}",
+                            child_dst.display()
+                        )));
+                    }
+                }
+            }
+
+            let first = self.report.plan.len();
+            self.visit(&entry_path, &child_dst)?;
+
+            if let Some(reason) = reason {
+                if let Some(op) = self.report.plan.get_mut(first) {
+                    op.renamed_from = Some(original.clone());
+                }
+                self.report.renames.push(Rename {
+                    source: entry_path,
+                    destination: child_dst,
+                    from: original,
+                    to: name,
+                    reason,
+                });
+            }
+        }
+        self.ancestors.pop();
+
+        Ok(())
+    }
+
+    fn key(&self, path: &Path) -> String {
+        let key = path.to_string_lossy();
+        if self.case_insensitive {
+            key.to_lowercase()
+        } else {
+            key.into_owned()
+        }
+    }
+
+    fn claim(&mut self, path: &Path) {
+        let key = self.key(path);
+        self.taken.insert(key);
+    }
+
+    /// Check if `path` is already written by this plan or exists on disk
+    ///
+    /// Existing directories are merged into, so they only collide with
+    /// entries that are not directories.
+    fn collides(&self, path: &Path, is_dir: bool) -> bool {
+        if self.taken.contains(&self.key(path)) {
+            return true;
+        }
+        match self.fs.symlink_metadata(path) {
+            Ok(metadata) => !(is_dir && metadata.is_dir()),
+            Err(_) => false,
+        }
+    }
+
+    fn unique_name(&self, dir: &Path, name: &str, is_dir: bool) -> String {
+        let (stem, extension) = match name.rfind('.') {
+            Some(pos) if pos > 0 && !is_dir => name.split_at(pos),
+            _ => (name, ""),
+        };
+        (1..=u32::MAX)
+            .map(|n| format!("{stem} ({n}){extension}"))
+            .find(|candidate| !self.collides(&dir.join(candidate), is_dir))
+            .unwrap_or_else(|| name.to_string())
+    }
+}
+
+/// Decode an entry name and compose it to NFC
+///
+/// Returns the fixed name and the reason it changed, if it did.
+fn fixed_name(raw: &OsStr) -> PathResult<(String, Option<RenameReason>)> {
+    if let Some(name) = raw.to_str() {
+        if UnicodeHandler::is_nfc(name) {
+            return Ok((name.to_string(), None));
+        }
+        return Ok((UnicodeHandler::to_nfc(name), Some(RenameReason::Nfc)));
+    }
+
+    let decoded = UnicodeHandler::convert_to_utf8(&platform::os_str_bytes(raw))?;
+    Ok((
+        UnicodeHandler::to_nfc(&decoded),
+        Some(RenameReason::LegacyEncoding),
+    ))
+}
@@ -0,0 +1,825 @@
+//! Cross-platform file operations
+//!
+//! Operations accept `CrossPath` values and convert them to the native form
+//! internally. [`copy_to`], [`move_to`] and [`convert_tree`] first build a
+//! plan (`Vec<PlannedOp>`) describing what they will do; with
+//! [`OpOptions::dry_run`] set the plan is returned without touching the
+//! filesystem, so it can be reviewed before execution. [`copy`],
+//! [`copy_dir_recursive`], [`rename_across_devices`] and
+//! [`remove_dir_all_robust`] act directly, like their `std::fs`
+//! counterparts, as do the link operations [`symlink`], [`junction`],
+//! [`hard_link`], [`read_link`] and [`link_kind`], and [`set_times`]. With the `trash` feature,
+//! `move_to_trash` moves entries to the Recycle Bin or desktop trash, and
+//! `move_to_trash_with` plans the move for a dry run.
+//!
+//! The `*_in` variants run against any [`FileSystem`], such as the in-memory
+//! [`MemoryFileSystem`](crate::vfs::MemoryFileSystem) used in tests. With the
+//! `tokio` feature, `ops::r#async` offers the same operations as futures.
+
+#[cfg(feature = "tokio")]
+pub mod r#async;
+#[cfg(feature = "unicode")]
+pub mod encoding;
+
+use crate::platform::{FileTimes, LinkKind};
+use crate::vfs::{FileSystem, OsFileSystem};
+use crate::{CrossPath, PathError, PathResult, PathStyle, platform, validation};
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Attempts made by [`remove_dir_all_robust`] before giving up
+const REMOVE_ATTEMPTS: u32 = 8;
+
+/// Kind of planned file operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum OpKind {
+    /// Create a directory
+    CreateDir,
+    /// Copy a file
+    Copy,
+    /// Move (rename) a file or directory
+    Move,
+    /// Recreate the symbolic link `source` at the destination, with the
+    /// same target
+    Symlink,
+    /// Move `source` to the trash (with the `trash` feature); has no
+    /// destination
+    Trash,
+}
+
+/// Conflict detected while planning an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Conflict {
+    /// The destination already exists
+    DestinationExists,
+    /// An earlier operation in the same plan writes to the same destination
+    DuplicateDestination,
+}
+
+/// A single step of an operation plan
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlannedOp {
+    /// Operation kind
+    pub kind: OpKind,
+    /// Native source path
+    pub source: PathBuf,
+    /// Native destination path (after conversion and renaming)
+    pub destination: Option<PathBuf>,
+    /// Original file name, if the entry had to be renamed
+    pub renamed_from: Option<String>,
+    /// Conflict preventing the operation, if any
+    pub conflict: Option<Conflict>,
+}
+
+impl PlannedOp {
+    fn new(kind: OpKind, source: &Path, destination: Option<&Path>) -> Self {
+        Self {
+            kind,
+            source: source.to_path_buf(),
+            destination: destination.map(Path::to_path_buf),
+            renamed_from: None,
+            conflict: None,
+        }
+    }
+}
+
+/// Options shared by file operations
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OpOptions {
+    /// Only plan the operation, do not touch the filesystem
+    pub dry_run: bool,
+    /// Replace existing destinations instead of reporting a conflict
+    pub overwrite: bool,
+    /// Style whose naming rules destination names must satisfy
+    /// (`Auto` uses the current platform)
+    pub target_style: PathStyle,
+}
+
+impl Default for OpOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            overwrite: false,
+            target_style: PathStyle::Auto,
+        }
+    }
+}
+
+impl OpOptions {
+    /// Options for planning without executing
+    #[must_use]
+    pub fn dry_run() -> Self {
+        Self {
+            dry_run: true,
+            ..Self::default()
+        }
+    }
+
+    fn resolved_style(&self) -> PathStyle {
+        match self.target_style {
+            PathStyle::Auto => platform::current_style(),
+            style => style,
+        }
+    }
+}
+
+/// Copy a file or directory tree to a destination
+///
+/// # Errors
+///
+/// Returns `PathError` if planning fails, the plan contains conflicts
+/// (unless `overwrite` is set), or an operation fails during execution.
+pub fn copy_to(
+    source: &CrossPath,
+    destination: &CrossPath,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    copy_to_in(&OsFileSystem, source, destination, options)
+}
+
+/// Copy a file or directory tree on the given filesystem
+///
+/// # Errors
+///
+/// Returns `PathError` if planning fails, the plan contains conflicts
+/// (unless `overwrite` is set), or an operation fails during execution.
+pub fn copy_to_in(
+    fs: &dyn FileSystem,
+    source: &CrossPath,
+    destination: &CrossPath,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    let (src, dst) = (native_path(fs, source)?, native_path(fs, destination)?);
+    let mut plan = Vec::new();
+    plan_tree(fs, &src, &dst, None, &mut plan)?;
+    finish(fs, plan, options)
+}
+
+/// Move a file or directory to a destination
+///
+/// Falls back to copy and delete when the destination is on another device;
+/// symlinks inside the tree are then recreated as links, not followed.
+///
+/// # Errors
+///
+/// Returns `PathError` if planning fails, the plan contains conflicts
+/// (unless `overwrite` is set), or the move fails during execution.
+pub fn move_to(
+    source: &CrossPath,
+    destination: &CrossPath,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    move_to_in(&OsFileSystem, source, destination, options)
+}
+
+/// Move a file or directory on the given filesystem
+///
+/// # Errors
+///
+/// Returns `PathError` if planning fails, the plan contains conflicts
+/// (unless `overwrite` is set), or the move fails during execution.
+pub fn move_to_in(
+    fs: &dyn FileSystem,
+    source: &CrossPath,
+    destination: &CrossPath,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    let (src, dst) = (native_path(fs, source)?, native_path(fs, destination)?);
+    fs.symlink_metadata(&src)?;
+    let plan = vec![PlannedOp::new(OpKind::Move, &src, Some(&dst))];
+    finish(fs, plan, options)
+}
+
+/// Copy a directory tree, renaming entries whose names are invalid for the
+/// target style
+///
+/// Characters the target cannot store are replaced with `_`, and on Windows
+/// trailing dots/spaces and reserved device names are fixed. Renamed entries
+/// record their original name in [`PlannedOp::renamed_from`]. Entries that
+/// end up with the same destination (including names differing only in case
+/// on Windows targets) are reported as conflicts.
+///
+/// # Errors
+///
+/// Returns `PathError` if planning fails, the plan contains conflicts
+/// (unless `overwrite` is set), or an operation fails during execution.
+pub fn convert_tree(
+    source: &CrossPath,
+    destination: &CrossPath,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    convert_tree_in(&OsFileSystem, source, destination, options)
+}
+
+/// Copy a directory tree on the given filesystem, renaming invalid entries
+///
+/// # Errors
+///
+/// Returns `PathError` if planning fails, the plan contains conflicts
+/// (unless `overwrite` is set), or an operation fails during execution.
+pub fn convert_tree_in(
+    fs: &dyn FileSystem,
+    source: &CrossPath,
+    destination: &CrossPath,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    let (src, dst) = (native_path(fs, source)?, native_path(fs, destination)?);
+    let mut plan = Vec::new();
+    plan_tree(fs, &src, &dst, Some(options.resolved_style()), &mut plan)?;
+    finish(fs, plan, options)
+}
+
+/// Copy a file, replacing the destination, and return the bytes copied
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the copy
+/// fails.
+pub fn copy(source: &CrossPath, destination: &CrossPath) -> PathResult<u64> {
+    copy_in(&OsFileSystem, source, destination)
+}
+
+/// Copy a file on the given filesystem
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the copy
+/// fails.
+pub fn copy_in(
+    fs: &dyn FileSystem,
+    source: &CrossPath,
+    destination: &CrossPath,
+) -> PathResult<u64> {
+    let (src, dst) = (native_path(fs, source)?, native_path(fs, destination)?);
+    Ok(fs.copy(&src, &dst)?)
+}
+
+/// Copy a directory tree, merging into existing directories and replacing
+/// existing files
+///
+/// Returns the total number of bytes copied. Use [`copy_to`] to review
+/// conflicts before copying.
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or an entry
+/// fails to copy.
+pub fn copy_dir_recursive(source: &CrossPath, destination: &CrossPath) -> PathResult<u64> {
+    copy_dir_recursive_in(&OsFileSystem, source, destination)
+}
+
+/// Copy a directory tree on the given filesystem
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or an entry
+/// fails to copy.
+pub fn copy_dir_recursive_in(
+    fs: &dyn FileSystem,
+    source: &CrossPath,
+    destination: &CrossPath,
+) -> PathResult<u64> {
+    let (src, dst) = (native_path(fs, source)?, native_path(fs, destination)?);
+    let mut plan = Vec::new();
+    plan_tree(fs, &src, &dst, None, &mut plan)?;
+
+    let mut copied = 0;
+    for op in &plan {
+        match (op.kind, &op.destination) {
+            (OpKind::Copy, Some(destination)) => copied += fs.copy(&op.source, destination)?,
+            _ => execute(fs, op)?,
+        }
+    }
+    Ok(copied)
+}
+
+/// Rename a file or directory, copying and deleting it when the
+/// destination is on another device
+///
+/// The copy keeps symlinks as links instead of copying their targets.
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the move
+/// fails.
+pub fn rename_across_devices(source: &CrossPath, destination: &CrossPath) -> PathResult<()> {
+    rename_across_devices_in(&OsFileSystem, source, destination)
+}
+
+/// Rename a file or directory on the given filesystem
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the move
+/// fails.
+pub fn rename_across_devices_in(
+    fs: &dyn FileSystem,
+    source: &CrossPath,
+    destination: &CrossPath,
+) -> PathResult<()> {
+    let (src, dst) = (native_path(fs, source)?, native_path(fs, destination)?);
+    rename_or_copy(fs, &src, &dst)
+}
+
+/// Remove a directory tree, retrying while it is in use
+///
+/// On Windows, sharing and lock violations and access denied errors
+/// (typically a scanner or indexer holding a file open) are retried with
+/// growing delays, for a little over a second in total; a directory
+/// reported as not empty is retried on every platform. A path that does not exist is
+/// not an error.
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or the tree is
+/// still not removable after the last attempt.
+pub fn remove_dir_all_robust(path: &CrossPath) -> PathResult<()> {
+    remove_dir_all_robust_in(&OsFileSystem, path)
+}
+
+/// Remove a directory tree on the given filesystem, retrying while it is
+/// in use
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or the tree is
+/// still not removable after the last attempt.
+pub fn remove_dir_all_robust_in(fs: &dyn FileSystem, path: &CrossPath) -> PathResult<()> {
+    let native = native_path(fs, path)?;
+    let mut delay = Duration::from_millis(10);
+    let mut attempt = 1;
+    loop {
+        match fs.remove_dir_all(&native) {
+            Ok(()) => return Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(err) if attempt < REMOVE_ATTEMPTS && is_transient(&err) => {
+                std::thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Write a file atomically
+///
+/// The contents are written to a temporary file next to `path`, flushed to
+/// disk and then moved over `path` (see [`replace_file`]), so readers see
+/// either the old or the new contents, never a partial write. An existing
+/// file keeps its permissions.
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or the file cannot
+/// be written; the temporary file is removed on failure.
+pub fn write_atomic(path: &CrossPath, contents: &[u8]) -> PathResult<()> {
+    use std::io::Write;
+
+    let native = native_path(&OsFileSystem, path)?;
+    let temp = temp_path(&native)?;
+
+    let result = (|| -> io::Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp)?;
+        file.write_all(contents)?;
+        if let Ok(existing) = std::fs::metadata(&native) {
+            file.set_permissions(existing.permissions())?;
+        }
+        file.sync_all()?;
+        drop(file);
+        platform::replace_file(&temp, &native)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    Ok(result?)
+}
+
+/// Replace `destination` with `source` in one atomic step
+///
+/// Uses `rename` (and syncs the directory) on Unix, and `ReplaceFileW`,
+/// which keeps the destination's attributes and ACLs, or `MoveFileExW` on
+/// Windows. Both files must be on the same volume.
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the file
+/// cannot be moved.
+pub fn replace_file(source: &CrossPath, destination: &CrossPath) -> PathResult<()> {
+    let (src, dst) = (
+        native_path(&OsFileSystem, source)?,
+        native_path(&OsFileSystem, destination)?,
+    );
+    Ok(platform::replace_file(&src, &dst)?)
+}
+
+/// Create a symbolic link at `link` pointing to `target`
+///
+/// On Windows the link is a directory or file symlink depending on what
+/// `target` is (a relative target is resolved against the link's
+/// directory); creating symlinks there needs Developer Mode or
+/// administrator rights, see [`junction`] for an alternative.
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the link
+/// cannot be created.
+pub fn symlink(target: &CrossPath, link: &CrossPath) -> PathResult<()> {
+    let (target, link) = (
+        native_path(&OsFileSystem, target)?,
+        native_path(&OsFileSystem, link)?,
+    );
+
+    Ok(OsFileSystem.symlink(&target, &link)?)
+}
+
+/// Create a directory junction at `link` pointing to the directory `target`
+///
+/// Junctions are Windows' unprivileged alternative to directory symlinks;
+/// the target is stored as an absolute path.
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the junction
+/// cannot be created, and `PathError::PlatformError` on other platforms.
+pub fn junction(target: &CrossPath, link: &CrossPath) -> PathResult<()> {
+    #[cfg(target_os = "windows")]
+    {
+        let (target, link) = (
+            native_path(&OsFileSystem, target)?,
+            native_path(&OsFileSystem, link)?,
+        );
+        Ok(platform::windows::create_junction(&target, &link)?)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (target, link);
+        Err(PathError::platform_error(
+            "Directory junctions are only supported on Windows",
+        ))
+    }
+}
+
+/// Create a hard link at `link` to the file `target`
+///
+/// Both names then refer to one physical file, which
+/// [`CrossPath::is_same_file`] recognizes.
+///
+/// # Errors
+///
+/// Returns `PathError` if either path cannot be converted or the link
+/// cannot be created.
+pub fn hard_link(target: &CrossPath, link: &CrossPath) -> PathResult<()> {
+    let (target, link) = (
+        native_path(&OsFileSystem, target)?,
+        native_path(&OsFileSystem, link)?,
+    );
+    Ok(std::fs::hard_link(target, link)?)
+}
+
+/// Read the target of a symbolic link or junction
+///
+/// The target is returned with the link's configuration, so a Windows
+/// target read through a WSL path can still be converted back.
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or is not a link.
+pub fn read_link(link: &CrossPath) -> PathResult<CrossPath> {
+    let native = native_path(&OsFileSystem, link)?;
+    let target = std::fs::read_link(native)?;
+    CrossPath::with_config(target.to_string_lossy(), link.config().clone())
+}
+
+/// Kind of link at `path`, without following it
+///
+/// Returns `None` for regular files and directories.
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or does not exist.
+pub fn link_kind(path: &CrossPath) -> PathResult<Option<LinkKind>> {
+    let native = native_path(&OsFileSystem, path)?;
+    std::fs::symlink_metadata(&native)?;
+    Ok(platform::link_kind(&native))
+}
+
+/// Move a file or directory to the trash instead of deleting it
+///
+/// Uses the Recycle Bin on Windows, the Finder's Trash on macOS and the
+/// XDG Trash (`~/.local/share/Trash` or a per-volume `.Trash-$uid`) on
+/// other Unix systems, so the entry can be restored from the desktop's
+/// file manager.
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted, does not exist, or
+/// cannot be moved to the trash.
+#[cfg(feature = "trash")]
+pub fn move_to_trash(path: &CrossPath) -> PathResult<()> {
+    move_to_trash_with(path, &OpOptions::default()).map(drop)
+}
+
+/// Move a file or directory to the trash, or only plan it
+///
+/// The plan holds a single [`OpKind::Trash`] operation; with
+/// [`OpOptions::dry_run`] set it is returned without touching the entry.
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted, does not exist, or
+/// cannot be moved to the trash.
+#[cfg(feature = "trash")]
+pub fn move_to_trash_with(path: &CrossPath, options: &OpOptions) -> PathResult<Vec<PlannedOp>> {
+    let native = native_path(&OsFileSystem, path)?;
+    std::fs::symlink_metadata(&native)?;
+    let plan = vec![PlannedOp::new(OpKind::Trash, &native, None)];
+    finish(&OsFileSystem, plan, options)
+}
+
+/// Set the timestamps of a file or directory, following links
+///
+/// Times that are `None` in `times` are left unchanged. Uses `utimensat`
+/// on Unix, which sets the access and modification times, and
+/// `SetFileTime` on Windows, which also sets the creation time. The change
+/// time cannot be set on any platform.
+///
+/// # Errors
+///
+/// Returns `PathError` if the path cannot be converted or does not exist,
+/// or the times cannot be set.
+pub fn set_times(path: &CrossPath, times: &FileTimes) -> PathResult<()> {
+    let native = native_path(&OsFileSystem, path)?;
+    Ok(platform::set_times(&native, times)?)
+}
+
+/// Unique temporary file next to `path` for an atomic write
+fn temp_path(path: &Path) -> PathResult<PathBuf> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let name = path
+        .file_name()
+        .ok_or_else(|| PathError::invalid_path(format!("No file name in {}", path.display())))?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(name);
+    temp_name.push(format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    Ok(path.with_file_name(temp_name))
+}
+
+/// Form of a cross-platform path native to the filesystem
+pub(crate) fn native_path(fs: &dyn FileSystem, path: &CrossPath) -> PathResult<PathBuf> {
+    path.to_style(fs.style()).map(PathBuf::from)
+}
+
+/// Plan copying `src` to `dst`, optionally renaming entries for `style`
+///
+/// Symlinks are followed, like `cp -RL`; a link to a directory that is
+/// being copied already is rejected instead of recursing forever.
+fn plan_tree(
+    fs: &dyn FileSystem,
+    src: &Path,
+    dst: &Path,
+    rename_for: Option<PathStyle>,
+    plan: &mut Vec<PlannedOp>,
+) -> PathResult<()> {
+    plan_entry(fs, src, dst, rename_for, plan, &mut Vec::new())
+}
+
+/// Plan copying `src` to `dst` as part of a move
+///
+/// Symlinks are recreated as links, like `cp -RP`, and never descended
+/// into, so a moved tree keeps its links and nothing outside it is copied.
+fn plan_move_tree(
+    fs: &dyn FileSystem,
+    src: &Path,
+    dst: &Path,
+    plan: &mut Vec<PlannedOp>,
+) -> PathResult<()> {
+    let metadata = fs.symlink_metadata(src)?;
+    if metadata.is_symlink() {
+        plan.push(PlannedOp::new(OpKind::Symlink, src, Some(dst)));
+    } else if metadata.is_dir() {
+        plan.push(PlannedOp::new(OpKind::CreateDir, src, Some(dst)));
+        for entry in fs.read_dir(src)? {
+            plan_move_tree(fs, &src.join(&entry), &dst.join(&entry), plan)?;
+        }
+    } else {
+        plan.push(PlannedOp::new(OpKind::Copy, src, Some(dst)));
+    }
+    Ok(())
+}
+
+/// Plan copying one entry, given the canonical paths of the directories
+/// above it
+fn plan_entry(
+    fs: &dyn FileSystem,
+    src: &Path,
+    dst: &Path,
+    rename_for: Option<PathStyle>,
+    plan: &mut Vec<PlannedOp>,
+    ancestors: &mut Vec<PathBuf>,
+) -> PathResult<()> {
+    let metadata = fs.metadata(src)?;
+    if !metadata.is_dir() {
+        plan.push(PlannedOp::new(OpKind::Copy, src, Some(dst)));
+        return Ok(());
+    }
+
+    let canonical = fs.canonicalize(src)?;
+    if ancestors.contains(&canonical) {
+        return Err(PathError::io_error(format!(
+            "Symlink loop: {} leads back to {}",
+            src.display(),
+            canonical.display()
+        ))
+        .with_path(src.to_string_lossy()));
+    }
+    plan.push(PlannedOp::new(OpKind::CreateDir, src, Some(dst)));
+
+    ancestors.push(canonical);
+    for entry in fs.read_dir(src)? {
+        // Names are only converted to text when they may need renaming
+        let fixed = rename_for.and_then(|style| {
+            let name = entry.to_string_lossy();
+            portable_name(&name, style).map(|fixed| (name.into_owned(), fixed))
+        });
+        let child_dst = match &fixed {
+            Some((_, fixed)) => dst.join(fixed),
+            None => dst.join(&entry),
+        };
+        let first = plan.len();
+
+        plan_entry(
+            fs,
+            &src.join(&entry),
+            &child_dst,
+            rename_for,
+            plan,
+            ancestors,
+        )?;
+
+        if let Some((name, _)) = fixed
+            && let Some(op) = plan.get_mut(first)
+        {
+            op.renamed_from = Some(name);
+        }
+    }
+    ancestors.pop();
+
+    Ok(())
+}
+
+/// Detect conflicts, then execute the plan unless this is a dry run
+fn finish(
+    fs: &dyn FileSystem,
+    mut plan: Vec<PlannedOp>,
+    options: &OpOptions,
+) -> PathResult<Vec<PlannedOp>> {
+    detect_conflicts(fs, &mut plan, options.resolved_style());
+
+    if options.dry_run {
+        return Ok(plan);
+    }
+
+    let blocking = plan.iter().find(|op| match op.conflict {
+        Some(Conflict::DestinationExists) => !options.overwrite,
+        Some(Conflict::DuplicateDestination) => true,
+        None => false,
+    });
+    if let Some(op) = blocking {
+        return Err(PathError::io_error(format!(
+            "Conflicting destination: {}",
+            op.destination.as_deref().unwrap_or(&op.source).display()
+        )));
+    }
+
+    for op in &plan {
+        execute(fs, op)?;
+    }
+
+    Ok(plan)
+}
+
+/// Mark operations whose destination exists or is written twice
+fn detect_conflicts(fs: &dyn FileSystem, plan: &mut [PlannedOp], style: PathStyle) {
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for op in plan.iter_mut() {
+        let Some(destination) = &op.destination else {
+            continue;
+        };
+
+        let mut key = destination.to_string_lossy().into_owned();
+        if style == PathStyle::Windows {
+            key = key.to_lowercase();
+        }
+        if !seen.insert(key) {
+            op.conflict = Some(Conflict::DuplicateDestination);
+            continue;
+        }
+
+        let exists = fs.symlink_metadata(destination).is_ok();
+        let existing_dir = op.kind == OpKind::CreateDir && fs.is_dir(destination);
+        if exists && !existing_dir {
+            op.conflict = Some(Conflict::DestinationExists);
+        }
+    }
+}
+
+/// Execute a single planned operation
+fn execute(fs: &dyn FileSystem, op: &PlannedOp) -> PathResult<()> {
+    // Only planned by `move_to_trash_with`, against the real filesystem
+    #[cfg(feature = "trash")]
+    if op.kind == OpKind::Trash {
+        return Ok(platform::move_to_trash(&op.source)?);
+    }
+
+    let Some(destination) = &op.destination else {
+        return Ok(());
+    };
+
+    match op.kind {
+        OpKind::CreateDir => fs.create_dir_all(destination)?,
+        OpKind::Copy => {
+            fs.copy(&op.source, destination)?;
+        }
+        OpKind::Move => rename_or_copy(fs, &op.source, destination)?,
+        OpKind::Symlink => fs.symlink(&fs.read_link(&op.source)?, destination)?,
+        OpKind::Trash => {}
+    }
+
+    Ok(())
+}
+
+/// Rename, falling back to copy and delete across devices
+fn rename_or_copy(fs: &dyn FileSystem, src: &Path, dst: &Path) -> PathResult<()> {
+    if let Err(err) = fs.rename(src, dst) {
+        if err.kind() != io::ErrorKind::CrossesDevices {
+            return Err(err.into());
+        }
+        copy_then_remove(fs, src, dst)?;
+    }
+    Ok(())
+}
+
+/// Check if a failed removal is worth retrying
+///
+/// Windows refuses to delete files another process (a virus scanner, an
+/// indexer, an editor) has open, and reports a directory as not empty while
+/// deletions of its entries are still pending.
+fn is_transient(err: &io::Error) -> bool {
+    const ERROR_ACCESS_DENIED: i32 = 5;
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    const ERROR_LOCK_VIOLATION: i32 = 33;
+    const ERROR_DIR_NOT_EMPTY: i32 = 145;
+
+    err.kind() == io::ErrorKind::DirectoryNotEmpty
+        || (cfg!(target_os = "windows")
+            && matches!(
+                err.raw_os_error(),
+                Some(
+                    ERROR_ACCESS_DENIED
+                        | ERROR_SHARING_VIOLATION
+                        | ERROR_LOCK_VIOLATION
+                        | ERROR_DIR_NOT_EMPTY
+                )
+            ))
+}
+
+/// Move across devices by copying and then deleting the source
+///
+/// Symlinks are moved as links (see [`plan_move_tree`]).
+fn copy_then_remove(fs: &dyn FileSystem, src: &Path, dst: &Path) -> PathResult<()> {
+    let mut plan = Vec::new();
+    plan_move_tree(fs, src, dst, &mut plan)?;
+    for op in &plan {
+        execute(fs, op)?;
+    }
+
+    if fs.symlink_metadata(src)?.is_dir() {
+        fs.remove_dir_all(src)?;
+    } else {
+        fs.remove_file(src)?;
+    }
+    Ok(())
+}
+
+/// Fix a file name for the target style
+///
+/// Returns `None` if the name is already valid.
+fn portable_name(name: &str, style: PathStyle) -> Option<String> {
+    let (issues, fixed) = validation::check_component(name, style.into());
+    (!issues.is_empty()).then_some(fixed)
+}
@@ -1,30 +1,31 @@
 use crate::PathResult;
+use crate::platform::PlatformPath;
+use crate::vfs::{FileSystem, OsFileSystem, VfsPath};
 use regex::Regex;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 
-/// Path parser for analyzing path structure
-#[derive(Debug, Clone)]
-pub struct PathParser {
-    windows_absolute: Regex,
-    unix_absolute: Regex,
-    unc_path: Regex,
-}
+/// Absolute Windows drive path (`C:\...`, `C:/...`)
+static WINDOWS_ABSOLUTE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-zA-Z]:[/\\].*$").unwrap());
+/// Absolute Unix path
+static UNIX_ABSOLUTE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^/.*$").unwrap());
+/// UNC path with server and share (`\\server\share`)
+static UNC_PATH: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\\\\[^\\]+\\[^\\]+").unwrap());
 
-impl Default for PathParser {
-    fn default() -> Self {
-        Self {
-            windows_absolute: Regex::new(r"^[a-zA-Z]:[/\\].*$").unwrap(),
-            unix_absolute: Regex::new(r"^/.*$").unwrap(),
-            unc_path: Regex::new(r"^\\\\[^\\]+\\[^\\]+").unwrap(),
-        }
-    }
-}
+/// Path parser for analyzing path structure
+///
+/// The patterns it matches with are compiled once per process, so parsers
+/// are free to create.
+#[derive(Debug, Clone, Default)]
+pub struct PathParser;
 
 impl PathParser {
     /// Create new path parser
     #[must_use]
     pub fn new() -> Self {
-        Self::default()
+        Self
     }
 
     /// Parse path into structured components
@@ -33,80 +34,169 @@ impl PathParser {
     ///
     /// Returns `PathError` if parsing fails (though currently it always succeeds).
     pub fn parse(path: &str) -> PathResult<ParsedPath> {
-        let parser = Self::new();
-        Ok(parser.parse_internal(path))
+        Ok(Self::parse_internal(path))
     }
 
-    fn parse_internal(&self, path: &str) -> ParsedPath {
-        let mut parsed = ParsedPath {
-            original: path.to_string(),
-            components: Vec::new(),
-            is_absolute: false,
-            has_drive: false,
-            drive_letter: None,
-            is_unc: false,
-            server: None,
-            share: None,
+    /// Parse path written in a known style into structured components
+    ///
+    /// Unlike [`parse`](Self::parse), this recognizes the `/c/...` drive
+    /// form of [`PathStyle::Msys`](crate::PathStyle::Msys), which is never
+    /// auto-detected.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if parsing fails (though currently it always succeeds).
+    pub fn parse_with_style(path: &str, style: super::PathStyle) -> PathResult<ParsedPath> {
+        if style == super::PathStyle::Msys
+            && let Some(parsed) = Self::parse_posix_drive(path, style)
+        {
+            return Ok(parsed);
+        }
+        Self::parse(path)
+    }
+
+    /// Parse path without copying its components
+    ///
+    /// Produces the same structure as [`parse`](Self::parse), but the
+    /// components borrow from `path` and carry their byte offsets in it, so
+    /// parse → format pipelines such as
+    /// [`PathFormatter::format_ref`](crate::formatter::PathFormatter::format_ref)
+    /// allocate once for the component list instead of once per component.
+    #[must_use]
+    pub fn parse_ref(path: &str) -> ParsedPathRef<'_> {
+        let mut parsed = ParsedPathRef::new(path);
+
+        // Detect device and NT object paths, whose root is kept as written
+        if let Some((kind, root_len)) = split_device_root(path) {
+            parsed.kind = kind;
+            parsed.is_absolute = true;
+            parsed.device = Some(&path[..root_len]);
+            parsed.set_components(root_len, &['/', '\\'], 0);
+            return parsed;
+        }
+
+        // Parse extended-length paths as the path they wrap; `unc` means the
+        // body at `start` stands for `\\` followed by it
+        let mut start = 0;
+        let mut unc = false;
+        loop {
+            let body = &path[start..];
+            let rest = if unc {
+                body.strip_prefix(r"?\")
+            } else {
+                body.strip_prefix(r"\\?\")
+            };
+            let Some(rest) = rest else { break };
+            start = path.len() - rest.len();
+            unc = rest
+                .get(..4)
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(r"UNC\"));
+            if unc {
+                start += 4;
+            }
+        }
+
+        let text = &path[start..];
+        let unc_body = if unc {
+            Some(start)
+        } else {
+            text.starts_with(r"\\").then_some(start + 2)
         };
 
         // Detect UNC path
-        if self.unc_path.is_match(path) {
+        if let Some(body) = unc_body
+            && let Some((server, share)) = split_unc_root(&path[body..])
+        {
             parsed.is_unc = true;
-            if let Some((server, share)) = Self::parse_unc_path(path) {
-                parsed.server = Some(server);
-                parsed.share = Some(share);
-            }
+            parsed.server = Some(server);
+            parsed.share = share;
+            parsed.set_components(body, &['/', '\\'], 1 + usize::from(share.is_some()));
             parsed.is_absolute = true;
+            parsed.kind = PathKind::Unc;
+            parsed.split_stream();
+            return parsed;
+        }
+        if unc {
+            parsed.set_components(start, &['/', '\\'], 0);
+            parsed.split_stream();
+            return parsed;
+        }
+
+        // Detect Cygwin drive path
+        if parsed.set_posix_drive(start, super::PathStyle::Cygwin) {
             return parsed;
         }
 
         // Detect Windows absolute path
-        if self.windows_absolute.is_match(path) {
+        if WINDOWS_ABSOLUTE.is_match(text) {
             parsed.is_absolute = true;
             parsed.has_drive = true;
-            parsed.drive_letter = Some(path.chars().next().unwrap().to_ascii_uppercase());
+            parsed.drive_letter = text.chars().next().map(|c| c.to_ascii_uppercase());
+            parsed.set_components(start + 2, &['/', '\\'], 0);
+            parsed.kind = PathKind::Absolute;
+            parsed.split_stream();
+            return parsed;
+        }
 
-            // Parse components
-            let normalized = path.replace('\\', "/");
-            let components: Vec<&str> = normalized.split('/').filter(|s| !s.is_empty()).collect();
-            parsed.components = components.into_iter().map(String::from).collect();
+        // Detect Windows drive-relative path
+        if is_drive_relative(text) {
+            parsed.has_drive = true;
+            parsed.drive_letter = text.chars().next().map(|c| c.to_ascii_uppercase());
+            parsed.set_components(start + 2, &['/', '\\'], 0);
+            parsed.kind = PathKind::DriveRelative;
+            parsed.split_stream();
+            return parsed;
+        }
 
+        // Detect Windows root-relative path
+        if is_root_relative(text) {
+            parsed.set_components(start, &['/', '\\'], 0);
+            parsed.kind = PathKind::RootRelative;
+            parsed.split_stream();
             return parsed;
         }
 
         // Detect Unix absolute path
-        if self.unix_absolute.is_match(path) {
+        if UNIX_ABSOLUTE.is_match(text) {
             parsed.is_absolute = true;
-
-            let components: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
-            parsed.components = components.into_iter().map(String::from).collect();
-
+            parsed.set_components(start, &['/'], 0);
+            parsed.kind = PathKind::Absolute;
             return parsed;
         }
 
         // Relative path
-        let components: Vec<&str> = path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
-        parsed.components = components.into_iter().map(String::from).collect();
+        parsed.set_components(start, &['/', '\\'], 0);
+        if Self::detect_style(text) == super::PathStyle::Windows {
+            parsed.split_stream();
+        }
+        parsed
+    }
 
+    /// Parse a Cygwin or MSYS drive path such as `/cygdrive/c/Users`
+    fn parse_posix_drive(path: &str, style: super::PathStyle) -> Option<ParsedPath> {
+        let mut parsed = ParsedPathRef::new(path);
         parsed
+            .set_posix_drive(0, style)
+            .then(|| parsed.into_owned())
     }
 
-    fn parse_unc_path(path: &str) -> Option<(String, String)> {
-        let parts: Vec<&str> = path.split('\\').filter(|s| !s.is_empty()).collect();
-        if parts.len() >= 2 {
-            return Some((parts[0].to_string(), parts[1].to_string()));
-        }
-        None
+    fn parse_internal(path: &str) -> ParsedPath {
+        Self::parse_ref(path).into_owned()
     }
 
     /// Detect path style
     #[must_use]
     pub fn detect_style(path: &str) -> super::PathStyle {
-        let parser = Self::new();
-
-        if parser.unc_path.is_match(path) || parser.windows_absolute.is_match(path) {
+        if UNC_PATH.is_match(path)
+            || WINDOWS_ABSOLUTE.is_match(path)
+            || split_device_root(path).is_some()
+            || is_drive_relative(path)
+            || is_root_relative(path)
+        {
             super::PathStyle::Windows
-        } else if parser.unix_absolute.is_match(path) {
+        } else if crate::converter::split_posix_drive(path, super::PathStyle::Cygwin).is_some() {
+            super::PathStyle::Cygwin
+        } else if UNIX_ABSOLUTE.is_match(path) {
             super::PathStyle::Unix
         } else if path.contains('\\') && !path.contains('/') {
             super::PathStyle::Windows
@@ -117,6 +207,79 @@ impl PathParser {
         }
     }
 
+    /// Split a path written in `style` into typed components
+    ///
+    /// Windows paths accept both separators and may carry a verbatim prefix
+    /// (`\\?\`), which is dropped. A UNC root yields `UncServer`, `UncShare`
+    /// and `Root`; in Unix style `//server/share` is treated the same way.
+    /// Empty segments (doubled or trailing separators) are skipped.
+    #[must_use]
+    pub fn components(path: &str, style: super::PathStyle) -> Components {
+        let windows = style == super::PathStyle::Windows;
+        let is_separator = |c: char| c == '/' || (windows && c == '\\');
+        let mut components = Vec::new();
+
+        let mut rest = path;
+        let mut unc = None;
+        if windows
+            && let Some(verbatim) = path
+                .strip_prefix(r"\\?\")
+                .or_else(|| path.strip_prefix(r"\\.\"))
+        {
+            match verbatim.get(..4) {
+                Some(prefix) if prefix.eq_ignore_ascii_case(r"UNC\") => unc = Some(&verbatim[4..]),
+                _ => rest = verbatim,
+            }
+        }
+
+        let mut chars = rest.chars();
+        let (first, second, third) = (chars.next(), chars.next(), chars.next());
+        if unc.is_none()
+            && first.is_some_and(is_separator)
+            && second.is_some_and(is_separator)
+            && third.is_some_and(|c| !is_separator(c))
+        {
+            unc = Some(&rest[2..]);
+        }
+
+        if let Some(unc) = unc {
+            let mut parts = unc.splitn(3, is_separator);
+            let server = parts.next().unwrap_or_default();
+            let share = parts.next().unwrap_or_default();
+            components.push(Component::UncServer(server.to_string()));
+            if !share.is_empty() {
+                components.push(Component::UncShare(share.to_string()));
+            }
+            components.push(Component::Root);
+            rest = parts.next().unwrap_or_default();
+        } else {
+            if windows
+                && let (Some(drive), Some(':')) = (first, second)
+                && drive.is_ascii_alphabetic()
+            {
+                components.push(Component::Drive(drive.to_ascii_uppercase()));
+                rest = &rest[2..];
+            }
+            if rest.starts_with(is_separator) {
+                components.push(Component::Root);
+            }
+        }
+
+        components.extend(
+            rest.split(is_separator)
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| match segment {
+                    "." => Component::CurDir,
+                    ".." => Component::ParentDir,
+                    name => Component::Normal(name.to_string()),
+                }),
+        );
+
+        Components {
+            inner: components.into_iter(),
+        }
+    }
+
     /// Normalize path by removing redundant components
     ///
     /// # Errors
@@ -168,6 +331,142 @@ impl PathParser {
 
         Ok(normalized)
     }
+
+    /// Normalize path against the filesystem, resolving symlinks
+    ///
+    /// Follows shell `pwd -P` semantics: every existing prefix is resolved
+    /// to its physical location before the next component is applied, so
+    /// `..` after a symlink moves to the parent of the link target. Once a
+    /// component does not exist, the remainder is collapsed lexically.
+    /// Relative paths are resolved against the current directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the current directory cannot be determined or
+    /// an existing prefix cannot be resolved.
+    pub fn normalize_physical(path: &Path) -> PathResult<PathBuf> {
+        Self::normalize_physical_in(&OsFileSystem, path)
+    }
+
+    /// Normalize path against the given filesystem, resolving symlinks
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the current directory cannot be determined or
+    /// an existing prefix cannot be resolved.
+    pub fn normalize_physical_in(fs: &dyn FileSystem, path: &Path) -> PathResult<PathBuf> {
+        let mut resolved = if VfsPath::new(fs, path).is_absolute() {
+            PathBuf::new()
+        } else {
+            physical(fs, &fs.current_dir()?)?
+        };
+        let mut exists = true;
+
+        for component in path.components() {
+            match component {
+                std::path::Component::Prefix(_) | std::path::Component::RootDir => {
+                    resolved.push(component.as_os_str());
+                }
+                std::path::Component::CurDir => {}
+                std::path::Component::ParentDir => {
+                    // `resolved` is physical while it exists, so popping
+                    // moves to the parent of any link target
+                    resolved.pop();
+                }
+                std::path::Component::Normal(name) => {
+                    resolved.push(name);
+                    if exists {
+                        match physical(fs, &resolved) {
+                            Ok(target) => resolved = target,
+                            Err(_) => exists = false,
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(resolved)
+    }
+}
+
+/// Resolve an existing path to its physical location
+fn physical(fs: &dyn FileSystem, path: &Path) -> PathResult<PathBuf> {
+    let resolved = fs.canonicalize(path)?;
+
+    // Drop the verbatim prefix Windows adds to canonical drive paths
+    #[cfg(target_os = "windows")]
+    if let Some(rest) = resolved.to_str().and_then(|s| s.strip_prefix(r"\\?\"))
+        && rest.as_bytes().get(1) == Some(&b':')
+    {
+        return Ok(PathBuf::from(rest));
+    }
+
+    Ok(resolved)
+}
+
+/// Check for a Windows drive-relative path (`C:foo`), which is relative to
+/// the current directory of its drive
+pub(crate) fn is_drive_relative(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() > 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && !matches!(bytes[2], b'/' | b'\\')
+}
+
+/// Check for a Windows root-relative path (`\foo`), which is relative to
+/// the root of the current drive
+pub(crate) fn is_root_relative(path: &str) -> bool {
+    path.starts_with('\\') && !path.starts_with(r"\\")
+}
+
+/// Characters that are not allowed in Windows path components
+pub(crate) const WINDOWS_INVALID_CHARS: [char; 7] = ['<', '>', ':', '"', '|', '?', '*'];
+
+/// Check if a character cannot appear in a path component of the given style
+pub(crate) fn is_invalid_component_char(c: char, style: super::PathStyle) -> bool {
+    match style {
+        super::PathStyle::Windows => WINDOWS_INVALID_CHARS.contains(&c) || c.is_control(),
+        super::PathStyle::Unix
+        | super::PathStyle::Cygwin
+        | super::PathStyle::Msys
+        | super::PathStyle::Custom(_)
+        | super::PathStyle::Auto => c == '\0',
+    }
+}
+
+/// How a path is anchored
+///
+/// Windows has two forms that are neither absolute nor relative to the
+/// current directory: `C:foo` is relative to the current directory of drive
+/// `C:`, and `\foo` to the root of the current drive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PathKind {
+    /// Absolute drive or Unix path (`C:\foo`, `/foo`)
+    Absolute,
+    /// UNC path (`\\server\share\foo`)
+    Unc,
+    /// Relative to the current directory (`foo\bar`)
+    #[default]
+    Relative,
+    /// Relative to the current directory of a drive (`C:foo`)
+    DriveRelative,
+    /// Relative to the root of the current drive (`\foo`)
+    RootRelative,
+    /// Win32 device path (`\\.\PhysicalDrive0`, `\\.\pipe\name`,
+    /// `\\?\Volume{GUID}\`)
+    Device,
+    /// NT object manager path (`\??\C:\foo`)
+    NtObject,
+}
+
+impl PathKind {
+    /// Check if the path addresses a device or NT object rather than a
+    /// file system location, so it has no Unix equivalent
+    #[must_use]
+    pub const fn is_device(self) -> bool {
+        matches!(self, Self::Device | Self::NtObject)
+    }
 }
 
 /// Parsed path information
@@ -177,6 +476,8 @@ pub struct ParsedPath {
     pub original: String,
     /// Path components
     pub components: Vec<String>,
+    /// How the path is anchored
+    pub kind: PathKind,
     /// Whether path is absolute
     pub is_absolute: bool,
     /// Whether path has drive letter
@@ -187,6 +488,320 @@ pub struct ParsedPath {
     pub is_unc: bool,
     /// UNC server name
     pub server: Option<String>,
-    /// UNC share name
+    /// UNC share name, `None` for a bare server (`\\server`)
     pub share: Option<String>,
+    /// Root of a device or NT object path as written (`\\.\pipe`,
+    /// `\??\C:`)
+    pub device: Option<String>,
+    /// NTFS alternate data stream of the last component as written after
+    /// its first `:`, including a stream type such as `:$DATA`
+    ///
+    /// Only recognized on Windows paths; the last component holds the name
+    /// without the stream.
+    pub stream: Option<String>,
+}
+
+impl ParsedPath {
+    /// Name of the alternate data stream the path addresses
+    /// (`Zone.Identifier` for `file.txt:Zone.Identifier:$DATA`)
+    #[must_use]
+    pub fn stream_name(&self) -> Option<&str> {
+        stream_name(self.stream.as_deref()?)
+    }
+
+    /// GUID of the volume a volume GUID path (`\\?\Volume{GUID}\dir`)
+    /// addresses, without braces
+    #[must_use]
+    pub fn volume_guid(&self) -> Option<&str> {
+        volume_guid(self.device.as_deref()?)
+    }
+}
+
+/// Parsed path borrowing its components from the input
+///
+/// Created by [`PathParser::parse_ref`]. Each component is a slice of
+/// `original`, and `spans[i]` is the byte range of `components[i]` in it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedPathRef<'a> {
+    /// Original path string
+    pub original: &'a str,
+    /// Path components
+    pub components: Vec<&'a str>,
+    /// Byte range of each component in `original`
+    pub spans: Vec<Range<usize>>,
+    /// How the path is anchored
+    pub kind: PathKind,
+    /// Whether path is absolute
+    pub is_absolute: bool,
+    /// Whether path has drive letter
+    pub has_drive: bool,
+    /// Drive letter (if present)
+    pub drive_letter: Option<char>,
+    /// Whether path is UNC
+    pub is_unc: bool,
+    /// UNC server name
+    pub server: Option<&'a str>,
+    /// UNC share name
+    pub share: Option<&'a str>,
+    /// Root of a device or NT object path as written (`\\.\pipe`,
+    /// `\??\C:`)
+    pub device: Option<&'a str>,
+    /// NTFS alternate data stream of the last component, see
+    /// [`ParsedPath::stream`]
+    pub stream: Option<&'a str>,
+}
+
+impl<'a> ParsedPathRef<'a> {
+    /// Relative path without components
+    fn new(original: &'a str) -> Self {
+        Self {
+            original,
+            components: Vec::new(),
+            spans: Vec::new(),
+            kind: PathKind::Relative,
+            is_absolute: false,
+            has_drive: false,
+            drive_letter: None,
+            is_unc: false,
+            server: None,
+            share: None,
+            device: None,
+            stream: None,
+        }
+    }
+
+    /// Copy the components into an owned [`ParsedPath`]
+    #[must_use]
+    pub fn into_owned(self) -> ParsedPath {
+        ParsedPath {
+            original: self.original.to_string(),
+            components: self.components.into_iter().map(String::from).collect(),
+            kind: self.kind,
+            is_absolute: self.is_absolute,
+            has_drive: self.has_drive,
+            drive_letter: self.drive_letter,
+            is_unc: self.is_unc,
+            server: self.server.map(String::from),
+            share: self.share.map(String::from),
+            device: self.device.map(String::from),
+            stream: self.stream.map(String::from),
+        }
+    }
+
+    /// Split `original[start..]` into non-empty components, skipping the
+    /// first `skip`
+    fn set_components(&mut self, start: usize, separators: &[char], skip: usize) {
+        let mut offset = start;
+        for segment in self.original[start..].split(separators) {
+            let span = offset..offset + segment.len();
+            // Separators are ASCII, one byte each
+            offset = span.end + 1;
+            if !segment.is_empty() {
+                self.components.push(segment);
+                self.spans.push(span);
+            }
+        }
+        let skip = skip.min(self.components.len());
+        self.components.drain(..skip);
+        self.spans.drain(..skip);
+    }
+
+    /// Name of the alternate data stream the path addresses, see
+    /// [`ParsedPath::stream_name`]
+    #[must_use]
+    pub fn stream_name(&self) -> Option<&'a str> {
+        stream_name(self.stream?)
+    }
+
+    /// GUID of the volume a volume GUID path addresses, see
+    /// [`ParsedPath::volume_guid`]
+    #[must_use]
+    pub fn volume_guid(&self) -> Option<&'a str> {
+        volume_guid(self.device?)
+    }
+
+    /// Move an alternate data stream suffix (`:Zone.Identifier`) of the
+    /// last component into `stream`
+    fn split_stream(&mut self) {
+        let (Some(last), Some(span)) = (self.components.last_mut(), self.spans.last_mut()) else {
+            return;
+        };
+        if let Some(colon) = last.find(':')
+            && colon > 0
+            && colon + 1 < last.len()
+        {
+            self.stream = Some(&last[colon + 1..]);
+            *last = &last[..colon];
+            span.end = span.start + colon;
+        }
+    }
+
+    /// Parse `original[start..]` as a Cygwin or MSYS drive path
+    ///
+    /// Returns `false`, leaving the path untouched, if it is not one.
+    fn set_posix_drive(&mut self, start: usize, style: super::PathStyle) -> bool {
+        let Some((drive, rest)) =
+            crate::converter::split_posix_drive(&self.original[start..], style)
+        else {
+            return false;
+        };
+        self.is_absolute = true;
+        self.has_drive = true;
+        self.drive_letter = Some(drive.to_ascii_uppercase());
+        self.kind = PathKind::Absolute;
+        self.set_components(self.original.len() - rest.len(), &['/'], 0);
+        true
+    }
+}
+
+impl From<ParsedPathRef<'_>> for ParsedPath {
+    fn from(parsed: ParsedPathRef<'_>) -> Self {
+        parsed.into_owned()
+    }
+}
+
+/// Check if a path may address an NTFS alternate data stream
+///
+/// Besides the streams the parser recognizes on Windows paths, a `:` in
+/// the last component of any relative path counts, since such a path may
+/// still be opened on Windows.
+#[cfg(feature = "security")]
+pub(crate) fn has_data_stream(path: &str) -> bool {
+    let parsed = PathParser::parse_ref(path);
+    parsed.stream.is_some()
+        || (parsed.kind == PathKind::Relative
+            && parsed.components.last().is_some_and(|last| {
+                last.find(':')
+                    .is_some_and(|colon| colon > 0 && colon + 1 < last.len())
+            }))
 }
+
+/// Strip the stream type from a stream suffix (`Zone.Identifier:$DATA`)
+///
+/// Returns `None` for the unnamed default stream (`:$DATA`).
+fn stream_name(stream: &str) -> Option<&str> {
+    let name = stream.split(':').next().unwrap_or_default();
+    (!name.is_empty()).then_some(name)
+}
+
+/// GUID of a volume device root (`\\?\Volume{GUID}`), without braces
+fn volume_guid(device: &str) -> Option<&str> {
+    let name = device
+        .strip_prefix(r"\\?\")
+        .or_else(|| device.strip_prefix(r"\\.\"))?;
+    let guid = name
+        .get(..7)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("Volume{"))
+        .and_then(|_| name[7..].strip_suffix('}'))?;
+    let groups: Vec<&str> = guid.split('-').collect();
+    let well_formed = groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.bytes().all(|b| b.is_ascii_hexdigit()));
+    well_formed.then_some(guid)
+}
+
+/// Split the root off a Win32 device path (`\\.\pipe\name`,
+/// `\\?\Volume{GUID}\`) or NT object path (`\??\C:\foo`)
+///
+/// Returns the kind and the byte length of the root, which runs up to the
+/// separator after the device name. Extended-length paths such as
+/// `\\?\C:\foo` are not device paths.
+pub(crate) fn split_device_root(path: &str) -> Option<(PathKind, usize)> {
+    let (kind, body) = if let Some(body) = path
+        .strip_prefix(r"\\.\")
+        .or_else(|| path.strip_prefix("//./"))
+    {
+        (PathKind::Device, body)
+    } else if let Some(body) = path.strip_prefix(r"\??\") {
+        (PathKind::NtObject, body)
+    } else if let Some(body) = path.strip_prefix(r"\\?\") {
+        let name = body.split(['\\', '/']).next().unwrap_or_default();
+        let volume = name
+            .get(..7)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("Volume{"));
+        if !volume && !name.eq_ignore_ascii_case("GLOBALROOT") {
+            return None;
+        }
+        (PathKind::Device, body)
+    } else {
+        return None;
+    };
+    let name_len = body.find(['\\', '/']).unwrap_or(body.len());
+    (name_len > 0).then_some((kind, path.len() - body.len() + name_len))
+}
+
+/// Split the server and share off a UNC path without its leading `\\`
+///
+/// The share is `None` for a bare server (`\\server`). Servers may be
+/// bracketed IPv6 literals (`\\[::1]\share`).
+fn split_unc_root(body: &str) -> Option<(&str, Option<&str>)> {
+    let (server, rest) = body.split_once('\\').unwrap_or((body, ""));
+    if server.is_empty() {
+        return None;
+    }
+    if rest.is_empty() {
+        return Some((server, None));
+    }
+    let share = rest.split('\\').next().unwrap_or_default();
+    (!share.is_empty()).then_some((server, Some(share)))
+}
+
+/// Get the drive of an administrative share (`C$` is drive `C:`)
+pub(crate) fn admin_share_drive(share: &str) -> Option<char> {
+    match share.as_bytes() {
+        &[letter, b'$'] if letter.is_ascii_alphabetic() => {
+            Some(char::from(letter.to_ascii_uppercase()))
+        }
+        _ => None,
+    }
+}
+
+/// A single path component, independent of the separator style
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Component {
+    /// Windows drive letter (upper case)
+    Drive(char),
+    /// Root directory
+    Root,
+    /// UNC server name
+    UncServer(String),
+    /// UNC share name
+    UncShare(String),
+    /// Current directory (`.`)
+    CurDir,
+    /// Parent directory (`..`)
+    ParentDir,
+    /// Regular file or directory name
+    Normal(String),
+}
+
+/// Iterator over the components of a path
+///
+/// Created by [`PathParser::components`] and
+/// [`CrossPath::components`](crate::CrossPath::components).
+#[derive(Debug, Clone)]
+pub struct Components {
+    inner: std::vec::IntoIter<Component>,
+}
+
+impl Iterator for Components {
+    type Item = Component;
+
+    fn next(&mut self) -> Option<Component> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Components {
+    fn next_back(&mut self) -> Option<Component> {
+        self.inner.next_back()
+    }
+}
+
+impl ExactSizeIterator for Components {}
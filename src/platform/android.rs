@@ -0,0 +1,488 @@
+//! Android storage paths and `content://` URIs
+//!
+//! Android exposes the same shared storage under several names: `/sdcard`,
+//! `/mnt/sdcard` and `/storage/self/primary` all lead to
+//! `/storage/emulated/0`, and `/data/data/<package>` is the first user's
+//! `/data/user/0/<package>`. [`canonical_storage_path`] rewrites the aliases
+//! to the per-user form, and [`app_location`] recognizes the directories
+//! private to an app.
+//!
+//! Files picked through the Storage Access Framework arrive as
+//! `content://` URIs instead. [`ContentUri`] parses and formats them; the
+//! ones issued by the external storage and downloads providers name a file
+//! path, all others are opaque handles that only a `ContentResolver` can
+//! open and are reported as not convertible. [`AndroidPath`] accepts both
+//! forms.
+//!
+//! Everything here works on strings, so it is available on every platform.
+
+use crate::{CrossPath, PathError, PathResult, PathStyle, url};
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+/// Shared storage of the first user
+pub const PRIMARY_STORAGE: &str = "/storage/emulated/0";
+
+/// Authority of the external storage documents provider
+pub const EXTERNAL_STORAGE_AUTHORITY: &str = "com.android.externalstorage.documents";
+
+/// Authority of the downloads documents provider
+pub const DOWNLOADS_AUTHORITY: &str = "com.android.providers.downloads.documents";
+
+/// Names of the first user's shared storage
+const STORAGE_ALIASES: &[&str] = &[
+    "/sdcard",
+    "/mnt/sdcard",
+    "/storage/self/primary",
+    "/storage/emulated/legacy",
+    "/mnt/user/0/primary",
+];
+
+/// Directories private to the first user's apps
+const DATA_ALIAS: &str = "/data/data";
+
+/// Rewrite a storage alias to its `/storage/emulated/<user>` or
+/// `/data/user/<user>` form
+///
+/// Paths that do not start with an alias are returned unchanged.
+#[must_use]
+pub fn canonical_storage_path(path: &str) -> Cow<'_, str> {
+    for alias in STORAGE_ALIASES {
+        if let Some(rest) = strip_dir(path, alias) {
+            return Cow::Owned(format!("{PRIMARY_STORAGE}{rest}"));
+        }
+    }
+    match strip_dir(path, DATA_ALIAS) {
+        Some(rest) => Cow::Owned(format!("/data/user/0{rest}")),
+        None => Cow::Borrowed(path),
+    }
+}
+
+/// Kind of app-private directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AppStorage {
+    /// Internal data directory (`/data/user/<user>/<package>`)
+    Internal,
+    /// App-specific directory on shared storage (`Android/data/<package>`)
+    External,
+    /// Expansion files (`Android/obb/<package>`)
+    Obb,
+    /// Media shared with other apps (`Android/media/<package>`)
+    Media,
+}
+
+impl AppStorage {
+    /// Directory under `Android/` on shared storage, if any
+    fn shared_dir(self) -> Option<&'static str> {
+        match self {
+            Self::Internal => None,
+            Self::External => Some("data"),
+            Self::Obb => Some("obb"),
+            Self::Media => Some("media"),
+        }
+    }
+}
+
+/// App-private directory a path lies in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppLocation {
+    /// Android user the directory belongs to
+    pub user: u32,
+    /// Package name of the app
+    pub package: String,
+    /// Kind of directory
+    pub storage: AppStorage,
+    /// Path below the directory, without a leading `/` (e.g., `files/a.txt`)
+    pub path: String,
+}
+
+/// Root of an app-private directory
+///
+/// `app_dir("com.example", AppStorage::External, 0)` gives
+/// `/storage/emulated/0/Android/data/com.example`.
+///
+/// # Errors
+///
+/// Returns `PathError::InvalidPath` if `package` is not a valid package name
+pub fn app_dir(package: &str, storage: AppStorage, user: u32) -> PathResult<String> {
+    if !is_package_name(package) {
+        return Err(PathError::invalid_path(format!(
+            "Invalid Android package name: {package}"
+        )));
+    }
+    Ok(match storage.shared_dir() {
+        Some(dir) => format!("/storage/emulated/{user}/Android/{dir}/{package}"),
+        None => format!("/data/user/{user}/{package}"),
+    })
+}
+
+/// App-private directory containing `path`, after resolving aliases
+#[must_use]
+pub fn app_location(path: &str) -> Option<AppLocation> {
+    let path = canonical_storage_path(path);
+    let (user, rest, storage) = if let Some(rest) = path.strip_prefix("/data/user/") {
+        let (user, rest) = rest.split_once('/')?;
+        (user, rest, AppStorage::Internal)
+    } else {
+        let rest = path.strip_prefix("/storage/emulated/")?;
+        let (user, rest) = rest.split_once("/Android/")?;
+        let (dir, rest) = rest.split_once('/')?;
+        let storage = [AppStorage::External, AppStorage::Obb, AppStorage::Media]
+            .into_iter()
+            .find(|storage| storage.shared_dir() == Some(dir))?;
+        (user, rest, storage)
+    };
+
+    let (package, rest) = rest.split_once('/').unwrap_or((rest, ""));
+    if !is_package_name(package) {
+        return None;
+    }
+    Some(AppLocation {
+        user: user.parse().ok()?,
+        package: package.to_string(),
+        storage,
+        path: rest.trim_end_matches('/').to_string(),
+    })
+}
+
+/// Parsed `content://` URI
+///
+/// Path segments are stored decoded, so a document ID such as
+/// `primary:Download/a.pdf` is one segment even though it is written
+/// `primary%3ADownload%2Fa.pdf` in the URI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentUri {
+    authority: String,
+    segments: Vec<String>,
+    query: Option<String>,
+}
+
+impl ContentUri {
+    /// Parse a `content://` URI
+    ///
+    /// The fragment, if any, is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::InvalidPath` if the text is not a `content://`
+    /// URI with an authority or has malformed percent-escapes
+    pub fn parse(uri: &str) -> PathResult<Self> {
+        let rest = uri
+            .get(..10)
+            .filter(|scheme| scheme.eq_ignore_ascii_case("content://"))
+            .map(|_| &uri[10..])
+            .ok_or_else(|| PathError::invalid_path(format!("Not a content URI: {uri}")))?;
+        let rest = rest.split('#').next().unwrap_or_default();
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query.to_string())),
+            None => (rest, None),
+        };
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        if authority.is_empty() {
+            return Err(PathError::invalid_path(format!(
+                "Content URI has no authority: {uri}"
+            )));
+        }
+
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(url::percent_decode)
+            .collect::<PathResult<_>>()?;
+        Ok(Self {
+            authority: authority.to_string(),
+            segments,
+            query,
+        })
+    }
+
+    /// Build a URI from an authority and decoded path segments
+    #[must_use]
+    pub fn new<I, S>(authority: &str, segments: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            authority: authority.to_string(),
+            segments: segments.into_iter().map(Into::into).collect(),
+            query: None,
+        }
+    }
+
+    /// External storage document URI for a file on shared or removable
+    /// storage
+    ///
+    /// `/sdcard/Download/a.pdf` gives
+    /// `content://com.android.externalstorage.documents/document/primary%3ADownload%2Fa.pdf`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::UnsupportedFormat` if the path is not on the
+    /// first user's shared storage or on a removable volume
+    pub fn for_document(path: &str) -> PathResult<Self> {
+        let path = canonical_storage_path(path);
+        let (volume, rest) = if let Some(rest) = strip_dir(&path, PRIMARY_STORAGE) {
+            ("primary", rest)
+        } else {
+            path.strip_prefix("/storage/")
+                .map(|rest| rest.split_once('/').unwrap_or((rest, "")))
+                .filter(|(volume, _)| is_volume_id(volume))
+                .ok_or_else(|| {
+                    PathError::unsupported_format(format!(
+                        "{path} is not on a storage volume with document URIs"
+                    ))
+                })?
+        };
+        let rest = rest.trim_matches('/');
+        Ok(Self::new(
+            EXTERNAL_STORAGE_AUTHORITY,
+            ["document".to_string(), format!("{volume}:{rest}")],
+        ))
+    }
+
+    /// Authority naming the provider (e.g., `com.example.fileprovider`)
+    #[must_use]
+    pub fn authority(&self) -> &str {
+        &self.authority
+    }
+
+    /// Decoded path segments
+    #[must_use]
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Raw query string, without the `?`
+    #[must_use]
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Document ID of a documents provider URI
+    ///
+    /// Found in `/document/<id>` and `/tree/<tree>/document/<id>` URIs.
+    #[must_use]
+    pub fn document_id(&self) -> Option<&str> {
+        match self.segments.as_slice() {
+            [document, id] | [_, _, document, id] if document == "document" => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Document ID of the tree a `/tree/<tree>/...` URI was granted for
+    #[must_use]
+    pub fn tree_document_id(&self) -> Option<&str> {
+        match self.segments.as_slice() {
+            [tree, id, ..] if tree == "tree" => Some(id),
+            _ => None,
+        }
+    }
+
+    /// File path the URI names, if its provider encodes one
+    ///
+    /// External storage documents map to `/storage/emulated/0` or the
+    /// removable volume in their ID, and downloads with a `raw:` ID carry
+    /// the path itself. Other URIs are opaque.
+    #[must_use]
+    pub fn file_path(&self) -> Option<String> {
+        let id = self.document_id().or_else(|| self.tree_document_id())?;
+        match self.authority.as_str() {
+            EXTERNAL_STORAGE_AUTHORITY => {
+                let (volume, rest) = id.split_once(':')?;
+                let root = match volume {
+                    "primary" => PRIMARY_STORAGE.to_string(),
+                    "home" => format!("{PRIMARY_STORAGE}/Documents"),
+                    volume if is_volume_id(volume) => format!("/storage/{volume}"),
+                    _ => return None,
+                };
+                let rest = rest.trim_matches('/');
+                Some(if rest.is_empty() {
+                    root
+                } else {
+                    format!("{root}/{rest}")
+                })
+            }
+            DOWNLOADS_AUTHORITY => id
+                .strip_prefix("raw:")
+                .filter(|path| path.starts_with('/'))
+                .map(|path| canonical_storage_path(path).into_owned()),
+            _ => None,
+        }
+    }
+
+    /// Whether the URI can be converted to a file path
+    #[must_use]
+    pub fn is_convertible(&self) -> bool {
+        self.file_path().is_some()
+    }
+}
+
+impl fmt::Display for ContentUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "content://{}", self.authority)?;
+        for segment in &self.segments {
+            f.write_char('/')?;
+            f.write_str(&encode_segment(segment))?;
+        }
+        if let Some(query) = &self.query {
+            write!(f, "?{query}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ContentUri {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> PathResult<Self> {
+        Self::parse(s)
+    }
+}
+
+/// Path or URI received from Android
+///
+/// File paths are kept with their storage aliases resolved; `file://` URLs
+/// are decoded to file paths.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AndroidPath {
+    /// Filesystem path
+    File(String),
+    /// `content://` URI
+    Content(ContentUri),
+}
+
+impl AndroidPath {
+    /// Parse a file path, `file://` URL or `content://` URI
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if a URL or URI is malformed
+    pub fn parse(input: &str) -> PathResult<Self> {
+        let has_scheme = |scheme: &str| {
+            input
+                .get(..scheme.len())
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+        };
+        if has_scheme("content:") {
+            ContentUri::parse(input).map(Self::Content)
+        } else if has_scheme("file:") {
+            Ok(Self::File(
+                canonical_storage_path(&url::to_path(input)?).into_owned(),
+            ))
+        } else {
+            Ok(Self::File(canonical_storage_path(input).into_owned()))
+        }
+    }
+
+    /// Whether the path can be converted to a file path
+    #[must_use]
+    pub fn is_convertible(&self) -> bool {
+        match self {
+            Self::File(_) => true,
+            Self::Content(uri) => uri.is_convertible(),
+        }
+    }
+
+    /// File path this path names
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::UnsupportedFormat` for opaque content URIs
+    pub fn to_file_path(&self) -> PathResult<String> {
+        match self {
+            Self::File(path) => Ok(path.clone()),
+            Self::Content(uri) => uri.file_path().ok_or_else(|| {
+                PathError::unsupported_format(format!(
+                    "{uri} is an opaque content URI without a file path"
+                ))
+            }),
+        }
+    }
+
+    /// Unix-style [`CrossPath`] for the file path this path names
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::UnsupportedFormat` for opaque content URIs, or
+    /// `PathError` if the file path is invalid
+    pub fn to_cross_path(&self) -> PathResult<CrossPath> {
+        CrossPath::with_style(self.to_file_path()?, PathStyle::Unix)
+    }
+
+    /// App-private directory the path lies in
+    #[must_use]
+    pub fn app_location(&self) -> Option<AppLocation> {
+        match self {
+            Self::File(path) => app_location(path),
+            Self::Content(uri) => app_location(&uri.file_path()?),
+        }
+    }
+}
+
+impl fmt::Display for AndroidPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => f.write_str(path),
+            Self::Content(uri) => uri.fmt(f),
+        }
+    }
+}
+
+impl FromStr for AndroidPath {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> PathResult<Self> {
+        Self::parse(s)
+    }
+}
+
+/// Rest of `path` after the directory `dir`, starting with `/` or empty
+fn strip_dir<'p>(path: &'p str, dir: &str) -> Option<&'p str> {
+    path.strip_prefix(dir)
+        .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Whether `name` is a valid package name (`com.example.app`)
+fn is_package_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('.').all(|part| {
+            part.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
+
+/// Whether `name` is a removable volume ID (`1A2B-3C4D`)
+fn is_volume_id(name: &str) -> bool {
+    name.split_once('-').is_some_and(|(high, low)| {
+        [high, low]
+            .iter()
+            .all(|part| part.len() == 4 && part.bytes().all(|b| b.is_ascii_hexdigit()))
+    })
+}
+
+/// Percent-encode a path segment as Android's `Uri.encode` does
+fn encode_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for &byte in segment.as_bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'!'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => encoded.push(char::from(byte)),
+            _ => {
+                let _ = write!(encoded, "%{byte:02X}");
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,285 @@
+//! macOS-specific path handling implementation
+//!
+//! This module complements the Unix implementation with macOS details:
+//! volume information from `statfs`/`getmntinfo`, volumes mounted under
+//! `/Volumes`, the Finder hidden flag (`UF_HIDDEN`), `com.apple.*` extended
+//! attributes and case-sensitivity probing, which differs between APFS and
+//! HFS+ volumes and their case-sensitive variants.
+
+use crate::{CaseSensitivity, PathError};
+use std::ffi::{CStr, CString};
+use std::os::macos::fs::MetadataExt;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+/// Directory removable and secondary volumes are mounted under
+pub const VOLUMES_ROOT: &str = "/Volumes";
+
+/// Mounted volume information
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeInfo {
+    /// Directory the volume is mounted on (e.g., `/`, `/Volumes/USB`)
+    pub mount_point: String,
+    /// Mounted device or source (e.g., `/dev/disk3s1`, `//user@nas/share`)
+    pub device: String,
+    /// Filesystem type (e.g., "apfs", "hfs", "msdos", "exfat", "smbfs")
+    pub fs_type: String,
+    /// Total space in bytes
+    pub total_space: u64,
+    /// Space available to unprivileged users in bytes
+    pub free_space: u64,
+    /// Whether the volume is mounted read-only
+    pub read_only: bool,
+    /// Whether the volume is stored locally (not a network mount)
+    pub local: bool,
+}
+
+impl VolumeInfo {
+    /// Build from a `statfs` record
+    fn from_statfs(stats: &libc::statfs) -> Self {
+        let text = |field: &[libc::c_char]| {
+            // SAFETY: the kernel NUL-terminates the fixed-size name fields
+            unsafe { CStr::from_ptr(field.as_ptr()) }
+                .to_string_lossy()
+                .into_owned()
+        };
+        let flags = i64::from(stats.f_flags);
+        let block_size = u64::from(stats.f_bsize);
+        Self {
+            mount_point: text(&stats.f_mntonname),
+            device: text(&stats.f_mntfromname),
+            fs_type: text(&stats.f_fstypename),
+            total_space: stats.f_blocks.saturating_mul(block_size),
+            free_space: stats.f_bavail.saturating_mul(block_size),
+            read_only: flags & i64::from(libc::MNT_RDONLY) != 0,
+            local: flags & i64::from(libc::MNT_LOCAL) != 0,
+        }
+    }
+}
+
+/// Get information about the volume containing a path
+///
+/// # Errors
+///
+/// Returns `PathError` if the path does not exist or `statfs` fails.
+pub fn volume_info(path: &Path) -> Result<VolumeInfo, PathError> {
+    let path_cstr = c_path(path)?;
+    let mut stats: libc::statfs = unsafe { std::mem::zeroed() };
+
+    unsafe {
+        if libc::statfs(path_cstr.as_ptr(), &raw mut stats) != 0 {
+            return Err(PathError::platform_error(format!(
+                "Failed to get volume information for {}",
+                path.display()
+            )));
+        }
+    }
+
+    Ok(VolumeInfo::from_statfs(&stats))
+}
+
+/// List all mounted volumes
+///
+/// # Errors
+///
+/// Returns `PathError` if the mount table cannot be read.
+pub fn mounted_volumes() -> Result<Vec<VolumeInfo>, PathError> {
+    let mut entries: *mut libc::statfs = std::ptr::null_mut();
+
+    // SAFETY: getmntinfo points `entries` at a buffer it owns, valid until
+    // the next call; it is copied out immediately
+    let count = unsafe { libc::getmntinfo(&raw mut entries, libc::MNT_NOWAIT) };
+    if count <= 0 || entries.is_null() {
+        return Err(PathError::platform_error("Failed to read the mount table"));
+    }
+    let count = usize::try_from(count).unwrap_or_default();
+    let entries = unsafe { std::slice::from_raw_parts(entries, count) };
+
+    Ok(entries.iter().map(VolumeInfo::from_statfs).collect())
+}
+
+/// Split a path under `/Volumes` into the volume name and the rest
+///
+/// `/Volumes/USB/photos/a.jpg` yields `("USB", "/photos/a.jpg")`; the
+/// volume root itself yields an empty rest.
+#[must_use]
+pub fn split_volume_path(path: &str) -> Option<(&str, &str)> {
+    let rest = path.strip_prefix(VOLUMES_ROOT)?.strip_prefix('/')?;
+    let (name, rest) = match rest.find('/') {
+        Some(pos) => rest.split_at(pos),
+        None => (rest, ""),
+    };
+    (!name.is_empty()).then_some((name, rest))
+}
+
+/// Check if a file has the Finder hidden flag (`chflags hidden`) or a
+/// leading dot
+///
+/// # Errors
+///
+/// Returns `PathError` if the file metadata cannot be read.
+pub fn is_hidden(path: &Path) -> Result<bool, PathError> {
+    let metadata = std::fs::symlink_metadata(path).map_err(|e| {
+        PathError::platform_error(format!("Failed to read {}: {e}", path.display()))
+    })?;
+    let dot_file = path
+        .file_name()
+        .is_some_and(|name| name.as_bytes().starts_with(b"."));
+    Ok(dot_file || metadata.st_flags() & libc::UF_HIDDEN != 0)
+}
+
+/// List the `com.apple.*` extended attributes of a file
+///
+/// These carry macOS metadata such as quarantine state
+/// (`com.apple.quarantine`), Finder info and resource forks. Symlinks are
+/// not followed.
+///
+/// # Errors
+///
+/// Returns `PathError` if the attributes cannot be listed.
+pub fn apple_xattrs(path: &Path) -> Result<Vec<String>, PathError> {
+    let path_cstr = c_path(path)?;
+    let error = || {
+        PathError::platform_error(format!(
+            "Failed to list extended attributes of {}",
+            path.display()
+        ))
+    };
+
+    let size = unsafe {
+        libc::listxattr(
+            path_cstr.as_ptr(),
+            std::ptr::null_mut(),
+            0,
+            libc::XATTR_NOFOLLOW,
+        )
+    };
+    let size = usize::try_from(size).map_err(|_| error())?;
+    if size == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut names = vec![0u8; size];
+    let written = unsafe {
+        libc::listxattr(
+            path_cstr.as_ptr(),
+            names.as_mut_ptr().cast(),
+            names.len(),
+            libc::XATTR_NOFOLLOW,
+        )
+    };
+    let written = usize::try_from(written).map_err(|_| error())?;
+    names.truncate(written);
+
+    Ok(names
+        .split(|&b| b == 0)
+        .filter(|name| name.starts_with(b"com.apple."))
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect())
+}
+
+/// Probe whether the volume containing a path compares names
+/// case-sensitively
+///
+/// APFS and HFS+ are case-insensitive by default but can be formatted
+/// case-sensitive, so the volume is asked via `pathconf`.
+///
+/// # Errors
+///
+/// Returns `PathError` if the path does not exist or the volume cannot be
+/// queried.
+pub fn case_sensitivity(path: &Path) -> Result<CaseSensitivity, PathError> {
+    let path_cstr = c_path(path)?;
+    match unsafe { libc::pathconf(path_cstr.as_ptr(), libc::_PC_CASE_SENSITIVE) } {
+        0 => Ok(CaseSensitivity::Insensitive),
+        result if result > 0 => Ok(CaseSensitivity::Sensitive),
+        _ => Err(PathError::platform_error(format!(
+            "Failed to probe case sensitivity of {}",
+            path.display()
+        ))),
+    }
+}
+
+/// Move a file or directory to the Trash through the Finder
+///
+/// Going through the Finder (via `osascript`) rather than renaming into
+/// `~/.Trash` records where the item came from, so "Put Back" works, and
+/// picks the right trash on external volumes.
+///
+/// # Errors
+///
+/// Returns an I/O error if the entry does not exist or the Finder refuses
+/// to delete it.
+#[cfg(feature = "trash")]
+pub fn move_to_trash(path: &Path) -> std::io::Result<()> {
+    let path = std::path::absolute(path)?;
+    std::fs::symlink_metadata(&path)?;
+    let Some(text) = path.to_str() else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Path is not valid UTF-8",
+        ));
+    };
+    let literal = text.replace('\\', "\\\\").replace('"', "\\\"");
+    let output = std::process::Command::new("osascript")
+        .args([
+            "-e",
+            &format!("tell application \"Finder\" to delete POSIX file \"{literal}\""),
+        ])
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "Finder could not trash {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Convert a path to a C string without lossy conversion
+fn c_path(path: &Path) -> Result<CString, PathError> {
+    CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| PathError::platform_error(e.to_string()).with_source(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_split_volume_path() {
+        assert_eq!(
+            split_volume_path("/Volumes/USB/photos/a.jpg"),
+            Some(("USB", "/photos/a.jpg"))
+        );
+        assert_eq!(split_volume_path("/Volumes/My Disk"), Some(("My Disk", "")));
+        assert_eq!(split_volume_path("/Volumes/"), None);
+        assert_eq!(split_volume_path("/VolumesX/a"), None);
+        assert_eq!(split_volume_path("/Users/me"), None);
+    }
+
+    #[test]
+    fn test_volume_queries() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test_file.txt");
+        File::create(&file_path).unwrap();
+
+        let info = volume_info(temp_dir.path()).unwrap();
+        assert!(!info.fs_type.is_empty());
+        assert!(info.total_space > 0);
+        assert!(
+            mounted_volumes()
+                .unwrap()
+                .iter()
+                .any(|v| v.mount_point == "/")
+        );
+
+        assert!(!is_hidden(&file_path).unwrap());
+        assert!(apple_xattrs(&file_path).is_ok());
+        assert!(case_sensitivity(temp_dir.path()).is_ok());
+    }
+}
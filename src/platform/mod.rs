@@ -6,6 +6,11 @@
 //! It abstracts away the differences between Windows and Unix-like systems,
 //! allowing for uniform access to filesystem metadata.
 
+// Android path forms are plain strings handed over by apps, so they are
+// handled on every platform
+pub mod android;
+#[cfg(target_os = "macos")]
+pub mod macos;
 #[cfg(not(target_os = "windows"))]
 pub mod unix;
 #[cfg(target_os = "windows")]
@@ -18,7 +23,8 @@ pub use unix::UnixPathExt;
 #[cfg(target_os = "windows")]
 pub use windows::WindowsPathExt;
 
-use super::PathStyle;
+use super::{CaseSensitivity, CrossPath, PathError, PathStyle, UnicodeNormalization};
+use std::time::SystemTime;
 
 /// Get current platform path style
 #[must_use]
@@ -34,6 +40,373 @@ pub fn current_style() -> PathStyle {
     }
 }
 
+/// Atomically replace `dst` with `src`
+pub(crate) fn replace_file(src: &std::path::Path, dst: &std::path::Path) -> std::io::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::replace_file(src, dst)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        unix::replace_file(src, dst)
+    }
+}
+
+/// Identity of the physical file at `path`, following links
+///
+/// The pair is (device, inode) on Unix and (volume serial number, file
+/// index) on Windows.
+pub(crate) fn file_id(path: &std::path::Path) -> std::io::Result<(u64, u64)> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::file_id(path)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        unix::file_id(path)
+    }
+}
+
+/// Move a file or directory to the platform's trash
+#[cfg(feature = "trash")]
+pub(crate) fn move_to_trash(path: &std::path::Path) -> std::io::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::move_to_trash(path)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::move_to_trash(path)
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        unix::move_to_trash(path)
+    }
+}
+
+/// Kind of link at `path`, without following it
+pub(crate) fn link_kind(path: &std::path::Path) -> Option<LinkKind> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::link_kind(path)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        unix::link_kind(path)
+    }
+}
+
+/// Probe whether the file system holding a path compares names
+/// case-sensitively
+///
+/// The nearest existing ancestor of `path` is probed. Windows volumes are
+/// reported as case-insensitive and macOS volumes are queried with
+/// `pathconf`. Elsewhere the case of an existing name is flipped and the
+/// result looked up: the volume is case-insensitive if it finds the same
+/// file.
+///
+/// # Errors
+///
+/// Returns `PathError` if no ancestor of the path exists or the volume
+/// cannot be queried.
+pub fn case_sensitivity(path: &std::path::Path) -> Result<CaseSensitivity, PathError> {
+    let existing = path
+        .ancestors()
+        .find(|ancestor| ancestor.symlink_metadata().is_ok())
+        .ok_or_else(|| {
+            PathError::platform_error(format!(
+                "Cannot probe case sensitivity of {}: no ancestor exists",
+                path.display()
+            ))
+        })?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let _ = existing;
+        Ok(CaseSensitivity::Insensitive)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::case_sensitivity(existing)
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        for ancestor in existing.ancestors() {
+            let (Some(parent), Some(name)) = (
+                ancestor.parent(),
+                ancestor.file_name().and_then(|n| n.to_str()),
+            ) else {
+                continue;
+            };
+            let flipped: String = name
+                .chars()
+                .map(|c| {
+                    if c.is_lowercase() {
+                        c.to_uppercase().next().unwrap_or(c)
+                    } else {
+                        c.to_lowercase().next().unwrap_or(c)
+                    }
+                })
+                .collect();
+            if flipped == name {
+                continue;
+            }
+
+            let original = ancestor.symlink_metadata()?;
+            return Ok(match parent.join(&flipped).symlink_metadata() {
+                Ok(other) if other.dev() == original.dev() && other.ino() == original.ino() => {
+                    CaseSensitivity::Insensitive
+                }
+                _ => CaseSensitivity::Sensitive,
+            });
+        }
+        // Nothing to flip (e.g. `/`), assume the platform default
+        Ok(CaseSensitivity::Sensitive)
+    }
+}
+
+/// Environment the process runs in, as far as paths are concerned
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RuntimeEnvironment {
+    /// Plain Windows, Linux, macOS or other Unix
+    #[default]
+    Native,
+    /// WSL 1, translating Linux system calls on the Windows kernel
+    Wsl1,
+    /// WSL 2, a Linux kernel in a lightweight virtual machine
+    Wsl2,
+    /// Cygwin, with drives under `/cygdrive`
+    Cygwin,
+    /// MSYS2 or Git Bash, with drives under `/`
+    Msys,
+    /// Docker, Podman, Kubernetes or another container
+    Container,
+}
+
+impl RuntimeEnvironment {
+    /// Classify an environment from `/proc/version`, `/proc/1/cgroup` and
+    /// environment variables
+    ///
+    /// Containers are recognized by the `container` variable (set by
+    /// Podman and systemd-nspawn) or a container runtime in the cgroup of
+    /// process 1, WSL by its kernel version string or `WSL_DISTRO_NAME`, and
+    /// MSYS2 and Cygwin by `MSYSTEM`, `CYGWIN` or their `/proc/version`.
+    #[must_use]
+    pub fn from_sources(
+        proc_version: Option<&str>,
+        cgroup: Option<&str>,
+        env: &dyn crate::expand::Environment,
+    ) -> Self {
+        const RUNTIMES: [&str; 5] = ["docker", "kubepods", "containerd", "libpod", "lxc"];
+
+        let is_set = |name: &str| env.var(name).is_some_and(|value| !value.is_empty());
+        let version = proc_version.unwrap_or_default();
+        if is_set("container")
+            || cgroup.is_some_and(|cgroup| {
+                cgroup
+                    .lines()
+                    .any(|line| RUNTIMES.iter().any(|runtime| line.contains(runtime)))
+            })
+        {
+            Self::Container
+        } else if version.to_ascii_lowercase().contains("microsoft") {
+            // WSL 1 reports `4.4.0-19041-Microsoft`, WSL 2
+            // `5.15.90.1-microsoft-standard-WSL2`
+            if version.contains("WSL2") || version.contains("microsoft-standard") {
+                Self::Wsl2
+            } else {
+                Self::Wsl1
+            }
+        } else if is_set("WSL_DISTRO_NAME") {
+            // Only WSL 2 runs custom kernels
+            Self::Wsl2
+        } else if is_set("MSYSTEM")
+            || version.starts_with("MSYS_NT")
+            || version.starts_with("MINGW")
+        {
+            Self::Msys
+        } else if is_set("CYGWIN") || version.starts_with("CYGWIN_NT") || cfg!(target_os = "cygwin")
+        {
+            Self::Cygwin
+        } else {
+            Self::Native
+        }
+    }
+
+    /// Check if this is WSL 1 or WSL 2
+    #[must_use]
+    pub const fn is_wsl(self) -> bool {
+        matches!(self, Self::Wsl1 | Self::Wsl2)
+    }
+}
+
+/// Detect the environment the process runs in
+///
+/// Reads `/proc/version`, `/proc/1/cgroup`, the container marker files
+/// `/.dockerenv` and `/run/.containerenv` and the environment; on Windows
+/// containers are recognized by the `ContainerType` registry value. Windows
+/// programs started from a Cygwin shell are only recognized if `CYGWIN` is
+/// set. The result is detected once per process.
+#[must_use]
+pub fn detect_environment() -> RuntimeEnvironment {
+    static DETECTED: std::sync::OnceLock<RuntimeEnvironment> = std::sync::OnceLock::new();
+
+    *DETECTED.get_or_init(|| {
+        #[cfg(target_os = "windows")]
+        if windows::is_container() {
+            return RuntimeEnvironment::Container;
+        }
+        if ["/.dockerenv", "/run/.containerenv"]
+            .iter()
+            .any(|marker| std::path::Path::new(marker).exists())
+        {
+            return RuntimeEnvironment::Container;
+        }
+        let proc_version = std::fs::read_to_string("/proc/version").ok();
+        let cgroup = std::fs::read_to_string("/proc/1/cgroup").ok();
+        RuntimeEnvironment::from_sources(
+            proc_version.as_deref(),
+            cgroup.as_deref(),
+            &crate::expand::SystemEnvironment,
+        )
+    })
+}
+
+/// How a file system treats file names, found by [`probe_filesystem`]
+///
+/// Apply it to a configuration with
+/// [`PathConfig::with_fs_behavior`](crate::PathConfig::with_fs_behavior).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FsBehavior {
+    /// `Sensitive` or `Insensitive`
+    pub case_sensitivity: CaseSensitivity,
+    /// Treatment of names differing only in Unicode normalization
+    pub unicode_normalization: UnicodeNormalization,
+}
+
+/// Probe how the file system holding a path treats file names
+///
+/// Unlike [`case_sensitivity`], which looks at existing names, this creates
+/// short-lived probe files in the nearest existing directory at or above
+/// `path` and looks them up under other spellings. The answer is for that
+/// directory: it picks up per-directory case sensitivity on NTFS (as
+/// enabled for WSL) and the case and normalization variants of APFS and
+/// HFS+.
+///
+/// # Errors
+///
+/// Returns `PathError` if no ancestor of the path exists or the probe files
+/// cannot be created, e.g. in a read-only directory.
+pub fn probe_filesystem(path: &std::path::Path) -> Result<FsBehavior, PathError> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Removes the probe files however the probe ends
+    struct Cleanup(Vec<std::path::PathBuf>);
+
+    impl Drop for Cleanup {
+        fn drop(&mut self) {
+            for path in &self.0 {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let dir = path
+        .ancestors()
+        .find(|ancestor| ancestor.is_dir())
+        .ok_or_else(|| {
+            PathError::platform_error(format!(
+                "Cannot probe file system of {}: no ancestor exists",
+                path.display()
+            ))
+        })?;
+    let base = format!(
+        ".cross-path-probe-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let (nfc, nfd) = ("\u{e9}", "e\u{301}");
+    let mut cleanup = Cleanup(Vec::new());
+    let mut create = |name: String| -> Result<(), PathError> {
+        let path = dir.join(name);
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        cleanup.0.push(path);
+        Ok(())
+    };
+    // Spelling under which a probe file was stored
+    let stored = |prefix: &str| -> Result<Option<String>, PathError> {
+        for entry in std::fs::read_dir(dir)? {
+            let name = entry?.file_name();
+            if let Some(name) = name.to_str().filter(|name| name.starts_with(prefix)) {
+                return Ok(Some(name.to_string()));
+            }
+        }
+        Ok(None)
+    };
+
+    create(format!("{base}-{nfc}"))?;
+    let case_sensitivity = if dir
+        .join(format!("{}-{nfc}", base.to_ascii_uppercase()))
+        .exists()
+    {
+        CaseSensitivity::Insensitive
+    } else {
+        CaseSensitivity::Sensitive
+    };
+
+    let unicode_normalization =
+        if stored(&format!("{base}-"))?.is_some_and(|name| name.ends_with(nfd)) {
+            UnicodeNormalization::Nfd
+        } else if !dir.join(format!("{base}-{nfd}")).exists() {
+            UnicodeNormalization::Exact
+        } else {
+            // Lookups fold the spellings; see whether names are stored as given
+            create(format!("{base}-d-{nfd}"))?;
+            if stored(&format!("{base}-d-"))?.is_some_and(|name| name.ends_with(nfc)) {
+                UnicodeNormalization::Nfc
+            } else {
+                UnicodeNormalization::Insensitive
+            }
+        };
+    drop(cleanup);
+
+    Ok(FsBehavior {
+        case_sensitivity,
+        unicode_normalization,
+    })
+}
+
+/// Raw bytes of an OS string
+///
+/// On Unix these are the bytes stored on disk; on Windows the UTF-16 name is
+/// re-encoded as UTF-8.
+#[must_use]
+pub fn os_str_bytes(s: &std::ffi::OsStr) -> Vec<u8> {
+    #[cfg(not(target_os = "windows"))]
+    {
+        std::os::unix::ffi::OsStrExt::as_bytes(s).to_vec()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        s.to_string_lossy().into_owned().into_bytes()
+    }
+}
+
 /// Platform-specific path operations
 pub trait PlatformPath {
     /// Get platform-specific path separator
@@ -54,8 +427,106 @@ pub trait PathExt: PlatformPath {
     /// Check if path exists and is accessible
     fn is_accessible(&self) -> bool;
 
+    /// Check if the current user may access the path in the given ways
+    ///
+    /// The answer reflects permissions and ACLs at the time of the call; the
+    /// operation itself can still fail if they change in between.
+    fn access(&self, mode: AccessMode) -> bool;
+
     /// Get disk information for path
     fn get_disk_info(&self) -> Option<DiskInfo>;
+
+    /// List the names of the extended attributes of the path
+    ///
+    /// These are extended attributes on Unix (e.g., `user.origin`,
+    /// `com.apple.quarantine`) and alternate data streams on Windows (e.g.,
+    /// `Zone.Identifier`); the unnamed main stream is not listed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path does not exist or the filesystem
+    /// does not support extended attributes.
+    fn xattrs(&self) -> Result<Vec<String>, PathError>;
+
+    /// Read an extended attribute or alternate data stream
+    ///
+    /// Returns `None` if the path has no attribute with that name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path does not exist or the attribute
+    /// cannot be read.
+    fn get_xattr(&self, name: &str) -> Result<Option<Vec<u8>>, PathError>;
+
+    /// Create or replace an extended attribute or alternate data stream
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path does not exist or the attribute
+    /// cannot be written.
+    fn set_xattr(&self, name: &str, value: &[u8]) -> Result<(), PathError>;
+
+    /// Remove an extended attribute or alternate data stream
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError` if the path or the attribute does not exist, or
+    /// the attribute cannot be removed.
+    fn remove_xattr(&self, name: &str) -> Result<(), PathError>;
+}
+
+/// Kinds of access checked by [`PathExt::access`]
+///
+/// Modes combine with `|`, e.g. `AccessMode::READ | AccessMode::WRITE`.
+/// [`AccessMode::EXISTS`] checks only that the path exists. For
+/// directories, writing means creating entries and executing means
+/// traversing.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub struct AccessMode {
+    /// Read the file or list the directory
+    pub read: bool,
+    /// Modify the file or create entries in the directory
+    pub write: bool,
+    /// Execute the file or traverse the directory
+    pub execute: bool,
+}
+
+impl AccessMode {
+    /// Only check that the path exists
+    pub const EXISTS: Self = Self {
+        read: false,
+        write: false,
+        execute: false,
+    };
+    /// Read access
+    pub const READ: Self = Self {
+        read: true,
+        ..Self::EXISTS
+    };
+    /// Write access
+    pub const WRITE: Self = Self {
+        write: true,
+        ..Self::EXISTS
+    };
+    /// Execute (or traverse) access
+    pub const EXECUTE: Self = Self {
+        execute: true,
+        ..Self::EXISTS
+    };
+}
+
+impl core::ops::BitOr for AccessMode {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self {
+            read: self.read || other.read,
+            write: self.write || other.write,
+            execute: self.execute || other.execute,
+        }
+    }
 }
 
 /// File attributes structure
@@ -69,10 +540,229 @@ pub struct FileAttributes {
     pub is_hidden: bool,
     /// Whether the file is read-only
     pub is_readonly: bool,
-    /// Creation timestamp (if available)
-    pub creation_time: Option<u64>,
-    /// Last modification timestamp (if available)
-    pub modification_time: Option<u64>,
+    /// Creation, modification, access and change times
+    pub times: FileTimes,
+    /// Kind of link the entry is, if any
+    pub link_kind: Option<LinkKind>,
+    /// Type of the entry
+    pub file_type: FileType,
+    /// Unix permission bits, including setuid, setgid and sticky
+    pub mode: Option<u32>,
+    /// Owning user ID (Unix only)
+    pub uid: Option<u32>,
+    /// Owning group ID (Unix only)
+    pub gid: Option<u32>,
+    /// Inode number on Unix, file index on Windows
+    pub inode: Option<u64>,
+    /// Number of hard links to the file
+    pub nlink: Option<u64>,
+}
+
+impl FileAttributes {
+    /// Resolve the owning user ID to a user name
+    ///
+    /// Returns `None` when there is no owner ID or no account for it.
+    #[must_use]
+    pub fn owner_name(&self) -> Option<String> {
+        #[cfg(not(target_os = "windows"))]
+        {
+            self.uid.and_then(unix::user_name)
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            None
+        }
+    }
+
+    /// Resolve the owning group ID to a group name
+    ///
+    /// Returns `None` when there is no group ID or no group for it.
+    #[must_use]
+    pub fn group_name(&self) -> Option<String> {
+        #[cfg(not(target_os = "windows"))]
+        {
+            self.gid.and_then(unix::group_name)
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            None
+        }
+    }
+}
+
+/// Timestamps of a filesystem entry, with the filesystem's full precision
+///
+/// Each time is `None` where the platform or filesystem does not record it.
+/// Passed to [`ops::set_times`](crate::ops::set_times), `None` leaves that
+/// time unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FileTimes {
+    /// Creation (birth) time
+    pub created: Option<SystemTime>,
+    /// Last modification of the contents
+    pub modified: Option<SystemTime>,
+    /// Last access
+    pub accessed: Option<SystemTime>,
+    /// Last change of the contents or metadata (`ctime`); cannot be set
+    pub changed: Option<SystemTime>,
+}
+
+impl FileTimes {
+    /// Read the times recorded in file metadata
+    ///
+    /// The change time is included on Unix; on Windows `std` does not
+    /// expose it, so it is `None`.
+    #[must_use]
+    pub fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        #[cfg(not(target_os = "windows"))]
+        let changed = {
+            use std::os::unix::fs::MetadataExt;
+
+            unix_time(metadata.ctime(), metadata.ctime_nsec())
+        };
+        #[cfg(target_os = "windows")]
+        let changed = None;
+
+        Self {
+            created: metadata.created().ok(),
+            modified: metadata.modified().ok(),
+            accessed: metadata.accessed().ok(),
+            changed,
+        }
+    }
+
+    /// Set the creation time
+    #[must_use]
+    pub fn with_created(mut self, time: SystemTime) -> Self {
+        self.created = Some(time);
+        self
+    }
+
+    /// Set the modification time
+    #[must_use]
+    pub fn with_modified(mut self, time: SystemTime) -> Self {
+        self.modified = Some(time);
+        self
+    }
+
+    /// Set the access time
+    #[must_use]
+    pub fn with_accessed(mut self, time: SystemTime) -> Self {
+        self.accessed = Some(time);
+        self
+    }
+}
+
+/// Time from seconds and nanoseconds relative to the Unix epoch
+#[cfg(not(target_os = "windows"))]
+fn unix_time(secs: i64, nanos: i64) -> Option<SystemTime> {
+    use std::time::Duration;
+
+    let nanos = u32::try_from(nanos).ok()?;
+    match u64::try_from(secs) {
+        Ok(secs) => SystemTime::UNIX_EPOCH.checked_add(Duration::new(secs, nanos)),
+        Err(_) => SystemTime::UNIX_EPOCH
+            .checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+            .checked_add(Duration::from_nanos(u64::from(nanos))),
+    }
+}
+
+/// Set the times of a file or directory, following links
+pub(crate) fn set_times(path: &std::path::Path, times: &FileTimes) -> std::io::Result<()> {
+    #[cfg(target_os = "windows")]
+    {
+        windows::set_times(path, times)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        unix::set_times(path, times)
+    }
+}
+
+/// Type of a filesystem entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum FileType {
+    /// Regular file
+    Regular,
+    /// Directory
+    Dir,
+    /// Symbolic link
+    Symlink,
+    /// Unix domain socket
+    Socket,
+    /// Named pipe
+    Fifo,
+    /// Block device
+    BlockDev,
+    /// Character device
+    CharDev,
+}
+
+impl From<std::fs::FileType> for FileType {
+    fn from(file_type: std::fs::FileType) -> Self {
+        #[cfg(not(target_os = "windows"))]
+        {
+            use std::os::unix::fs::FileTypeExt;
+
+            if file_type.is_socket() {
+                return Self::Socket;
+            } else if file_type.is_fifo() {
+                return Self::Fifo;
+            } else if file_type.is_block_device() {
+                return Self::BlockDev;
+            } else if file_type.is_char_device() {
+                return Self::CharDev;
+            }
+        }
+
+        if file_type.is_symlink() {
+            Self::Symlink
+        } else if file_type.is_dir() {
+            Self::Dir
+        } else {
+            Self::Regular
+        }
+    }
+}
+
+/// Kind of filesystem link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum LinkKind {
+    /// Symbolic link, to a file or a directory
+    Symlink,
+    /// NTFS directory junction (Windows only)
+    Junction,
+    /// File with more than one name (link count above 1)
+    HardLink,
+}
+
+/// Kind of a drive or mounted volume
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum DriveType {
+    /// Fixed disk
+    Fixed,
+    /// Removable media such as a USB stick
+    Removable,
+    /// Network drive
+    Network,
+    /// CD or DVD drive
+    CdRom,
+    /// RAM disk
+    RamDisk,
+    /// Drive whose type could not be determined
+    Unknown,
+}
+
+/// Logical drive present on a Windows system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LogicalDrive {
+    /// Drive letter (upper case)
+    pub letter: char,
+    /// Kind of drive
+    pub drive_type: DriveType,
 }
 
 /// Disk information structure
@@ -82,6 +772,21 @@ pub struct DiskInfo {
     pub total_space: u64,
     /// Free disk space in bytes
     pub free_space: u64,
+    /// Used disk space in bytes, including space reserved for the
+    /// administrator (so used and free may not add up to the total)
+    pub used_space: u64,
     /// Filesystem type name (e.g., "NTFS", "ext4")
     pub filesystem_type: String,
+    /// Directory or drive root the filesystem is mounted on (e.g., `/`,
+    /// `/mnt/c`, `C:\`)
+    pub mount_point: Option<CrossPath>,
+    /// Mounted device or volume (e.g., `/dev/sda1`, `C:\`,
+    /// `\\?\Volume{...}\`)
+    pub device: Option<String>,
+    /// Volume label (e.g., "Windows", "USB STICK")
+    pub volume_name: Option<String>,
+    /// Whether the volume is mounted read-only
+    pub is_readonly: bool,
+    /// Removable, network, optical or fixed storage
+    pub drive_type: DriveType,
 }
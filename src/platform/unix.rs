@@ -5,9 +5,13 @@
 //!
 //! It uses POSIX standard APIs (via `libc`) to interact with the underlying system.
 
-use crate::PathError;
-use crate::platform::{DiskInfo, FileAttributes, PathExt, PlatformPath};
+use crate::platform::{
+    AccessMode, DiskInfo, DriveType, FileAttributes, FileTimes, LinkKind, PathExt, PlatformPath,
+};
+use crate::{CrossPath, PathError};
 use std::fs;
+#[cfg(all(feature = "trash", not(target_os = "macos")))]
+use std::os::unix::{ffi::OsStrExt, fs::DirBuilderExt, fs::OpenOptionsExt};
 use std::path::{Path, PathBuf};
 
 /// Unix platform path extension
@@ -40,6 +44,8 @@ impl PlatformPath for UnixPathExt {
 
 impl PathExt for UnixPathExt {
     fn get_attributes(&self) -> Option<FileAttributes> {
+        use std::os::unix::fs::MetadataExt;
+
         let metadata = fs::metadata(&self.path).ok()?;
 
         let size = metadata.len();
@@ -52,26 +58,23 @@ impl PathExt for UnixPathExt {
             .file_name()
             .and_then(|n| n.to_str())
             .is_some_and(|s| s.starts_with('.'));
-
-        let creation_time = metadata
-            .created()
-            .ok()
-            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d| d.as_secs());
-
-        let modification_time = metadata
-            .modified()
-            .ok()
-            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d| d.as_secs());
+        // Finder also hides files flagged with `chflags hidden`
+        #[cfg(target_os = "macos")]
+        let is_hidden = is_hidden || super::macos::is_hidden(&self.path).unwrap_or(false);
 
         Some(FileAttributes {
             size,
             is_directory,
             is_hidden,
             is_readonly,
-            creation_time,
-            modification_time,
+            times: FileTimes::from_metadata(&metadata),
+            link_kind: link_kind(&self.path),
+            file_type: metadata.file_type().into(),
+            mode: Some(metadata.mode() & 0o7777),
+            uid: Some(metadata.uid()),
+            gid: Some(metadata.gid()),
+            inode: Some(metadata.ino()),
+            nlink: Some(metadata.nlink()),
         })
     }
 
@@ -79,14 +82,554 @@ impl PathExt for UnixPathExt {
         self.path.exists()
     }
 
+    fn access(&self, mode: AccessMode) -> bool {
+        use std::os::unix::ffi::OsStrExt;
+
+        let Ok(c_path) = std::ffi::CString::new(self.path.as_os_str().as_bytes()) else {
+            return false;
+        };
+        let mut flags = 0;
+        if mode.read {
+            flags |= libc::R_OK;
+        }
+        if mode.write {
+            flags |= libc::W_OK;
+        }
+        if mode.execute {
+            flags |= libc::X_OK;
+        }
+        if flags == 0 {
+            flags = libc::F_OK;
+        }
+        // SAFETY: the path is NUL-terminated; AT_EACCESS checks the
+        // effective user and group, like the operation itself would
+        unsafe { libc::faccessat(libc::AT_FDCWD, c_path.as_ptr(), flags, libc::AT_EACCESS) == 0 }
+    }
+
+    fn xattrs(&self) -> Result<Vec<String>, PathError> {
+        xattr::list(&self.path)
+    }
+
+    fn get_xattr(&self, name: &str) -> Result<Option<Vec<u8>>, PathError> {
+        xattr::get(&self.path, name)
+    }
+
+    fn set_xattr(&self, name: &str, value: &[u8]) -> Result<(), PathError> {
+        xattr::set(&self.path, name, value)
+    }
+
+    fn remove_xattr(&self, name: &str) -> Result<(), PathError> {
+        xattr::remove(&self.path, name)
+    }
+
     fn get_disk_info(&self) -> Option<DiskInfo> {
         let stats = get_filesystem_stats(&self.path).ok()?;
+        let mount = find_mount(&self.path);
+        #[allow(clippy::unnecessary_cast)]
+        let is_readonly = stats.mount_flags & (libc::ST_RDONLY as u64) != 0;
 
         Some(DiskInfo {
             total_space: stats.total_blocks.saturating_mul(stats.block_size),
             free_space: stats.available_blocks.saturating_mul(stats.block_size),
-            filesystem_type: "Unix".to_string(),
+            used_space: stats
+                .total_blocks
+                .saturating_sub(stats.free_blocks)
+                .saturating_mul(stats.block_size),
+            filesystem_type: mount
+                .as_ref()
+                .map_or_else(|| "unknown".to_string(), |m| m.fs_type.clone()),
+            mount_point: mount
+                .as_ref()
+                .and_then(|m| CrossPath::new(&m.mount_point).ok()),
+            volume_name: mount.as_ref().and_then(volume_label),
+            is_readonly,
+            drive_type: mount.as_ref().map_or(DriveType::Unknown, drive_type),
+            device: mount.map(|m| m.device),
+        })
+    }
+}
+
+/// Extended attribute system calls, which differ between Linux and macOS
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+mod xattr {
+    use crate::PathError;
+    use std::ffi::CString;
+    use std::io;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    #[cfg(target_os = "macos")]
+    const NO_ATTRIBUTE: i32 = libc::ENOATTR;
+    #[cfg(not(target_os = "macos"))]
+    const NO_ATTRIBUTE: i32 = libc::ENODATA;
+
+    pub(super) fn list(path: &Path) -> Result<Vec<String>, PathError> {
+        let path_cstr = c_string(path.as_os_str().as_bytes())?;
+        let names = read_sized(|buffer, size| {
+            // SAFETY: the path is NUL-terminated and `buffer` holds `size` bytes
+            unsafe {
+                #[cfg(target_os = "macos")]
+                {
+                    libc::listxattr(path_cstr.as_ptr(), buffer, size, 0)
+                }
+                #[cfg(not(target_os = "macos"))]
+                {
+                    libc::listxattr(path_cstr.as_ptr(), buffer, size)
+                }
+            }
         })
+        .map_err(|e| error(path, "list", e))?;
+        Ok(names
+            .split(|&b| b == 0)
+            .filter(|name| !name.is_empty())
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .collect())
+    }
+
+    pub(super) fn get(path: &Path, name: &str) -> Result<Option<Vec<u8>>, PathError> {
+        let path_cstr = c_string(path.as_os_str().as_bytes())?;
+        let name_cstr = c_string(name.as_bytes())?;
+        let value = read_sized(|buffer, size| {
+            // SAFETY: both strings are NUL-terminated and `buffer` holds
+            // `size` bytes
+            unsafe {
+                #[cfg(target_os = "macos")]
+                {
+                    libc::getxattr(
+                        path_cstr.as_ptr(),
+                        name_cstr.as_ptr(),
+                        buffer.cast(),
+                        size,
+                        0,
+                        0,
+                    )
+                }
+                #[cfg(not(target_os = "macos"))]
+                {
+                    libc::getxattr(path_cstr.as_ptr(), name_cstr.as_ptr(), buffer.cast(), size)
+                }
+            }
+        });
+        match value {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.raw_os_error() == Some(NO_ATTRIBUTE) => Ok(None),
+            Err(e) => Err(error(path, "read", e)),
+        }
+    }
+
+    pub(super) fn set(path: &Path, name: &str, value: &[u8]) -> Result<(), PathError> {
+        let path_cstr = c_string(path.as_os_str().as_bytes())?;
+        let name_cstr = c_string(name.as_bytes())?;
+        let (data, size) = (value.as_ptr().cast(), value.len());
+        // SAFETY: both strings are NUL-terminated and `data` holds `size` bytes
+        let result = unsafe {
+            #[cfg(target_os = "macos")]
+            {
+                libc::setxattr(path_cstr.as_ptr(), name_cstr.as_ptr(), data, size, 0, 0)
+            }
+            #[cfg(not(target_os = "macos"))]
+            {
+                libc::setxattr(path_cstr.as_ptr(), name_cstr.as_ptr(), data, size, 0)
+            }
+        };
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(error(path, "write", io::Error::last_os_error()))
+        }
+    }
+
+    pub(super) fn remove(path: &Path, name: &str) -> Result<(), PathError> {
+        let path_cstr = c_string(path.as_os_str().as_bytes())?;
+        let name_cstr = c_string(name.as_bytes())?;
+        // SAFETY: both strings are NUL-terminated
+        let result = unsafe {
+            #[cfg(target_os = "macos")]
+            {
+                libc::removexattr(path_cstr.as_ptr(), name_cstr.as_ptr(), 0)
+            }
+            #[cfg(not(target_os = "macos"))]
+            {
+                libc::removexattr(path_cstr.as_ptr(), name_cstr.as_ptr())
+            }
+        };
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(error(path, "remove", io::Error::last_os_error()))
+        }
+    }
+
+    /// Query the size of a value, then read it, retrying if it grew in
+    /// between
+    fn read_sized(call: impl Fn(*mut libc::c_char, usize) -> libc::ssize_t) -> io::Result<Vec<u8>> {
+        loop {
+            let size = usize::try_from(call(std::ptr::null_mut(), 0))
+                .map_err(|_| io::Error::last_os_error())?;
+            let mut buffer = vec![0u8; size];
+            if let Ok(written) = usize::try_from(call(buffer.as_mut_ptr().cast(), buffer.len())) {
+                buffer.truncate(written);
+                return Ok(buffer);
+            }
+            let e = io::Error::last_os_error();
+            if e.raw_os_error() != Some(libc::ERANGE) {
+                return Err(e);
+            }
+        }
+    }
+
+    fn error(path: &Path, action: &str, e: io::Error) -> PathError {
+        PathError::io_error(format!(
+            "Failed to {action} extended attributes of {}: {e}",
+            path.display()
+        ))
+        .with_path(path.to_string_lossy())
+        .with_source(e)
+    }
+
+    fn c_string(bytes: &[u8]) -> Result<CString, PathError> {
+        CString::new(bytes).map_err(|e| PathError::invalid_path(e.to_string()).with_source(e))
+    }
+}
+
+/// Extended attributes are not supported on this platform
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+mod xattr {
+    use crate::PathError;
+    use std::path::Path;
+
+    fn unsupported<T>(_: &Path) -> Result<T, PathError> {
+        Err(PathError::platform_error(
+            "Extended attributes are not supported on this platform",
+        ))
+    }
+
+    pub(super) fn list(path: &Path) -> Result<Vec<String>, PathError> {
+        unsupported(path)
+    }
+
+    pub(super) fn get(path: &Path, _: &str) -> Result<Option<Vec<u8>>, PathError> {
+        unsupported(path)
+    }
+
+    pub(super) fn set(path: &Path, _: &str, _: &[u8]) -> Result<(), PathError> {
+        unsupported(path)
+    }
+
+    pub(super) fn remove(path: &Path, _: &str) -> Result<(), PathError> {
+        unsupported(path)
+    }
+}
+
+/// Kind of link at `path`, without following it
+///
+/// Files with a link count above one are hard links; directories always
+/// have several links on Unix and are never reported as such.
+#[must_use]
+pub fn link_kind(path: &Path) -> Option<LinkKind> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = fs::symlink_metadata(path).ok()?;
+    if metadata.is_symlink() {
+        Some(LinkKind::Symlink)
+    } else if metadata.is_file() && metadata.nlink() > 1 {
+        Some(LinkKind::HardLink)
+    } else {
+        None
+    }
+}
+
+/// Device and inode numbers of the file at `path`, following links
+///
+/// # Errors
+///
+/// Returns an I/O error if the file cannot be inspected.
+pub fn file_id(path: &Path) -> std::io::Result<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = fs::metadata(path)?;
+    Ok((metadata.dev(), metadata.ino()))
+}
+
+/// Atomically replace `dst` with `src`
+///
+/// Renames `src` over `dst` and syncs the parent directory, so the rename
+/// itself survives a crash.
+///
+/// # Errors
+///
+/// Returns an I/O error if the file cannot be renamed.
+pub fn replace_file(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs::rename(src, dst)?;
+    let dir = match dst.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    // Some filesystems cannot sync directories; the rename is done anyway
+    if let Ok(dir) = fs::File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// Move a file or directory to the trash following the XDG Trash
+/// specification
+///
+/// Entries on the home volume go to `$XDG_DATA_HOME/Trash`; entries on other
+/// volumes go to `$topdir/.Trash/$uid` (when the administrator created a
+/// sticky `.Trash`) or `$topdir/.Trash-$uid`. A `.trashinfo` record next to
+/// the entry lets file managers restore it.
+///
+/// # Errors
+///
+/// Returns an I/O error if the entry does not exist, no trash directory can
+/// be used for its volume, or the entry cannot be moved.
+#[cfg(all(feature = "trash", not(target_os = "macos")))]
+pub fn move_to_trash(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::MetadataExt;
+
+    let path = std::path::absolute(path)?;
+    let device = fs::symlink_metadata(&path)?.dev();
+    let (Some(name), Some(parent)) = (path.file_name(), path.parent()) else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Cannot trash a root directory",
+        ));
+    };
+
+    let home_trash = xdg_data_home()?.join("Trash");
+    let on_home = home_trash
+        .ancestors()
+        .find_map(|dir| fs::metadata(dir).ok())
+        .is_some_and(|m| m.dev() == device);
+    let (trash, info_path) = if on_home {
+        (home_trash, path.clone())
+    } else {
+        let top = mount_root(parent, device);
+        let relative = path.strip_prefix(&top).unwrap_or(&path).to_path_buf();
+        (volume_trash(&top)?, relative)
+    };
+
+    let (files, info) = (trash.join("files"), trash.join("info"));
+    for dir in [&files, &info] {
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)?;
+    }
+
+    let record = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        crate::url::percent_encode_bytes(info_path.as_os_str().as_bytes()),
+        local_timestamp()
+    );
+    // Reserve a unique name by creating its info file first
+    for n in 1u32.. {
+        let mut entry = name.to_os_string();
+        if n > 1 {
+            entry.push(format!(".{n}"));
+        }
+        let mut info_name = entry.clone();
+        info_name.push(".trashinfo");
+        let info_file = info.join(info_name);
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&info_file)
+        {
+            Ok(mut file) => {
+                use std::io::Write;
+                let result = file
+                    .write_all(record.as_bytes())
+                    .and_then(|()| fs::rename(&path, files.join(&entry)));
+                if result.is_err() {
+                    let _ = fs::remove_file(&info_file);
+                }
+                return result;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!("trash names are exhausted")
+}
+
+/// `$XDG_DATA_HOME`, defaulting to `~/.local/share`
+#[cfg(all(feature = "trash", not(target_os = "macos")))]
+fn xdg_data_home() -> std::io::Result<PathBuf> {
+    let absolute = |var: &str| {
+        std::env::var_os(var)
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+    };
+    absolute("XDG_DATA_HOME")
+        .or_else(|| absolute("HOME").map(|home| home.join(".local/share")))
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "No home directory"))
+}
+
+/// Topmost directory above `dir` on the given device
+#[cfg(all(feature = "trash", not(target_os = "macos")))]
+fn mount_root(dir: &Path, device: u64) -> PathBuf {
+    use std::os::unix::fs::MetadataExt;
+
+    dir.ancestors()
+        .take_while(|d| fs::metadata(d).is_ok_and(|m| m.dev() == device))
+        .last()
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// Trash directory of the volume mounted at `top`
+#[cfg(all(feature = "trash", not(target_os = "macos")))]
+fn volume_trash(top: &Path) -> std::io::Result<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    // SAFETY: getuid cannot fail
+    let uid = unsafe { libc::getuid() };
+    let shared = top.join(".Trash");
+    if let Ok(metadata) = fs::symlink_metadata(&shared)
+        && metadata.is_dir()
+        && metadata.permissions().mode() & 0o1000 != 0
+    {
+        let own = shared.join(uid.to_string());
+        if fs::DirBuilder::new().mode(0o700).create(&own).is_ok() || own.is_dir() {
+            return Ok(own);
+        }
+    }
+    let own = top.join(format!(".Trash-{uid}"));
+    match fs::DirBuilder::new().mode(0o700).create(&own) {
+        Ok(()) => Ok(own),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists && own.is_dir() => Ok(own),
+        Err(e) => Err(e),
+    }
+}
+
+/// Current local time as `YYYY-MM-DDThh:mm:ss`
+#[cfg(all(feature = "trash", not(target_os = "macos")))]
+fn local_timestamp() -> String {
+    // SAFETY: a null argument asks time for the current time only
+    let now = unsafe { libc::time(std::ptr::null_mut()) };
+    // SAFETY: libc::tm is plain data and localtime_r fills it in
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    unsafe { libc::localtime_r(&raw const now, &raw mut tm) };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        tm.tm_year + 1900,
+        tm.tm_mon + 1,
+        tm.tm_mday,
+        tm.tm_hour,
+        tm.tm_min,
+        tm.tm_sec
+    )
+}
+
+/// Set the access and modification times of `path` with `utimensat`
+///
+/// Times that are `None` are left unchanged; the creation and change times
+/// cannot be set on Unix and are ignored.
+///
+/// # Errors
+///
+/// Returns an I/O error if the path contains a NUL byte or the times cannot
+/// be set.
+pub fn set_times(path: &Path, times: &FileTimes) -> std::io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let spec = |time: Option<std::time::SystemTime>| {
+        let mut spec: libc::timespec = unsafe { std::mem::zeroed() };
+        match time.map(|t| t.duration_since(std::time::UNIX_EPOCH)) {
+            None => spec.tv_nsec = libc::UTIME_OMIT,
+            Some(Ok(after)) => {
+                spec.tv_sec = libc::time_t::try_from(after.as_secs()).unwrap_or(libc::time_t::MAX);
+                spec.tv_nsec = after.subsec_nanos().into();
+            }
+            Some(Err(before)) => {
+                // Nanoseconds count forward even before the epoch
+                let before = before.duration();
+                let mut secs =
+                    libc::time_t::try_from(before.as_secs()).unwrap_or(libc::time_t::MAX);
+                let mut nanos = before.subsec_nanos();
+                if nanos > 0 {
+                    secs += 1;
+                    nanos = 1_000_000_000 - nanos;
+                }
+                spec.tv_sec = -secs;
+                spec.tv_nsec = nanos.into();
+            }
+        }
+        spec
+    };
+    let specs = [spec(times.accessed), spec(times.modified)];
+    // SAFETY: the path is NUL-terminated and `specs` holds two timespecs
+    if unsafe { libc::utimensat(libc::AT_FDCWD, c_path.as_ptr(), specs.as_ptr(), 0) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+/// Name of the user with the given ID, from the password database
+#[must_use]
+pub fn user_name(uid: u32) -> Option<String> {
+    let mut buffer = vec![0 as libc::c_char; 1024];
+    loop {
+        // SAFETY: libc::passwd is plain data that getpwuid_r fills in
+        let mut entry: libc::passwd = unsafe { std::mem::zeroed() };
+        let mut result = std::ptr::null_mut();
+        // SAFETY: the buffer outlives the call and its length is passed
+        let status = unsafe {
+            libc::getpwuid_r(
+                uid,
+                &raw mut entry,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &raw mut result,
+            )
+        };
+        if status == libc::ERANGE && buffer.len() < 1 << 20 {
+            buffer.resize(buffer.len() * 2, 0);
+            continue;
+        }
+        if status != 0 || result.is_null() {
+            return None;
+        }
+        // SAFETY: on success pw_name points to a NUL-terminated string in the buffer
+        let name = unsafe { std::ffi::CStr::from_ptr(entry.pw_name) };
+        return Some(name.to_string_lossy().into_owned());
+    }
+}
+
+/// Name of the group with the given ID, from the group database
+#[must_use]
+pub fn group_name(gid: u32) -> Option<String> {
+    let mut buffer = vec![0 as libc::c_char; 1024];
+    loop {
+        // SAFETY: libc::group is plain data that getgrgid_r fills in
+        let mut entry: libc::group = unsafe { std::mem::zeroed() };
+        let mut result = std::ptr::null_mut();
+        // SAFETY: the buffer outlives the call and its length is passed
+        let status = unsafe {
+            libc::getgrgid_r(
+                gid,
+                &raw mut entry,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &raw mut result,
+            )
+        };
+        if status == libc::ERANGE && buffer.len() < 1 << 20 {
+            buffer.resize(buffer.len() * 2, 0);
+            continue;
+        }
+        if status != 0 || result.is_null() {
+            return None;
+        }
+        // SAFETY: on success gr_name points to a NUL-terminated string in the buffer
+        let name = unsafe { std::ffi::CStr::from_ptr(entry.gr_name) };
+        return Some(name.to_string_lossy().into_owned());
     }
 }
 
@@ -180,7 +723,7 @@ pub fn is_standard_unix_directory(path: &str) -> bool {
 /// Returns `PathError` if the filesystem statistics cannot be retrieved.
 pub fn get_filesystem_stats(path: &Path) -> Result<FilesystemStats, PathError> {
     let path_cstr = std::ffi::CString::new(path.to_string_lossy().as_ref())
-        .map_err(|e| PathError::platform_error(e.to_string()))?;
+        .map_err(|e| PathError::platform_error(e.to_string()).with_source(e))?;
 
     let mut statfs: libc::statvfs = unsafe { std::mem::zeroed() };
 
@@ -287,6 +830,278 @@ pub struct FilesystemStats {
     pub max_filename_length: u64,
 }
 
+/// Mount table entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountEntry {
+    /// Mounted device or source (e.g., `/dev/sda1`, `C:\`, `//server/share`)
+    pub device: String,
+    /// Directory the filesystem is mounted on
+    pub mount_point: String,
+    /// Filesystem type (e.g., "ext4", "drvfs", "cifs")
+    pub fs_type: String,
+    /// Mount options
+    pub options: Vec<String>,
+}
+
+/// Parse a mount table in `/proc/mounts` format
+///
+/// Octal escapes used for spaces and backslashes (e.g., `\040`) are decoded.
+#[must_use]
+pub fn parse_mount_table(content: &str) -> Vec<MountEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = unescape_mount_field(fields.next()?);
+            let mount_point = unescape_mount_field(fields.next()?);
+            let fs_type = fields.next()?.to_string();
+            let options = fields
+                .next()
+                .map(|o| o.split(',').map(String::from).collect())
+                .unwrap_or_default();
+            Some(MountEntry {
+                device,
+                mount_point,
+                fs_type,
+                options,
+            })
+        })
+        .collect()
+}
+
+/// Parse a mount table in `/proc/self/mountinfo` format
+///
+/// Unlike `/proc/mounts`, mountinfo lists the mount options separately from
+/// the filesystem type and source, after a `-` separator that follows a
+/// variable number of optional fields.
+#[must_use]
+pub fn parse_mountinfo(content: &str) -> Vec<MountEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (mount, filesystem) = line.split_once(" - ")?;
+            let mut mount = mount.split_whitespace().skip(4);
+            let mount_point = unescape_mount_field(mount.next()?);
+            let options = mount.next()?.split(',').map(String::from).collect();
+            let mut filesystem = filesystem.split_whitespace();
+            let fs_type = filesystem.next()?.to_string();
+            let device = unescape_mount_field(filesystem.next()?);
+            Some(MountEntry {
+                device,
+                mount_point,
+                fs_type,
+                options,
+            })
+        })
+        .collect()
+}
+
+/// Find the mount holding a path
+///
+/// The path is resolved and matched against the mount points of
+/// `/proc/self/mountinfo` (falling back to the mount table); the deepest
+/// mount point wins, and of equal ones the last mounted, which hides the
+/// others. On macOS the volume is asked via `statfs`.
+#[must_use]
+pub fn find_mount(path: &Path) -> Option<MountEntry> {
+    #[cfg(target_os = "macos")]
+    {
+        let volume = super::macos::volume_info(path).ok()?;
+        Some(MountEntry {
+            device: volume.device,
+            mount_point: volume.mount_point,
+            fs_type: volume.fs_type,
+            options: Vec::new(),
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let resolved = fs::canonicalize(path)
+            .or_else(|_| std::path::absolute(path))
+            .ok()?;
+        let entries = match fs::read_to_string("/proc/self/mountinfo") {
+            Ok(content) => parse_mountinfo(&content),
+            Err(_) => read_mount_table().ok()?,
+        };
+        mount_containing(entries, &resolved)
+    }
+}
+
+/// Classify a mount by its filesystem type and device
+///
+/// Network filesystems, RAM-backed filesystems and optical media are
+/// recognized by type; block devices are removable when the kernel says so
+/// (`/sys/class/block/<dev>/removable` on Linux).
+#[must_use]
+pub fn drive_type(entry: &MountEntry) -> DriveType {
+    let fs_type = entry.fs_type.as_str();
+    let fs_type = fs_type.strip_prefix("fuse.").unwrap_or(fs_type);
+    match fs_type {
+        "drvfs" => DriveType::Fixed,
+        "9p" if entry.options.iter().any(|o| o.starts_with("aname=drvfs")) => DriveType::Fixed,
+        "nfs" | "nfs4" | "cifs" | "smb3" | "smbfs" | "afpfs" | "webdav" | "davfs" | "sshfs"
+        | "9p" | "ceph" | "glusterfs" | "lustre" => DriveType::Network,
+        "tmpfs" | "ramfs" => DriveType::RamDisk,
+        "iso9660" | "udf" | "cd9660" => DriveType::CdRom,
+        _ if entry.device.starts_with("/dev/") => {
+            if is_removable_device(&entry.device) {
+                DriveType::Removable
+            } else {
+                DriveType::Fixed
+            }
+        }
+        _ => DriveType::Unknown,
+    }
+}
+
+/// Check the kernel's removable flag for a block device or its parent disk
+fn is_removable_device(device: &str) -> bool {
+    let Some(name) = fs::canonicalize(device)
+        .ok()
+        .and_then(|d| d.file_name().map(std::ffi::OsStr::to_os_string))
+    else {
+        return false;
+    };
+    let block = Path::new("/sys/class/block").join(name);
+    // Partitions have no flag of their own; their disk is the parent
+    [block.join("removable"), block.join("../removable")]
+        .iter()
+        .find_map(|flag| fs::read_to_string(flag).ok())
+        .is_some_and(|flag| flag.trim() == "1")
+}
+
+/// Label of the volume behind a mount
+///
+/// Linux lists labelled devices under `/dev/disk/by-label`; on macOS volumes
+/// are mounted under `/Volumes/<name>`.
+fn volume_label(entry: &MountEntry) -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        let name = Path::new(&entry.mount_point)
+            .strip_prefix(super::macos::VOLUMES_ROOT)
+            .ok()?;
+        Some(name.to_string_lossy().into_owned()).filter(|n| !n.is_empty())
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let device = fs::canonicalize(&entry.device).ok()?;
+        fs::read_dir("/dev/disk/by-label")
+            .ok()?
+            .flatten()
+            .find(|link| fs::canonicalize(link.path()).is_ok_and(|d| d == device))
+            .map(|link| unescape_udev_label(&link.file_name().to_string_lossy()))
+    }
+}
+
+/// Decode the `\xNN` escapes udev uses in `/dev/disk/by-label` names
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn unescape_udev_label(name: &str) -> String {
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut rest = name.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'\\'
+            && let Some(hex) = tail.strip_prefix(b"x").and_then(|t| t.get(..2))
+            && let Some(value) = std::str::from_utf8(hex)
+                .ok()
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+        {
+            bytes.push(value);
+            rest = &tail[3..];
+            continue;
+        }
+        bytes.push(byte);
+        rest = tail;
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Deepest, most recently mounted entry whose mount point contains `path`
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn mount_containing(entries: Vec<MountEntry>, path: &Path) -> Option<MountEntry> {
+    entries
+        .into_iter()
+        .filter(|entry| path.starts_with(&entry.mount_point))
+        .reduce(|best, entry| {
+            if entry.mount_point.len() >= best.mount_point.len() {
+                entry
+            } else {
+                best
+            }
+        })
+}
+
+/// Read the current mount table
+///
+/// On macOS, which has no mount table file, the mounts come from
+/// `getmntinfo` and carry `ro` or `rw` as their only option.
+///
+/// # Errors
+///
+/// Returns `PathError` if neither `/proc/self/mounts` nor `/etc/mtab` can be
+/// read, or `getmntinfo` fails on macOS.
+#[cfg(not(target_os = "macos"))]
+pub fn read_mount_table() -> Result<Vec<MountEntry>, PathError> {
+    let content = fs::read_to_string("/proc/self/mounts")
+        .or_else(|_| fs::read_to_string("/etc/mtab"))
+        .map_err(|e| {
+            PathError::platform_error(format!("Failed to read mount table: {e}")).with_source(e)
+        })?;
+    Ok(parse_mount_table(&content))
+}
+
+/// Read the current mount table
+///
+/// On macOS, which has no mount table file, the mounts come from
+/// `getmntinfo` and carry `ro` or `rw` as their only option.
+///
+/// # Errors
+///
+/// Returns `PathError` if neither `/proc/self/mounts` nor `/etc/mtab` can be
+/// read, or `getmntinfo` fails on macOS.
+#[cfg(target_os = "macos")]
+pub fn read_mount_table() -> Result<Vec<MountEntry>, PathError> {
+    Ok(super::macos::mounted_volumes()?
+        .into_iter()
+        .map(|volume| MountEntry {
+            device: volume.device,
+            mount_point: volume.mount_point,
+            fs_type: volume.fs_type,
+            options: vec![if volume.read_only { "ro" } else { "rw" }.to_string()],
+        })
+        .collect())
+}
+
+/// Decode octal escapes in a mount table field
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\'
+            && i + 3 < bytes.len()
+            && bytes[i + 1..=i + 3]
+                .iter()
+                .all(|b| (b'0'..=b'7').contains(b))
+        {
+            let value = bytes[i + 1..=i + 3]
+                .iter()
+                .fold(0u32, |acc, b| acc * 8 + u32::from(b - b'0'));
+            if let Ok(byte) = u8::try_from(value) {
+                result.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        result.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&result).into_owned()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -342,4 +1157,188 @@ mod tests {
         let hidden_attrs = hidden_ext.get_attributes().unwrap();
         assert!(hidden_attrs.is_hidden);
     }
+
+    #[test]
+    fn test_unix_access() {
+        use crate::platform::AccessMode;
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("data.txt");
+        File::create(&file_path).unwrap();
+        let ext = UnixPathExt::new(&file_path);
+
+        assert!(ext.access(AccessMode::EXISTS));
+        assert!(ext.access(AccessMode::READ | AccessMode::WRITE));
+        assert!(!ext.access(AccessMode::EXECUTE));
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o555)).unwrap();
+        assert!(ext.access(AccessMode::READ | AccessMode::EXECUTE));
+        // Root may write regardless of the mode bits
+        if unsafe { libc::geteuid() } != 0 {
+            assert!(!ext.access(AccessMode::WRITE));
+        }
+
+        assert!(UnixPathExt::new(temp_dir.path()).access(AccessMode::WRITE | AccessMode::EXECUTE));
+        assert!(!UnixPathExt::new(temp_dir.path().join("missing")).access(AccessMode::EXISTS));
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn test_unix_xattrs() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("download.zip");
+        File::create(&file_path).unwrap();
+        let ext = UnixPathExt::new(&file_path);
+
+        // Some filesystems (e.g. tmpfs on older kernels) reject user
+        // attributes entirely
+        if ext
+            .set_xattr("user.origin", b"https://example.com")
+            .is_err()
+        {
+            return;
+        }
+        assert_eq!(ext.xattrs().unwrap(), ["user.origin"]);
+        assert_eq!(
+            ext.get_xattr("user.origin").unwrap().as_deref(),
+            Some(&b"https://example.com"[..])
+        );
+        ext.set_xattr("user.origin", b"").unwrap();
+        assert_eq!(ext.get_xattr("user.origin").unwrap(), Some(Vec::new()));
+        assert_eq!(ext.get_xattr("user.missing").unwrap(), None);
+
+        ext.remove_xattr("user.origin").unwrap();
+        assert!(ext.xattrs().unwrap().is_empty());
+        assert!(ext.remove_xattr("user.origin").is_err());
+        assert!(
+            UnixPathExt::new(temp_dir.path().join("missing"))
+                .xattrs()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unix_file_attributes() {
+        use crate::platform::FileType;
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("script.sh");
+        File::create(&file_path).unwrap();
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o750)).unwrap();
+        fs::hard_link(&file_path, temp_dir.path().join("alias.sh")).unwrap();
+
+        let attrs = UnixPathExt::new(&file_path).get_attributes().unwrap();
+        let metadata = fs::metadata(&file_path).unwrap();
+        assert_eq!(attrs.file_type, FileType::Regular);
+        assert_eq!(attrs.mode, Some(0o750));
+        assert_eq!(attrs.uid, Some(metadata.uid()));
+        assert_eq!(attrs.gid, Some(metadata.gid()));
+        assert_eq!(attrs.inode, Some(metadata.ino()));
+        assert_eq!(attrs.nlink, Some(2));
+
+        let dir_attrs = UnixPathExt::new(temp_dir.path()).get_attributes().unwrap();
+        assert_eq!(dir_attrs.file_type, FileType::Dir);
+
+        let devnull = UnixPathExt::new("/dev/null").get_attributes().unwrap();
+        assert_eq!(devnull.file_type, FileType::CharDev);
+        assert_eq!(devnull.owner_name().as_deref(), Some("root"));
+
+        assert_eq!(user_name(0).as_deref(), Some("root"));
+        assert!(group_name(0).is_some());
+        assert_eq!(user_name(u32::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_parse_mountinfo() {
+        let table = "22 1 8:1 / / rw,relatime shared:1 - ext4 /dev/sda1 rw\n\
+                     95 22 0:52 / /mnt/c rw,noatime - 9p C:\\134 rw,aname=drvfs\n\
+                     96 22 0:53 / /home/me/My\\040Data rw master:2 - btrfs /dev/sdb1 rw\n\
+                     97 22 0:54 / /mnt/c rw - tmpfs tmpfs rw\n";
+        let entries = parse_mountinfo(table);
+
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].fs_type, "ext4");
+        assert_eq!(entries[0].device, "/dev/sda1");
+        assert_eq!(entries[1].device, "C:\\");
+        assert_eq!(entries[1].options, vec!["rw", "noatime"]);
+        assert_eq!(entries[2].mount_point, "/home/me/My Data");
+
+        let mount = |path: &str| mount_containing(entries.clone(), Path::new(path)).unwrap();
+        assert_eq!(mount("/etc/hosts").fs_type, "ext4");
+        assert_eq!(mount("/home/me/My Data/x").fs_type, "btrfs");
+        // Later mounts hide earlier ones on the same mount point
+        assert_eq!(mount("/mnt/c/Users").fs_type, "tmpfs");
+        // Mount points match whole components only
+        assert_eq!(mount("/mnt/cd").fs_type, "ext4");
+    }
+
+    #[test]
+    fn test_disk_info_filesystem_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let info = UnixPathExt::new(temp_dir.path()).get_disk_info().unwrap();
+        assert_ne!(info.filesystem_type, "unknown");
+        let mount_point = info.mount_point.unwrap();
+        assert!(
+            fs::canonicalize(temp_dir.path())
+                .unwrap()
+                .starts_with(mount_point.as_original())
+        );
+        assert!(info.used_space <= info.total_space);
+        assert!(info.free_space <= info.total_space);
+        assert!(!info.is_readonly);
+        assert_ne!(info.drive_type, DriveType::Unknown);
+    }
+
+    #[test]
+    fn test_mount_drive_type() {
+        let entry = |fs_type: &str, device: &str, options: &[&str]| MountEntry {
+            device: device.to_string(),
+            mount_point: "/mnt/x".to_string(),
+            fs_type: fs_type.to_string(),
+            options: options.iter().map(ToString::to_string).collect(),
+        };
+        assert_eq!(
+            drive_type(&entry("nfs4", "nas:/export", &[])),
+            DriveType::Network
+        );
+        assert_eq!(
+            drive_type(&entry("fuse.sshfs", "me@host:", &[])),
+            DriveType::Network
+        );
+        assert_eq!(
+            drive_type(&entry("9p", "C:\\", &["aname=drvfs"])),
+            DriveType::Fixed
+        );
+        assert_eq!(
+            drive_type(&entry("tmpfs", "tmpfs", &[])),
+            DriveType::RamDisk
+        );
+        assert_eq!(
+            drive_type(&entry("iso9660", "/dev/sr0", &[])),
+            DriveType::CdRom
+        );
+        assert_eq!(drive_type(&entry("proc", "proc", &[])), DriveType::Unknown);
+        assert_eq!(
+            drive_type(&entry("ext4", "/dev/does-not-exist", &[])),
+            DriveType::Fixed
+        );
+
+        assert_eq!(unescape_udev_label(r"USB\x20STICK"), "USB STICK");
+        assert_eq!(unescape_udev_label(r"a\x2"), r"a\x2");
+    }
+
+    #[test]
+    fn test_parse_mount_table() {
+        let table = "/dev/sda1 / ext4 rw,relatime 0 0\n\
+                     C:\\134 /mnt/c 9p rw,aname=drvfs 0 0\n\
+                     //nas/media /mnt/My\\040Media cifs rw 0 0\n";
+        let entries = parse_mount_table(table);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].fs_type, "ext4");
+        assert_eq!(entries[1].device, "C:\\");
+        assert_eq!(entries[2].mount_point, "/mnt/My Media");
+        assert_eq!(entries[0].options, vec!["rw", "relatime"]);
+    }
 }
@@ -5,21 +5,36 @@
 //!
 //! It uses the `windows` crate to interact with the Windows API.
 
-use crate::PathError;
-use crate::platform::{DiskInfo, FileAttributes, PathExt, PlatformPath};
+use crate::platform::{
+    AccessMode, DiskInfo, DriveType, FileAttributes, FileTimes, LinkKind, LogicalDrive, PathExt,
+    PlatformPath,
+};
+use crate::{CrossPath, PathError};
 use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::iter::Iterator;
 use core::option::Option;
 use std::ffi::OsString;
-use std::os::windows::ffi::OsStringExt;
-use std::os::windows::fs::MetadataExt;
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+use std::os::windows::fs::{MetadataExt, OpenOptionsExt};
+use std::os::windows::io::AsRawHandle;
 use std::path::{Path, PathBuf};
-use windows::Win32::Foundation::GetLastError;
+use windows::Win32::Foundation::{ERROR_MORE_DATA, FILETIME, GetLastError, HANDLE};
 use windows::Win32::Storage::FileSystem::{
-    FILE_ATTRIBUTE_HIDDEN, GetDiskFreeSpaceExW, GetFileAttributesW, GetVolumeInformationW,
+    BY_HANDLE_FILE_INFORMATION, FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_REPARSE_POINT,
+    FILE_BASIC_INFO, FILE_FLAG_BACKUP_SEMANTICS, FILE_FLAG_OPEN_REPARSE_POINT, FILE_SHARE_DELETE,
+    FILE_SHARE_READ, FILE_SHARE_WRITE, FileBasicInfo, FindClose, FindFirstFileW, FindFirstStreamW,
+    FindFirstVolumeW, FindNextStreamW, FindNextVolumeW, FindStreamInfoStandard, FindVolumeClose,
+    GetDiskFreeSpaceExW, GetDriveTypeW, GetFileAttributesW, GetFileInformationByHandle,
+    GetFileInformationByHandleEx, GetLogicalDrives, GetLongPathNameW, GetShortPathNameW,
+    GetVolumeInformationW, GetVolumeNameForVolumeMountPointW, GetVolumePathNameW,
+    GetVolumePathNamesForVolumeNameW, MOVEFILE_REPLACE_EXISTING, MOVEFILE_WRITE_THROUGH,
+    MoveFileExW, REPLACEFILE_IGNORE_MERGE_ERRORS, ReplaceFileW, SetFileTime, WIN32_FIND_DATAW,
+    WIN32_FIND_STREAM_DATA,
 };
+use windows::Win32::System::IO::DeviceIoControl;
+use windows::Win32::System::SystemServices::FILE_READ_ONLY_VOLUME;
 use windows::core::PCWSTR;
 
 /// Windows platform path extension
@@ -61,26 +76,28 @@ impl PathExt for WindowsPathExt {
         // Get hidden attribute using Windows metadata
         let attrs = metadata.file_attributes();
         let is_hidden = (attrs & FILE_ATTRIBUTE_HIDDEN.0) != 0;
+        let information = file_information(&self.path).ok();
 
-        let creation_time = metadata
-            .created()
-            .ok()
-            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d| d.as_secs());
-
-        let modification_time = metadata
-            .modified()
-            .ok()
-            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-            .map(|d| d.as_secs());
+        let times = FileTimes {
+            changed: change_time(&self.path),
+            ..FileTimes::from_metadata(&metadata)
+        };
 
         Some(FileAttributes {
             size,
             is_directory,
             is_hidden,
             is_readonly,
-            creation_time,
-            modification_time,
+            times,
+            link_kind: link_kind(&self.path),
+            file_type: metadata.file_type().into(),
+            mode: None,
+            uid: None,
+            gid: None,
+            inode: information
+                .as_ref()
+                .map(|info| (u64::from(info.nFileIndexHigh) << 32) | u64::from(info.nFileIndexLow)),
+            nlink: information.map(|info| u64::from(info.nNumberOfLinks)),
         })
     }
 
@@ -88,6 +105,74 @@ impl PathExt for WindowsPathExt {
         self.path.exists()
     }
 
+    fn access(&self, mode: AccessMode) -> bool {
+        // Specific rights, which mean list/add/traverse on directories
+        const FILE_READ_DATA: u32 = 0x0001;
+        const FILE_WRITE_DATA: u32 = 0x0002;
+        const FILE_APPEND_DATA: u32 = 0x0004;
+        const FILE_EXECUTE: u32 = 0x0020;
+        const FILE_READ_ATTRIBUTES: u32 = 0x0080;
+        const ERROR_SHARING_VIOLATION: i32 = 32;
+
+        let mut rights = FILE_READ_ATTRIBUTES;
+        if mode.read {
+            rights |= FILE_READ_DATA;
+        }
+        if mode.write {
+            rights |= FILE_WRITE_DATA | FILE_APPEND_DATA;
+        }
+        if mode.execute {
+            rights |= FILE_EXECUTE;
+        }
+        // Opening with the rights asks the system to evaluate the ACL and
+        // the read-only attribute; sharing with everyone keeps open files
+        // from failing the probe
+        match std::fs::OpenOptions::new()
+            .access_mode(rights)
+            .share_mode(FILE_SHARE_READ.0 | FILE_SHARE_WRITE.0 | FILE_SHARE_DELETE.0)
+            .custom_flags(FILE_FLAG_BACKUP_SEMANTICS.0)
+            .open(&self.path)
+        {
+            Ok(_) => true,
+            Err(e) => e.raw_os_error() == Some(ERROR_SHARING_VIOLATION),
+        }
+    }
+
+    fn xattrs(&self) -> Result<Vec<String>, PathError> {
+        alternate_streams(&self.path)
+    }
+
+    fn get_xattr(&self, name: &str) -> Result<Option<Vec<u8>>, PathError> {
+        // A missing stream and a missing file both read as not found
+        if !self.path.exists() {
+            return Err(PathError::io_error(format!(
+                "Path does not exist: {}",
+                self.path.display()
+            )));
+        }
+        match std::fs::read(stream_path(&self.path, name)?) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_xattr(&self, name: &str, value: &[u8]) -> Result<(), PathError> {
+        if !self.path.exists() {
+            return Err(PathError::io_error(format!(
+                "Path does not exist: {}",
+                self.path.display()
+            )));
+        }
+        std::fs::write(stream_path(&self.path, name)?, value)?;
+        Ok(())
+    }
+
+    fn remove_xattr(&self, name: &str) -> Result<(), PathError> {
+        std::fs::remove_file(stream_path(&self.path, name)?)?;
+        Ok(())
+    }
+
     fn get_disk_info(&self) -> Option<DiskInfo> {
         // Find root path (e.g., "C:\" or "\\Server\Share\")
         let root = self.path.components().next().and_then(|c| match c {
@@ -120,33 +205,47 @@ impl PathExt for WindowsPathExt {
             }
         }
 
-        // Get Filesystem Name
+        // Get Filesystem Name, volume label and flags
         let mut fs_name_buf = [0u16; 256];
-        let fs_type = unsafe {
-            let res = GetVolumeInformationW(
+        let mut label_buf = [0u16; 256];
+        let mut fs_flags = 0u32;
+        let has_volume_info = unsafe {
+            GetVolumeInformationW(
                 PCWSTR(wide_root.as_ptr()),
+                Some(&mut label_buf),
                 None,
                 None,
-                None,
-                None,
+                Some(&raw mut fs_flags),
                 Some(&mut fs_name_buf),
-            );
-
-            if res.is_ok() {
-                let len = fs_name_buf
-                    .iter()
-                    .position(|&c| c == 0)
-                    .unwrap_or(fs_name_buf.len());
-                String::from_utf16_lossy(&fs_name_buf[..len])
-            } else {
-                "Unknown".to_string()
-            }
+            )
+        }
+        .is_ok();
+        let fs_type = if has_volume_info {
+            wide_to_string(&fs_name_buf)
+        } else {
+            "Unknown".to_string()
         };
+        let volume_name =
+            Some(wide_to_string(&label_buf)).filter(|l| has_volume_info && !l.is_empty());
+
+        // Volume GUID path; network shares have none
+        let mut volume_buf = [0u16; 64];
+        let device = unsafe {
+            GetVolumeNameForVolumeMountPointW(PCWSTR(wide_root.as_ptr()), &mut volume_buf)
+        }
+        .ok()
+        .map(|()| wide_to_string(&volume_buf));
 
         Some(DiskInfo {
             total_space: total_bytes,
             free_space: free_bytes_caller,
+            used_space: total_bytes.saturating_sub(total_free_bytes),
             filesystem_type: fs_type,
+            mount_point: CrossPath::new(&root_str).ok(),
+            device,
+            volume_name,
+            is_readonly: fs_flags & FILE_READ_ONLY_VOLUME != 0,
+            drive_type: drive_type(&wide_root).unwrap_or(DriveType::Unknown),
         })
     }
 }
@@ -242,6 +341,618 @@ pub fn windows_path_exists(path: &str) -> Result<bool, PathError> {
     Ok(attrs != 0xFFFFFFFF)
 }
 
+/// Expand the 8.3 short names in a path to their long form
+///
+/// `C:\PROGRA~1\COMMON~1` becomes `C:\Program Files\Common Files`, so
+/// paths handed over by legacy tools convert and compare like the long
+/// form. Components that are already long are kept as written. The path
+/// must exist.
+///
+/// # Errors
+///
+/// Returns an I/O error if the path does not exist or cannot be queried.
+pub fn to_long_path(path: &Path) -> std::io::Result<PathBuf> {
+    query_path_name(path, |path, buffer| unsafe {
+        GetLongPathNameW(PCWSTR(path.as_ptr()), buffer)
+    })
+}
+
+/// Shorten the components of a path to their 8.3 short names
+///
+/// Components without a short name, e.g. on volumes with short name
+/// generation disabled, are kept long. The path must exist.
+///
+/// # Errors
+///
+/// Returns an I/O error if the path does not exist or cannot be queried.
+pub fn to_short_path(path: &Path) -> std::io::Result<PathBuf> {
+    query_path_name(path, |path, buffer| unsafe {
+        GetShortPathNameW(PCWSTR(path.as_ptr()), buffer)
+    })
+}
+
+/// Call a `Get*PathNameW` function, growing the buffer as it asks
+fn query_path_name(
+    path: &Path,
+    call: impl Fn(&[u16], Option<&mut [u16]>) -> u32,
+) -> std::io::Result<PathBuf> {
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut buffer = vec![0u16; 260];
+    loop {
+        // The length excludes the NUL on success and includes it when the
+        // buffer is too small
+        let len = call(&wide, Some(&mut buffer)) as usize;
+        if len == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if len < buffer.len() {
+            buffer.truncate(len);
+            return Ok(PathBuf::from(OsString::from_wide(&buffer)));
+        }
+        buffer.resize(len, 0);
+    }
+}
+
+/// Volume and the places it is mounted at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumeMount {
+    /// Volume GUID path (`\\?\Volume{GUID}\`)
+    pub volume_name: String,
+    /// Drive roots and directories the volume is mounted at (`C:\`,
+    /// `D:\mnt\data\`)
+    pub mount_points: Vec<PathBuf>,
+}
+
+/// Enumerate the volumes on the system with their mount points
+///
+/// Volumes that are not mounted anywhere have no mount points.
+#[must_use]
+pub fn volume_mounts() -> Vec<VolumeMount> {
+    let mut volumes = Vec::new();
+    let mut buffer = [0u16; 64];
+    unsafe {
+        let Ok(handle) = FindFirstVolumeW(&mut buffer) else {
+            return volumes;
+        };
+        loop {
+            let volume_name = wide_to_string(&buffer);
+            volumes.push(VolumeMount {
+                mount_points: volume_mount_points(&volume_name).unwrap_or_default(),
+                volume_name,
+            });
+            if FindNextVolumeW(handle, &mut buffer).is_err() {
+                break;
+            }
+        }
+        let _ = FindVolumeClose(handle);
+    }
+    volumes
+}
+
+/// Drive roots and directories a volume is mounted at
+///
+/// `volume_name` is a volume GUID path (`\\?\Volume{GUID}\`); the
+/// trailing backslash may be omitted.
+///
+/// # Errors
+///
+/// Returns an I/O error if no volume has that name.
+pub fn volume_mount_points(volume_name: &str) -> std::io::Result<Vec<PathBuf>> {
+    let mut name = volume_name.replace('/', "\\");
+    if !name.ends_with('\\') {
+        name.push('\\');
+    }
+    let wide: Vec<u16> = name.encode_utf16().chain(Some(0)).collect();
+    let mut buffer = vec![0u16; 256];
+    let mut len = 0u32;
+    unsafe {
+        while let Err(e) =
+            GetVolumePathNamesForVolumeNameW(PCWSTR(wide.as_ptr()), Some(&mut buffer), &raw mut len)
+        {
+            if e.code() != ERROR_MORE_DATA.to_hresult() {
+                return Err(e.into());
+            }
+            buffer.resize(len as usize, 0);
+        }
+    }
+    // Double-NUL-terminated list of NUL-terminated paths
+    Ok(buffer
+        .split(|&c| c == 0)
+        .take_while(|path| !path.is_empty())
+        .map(|path| PathBuf::from(OsString::from_wide(path)))
+        .collect())
+}
+
+/// Translate a volume GUID path into a path through a mount point of its
+/// volume
+///
+/// `\\?\Volume{GUID}\dir\file` becomes `C:\dir\file` if the volume is
+/// drive `C:`. Drive letters are preferred over directory mounts.
+///
+/// # Errors
+///
+/// Returns `PathError::InvalidPath` if `path` is not a volume GUID path and
+/// `PathError::PlatformError` if the volume is not mounted anywhere.
+pub fn resolve_volume_path(path: &str) -> Result<PathBuf, PathError> {
+    let parsed = crate::PathParser::parse_ref(path);
+    let guid = parsed
+        .volume_guid()
+        .ok_or_else(|| PathError::invalid_path(format!("Not a volume GUID path: {path}")))?;
+    let volume_name = format!(r"\\?\Volume{{{guid}}}\");
+    let mount_points = volume_mount_points(&volume_name).map_err(|e| {
+        PathError::platform_error(format!("Unknown volume {volume_name}: {e}")).with_source(e)
+    })?;
+    let mount_point = mount_points
+        .iter()
+        .find(|mount| {
+            get_drive_letter(&mount.to_string_lossy()).is_some() && mount.as_os_str().len() <= 3
+        })
+        .or_else(|| mount_points.first())
+        .ok_or_else(|| PathError::platform_error(format!("Volume {volume_name} is not mounted")))?;
+    Ok(parsed
+        .components
+        .iter()
+        .fold(mount_point.clone(), |path, component| path.join(component)))
+}
+
+/// Volume GUID path addressing the same file as `path`
+///
+/// `C:\dir\file` becomes `\\?\Volume{GUID}\dir\file`, which stays valid
+/// if the volume's drive letter or mount point changes. The path must
+/// exist.
+///
+/// # Errors
+///
+/// Returns an I/O error if the path does not exist or its volume has no
+/// GUID path, e.g. on network shares.
+pub fn to_volume_path(path: &Path) -> std::io::Result<String> {
+    let path = std::fs::canonicalize(path)?;
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut mount_point = vec![0u16; wide.len().max(260)];
+    let mut volume_name = [0u16; 64];
+    unsafe {
+        GetVolumePathNameW(PCWSTR(wide.as_ptr()), &mut mount_point)?;
+        GetVolumeNameForVolumeMountPointW(PCWSTR(mount_point.as_ptr()), &mut volume_name)?;
+    }
+    let (mount_point, volume_name) = (wide_to_string(&mount_point), wide_to_string(&volume_name));
+    let path = path.to_string_lossy();
+    // The canonical path carries the `\\?\` prefix the mount point lacks
+    let path = path.strip_prefix(r"\\?\").unwrap_or(&path);
+    let rest = path
+        .get(mount_point.len()..)
+        .filter(|_| path[..mount_point.len()].eq_ignore_ascii_case(&mount_point))
+        .unwrap_or_default();
+    Ok(format!("{volume_name}{rest}"))
+}
+
+/// Check if the process runs in a Windows container
+///
+/// Windows containers carry a `ContainerType` value under
+/// `HKLM\SYSTEM\CurrentControlSet\Control`.
+#[must_use]
+pub fn is_container() -> bool {
+    use windows::Win32::System::Registry::{HKEY_LOCAL_MACHINE, RRF_RT_REG_DWORD, RegGetValueW};
+    use windows::core::w;
+
+    let mut value = 0u32;
+    let mut size = u32::try_from(core::mem::size_of::<u32>()).unwrap_or_default();
+    unsafe {
+        RegGetValueW(
+            HKEY_LOCAL_MACHINE,
+            w!(r"SYSTEM\CurrentControlSet\Control"),
+            w!("ContainerType"),
+            RRF_RT_REG_DWORD,
+            None,
+            Some((&raw mut value).cast()),
+            Some(&raw mut size),
+        )
+    }
+    .is_ok()
+}
+
+/// Reparse tag of directory junctions (mount points)
+const IO_REPARSE_TAG_MOUNT_POINT: u32 = 0xA000_0003;
+/// Reparse tag of symbolic links
+const IO_REPARSE_TAG_SYMLINK: u32 = 0xA000_000C;
+/// `FSCTL_SET_REPARSE_POINT` control code
+const FSCTL_SET_REPARSE_POINT: u32 = 0x0009_00A4;
+
+/// Kind of link at `path`, without following it
+#[must_use]
+pub fn link_kind(path: &Path) -> Option<LinkKind> {
+    let metadata = std::fs::symlink_metadata(path).ok()?;
+    if metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT.0 != 0 {
+        return match reparse_tag(path)? {
+            IO_REPARSE_TAG_MOUNT_POINT => Some(LinkKind::Junction),
+            IO_REPARSE_TAG_SYMLINK => Some(LinkKind::Symlink),
+            _ => None,
+        };
+    }
+    if metadata.is_file() && file_information(path).ok()?.nNumberOfLinks > 1 {
+        return Some(LinkKind::HardLink);
+    }
+    None
+}
+
+/// Reparse tag of a reparse point, as reported by `FindFirstFileW`
+fn reparse_tag(path: &Path) -> Option<u32> {
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut data = WIN32_FIND_DATAW::default();
+    unsafe {
+        let handle = FindFirstFileW(PCWSTR(wide.as_ptr()), &raw mut data).ok()?;
+        let _ = FindClose(handle);
+    }
+    Some(data.dwReserved0)
+}
+
+/// Names of the alternate data streams of `path`, without the main stream
+fn alternate_streams(path: &Path) -> Result<Vec<String>, PathError> {
+    const ERROR_HANDLE_EOF: u32 = 38;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut data = WIN32_FIND_STREAM_DATA::default();
+    let mut names = Vec::new();
+    unsafe {
+        let handle = match FindFirstStreamW(
+            PCWSTR(wide.as_ptr()),
+            FindStreamInfoStandard,
+            (&raw mut data).cast(),
+            None,
+        ) {
+            Ok(handle) => handle,
+            // Directories without named streams have no streams at all
+            Err(e) if e.code() == windows::core::HRESULT::from_win32(ERROR_HANDLE_EOF) => {
+                return Ok(names);
+            }
+            Err(e) => {
+                return Err(PathError::io_error(format!(
+                    "Failed to list streams of {}: {e}",
+                    path.display()
+                ))
+                .with_path(path.to_string_lossy())
+                .with_source(e));
+            }
+        };
+        loop {
+            // Streams are reported as ":name:$DATA", the main one as "::$DATA"
+            let stream = wide_to_string(&data.cStreamName);
+            if let Some(name) = stream
+                .strip_prefix(':')
+                .and_then(|s| s.strip_suffix(":$DATA"))
+                .filter(|name| !name.is_empty())
+            {
+                names.push(name.to_string());
+            }
+            if FindNextStreamW(handle, (&raw mut data).cast()).is_err() {
+                break;
+            }
+        }
+        let _ = FindClose(handle);
+    }
+    Ok(names)
+}
+
+/// Path of the alternate data stream `name` of `path`
+fn stream_path(path: &Path, name: &str) -> Result<PathBuf, PathError> {
+    if name.is_empty() || name.contains([':', '\\', '/']) {
+        return Err(PathError::invalid_path(format!(
+            "Invalid stream name: {name}"
+        )));
+    }
+    let mut stream = path.as_os_str().to_os_string();
+    stream.push(":");
+    stream.push(name);
+    Ok(PathBuf::from(stream))
+}
+
+/// Volume serial number and file index of the file at `path`, following
+/// links
+///
+/// # Errors
+///
+/// Returns an I/O error if the file cannot be opened or inspected.
+pub fn file_id(path: &Path) -> std::io::Result<(u64, u64)> {
+    let info = file_information(path)?;
+    Ok((
+        u64::from(info.dwVolumeSerialNumber),
+        (u64::from(info.nFileIndexHigh) << 32) | u64::from(info.nFileIndexLow),
+    ))
+}
+
+/// Seconds between 1601-01-01, the `FILETIME` epoch, and the Unix epoch
+const FILETIME_UNIX_OFFSET: u64 = 11_644_473_600;
+
+/// Last change time of a file or directory (`ChangeTime` of
+/// `FILE_BASIC_INFO`)
+fn change_time(path: &Path) -> Option<std::time::SystemTime> {
+    let file = open_attributes(path, false).ok()?;
+    let mut info = FILE_BASIC_INFO::default();
+    unsafe {
+        GetFileInformationByHandleEx(
+            HANDLE(file.as_raw_handle()),
+            FileBasicInfo,
+            (&raw mut info).cast(),
+            u32::try_from(std::mem::size_of::<FILE_BASIC_INFO>()).ok()?,
+        )
+        .ok()?;
+    }
+    let ticks = u64::try_from(info.ChangeTime).ok().filter(|&t| t > 0)?;
+    let since_1601 = std::time::Duration::new(
+        ticks / 10_000_000,
+        u32::try_from(ticks % 10_000_000).ok()? * 100,
+    );
+    std::time::UNIX_EPOCH
+        .checked_sub(std::time::Duration::from_secs(FILETIME_UNIX_OFFSET))?
+        .checked_add(since_1601)
+}
+
+/// `FILETIME` for a time, counted in 100 ns ticks since 1601
+fn filetime(time: std::time::SystemTime) -> std::io::Result<FILETIME> {
+    let since_1601 = std::time::UNIX_EPOCH
+        .checked_sub(std::time::Duration::from_secs(FILETIME_UNIX_OFFSET))
+        .and_then(|epoch| time.duration_since(epoch).ok())
+        .ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "Time is before 1601")
+        })?;
+    let ticks = u64::try_from(since_1601.as_nanos() / 100)
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Time is too late"))?;
+    Ok(FILETIME {
+        dwLowDateTime: u32::try_from(ticks & 0xFFFF_FFFF).unwrap_or_default(),
+        dwHighDateTime: u32::try_from(ticks >> 32).unwrap_or_default(),
+    })
+}
+
+/// Set the creation, access and modification times of `path` with
+/// `SetFileTime`
+///
+/// Times that are `None` are left unchanged; the change time cannot be set
+/// and is ignored.
+///
+/// # Errors
+///
+/// Returns an I/O error if the file cannot be opened or a time is out of
+/// the `FILETIME` range.
+pub fn set_times(path: &Path, times: &FileTimes) -> std::io::Result<()> {
+    let created = times.created.map(filetime).transpose()?;
+    let accessed = times.accessed.map(filetime).transpose()?;
+    let modified = times.modified.map(filetime).transpose()?;
+    let file = open_attributes(path, true)?;
+    unsafe {
+        SetFileTime(
+            HANDLE(file.as_raw_handle()),
+            created.as_ref().map(std::ptr::from_ref),
+            accessed.as_ref().map(std::ptr::from_ref),
+            modified.as_ref().map(std::ptr::from_ref),
+        )
+        .map_err(std::io::Error::from)
+    }
+}
+
+/// Open a file or directory to read or write its attributes only
+fn open_attributes(path: &Path, write: bool) -> std::io::Result<std::fs::File> {
+    const FILE_READ_ATTRIBUTES: u32 = 0x80;
+    const FILE_WRITE_ATTRIBUTES: u32 = 0x100;
+
+    std::fs::OpenOptions::new()
+        .access_mode(if write {
+            FILE_READ_ATTRIBUTES | FILE_WRITE_ATTRIBUTES
+        } else {
+            FILE_READ_ATTRIBUTES
+        })
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS.0)
+        .open(path)
+}
+
+/// Handle information of a file or directory
+fn file_information(path: &Path) -> std::io::Result<BY_HANDLE_FILE_INFORMATION> {
+    // Backup semantics are needed to open directories
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS.0)
+        .open(path)?;
+    let mut info = BY_HANDLE_FILE_INFORMATION::default();
+    unsafe {
+        GetFileInformationByHandle(HANDLE(file.as_raw_handle()), &raw mut info)
+            .map_err(std::io::Error::from)?;
+    }
+    Ok(info)
+}
+
+/// Create a directory junction at `link` pointing to the directory `target`
+///
+/// Unlike symbolic links, junctions need no privileges, but they can only
+/// point to absolute local paths.
+///
+/// # Errors
+///
+/// Returns an I/O error if `link` exists or the junction cannot be set.
+pub fn create_junction(target: &Path, link: &Path) -> std::io::Result<()> {
+    let target = std::path::absolute(target)?;
+    let print: Vec<u16> = target.as_os_str().encode_wide().collect();
+    let substitute: Vec<u16> = r"\??\"
+        .encode_utf16()
+        .chain(print.iter().copied())
+        .collect();
+
+    // REPARSE_DATA_BUFFER with a MountPointReparseBuffer
+    let names = u16::try_from((substitute.len() + print.len() + 2) * 2)
+        .map_err(|_| std::io::Error::other("Junction target is too long"))?;
+    let mut buffer: Vec<u8> = Vec::with_capacity(16 + usize::from(names));
+    buffer.extend(IO_REPARSE_TAG_MOUNT_POINT.to_le_bytes());
+    buffer.extend((names + 8).to_le_bytes());
+    buffer.extend(0u16.to_le_bytes());
+    let substitute_len = u16::try_from(substitute.len() * 2).unwrap_or(u16::MAX);
+    buffer.extend(0u16.to_le_bytes());
+    buffer.extend(substitute_len.to_le_bytes());
+    buffer.extend((substitute_len + 2).to_le_bytes());
+    buffer.extend(
+        u16::try_from(print.len() * 2)
+            .unwrap_or(u16::MAX)
+            .to_le_bytes(),
+    );
+    for unit in substitute.iter().chain(&[0]).chain(&print).chain(&[0]) {
+        buffer.extend(unit.to_le_bytes());
+    }
+
+    std::fs::create_dir(link)?;
+    let result = std::fs::OpenOptions::new()
+        .write(true)
+        .custom_flags(FILE_FLAG_OPEN_REPARSE_POINT.0 | FILE_FLAG_BACKUP_SEMANTICS.0)
+        .open(link)
+        .and_then(|dir| unsafe {
+            DeviceIoControl(
+                HANDLE(dir.as_raw_handle()),
+                FSCTL_SET_REPARSE_POINT,
+                Some(buffer.as_ptr().cast()),
+                u32::try_from(buffer.len()).unwrap_or(u32::MAX),
+                None,
+                0,
+                None,
+                None,
+            )
+            .map_err(std::io::Error::from)
+        });
+    if result.is_err() {
+        let _ = std::fs::remove_dir(link);
+    }
+    result
+}
+
+/// Move a file or directory to the Recycle Bin
+///
+/// Uses `SHFileOperationW` with undo enabled and all dialogs suppressed.
+/// Volumes without a Recycle Bin (such as network shares) delete the entry
+/// permanently, as Explorer does when confirmations are turned off.
+///
+/// # Errors
+///
+/// Returns an I/O error if the entry does not exist or cannot be recycled.
+#[cfg(feature = "trash")]
+pub fn move_to_trash(path: &Path) -> std::io::Result<()> {
+    use windows::Win32::UI::Shell::{
+        FO_DELETE, FOF_ALLOWUNDO, FOF_NOCONFIRMATION, FOF_NOERRORUI, FOF_SILENT, SHFILEOPSTRUCTW,
+        SHFileOperationW,
+    };
+
+    let path = std::path::absolute(path)?;
+    std::fs::symlink_metadata(&path)?;
+    // The source list is terminated by two NULs
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain([0, 0]).collect();
+    let flags = FOF_ALLOWUNDO | FOF_NOCONFIRMATION | FOF_NOERRORUI | FOF_SILENT;
+    let mut operation = SHFILEOPSTRUCTW {
+        wFunc: FO_DELETE,
+        pFrom: PCWSTR(wide.as_ptr()),
+        fFlags: u16::try_from(flags.0).unwrap_or_default(),
+        ..Default::default()
+    };
+    let result = unsafe { SHFileOperationW(&raw mut operation) };
+    if result != 0 {
+        return Err(std::io::Error::other(format!(
+            "Failed to recycle {} (error {result:#x})",
+            path.display()
+        )));
+    }
+    if operation.fAnyOperationsAborted.as_bool() {
+        return Err(std::io::Error::other(format!(
+            "Recycling {} was aborted",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Atomically replace `dst` with `src`
+///
+/// An existing destination is replaced with `ReplaceFileW`, which keeps its
+/// attributes and security descriptor; otherwise, or if that fails, `src`
+/// is moved over it with `MoveFileExW`.
+///
+/// # Errors
+///
+/// Returns an I/O error if the file cannot be moved.
+pub fn replace_file(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let wide =
+        |path: &Path| -> Vec<u16> { path.as_os_str().encode_wide().chain(Some(0)).collect() };
+    let (src_wide, dst_wide) = (wide(src), wide(dst));
+
+    unsafe {
+        if dst.exists()
+            && ReplaceFileW(
+                PCWSTR(dst_wide.as_ptr()),
+                PCWSTR(src_wide.as_ptr()),
+                PCWSTR::null(),
+                REPLACEFILE_IGNORE_MERGE_ERRORS,
+                None,
+                None,
+            )
+            .is_ok()
+        {
+            return Ok(());
+        }
+        MoveFileExW(
+            PCWSTR(src_wide.as_ptr()),
+            PCWSTR(dst_wide.as_ptr()),
+            MOVEFILE_REPLACE_EXISTING | MOVEFILE_WRITE_THROUGH,
+        )
+        .map_err(std::io::Error::from)
+    }
+}
+
+/// Enumerate the drive letters currently present on the system
+#[must_use]
+pub fn logical_drive_letters() -> Vec<char> {
+    let mask = unsafe { GetLogicalDrives() };
+    (b'A'..=b'Z')
+        .enumerate()
+        .filter(|(i, _)| mask & (1 << i) != 0)
+        .map(|(_, letter)| char::from(letter))
+        .collect()
+}
+
+/// Enumerate the logical drives currently present on the system
+///
+/// Drives without a root directory (unmounted volumes) are skipped.
+#[must_use]
+pub fn logical_drives() -> Vec<LogicalDrive> {
+    logical_drive_letters()
+        .into_iter()
+        .filter_map(|letter| {
+            let root: Vec<u16> = format!("{letter}:\\\0").encode_utf16().collect();
+            let drive_type = drive_type(&root)?;
+            Some(LogicalDrive { letter, drive_type })
+        })
+        .collect()
+}
+
+/// Kind of the drive with the given NUL-terminated root
+///
+/// Returns `None` for roots that are not mounted.
+fn drive_type(root: &[u16]) -> Option<DriveType> {
+    // Return values of `GetDriveTypeW`
+    const DRIVE_NO_ROOT_DIR: u32 = 1;
+    const DRIVE_REMOVABLE: u32 = 2;
+    const DRIVE_FIXED: u32 = 3;
+    const DRIVE_REMOTE: u32 = 4;
+    const DRIVE_CDROM: u32 = 5;
+    const DRIVE_RAMDISK: u32 = 6;
+
+    Some(match unsafe { GetDriveTypeW(PCWSTR(root.as_ptr())) } {
+        DRIVE_NO_ROOT_DIR => return None,
+        DRIVE_REMOVABLE => DriveType::Removable,
+        DRIVE_FIXED => DriveType::Fixed,
+        DRIVE_REMOTE => DriveType::Network,
+        DRIVE_CDROM => DriveType::CdRom,
+        DRIVE_RAMDISK => DriveType::RamDisk,
+        _ => DriveType::Unknown,
+    })
+}
+
+/// String from a NUL-terminated UTF-16 buffer
+fn wide_to_string(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,6 +966,37 @@ mod tests {
         assert!(!is_valid_windows_path(r"relative\path"));
     }
 
+    #[test]
+    fn test_short_and_long_paths() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let long = temp_dir.path().join("Long Directory Name");
+        std::fs::create_dir(&long).unwrap();
+        let long = to_long_path(&long).unwrap();
+
+        let short = to_short_path(&long).unwrap();
+        assert_eq!(to_long_path(&short).unwrap(), long);
+        assert!(to_long_path(&long.join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_volume_paths() {
+        let volumes = volume_mounts();
+        assert!(volumes.iter().any(|volume| !volume.mount_points.is_empty()));
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("data.txt");
+        std::fs::write(&file, b"").unwrap();
+        let volume_path = to_volume_path(&file).unwrap();
+        assert!(volume_path.starts_with(r"\\?\Volume{"));
+
+        let resolved = resolve_volume_path(&volume_path).unwrap();
+        assert_eq!(
+            std::fs::canonicalize(resolved).unwrap(),
+            std::fs::canonicalize(&file).unwrap()
+        );
+        assert!(resolve_volume_path(r"C:\data").is_err());
+    }
+
     #[test]
     fn test_get_drive_letter() {
         assert_eq!(get_drive_letter(r"C:\Windows"), Some('C'));
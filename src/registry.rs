@@ -0,0 +1,211 @@
+//! Global mapping registry
+//!
+//! Process-wide store for drive mappings, the default configuration,
+//! custom path styles and the metrics sink. Once drive mappings have been registered,
+//! [`PathConfig::default`](crate::PathConfig) uses them instead of the
+//! built-in defaults, so every `CrossPath` created afterwards picks them up.
+//!
+//! A whole [`PathConfig`] can be installed as well, for the process with
+//! [`set_global_config`] or for the current thread and the duration of a
+//! closure with [`with_config`]. Constructors that take no configuration,
+//! such as [`CrossPath::new`](crate::CrossPath::new), use
+//! [`current_config`].
+//!
+//! The registry is updated by [`MappingWatcher`](crate::mapping::watcher::MappingWatcher)
+//! when drives or shares are mounted and unmounted.
+
+use crate::metrics::Metrics;
+use crate::style::StyleDefinition;
+use crate::{PathConfig, PathStyle};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+static DRIVE_MAPPINGS: RwLock<Option<Vec<(String, String)>>> = RwLock::new(None);
+static ENVIRONMENT_DEFAULTS: AtomicBool = AtomicBool::new(false);
+static GLOBAL_CONFIG: RwLock<Option<PathConfig>> = RwLock::new(None);
+static METRICS: RwLock<Option<Arc<dyn Metrics>>> = RwLock::new(None);
+static STYLES: RwLock<Vec<Arc<dyn StyleDefinition>>> = RwLock::new(Vec::new());
+
+thread_local! {
+    /// Configurations installed by [`with_config`], innermost last
+    static SCOPED_CONFIGS: RefCell<Vec<PathConfig>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Get the registered drive mappings
+///
+/// Returns `None` if no mappings have been registered.
+#[must_use]
+pub fn drive_mappings() -> Option<Vec<(String, String)>> {
+    DRIVE_MAPPINGS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Register drive mappings, replacing any previously registered ones
+pub fn set_drive_mappings(mappings: Vec<(String, String)>) {
+    *DRIVE_MAPPINGS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(mappings);
+}
+
+/// Remove registered drive mappings, restoring the discovered defaults
+pub fn clear_drive_mappings() {
+    *DRIVE_MAPPINGS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Modify the registered drive mappings in place
+///
+/// If no mappings are registered yet, the discovered mappings are used as
+/// the starting point.
+pub fn update_drive_mappings<F>(update: F)
+where
+    F: FnOnce(&mut Vec<(String, String)>),
+{
+    let mut guard = DRIVE_MAPPINGS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    let mappings = guard.get_or_insert_with(default_drive_mappings);
+    update(mappings);
+}
+
+/// Set whether unregistered drive mappings follow the detected environment
+///
+/// When enabled, [`PathConfig::default`](crate::PathConfig) maps drives
+/// the way the environment reported by
+/// [`detect_environment`](crate::platform::detect_environment) mounts them,
+/// e.g. `/cygdrive/c` under Cygwin and `/c` under MSYS2, instead of
+/// `/mnt/c`. Disabled by default.
+pub fn set_environment_defaults(enabled: bool) {
+    ENVIRONMENT_DEFAULTS.store(enabled, Ordering::Relaxed);
+}
+
+/// Check if unregistered drive mappings follow the detected environment
+#[must_use]
+pub fn environment_defaults() -> bool {
+    ENVIRONMENT_DEFAULTS.load(Ordering::Relaxed)
+}
+
+/// Drive mappings used while none are registered
+pub(crate) fn default_drive_mappings() -> Vec<(String, String)> {
+    use crate::mapping::drives::DriveMappings;
+
+    if environment_defaults() {
+        DriveMappings::for_environment(crate::platform::detect_environment()).into_vec()
+    } else {
+        DriveMappings::cached().into_vec()
+    }
+}
+
+/// Install the default configuration for the process
+///
+/// Replaces any previously installed configuration.
+pub fn set_global_config(config: PathConfig) {
+    *GLOBAL_CONFIG
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = Some(config);
+}
+
+/// Remove the process default configuration
+pub fn clear_global_config() {
+    *GLOBAL_CONFIG
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Run `f` with `config` as the default configuration of the current thread
+///
+/// Scopes nest, and take precedence over the process default. The previous
+/// configuration is restored when `f` returns or panics.
+pub fn with_config<R, F: FnOnce() -> R>(config: PathConfig, f: F) -> R {
+    /// Pops the scoped configuration, even on unwind
+    struct Restore;
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            SCOPED_CONFIGS.with(|configs| configs.borrow_mut().pop());
+        }
+    }
+
+    SCOPED_CONFIGS.with(|configs| configs.borrow_mut().push(config));
+    let _restore = Restore;
+    f()
+}
+
+/// Get the configuration used when none is given
+///
+/// This is the innermost [`with_config`] scope of the current thread, else
+/// the process default from [`set_global_config`], else
+/// [`PathConfig::default`].
+#[must_use]
+pub fn current_config() -> PathConfig {
+    if let Some(config) = SCOPED_CONFIGS.with(|configs| configs.borrow().last().cloned()) {
+        return config;
+    }
+    GLOBAL_CONFIG
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+        .unwrap_or_default()
+}
+
+/// Register a custom path style, replacing any style of the same name
+///
+/// Returns the [`PathStyle`] to convert paths to the style with.
+pub fn register_style(definition: Arc<dyn StyleDefinition>) -> PathStyle {
+    let style = PathStyle::custom(definition.name());
+    let mut styles = STYLES.write().unwrap_or_else(PoisonError::into_inner);
+    styles.retain(|registered| registered.name() != definition.name());
+    styles.push(definition);
+    style
+}
+
+/// Remove a registered custom path style
+pub fn unregister_style(name: &str) {
+    STYLES
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .retain(|registered| registered.name() != name);
+}
+
+/// Get the definition of a registered custom path style
+#[must_use]
+pub fn style_definition(name: &str) -> Option<Arc<dyn StyleDefinition>> {
+    STYLES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|registered| registered.name() == name)
+        .cloned()
+}
+
+/// Get all registered custom path styles, in registration order
+#[must_use]
+pub fn style_definitions() -> Vec<Arc<dyn StyleDefinition>> {
+    STYLES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Install a metrics sink for all conversions in the process
+pub fn set_metrics(metrics: Arc<dyn Metrics>) {
+    *METRICS.write().unwrap_or_else(PoisonError::into_inner) = Some(metrics);
+}
+
+/// Remove the installed metrics sink
+pub fn clear_metrics() {
+    *METRICS.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Get the installed metrics sink
+#[must_use]
+pub fn metrics() -> Option<Arc<dyn Metrics>> {
+    METRICS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
@@ -0,0 +1,318 @@
+//! Path prefix rewrite rules
+//!
+//! A [`PathRewriter`] remaps paths from one tree to another, such as the
+//! CI checkout `/builds/runner/app` to a developer checkout `D:\src\app`
+//! when symbolizing stack traces. Rules are either literal prefix swaps or
+//! regular expressions with capture substitution, and can be used on their
+//! own or installed as [`PathConfig::rewriter`](crate::PathConfig) to run
+//! before every conversion.
+//!
+//! Of the rules matching a path, the one matching the longest prefix wins;
+//! ties go to the rule added first. The rest of the path takes the
+//! separators of the replacement, so `/builds/runner/app/src/main.rs`
+//! becomes `D:\src\app\src\main.rs`. The result is rewritten again until no
+//! rule matches, but each rule fires at most once per path, and a path
+//! rewritten back to an earlier form is rejected as a loop.
+//!
+//! ```rust
+//! use cross_path::rewrite::{PathRewriter, RewriteRule};
+//!
+//! let rewriter = PathRewriter::from_rules([
+//!     RewriteRule::prefix("/builds/runner", r"D:\src"),
+//!     RewriteRule::regex(r"/cargo/registry/src/[^/]+/(?<krate>[^/]+)", r"C:\deps\$krate"),
+//! ])?;
+//! assert_eq!(rewriter.rewrite("/builds/runner/app/main.rs")?, r"D:\src\app\main.rs");
+//! assert_eq!(
+//!     rewriter.rewrite("/cargo/registry/src/index-1/serde-1.0.0/src/lib.rs")?,
+//!     r"C:\deps\serde-1.0.0\src\lib.rs"
+//! );
+//! assert_eq!(rewriter.rewrite("/home/me/notes.txt")?, "/home/me/notes.txt");
+//! # Ok::<(), cross_path::PathError>(())
+//! ```
+
+use crate::{PathError, PathResult};
+use regex::Regex;
+use std::borrow::Cow;
+
+/// A single rewrite rule
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RewriteRule {
+    /// Replace a leading directory (`/builds/runner` → `D:\src`)
+    ///
+    /// `from` matches whole components only, with either separator;
+    /// Windows-style prefixes ignore ASCII case.
+    Prefix {
+        /// Directory to replace
+        from: String,
+        /// Directory to put in its place
+        to: String,
+    },
+    /// Replace the text matched by a regular expression at the start of
+    /// the path
+    ///
+    /// `replacement` may refer to captures as `$1` or `$name`.
+    Regex {
+        /// Regular expression, implicitly anchored at the start of the path
+        pattern: String,
+        /// Replacement text
+        replacement: String,
+    },
+}
+
+impl RewriteRule {
+    /// Create a literal prefix rule
+    pub fn prefix(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self::Prefix {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    /// Create a regular expression rule
+    pub fn regex(pattern: impl Into<String>, replacement: impl Into<String>) -> Self {
+        Self::Regex {
+            pattern: pattern.into(),
+            replacement: replacement.into(),
+        }
+    }
+}
+
+/// Compiled form of a rule
+#[derive(Debug, Clone)]
+enum Matcher {
+    Prefix {
+        from: String,
+        to: String,
+        windows: bool,
+    },
+    Regex {
+        regex: Regex,
+        replacement: String,
+    },
+}
+
+impl Matcher {
+    fn compile(rule: &RewriteRule) -> PathResult<Self> {
+        Ok(match rule {
+            RewriteRule::Prefix { from, to } => {
+                // Keep a lone root, so `/` covers every absolute path
+                let trimmed = from.trim_end_matches(['/', '\\']);
+                Self::Prefix {
+                    from: if trimmed.is_empty() { from } else { trimmed }.to_string(),
+                    to: to.clone(),
+                    windows: is_windows_root(from),
+                }
+            }
+            RewriteRule::Regex {
+                pattern,
+                replacement,
+            } => Self::Regex {
+                regex: Regex::new(&format!("^(?:{pattern})")).map_err(|e| {
+                    PathError::parse_error(format!("Invalid rewrite pattern: {e}")).with_source(e)
+                })?,
+                replacement: replacement.clone(),
+            },
+        })
+    }
+
+    /// Length of the prefix of `path` this matcher covers
+    fn match_len(&self, path: &str) -> Option<usize> {
+        match self {
+            Self::Prefix { from, windows, .. } => {
+                let head = path.get(..from.len())?;
+                let same = head.bytes().zip(from.bytes()).all(|(a, b)| {
+                    a == b
+                        || (is_separator(a) && is_separator(b))
+                        || (*windows && a.eq_ignore_ascii_case(&b))
+                });
+                let rest = &path[from.len()..];
+                let boundary =
+                    rest.is_empty() || rest.starts_with(['/', '\\']) || from.ends_with(['/', '\\']);
+                (same && boundary).then_some(from.len())
+            }
+            Self::Regex { regex, .. } => regex.find(path).map(|m| m.end()),
+        }
+    }
+}
+
+/// Ordered list of path rewrite rules
+///
+/// Compares and serializes as its list of rules.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "Vec<RewriteRule>", into = "Vec<RewriteRule>")]
+pub struct PathRewriter {
+    rules: Vec<RewriteRule>,
+    matchers: Vec<Matcher>,
+}
+
+impl PathRewriter {
+    /// Create rewriter without rules
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create rewriter from rules, in order of precedence for ties
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::ParseError` if a pattern is not a valid regular
+    /// expression.
+    pub fn from_rules<I: IntoIterator<Item = RewriteRule>>(rules: I) -> PathResult<Self> {
+        let mut rewriter = Self::new();
+        for rule in rules {
+            rewriter.push(rule)?;
+        }
+        Ok(rewriter)
+    }
+
+    /// Add a rule after the existing ones
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::ParseError` if the pattern of a regular
+    /// expression rule is invalid.
+    pub fn push(&mut self, rule: RewriteRule) -> PathResult<()> {
+        self.matchers.push(Matcher::compile(&rule)?);
+        self.rules.push(rule);
+        Ok(())
+    }
+
+    /// Get the rules in order
+    #[must_use]
+    pub fn rules(&self) -> &[RewriteRule] {
+        &self.rules
+    }
+
+    /// Number of rules
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Check if there are no rules
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Check if any rule matches a path
+    #[must_use]
+    pub fn matches(&self, path: &str) -> bool {
+        self.matchers.iter().any(|m| m.match_len(path).is_some())
+    }
+
+    /// Rewrite a path, borrowing it if no rule matches
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::InvalidPath` if the rules rewrite the path back
+    /// to an earlier form.
+    pub fn rewrite<'a>(&self, path: &'a str) -> PathResult<Cow<'a, str>> {
+        let mut current = Cow::Borrowed(path);
+        let mut fired = vec![false; self.rules.len()];
+        let mut seen: Vec<String> = Vec::new();
+
+        for _ in 0..self.rules.len() {
+            let Some((index, len)) = self.best_match(&current, &fired) else {
+                break;
+            };
+            fired[index] = true;
+            let next = self.apply(index, &current, len);
+            if next == current {
+                continue;
+            }
+            if next == path || seen.contains(&next) {
+                return Err(PathError::invalid_path(format!(
+                    "Rewrite rules loop on {path}"
+                )));
+            }
+            seen.push(next.clone());
+            current = Cow::Owned(next);
+        }
+        Ok(current)
+    }
+
+    /// Find the rule matching the longest prefix among those not fired yet
+    fn best_match(&self, path: &str, fired: &[bool]) -> Option<(usize, usize)> {
+        let mut best: Option<(usize, usize)> = None;
+        for (index, matcher) in self.matchers.iter().enumerate() {
+            if fired[index] {
+                continue;
+            }
+            if let Some(len) = matcher.match_len(path)
+                && best.is_none_or(|(_, best_len)| len > best_len)
+            {
+                best = Some((index, len));
+            }
+        }
+        best
+    }
+
+    /// Replace the first `len` bytes of `path` using rule `index`
+    fn apply(&self, index: usize, path: &str, len: usize) -> String {
+        let rest = &path[len..];
+        let replacement = match &self.matchers[index] {
+            Matcher::Prefix { to, .. } => to.clone(),
+            Matcher::Regex { regex, replacement } => {
+                let mut expanded = String::new();
+                if let Some(captures) = regex.captures(path) {
+                    captures.expand(replacement, &mut expanded);
+                }
+                expanded
+            }
+        };
+        if rest.is_empty() {
+            return replacement;
+        }
+
+        let (base, rest) = if is_windows_root(&replacement) {
+            (
+                replacement.trim_end_matches(['/', '\\']),
+                Cow::Owned(rest.replace('/', "\\")),
+            )
+        } else if replacement.starts_with('/') {
+            (
+                replacement.trim_end_matches(['/', '\\']),
+                Cow::Owned(rest.replace('\\', "/")),
+            )
+        } else {
+            (replacement.as_str(), Cow::Borrowed(rest))
+        };
+        format!("{base}{rest}")
+    }
+}
+
+impl PartialEq for PathRewriter {
+    fn eq(&self, other: &Self) -> bool {
+        self.rules == other.rules
+    }
+}
+
+impl Eq for PathRewriter {}
+
+impl TryFrom<Vec<RewriteRule>> for PathRewriter {
+    type Error = PathError;
+
+    fn try_from(rules: Vec<RewriteRule>) -> PathResult<Self> {
+        Self::from_rules(rules)
+    }
+}
+
+impl From<PathRewriter> for Vec<RewriteRule> {
+    fn from(rewriter: PathRewriter) -> Self {
+        rewriter.rules
+    }
+}
+
+/// Check whether a path starts with a drive letter or `\\`
+fn is_windows_root(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with(r"\\")
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+fn is_separator(byte: u8) -> bool {
+    byte == b'/' || byte == b'\\'
+}
@@ -0,0 +1,325 @@
+//! Configurable file name sanitization
+//!
+//! [`Sanitizer`] turns untrusted names into ones that can be created on the
+//! target platform while keeping as much of the original as possible:
+//! components are sanitized one by one, separators can be preserved, and
+//! length limits never split a UTF-8 character or drop the extension.
+//!
+//! ```rust
+//! use cross_path::sanitize::{NonAscii, Sanitizer};
+//!
+//! let sanitizer = Sanitizer::new().non_ascii(NonAscii::Transliterate);
+//! assert_eq!(sanitizer.sanitize("../Fotos/Straße: 1?.jpg"), "Fotos/Strasse_ 1_.jpg");
+//! ```
+
+use crate::ValidationTarget;
+use crate::parser::WINDOWS_INVALID_CHARS;
+use crate::validation::WINDOWS_RESERVED_NAMES;
+
+/// Handling of characters outside ASCII
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum NonAscii {
+    /// Keep them unchanged
+    #[default]
+    Keep,
+    /// Replace each with the replacement character
+    Replace,
+    /// Map them to ASCII look-alikes (`é` → `e`, `ß` → `ss`), replacing
+    /// characters that have none
+    ///
+    /// Accents are only removed from precomposed characters with the
+    /// `unicode` feature; without it only the built-in ligature table is
+    /// used.
+    Transliterate,
+}
+
+/// Non-destructive file name sanitizer
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Sanitizer {
+    target: ValidationTarget,
+    replacement: char,
+    non_ascii: NonAscii,
+    preserve_separators: bool,
+    max_component_len: usize,
+    max_path_len: Option<usize>,
+}
+
+impl Default for Sanitizer {
+    fn default() -> Self {
+        Self {
+            target: ValidationTarget::Both,
+            replacement: '_',
+            non_ascii: NonAscii::Keep,
+            preserve_separators: true,
+            max_component_len: 255,
+            max_path_len: None,
+        }
+    }
+}
+
+impl Sanitizer {
+    /// Create sanitizer producing names valid on both Windows and Unix
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Platform whose naming rules the result must satisfy
+    #[must_use]
+    pub fn target(mut self, target: ValidationTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Character substituted for invalid characters (default `_`)
+    ///
+    /// The character should itself be valid on the target.
+    #[must_use]
+    pub fn replacement(mut self, replacement: char) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// How characters outside ASCII are handled
+    #[must_use]
+    pub fn non_ascii(mut self, non_ascii: NonAscii) -> Self {
+        self.non_ascii = non_ascii;
+        self
+    }
+
+    /// Whether `/` and `\` separate components (the default) or are
+    /// replaced, producing a single file name
+    #[must_use]
+    pub fn preserve_separators(mut self, preserve: bool) -> Self {
+        self.preserve_separators = preserve;
+        self
+    }
+
+    /// Maximum length of each component in UTF-8 bytes (default 255)
+    #[must_use]
+    pub fn max_component_len(mut self, max: usize) -> Self {
+        self.max_component_len = max;
+        self
+    }
+
+    /// Maximum length of the whole result in UTF-8 bytes
+    ///
+    /// The last components are shortened, then dropped, until the result
+    /// fits.
+    #[must_use]
+    pub fn max_path_len(mut self, max: usize) -> Self {
+        self.max_path_len = Some(max);
+        self
+    }
+
+    /// Sanitize a path or file name
+    ///
+    /// The result is always relative: roots and drive prefixes lose their
+    /// meaning, and `.` and `..` components are dropped so the result can
+    /// be joined below a directory. Invalid characters and controls are
+    /// replaced, trailing dots and spaces removed for Windows targets, and
+    /// reserved device names get the replacement character appended to
+    /// their stem (`con.txt` → `con_.txt`). Each separator is kept as
+    /// written.
+    #[must_use]
+    pub fn sanitize(&self, path: &str) -> String {
+        let mut parts: Vec<(char, String)> = Vec::new();
+        if self.preserve_separators {
+            let mut separator = '/';
+            let mut rest = path;
+            loop {
+                let end = rest.find(['/', '\\']).unwrap_or(rest.len());
+                let component = &rest[..end];
+                if !matches!(component, "" | "." | "..") {
+                    let name = self.sanitize_component(component);
+                    if !name.is_empty() {
+                        parts.push((separator, name));
+                    }
+                }
+                let Some(next) = rest[end..].chars().next() else {
+                    break;
+                };
+                separator = next;
+                rest = &rest[end + next.len_utf8()..];
+            }
+        } else {
+            let name = self.sanitize_component(path);
+            if !name.is_empty() {
+                parts.push(('/', name));
+            }
+        }
+
+        if let Some(max) = self.max_path_len {
+            self.fit_path(&mut parts, max);
+        }
+
+        let mut result = String::new();
+        for (index, (separator, name)) in parts.iter().enumerate() {
+            if index > 0 {
+                result.push(*separator);
+            }
+            result.push_str(name);
+        }
+        result
+    }
+
+    /// Sanitize a single component
+    fn sanitize_component(&self, component: &str) -> String {
+        let mut name = String::with_capacity(component.len());
+        for c in component.chars() {
+            match self.substitute(c, self.is_invalid(c)) {
+                Some(substitute) => name.push_str(&substitute),
+                None => name.push(c),
+            }
+        }
+
+        let mut name = truncate_name(&name, self.max_component_len);
+        self.fix_windows_name(&mut name, self.max_component_len);
+        name
+    }
+
+    /// Remove trailing dots and spaces and disarm reserved device names
+    /// for Windows targets, staying within `max` bytes
+    fn fix_windows_name(&self, name: &mut String, max: usize) {
+        if !self.target.includes_windows() {
+            return;
+        }
+        name.truncate(name.trim_end_matches(['.', ' ']).len());
+        let stem_len = name.split('.').next().unwrap_or_default().len();
+        if WINDOWS_RESERVED_NAMES
+            .iter()
+            .any(|r| r.eq_ignore_ascii_case(name[..stem_len].trim_end()))
+        {
+            if name.len() + self.replacement.len_utf8() <= max {
+                name.insert(stem_len, self.replacement);
+            } else {
+                // Reserved names are ASCII, so the last byte is a character
+                name.replace_range(
+                    stem_len - 1..stem_len,
+                    self.replacement.encode_utf8(&mut [0; 4]),
+                );
+            }
+        }
+    }
+
+    /// Text that replaces `c`, if it is `invalid` or the non-ASCII policy
+    /// rewrites it
+    pub(crate) fn substitute(&self, c: char, invalid: bool) -> Option<String> {
+        if c.is_ascii() || self.non_ascii == NonAscii::Keep {
+            invalid.then(|| self.replacement.to_string())
+        } else if self.non_ascii == NonAscii::Transliterate {
+            Some(transliterate(c).unwrap_or_else(|| self.replacement.to_string()))
+        } else {
+            Some(self.replacement.to_string())
+        }
+    }
+
+    /// Check if a character is invalid on the target
+    fn is_invalid(&self, c: char) -> bool {
+        c == '\0'
+            || c == '/'
+            || c == '\\'
+            || (self.target.includes_windows()
+                && (WINDOWS_INVALID_CHARS.contains(&c) || c.is_control()))
+    }
+
+    /// Shorten or drop trailing components until the joined path fits
+    fn fit_path(&self, parts: &mut Vec<(char, String)>, max: usize) {
+        let joined_len = |parts: &[(char, String)]| {
+            parts
+                .iter()
+                .map(|(_, n)| n.len() + 1)
+                .sum::<usize>()
+                .saturating_sub(1)
+        };
+        while joined_len(parts) > max {
+            let excess = joined_len(parts) - max;
+            let Some((_, last)) = parts.last_mut() else {
+                break;
+            };
+            if last.len() > excess {
+                let len = last.len() - excess;
+                *last = truncate_name(last, len);
+                self.fix_windows_name(last, len);
+                if !last.is_empty() {
+                    continue;
+                }
+            }
+            parts.pop();
+        }
+    }
+}
+
+/// Truncate a name to at most `max` bytes on a character boundary
+///
+/// The extension is kept when the stem can still hold at least one
+/// character.
+fn truncate_name(name: &str, max: usize) -> String {
+    if name.len() <= max {
+        return name.to_string();
+    }
+    let floor = |text: &str, len: usize| {
+        let mut end = len.min(text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        end
+    };
+
+    if let Some(dot) = name.rfind('.').filter(|&dot| dot > 0) {
+        let extension = &name[dot..];
+        if extension.len() < max {
+            let stem = &name[..floor(name, max - extension.len()).min(dot)];
+            if !stem.is_empty() {
+                return format!("{stem}{extension}");
+            }
+        }
+    }
+    name[..floor(name, max)].to_string()
+}
+
+/// Map a character to ASCII, if it has a sensible equivalent
+fn transliterate(c: char) -> Option<String> {
+    let ligature = match c {
+        'ß' => "ss",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ø' => "O",
+        'ø' => "o",
+        'Đ' | 'Ð' => "D",
+        'đ' | 'ð' => "d",
+        'Ł' => "L",
+        'ł' => "l",
+        'Þ' => "TH",
+        'þ' => "th",
+        'ı' => "i",
+        '‘' | '’' | '′' => "'",
+        '“' | '”' | '″' => "\"",
+        '–' | '—' | '‐' => "-",
+        '…' => "...",
+        '\u{00A0}' => " ",
+        _ => "",
+    };
+    if !ligature.is_empty() {
+        return Some(ligature.to_string());
+    }
+
+    #[cfg(feature = "unicode")]
+    {
+        use unicode_normalization::UnicodeNormalization;
+        use unicode_normalization::char::is_combining_mark;
+
+        let stripped: String = core::iter::once(c)
+            .nfd()
+            .filter(|&d| !is_combining_mark(d))
+            .collect();
+        if !stripped.is_empty() && stripped.is_ascii() {
+            return Some(stripped);
+        }
+    }
+    None
+}
@@ -0,0 +1,289 @@
+//! Finding paths in free-form text
+//!
+//! [`PathScanner`] locates absolute Windows, Unix and UNC paths and
+//! `file://` URLs inside compiler output, logs and other text, and reports
+//! their byte ranges so they can be converted in place. Relative paths are
+//! too ambiguous to find reliably and are left alone.
+//!
+//! Paths end at whitespace, quotes and characters no portable path
+//! contains. A path that directly follows a quote extends to the closing
+//! quote, so `"C:\Program Files\app"` is found whole. Trailing punctuation,
+//! unbalanced closing brackets and the `(line,col)` suffix of MSVC
+//! diagnostics are not part of the path; neither is a `:line:col` suffix,
+//! since `:` ends a path. One-letter Unix paths such as `/s` are skipped as
+//! likely command switches.
+//!
+//! ```rust
+//! use cross_path::scan::{PathScanner, ScanKind};
+//!
+//! let log = r"error: C:\src\app\main.rs(12,5): unresolved name, see /usr/share/doc.";
+//! let found = PathScanner::new().scan(log);
+//! assert_eq!(found.len(), 2);
+//! assert_eq!(found[0].kind, ScanKind::Windows);
+//! assert_eq!(found[0].text, r"C:\src\app\main.rs");
+//! assert_eq!(&log[found[1].range.clone()], "/usr/share/doc");
+//! ```
+
+use regex::Regex;
+use std::borrow::Cow;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+/// Candidate paths, refined by [`PathScanner::refine`]
+static CANDIDATE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?x)
+          (?P<url>\b[Ff][Ii][Ll][Ee]://[^\s"'<>`|]*)
+        | (?P<unc>\\\\[^\s\\/"'<>`|:*?]+(?:\\[^\s\\/"'<>`|:*?]*)*)
+        | (?P<windows>\b[A-Za-z]:[\\/][^\s"'<>`|:*?]*)
+        | (?P<unix>~?/[^\s/"'<>`|:*?][^\s"'<>`|:*?]*)
+        "#,
+    )
+    .unwrap()
+});
+
+/// `(line)` or `(line,col)` suffix of MSVC diagnostics
+static MSVC_LOCATION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\(\d+(,\d+)*\)$").unwrap());
+
+/// Kind of path found in text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScanKind {
+    /// Windows drive path (`C:\src`, `C:/src`)
+    Windows,
+    /// Absolute Unix path (`/usr/lib`) or home-relative path (`~/src`)
+    Unix,
+    /// UNC path (`\\server\share\dir`)
+    Unc,
+    /// `file://` URL
+    FileUrl,
+}
+
+/// A path found in text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathMatch<'t> {
+    /// Kind of path
+    pub kind: ScanKind,
+    /// Byte range of the path in the scanned text
+    pub range: Range<usize>,
+    /// The path as written
+    pub text: &'t str,
+}
+
+/// Finds paths in free-form text
+///
+/// Every kind of path is found unless disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct PathScanner {
+    windows: bool,
+    unix: bool,
+    unc: bool,
+    file_urls: bool,
+}
+
+impl Default for PathScanner {
+    fn default() -> Self {
+        Self {
+            windows: true,
+            unix: true,
+            unc: true,
+            file_urls: true,
+        }
+    }
+}
+
+impl PathScanner {
+    /// Create scanner finding every kind of path
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether Windows drive paths are found
+    #[must_use]
+    pub fn windows(mut self, enabled: bool) -> Self {
+        self.windows = enabled;
+        self
+    }
+
+    /// Set whether Unix paths are found
+    #[must_use]
+    pub fn unix(mut self, enabled: bool) -> Self {
+        self.unix = enabled;
+        self
+    }
+
+    /// Set whether UNC paths are found
+    #[must_use]
+    pub fn unc(mut self, enabled: bool) -> Self {
+        self.unc = enabled;
+        self
+    }
+
+    /// Set whether `file://` URLs are found
+    #[must_use]
+    pub fn file_urls(mut self, enabled: bool) -> Self {
+        self.file_urls = enabled;
+        self
+    }
+
+    /// Check if a kind of path is found
+    #[must_use]
+    pub fn finds(&self, kind: ScanKind) -> bool {
+        match kind {
+            ScanKind::Windows => self.windows,
+            ScanKind::Unix => self.unix,
+            ScanKind::Unc => self.unc,
+            ScanKind::FileUrl => self.file_urls,
+        }
+    }
+
+    /// Iterate over the paths in `text`, in order
+    pub fn find_iter<'s, 't>(&'s self, text: &'t str) -> impl Iterator<Item = PathMatch<'t>> + 's
+    where
+        't: 's,
+    {
+        let mut pos = 0;
+        std::iter::from_fn(move || {
+            while pos < text.len() {
+                let captures = CANDIDATE.captures_at(text, pos)?;
+                let (kind, candidate) = [
+                    ("url", ScanKind::FileUrl),
+                    ("unc", ScanKind::Unc),
+                    ("windows", ScanKind::Windows),
+                    ("unix", ScanKind::Unix),
+                ]
+                .into_iter()
+                .find_map(|(name, kind)| Some((kind, captures.name(name)?)))?;
+
+                // Skip a disabled kind whole, so no other kind is found
+                // inside it
+                if !self.finds(kind) {
+                    pos = candidate.end().max(candidate.start() + 1);
+                    continue;
+                }
+                match Self::refine(text, kind, candidate.range()) {
+                    Some(range) => {
+                        pos = range.end.max(candidate.start() + 1);
+                        return Some(PathMatch {
+                            kind,
+                            text: &text[range.clone()],
+                            range,
+                        });
+                    }
+                    None => pos = next_char(text, candidate.start()),
+                }
+            }
+            None
+        })
+    }
+
+    /// Collect the paths in `text`, in order
+    #[must_use]
+    pub fn scan<'t>(&self, text: &'t str) -> Vec<PathMatch<'t>> {
+        self.find_iter(text).collect()
+    }
+
+    /// Replace the paths in `text`
+    ///
+    /// `replace` returns the new text for a path, or `None` to keep it.
+    /// Returns the text borrowed if nothing was replaced.
+    pub fn replace_all<'t, F>(&self, text: &'t str, mut replace: F) -> Cow<'t, str>
+    where
+        F: FnMut(&PathMatch<'t>) -> Option<String>,
+    {
+        let mut result = String::new();
+        let mut copied = 0;
+        let mut replaced = false;
+        for found in self.find_iter(text) {
+            if let Some(replacement) = replace(&found) {
+                result.push_str(&text[copied..found.range.start]);
+                result.push_str(&replacement);
+                copied = found.range.end;
+                replaced = true;
+            }
+        }
+        if !replaced {
+            return Cow::Borrowed(text);
+        }
+        result.push_str(&text[copied..]);
+        Cow::Owned(result)
+    }
+
+    /// Decide whether a candidate is a path and find its exact range
+    fn refine(text: &str, kind: ScanKind, range: Range<usize>) -> Option<Range<usize>> {
+        let before = text[..range.start].chars().next_back();
+        let accepted = match kind {
+            // `\b` in the pattern already rules out `http://`-like prefixes
+            ScanKind::FileUrl | ScanKind::Windows => true,
+            ScanKind::Unc => before != Some('\\'),
+            // Skip `and/or`, `1/2` and the path part of URLs
+            ScanKind::Unix => before.is_none_or(|c| {
+                c.is_whitespace()
+                    || matches!(
+                        c,
+                        '"' | '\'' | '`' | '(' | '[' | '{' | '<' | '>' | '=' | ',' | ':'
+                    )
+            }),
+        };
+        if !accepted {
+            return None;
+        }
+
+        // A quoted path may contain spaces
+        if let Some(quote @ ('"' | '\'')) = before {
+            let line_end = text[range.start..]
+                .find(['\n', '\r'])
+                .map_or(text.len(), |i| range.start + i);
+            if let Some(close) = text[range.start..line_end].find(quote) {
+                return Some(range.start..range.start + close);
+            }
+        }
+
+        let mut end = range.end;
+        let path = &text[range.start..end];
+        if kind == ScanKind::Windows
+            && let Some(location) = MSVC_LOCATION.find(path)
+        {
+            end = range.start + location.start();
+        }
+        end = range.start + trim_trailing(&text[range.start..end]).len();
+
+        // `/s` and `~/` are more likely a command switch and prose
+        let path = &text[range.start..end];
+        let too_short = kind == ScanKind::Unix && (path.len() <= 2 || path == "~/");
+        (!path.is_empty() && !too_short).then_some(range.start..end)
+    }
+}
+
+/// Drop trailing punctuation and closing brackets without an opening one
+fn trim_trailing(path: &str) -> &str {
+    let mut path = path;
+    loop {
+        let Some(last) = path.chars().next_back() else {
+            return path;
+        };
+        let trim = match last {
+            '.' | ',' | ';' | '!' | '?' => {
+                // Keep `..` and the `.` of `/.`
+                !path.ends_with("..") && !path.ends_with("/.") && !path.ends_with("\\.")
+            }
+            ')' => path.matches('(').count() < path.matches(')').count(),
+            ']' => path.matches('[').count() < path.matches(']').count(),
+            '}' => path.matches('{').count() < path.matches('}').count(),
+            _ => false,
+        };
+        if !trim {
+            return path;
+        }
+        path = &path[..path.len() - last.len_utf8()];
+    }
+}
+
+/// Byte index of the character after the one at `index`
+fn next_char(text: &str, index: usize) -> usize {
+    text[index..]
+        .chars()
+        .next()
+        .map_or(text.len(), |c| index + c.len_utf8())
+}
@@ -1,31 +1,376 @@
-use crate::{PathError, PathResult};
+//! Path security checks
+//!
+//! [`PathSecurityChecker`] rejects paths that try to traverse directories,
+//! match dangerous patterns, use reserved device names or reach into system
+//! directories. The rules come from a [`SecurityPolicy`]; the default policy
+//! matches the built-in rules, and [`SecurityPolicyBuilder`] adjusts them.
+//! Set [`PathConfig::security_policy`](crate::PathConfig::security_policy)
+//! to make [`CrossPath::is_safe`](crate::CrossPath::is_safe) use a custom
+//! policy.
+//!
+//! Bidirectional overrides, zero-width characters and other invisible code
+//! points can disguise a name (`invoice\u{202E}fdp.exe` displays as
+//! `invoiceexe.pdf`); the policy decides whether such paths are rejected,
+//! stripped or reported through the metrics hooks.
+//!
+//! With the `fs-security` feature a policy can also name a base directory;
+//! paths are then resolved against the filesystem, following symlinks, and
+//! rejected if their physical location leaves the base directory.
+
+use crate::parser::{self, Component};
+use crate::validation::{PortabilityIssue, ValidationTarget, WINDOWS_RESERVED_NAMES};
+use crate::{PathError, PathParser, PathResult, PathStyle};
+use alloc::borrow::Cow;
 use regex::Regex;
-use std::path::Path;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+/// Patterns rejected by the default policy
+const DEFAULT_DANGEROUS_PATTERNS: [&str; 4] = [
+    r"(?i)\.(exe|bat|cmd|sh|php|py|js)$",
+    r"^/proc/",
+    r"^/dev/",
+    r"^/sys/",
+];
+
+/// Handling of invisible and bidirectional control characters in paths
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum InvisibleCharAction {
+    /// Fail the check with `PathError::SecurityError`
+    #[default]
+    Reject,
+    /// Remove the characters and check the remaining path
+    Strip,
+    /// Accept the path and report a warning to the installed metrics sink
+    Warn,
+}
+
+/// Kind of a suspicious character found in a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum SuspiciousCharKind {
+    /// Bidirectional override, embedding, isolate or mark (`U+202E`, ...)
+    BidiControl,
+    /// Zero-width space, joiner or non-joiner, word joiner or BOM
+    ZeroWidth,
+    /// Other code point that renders as nothing (soft hyphen, fillers, tags,
+    /// variation selectors, ...)
+    Invisible,
+}
+
+/// Suspicious character found in a path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SuspiciousChar {
+    /// Byte offset in the path
+    pub index: usize,
+    /// The character
+    pub ch: char,
+    /// What kind of character it is
+    pub kind: SuspiciousCharKind,
+}
+
+/// Rules applied by [`PathSecurityChecker`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SecurityPolicy {
+    dangerous_patterns: Vec<String>,
+    system_dirs: Vec<String>,
+    allowed_roots: Vec<String>,
+    reserved_names_on_windows: bool,
+    reserved_names_on_unix: bool,
+    max_path_length: Option<usize>,
+    invisible_chars: InvisibleCharAction,
+    validation_target: Option<ValidationTarget>,
+    reject_data_streams: bool,
+    #[cfg(feature = "fs-security")]
+    base_dir: Option<String>,
+}
+
+impl Default for SecurityPolicy {
+    fn default() -> Self {
+        Self {
+            dangerous_patterns: DEFAULT_DANGEROUS_PATTERNS
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            system_dirs: default_system_dirs()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            allowed_roots: Vec::new(),
+            reserved_names_on_windows: true,
+            reserved_names_on_unix: false,
+            max_path_length: None,
+            invisible_chars: InvisibleCharAction::default(),
+            validation_target: None,
+            reject_data_streams: false,
+            #[cfg(feature = "fs-security")]
+            base_dir: None,
+        }
+    }
+}
+
+impl SecurityPolicy {
+    /// Start building a policy from the default rules
+    #[must_use]
+    pub fn builder() -> SecurityPolicyBuilder {
+        SecurityPolicyBuilder::new()
+    }
+
+    /// Regular expressions a path must not match
+    #[must_use]
+    pub fn dangerous_patterns(&self) -> &[String] {
+        &self.dangerous_patterns
+    }
+
+    /// Directories a path must not be inside of
+    #[must_use]
+    pub fn system_dirs(&self) -> &[String] {
+        &self.system_dirs
+    }
+
+    /// Roots absolute paths are confined to (empty means unrestricted)
+    #[must_use]
+    pub fn allowed_roots(&self) -> &[String] {
+        &self.allowed_roots
+    }
+
+    /// Maximum path length in characters
+    #[must_use]
+    pub fn max_path_length(&self) -> Option<usize> {
+        self.max_path_length
+    }
+
+    /// Handling of invisible and bidirectional control characters
+    #[must_use]
+    pub fn invisible_chars(&self) -> InvisibleCharAction {
+        self.invisible_chars
+    }
+
+    /// Platform whose naming rules every component must satisfy
+    #[must_use]
+    pub fn validation_target(&self) -> Option<ValidationTarget> {
+        self.validation_target
+    }
+
+    /// Check if paths addressing an NTFS alternate data stream are rejected
+    #[must_use]
+    pub fn rejects_data_streams(&self) -> bool {
+        self.reject_data_streams
+    }
+
+    /// Directory paths must physically stay inside
+    #[cfg(feature = "fs-security")]
+    #[must_use]
+    pub fn base_dir(&self) -> Option<&str> {
+        self.base_dir.as_deref()
+    }
+
+    /// Check if reserved device names are rejected on hosts of `style`
+    #[must_use]
+    pub fn checks_reserved_names(&self, style: PathStyle) -> bool {
+        match ValidationTarget::from(style) {
+            ValidationTarget::Windows => self.reserved_names_on_windows,
+            ValidationTarget::Unix => self.reserved_names_on_unix,
+            ValidationTarget::Both => self.reserved_names_on_windows && self.reserved_names_on_unix,
+        }
+    }
+}
+
+/// Builder for [`SecurityPolicy`]
+///
+/// ```rust
+/// use cross_path::security::SecurityPolicy;
+/// use cross_path::PathStyle;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let policy = SecurityPolicy::builder()
+///     .remove_dangerous_pattern(r"(?i)\.(exe|bat|cmd|sh|php|py|js)$")
+///     .add_dangerous_pattern(r"(?i)\.(exe|dll)$")
+///     .allow_root("/srv/uploads")
+///     .reserved_names(PathStyle::Unix, true)
+///     .max_path_length(1024)
+///     .build()?;
+/// assert!(policy.checks_reserved_names(PathStyle::Unix));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SecurityPolicyBuilder {
+    policy: SecurityPolicy,
+}
+
+impl SecurityPolicyBuilder {
+    /// Create builder starting from the default rules
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject paths matching a regular expression
+    #[must_use]
+    pub fn add_dangerous_pattern(mut self, pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        if !self.policy.dangerous_patterns.contains(&pattern) {
+            self.policy.dangerous_patterns.push(pattern);
+        }
+        self
+    }
+
+    /// Stop rejecting paths matching a previously added regular expression
+    #[must_use]
+    pub fn remove_dangerous_pattern(mut self, pattern: &str) -> Self {
+        self.policy.dangerous_patterns.retain(|p| p != pattern);
+        self
+    }
+
+    /// Remove all dangerous patterns
+    #[must_use]
+    pub fn clear_dangerous_patterns(mut self) -> Self {
+        self.policy.dangerous_patterns.clear();
+        self
+    }
+
+    /// Reject paths inside a directory
+    #[must_use]
+    pub fn add_system_dir(mut self, dir: impl Into<String>) -> Self {
+        let dir = dir.into();
+        if !self.policy.system_dirs.contains(&dir) {
+            self.policy.system_dirs.push(dir);
+        }
+        self
+    }
+
+    /// Stop rejecting paths inside a directory
+    #[must_use]
+    pub fn remove_system_dir(mut self, dir: &str) -> Self {
+        self.policy.system_dirs.retain(|d| d != dir);
+        self
+    }
+
+    /// Confine absolute paths to a root directory
+    ///
+    /// Once a root is allowed, absolute paths outside every allowed root are
+    /// rejected. Paths inside an allowed root skip the system directory
+    /// check.
+    #[must_use]
+    pub fn allow_root(mut self, root: impl Into<String>) -> Self {
+        let root = root.into();
+        if !self.policy.allowed_roots.contains(&root) {
+            self.policy.allowed_roots.push(root);
+        }
+        self
+    }
+
+    /// Enable or disable the reserved device name check (`CON`, `NUL`, ...)
+    /// on hosts of the given style
+    ///
+    /// Cygwin and MSYS2 count as Windows; `Auto` applies to the current
+    /// platform.
+    #[must_use]
+    pub fn reserved_names(mut self, style: PathStyle, enabled: bool) -> Self {
+        let target = ValidationTarget::from(style);
+        if target.includes_windows() {
+            self.policy.reserved_names_on_windows = enabled;
+        }
+        if target.includes_unix() {
+            self.policy.reserved_names_on_unix = enabled;
+        }
+        self
+    }
+
+    /// Reject paths longer than `max` characters
+    #[must_use]
+    pub fn max_path_length(mut self, max: usize) -> Self {
+        self.policy.max_path_length = Some(max);
+        self
+    }
+
+    /// Choose how invisible and bidirectional control characters are handled
+    #[must_use]
+    pub fn invisible_chars(mut self, action: InvisibleCharAction) -> Self {
+        self.policy.invisible_chars = action;
+        self
+    }
+
+    /// Check every component against the naming rules of `target`
+    ///
+    /// Unlike the host-dependent reserved name check, this applies on every
+    /// host: reserved names, trailing dots or spaces and invalid characters
+    /// are rejected for Windows targets and NUL characters for Unix targets,
+    /// so paths can be vetted on Linux before they are created on Windows.
+    #[must_use]
+    pub fn validation_target(mut self, target: ValidationTarget) -> Self {
+        self.policy.validation_target = Some(target);
+        self
+    }
+
+    /// Reject paths addressing an NTFS alternate data stream
+    /// (`report.pdf:payload.exe`)
+    ///
+    /// Streams hide content from directory listings, so uploads and
+    /// archive entries commonly use them to smuggle data.
+    #[must_use]
+    pub fn reject_data_streams(mut self, reject: bool) -> Self {
+        self.policy.reject_data_streams = reject;
+        self
+    }
+
+    /// Require paths to physically stay inside `dir`
+    ///
+    /// Symlinks in existing components are followed, so a link pointing out
+    /// of `dir` is caught even though the path itself has no `..`. Relative
+    /// paths are resolved against `dir`.
+    #[cfg(feature = "fs-security")]
+    #[must_use]
+    pub fn base_dir(mut self, dir: impl Into<String>) -> Self {
+        self.policy.base_dir = Some(dir.into());
+        self
+    }
+
+    /// Build the policy
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::ParseError` if a dangerous pattern is not a valid
+    /// regular expression.
+    pub fn build(self) -> PathResult<SecurityPolicy> {
+        compile_patterns(&self.policy.dangerous_patterns)?;
+        Ok(self.policy)
+    }
+}
+
+impl From<SecurityPolicy> for SecurityPolicyBuilder {
+    fn from(policy: SecurityPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+/// `../` and `..\`
+static TRAVERSAL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\.\./|\.\.\\)").unwrap());
+/// Compiled default dangerous patterns, shared by every checker using them
+static DEFAULT_PATTERNS: LazyLock<Arc<[Regex]>> = LazyLock::new(|| {
+    compile_patterns(&DEFAULT_DANGEROUS_PATTERNS)
+        .unwrap()
+        .into()
+});
 
 /// Path security checker for preventing path-based attacks
+///
+/// Checkers using the default dangerous patterns share one compiled set,
+/// so creating them does not compile any regular expression.
 #[derive(Debug, Clone)]
 pub struct PathSecurityChecker {
-    path_traversal_regex: Regex,
-    dangerous_patterns: Vec<Regex>,
-    #[allow(dead_code)] // Reserved names are only used on Windows
-    reserved_names: Vec<&'static str>,
+    dangerous_patterns: Arc<[Regex]>,
+    policy: SecurityPolicy,
 }
 
 impl Default for PathSecurityChecker {
     fn default() -> Self {
         Self {
-            path_traversal_regex: Regex::new(r"(\.\./|\.\.\\)").unwrap(),
-            dangerous_patterns: vec![
-                Regex::new(r"(?i)\.(exe|bat|cmd|sh|php|py|js)$").unwrap(),
-                Regex::new(r"^/proc/").unwrap(),
-                Regex::new(r"^/dev/").unwrap(),
-                Regex::new(r"^/sys/").unwrap(),
-            ],
-            reserved_names: vec![
-                "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7",
-                "COM8", "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8",
-                "LPT9",
-            ],
+            dangerous_patterns: Arc::clone(&DEFAULT_PATTERNS),
+            policy: SecurityPolicy::default(),
         }
     }
 }
@@ -37,6 +382,29 @@ impl PathSecurityChecker {
         Self::default()
     }
 
+    /// Create security checker applying a policy
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::ParseError` if a dangerous pattern of the policy
+    /// is not a valid regular expression.
+    pub fn with_policy(policy: &SecurityPolicy) -> PathResult<Self> {
+        let default_patterns = policy
+            .dangerous_patterns
+            .iter()
+            .map(String::as_str)
+            .eq(DEFAULT_DANGEROUS_PATTERNS);
+        let dangerous_patterns = if default_patterns {
+            Arc::clone(&DEFAULT_PATTERNS)
+        } else {
+            compile_patterns(&policy.dangerous_patterns)?.into()
+        };
+        Ok(Self {
+            dangerous_patterns,
+            policy: policy.clone(),
+        })
+    }
+
     /// Check path security (static method)
     ///
     /// # Errors
@@ -53,131 +421,239 @@ impl PathSecurityChecker {
     ///
     /// Returns `PathError` if the path violates any security rules.
     pub fn check(&self, path: &Path) -> PathResult<bool> {
+        // Check for invisible characters, running the remaining checks on
+        // the stripped path if the policy strips them
+        let stripped;
+        let path = match self.check_unicode(&path.to_string_lossy())? {
+            Cow::Borrowed(_) => path,
+            Cow::Owned(clean) => {
+                stripped = PathBuf::from(clean);
+                stripped.as_path()
+            }
+        };
+
+        let path_str = path.to_string_lossy();
+        let rejected = |msg: &str, span: Option<Range<usize>>| {
+            let error = PathError::security_error(msg).with_path(path_str.as_ref());
+            Err(match span {
+                Some(span) => error.with_span(span),
+                None => error,
+            })
+        };
+
+        // Check for overlong paths
+        if let Some(max) = self.policy.max_path_length
+            && path_str.chars().count() > max
+        {
+            return rejected(
+                &format!("Path exceeds the maximum length of {max} characters"),
+                None,
+            );
+        }
+
+        // Check for alternate data streams
+        if self.policy.reject_data_streams && parser::has_data_stream(&path_str) {
+            return rejected(
+                "Path addresses an NTFS alternate data stream",
+                stream_span(&path_str),
+            );
+        }
+
         // Check for path traversal attacks
-        if self.detect_path_traversal(path) {
-            return Err(PathError::security_error("Path traversal attack detected"));
+        if let Some(span) = Self::detect_path_traversal(&path_str) {
+            return rejected("Path traversal attack detected", Some(span));
         }
 
         // Check for dangerous patterns
-        if self.contains_dangerous_patterns(path) {
-            return Err(PathError::security_error(
-                "Path contains dangerous patterns",
-            ));
+        if let Some(span) = self.find_dangerous_pattern(&path_str) {
+            return rejected("Path contains dangerous patterns", Some(span));
         }
 
         // Check for reserved names (Windows)
-        if self.contains_reserved_names(path) {
-            return Err(PathError::security_error(
-                "Path contains Windows reserved names",
-            ));
+        if let Some(span) = self.find_reserved_name(path, &path_str) {
+            return rejected("Path contains Windows reserved names", Some(span));
+        }
+
+        // Check component names against the target platform
+        if let Some(target) = self.policy.validation_target {
+            Self::check_portability(&path_str, target)?;
+        }
+
+        // Check that absolute paths stay inside the allowed roots
+        let in_allowed_root = self
+            .policy
+            .allowed_roots
+            .iter()
+            .any(|root| is_within(&path_str, root));
+        if !self.policy.allowed_roots.is_empty() && !in_allowed_root && is_absolute(&path_str) {
+            return rejected("Path is outside the allowed roots", None);
         }
 
         // Check for system directory access attempts
-        if Self::accesses_system_directories(path) {
-            return Err(PathError::security_error(
-                "Attempt to access system directories",
-            ));
+        if !in_allowed_root && let Some(span) = self.find_system_directory(&path_str) {
+            return rejected("Attempt to access system directories", Some(span));
         }
 
+        #[cfg(feature = "fs-security")]
+        self.check_physical(path)?;
+
         Ok(true)
     }
 
-    /// Detect path traversal patterns
-    fn detect_path_traversal(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
-        self.path_traversal_regex.is_match(&path_str)
+    /// Check that the physical location of a path stays inside the base
+    /// directory of the policy
+    ///
+    /// Each existing component is resolved with the filesystem, following
+    /// symlinks; components that do not exist yet are resolved lexically.
+    /// Always succeeds if the policy has no base directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::SecurityError` if the path resolves outside the
+    /// base directory, or `PathError` if an existing component cannot be
+    /// resolved.
+    #[cfg(feature = "fs-security")]
+    pub fn check_physical(&self, path: &Path) -> PathResult<bool> {
+        let Some(base) = &self.policy.base_dir else {
+            return Ok(true);
+        };
+        let base = PathParser::normalize_physical(Path::new(base))?;
+        let resolved = PathParser::normalize_physical(&base.join(path))?;
+        if resolved.starts_with(&base) {
+            Ok(true)
+        } else {
+            Err(PathError::security_error(format!(
+                "{} resolves outside {}",
+                path.display(),
+                base.display()
+            ))
+            .with_path(path.to_string_lossy()))
+        }
     }
 
-    /// Check for dangerous file patterns
-    fn contains_dangerous_patterns(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
+    /// Apply the policy's invisible character handling to a path
+    ///
+    /// Returns the path with the characters removed if the policy strips
+    /// them, and the path unchanged otherwise. With
+    /// [`InvisibleCharAction::Warn`] each character is reported to the
+    /// installed metrics sink as a security warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PathError::SecurityError` if the path contains invisible
+    /// characters and the policy rejects them.
+    pub fn check_unicode<'a>(&self, path: &'a str) -> PathResult<Cow<'a, str>> {
+        let found = find_suspicious_chars(path);
+        let Some(first) = found.first() else {
+            return Ok(Cow::Borrowed(path));
+        };
+        match self.policy.invisible_chars {
+            InvisibleCharAction::Reject => Err(PathError::security_error(format!(
+                "Path contains invisible character U+{:04X} at byte {}",
+                u32::from(first.ch),
+                first.index
+            ))
+            .with_path(path)
+            .with_span(first.index..first.index + first.ch.len_utf8())),
+            InvisibleCharAction::Strip => Ok(Cow::Owned(
+                path.chars()
+                    .filter(|&c| suspicious_kind(c).is_none())
+                    .collect(),
+            )),
+            InvisibleCharAction::Warn => {
+                for suspicious in &found {
+                    let warning = PathError::security_error(format!(
+                        "Path contains invisible character U+{:04X} at byte {}",
+                        u32::from(suspicious.ch),
+                        suspicious.index
+                    ))
+                    .with_path(path)
+                    .with_span(suspicious.index..suspicious.index + suspicious.ch.len_utf8());
+                    crate::metrics::emit(|m| m.record_security_warning(&warning));
+                }
+                Ok(Cow::Borrowed(path))
+            }
+        }
+    }
+
+    /// Find the `..` of a path traversal pattern
+    fn detect_path_traversal(path: &str) -> Option<Range<usize>> {
+        TRAVERSAL.find(path).map(|m| m.start()..m.start() + 2)
+    }
+
+    /// Find the first match of a dangerous file pattern
+    fn find_dangerous_pattern(&self, path: &str) -> Option<Range<usize>> {
         self.dangerous_patterns
             .iter()
-            .any(|re| re.is_match(&path_str))
+            .find_map(|re| re.find(path))
+            .map(|m| m.range())
     }
 
-    /// Check for Windows reserved names
-    #[allow(clippy::unused_self)]
-    fn contains_reserved_names(&self, path: &Path) -> bool {
-        #[cfg(target_os = "windows")]
+    /// Find a Windows reserved file name
+    ///
+    /// Only runs on hosts for which the policy enables the check; by default
+    /// that is Windows, to avoid false positives on valid Unix file names.
+    fn find_reserved_name(&self, path: &Path, path_str: &str) -> Option<Range<usize>> {
+        if !self
+            .policy
+            .checks_reserved_names(crate::platform::current_style())
         {
-            if let Some(file_name) = path.file_name() {
-                let name = file_name.to_string_lossy();
-                let name_without_ext = name.split('.').next().unwrap_or("");
-                self.reserved_names
-                    .iter()
-                    .any(|&reserved| name_without_ext.eq_ignore_ascii_case(reserved))
-            } else {
-                false
-            }
+            return None;
         }
-        #[cfg(not(target_os = "windows"))]
+        let name = path.file_name()?.to_string_lossy();
+        let name_without_ext = name.split('.').next().unwrap_or("");
+        if !WINDOWS_RESERVED_NAMES
+            .iter()
+            .any(|&reserved| name_without_ext.eq_ignore_ascii_case(reserved))
         {
-            // On non-Windows systems, we generally don't need to check for Windows reserved names
-            // unless we are specifically validating for cross-platform compatibility.
-            // For now, we skip this check to avoid false positives on valid Unix filenames.
-            let _ = path; // Suppress unused variable warning
-            false
+            return None;
         }
+        let start = path_str.rfind(name.as_ref())?;
+        Some(start..start + name.len())
     }
 
-    /// Check if path attempts to access system directories
-    fn accesses_system_directories(path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
-
-        #[cfg(target_os = "windows")]
-        {
-            let system_dirs = vec![
-                r"C:\Windows",
-                r"C:\System32",
-                r"C:\Program Files",
-                r"C:\ProgramData",
-            ];
-            system_dirs.iter().any(|&dir| path_str.starts_with(dir))
-        }
-
-        #[cfg(not(target_os = "windows"))]
-        {
-            // Common Unix system directories
-            // Covers Linux, macOS, FreeBSD, OpenBSD, Android, etc.
-            let system_dirs = vec![
-                "/bin",
-                "/sbin",
-                "/usr/bin",
-                "/usr/sbin",
-                "/etc",
-                "/root",
-                "/var",
-                "/lib",
-                "/boot",
-                "/dev",
-                "/proc",
-                "/sys",
-            ];
-
-            // Android specific system directories
-            #[cfg(target_os = "android")]
-            let system_dirs = {
-                let mut dirs = system_dirs;
-                dirs.extend_from_slice(&["/system", "/data", "/cache", "/vendor", "/oem", "/odm"]);
-                dirs
+    /// Check every component against the naming rules of a target
+    fn check_portability(path: &str, target: ValidationTarget) -> PathResult<()> {
+        let style = PathParser::detect_style(path);
+        let mut offset = 0;
+        for component in PathParser::components(path, style) {
+            let Component::Normal(name) = component else {
+                continue;
             };
-
-            // macOS specific system directories
-            #[cfg(target_os = "macos")]
-            let system_dirs = {
-                let mut dirs = system_dirs;
-                dirs.extend_from_slice(&[
-                    "/System", "/Library", "/private", "/Volumes", "/Network",
-                ]);
-                dirs
+            let start = path[offset..].find(&*name).map_or(offset, |i| offset + i);
+            offset = start + name.len();
+            let (issues, _) = crate::validation::check_component(&name, target);
+            let reason = match issues.first() {
+                None => continue,
+                Some(PortabilityIssue::InvalidChar(c)) => format!("invalid character {c:?}"),
+                Some(PortabilityIssue::ReservedName(reserved)) => {
+                    format!("reserved name {reserved}")
+                }
+                Some(PortabilityIssue::TrailingDotOrSpace) => "trailing dot or space".to_string(),
             };
-
-            system_dirs.iter().any(|&dir| path_str.starts_with(dir))
+            return Err(
+                PathError::security_error(format!("Path component '{name}' has {reason}"))
+                    .with_path(path)
+                    .with_span(start..offset),
+            );
         }
+        Ok(())
+    }
+
+    /// Find the system directory a path starts with
+    fn find_system_directory(&self, path: &str) -> Option<Range<usize>> {
+        self.policy
+            .system_dirs
+            .iter()
+            .find(|dir| path.starts_with(dir.as_str()))
+            .map(|dir| 0..dir.len())
     }
 
     /// Sanitize path by removing dangerous characters
+    ///
+    /// Produces a single file name of at most 255 bytes; use
+    /// [`Sanitizer`](crate::sanitize::Sanitizer) to keep the directory
+    /// structure or control the replacements.
     #[must_use]
     pub fn sanitize_path(path: &str) -> String {
         let mut sanitized = path.to_string();
@@ -193,9 +669,154 @@ impl PathSecurityChecker {
 
         // Limit path length
         if sanitized.len() > 255 {
-            sanitized = sanitized[..255].to_string();
+            let mut end = 255;
+            while !sanitized.is_char_boundary(end) {
+                end -= 1;
+            }
+            sanitized.truncate(end);
         }
 
         sanitized
     }
 }
+
+/// Find invisible and bidirectional control characters in a path
+#[must_use]
+pub fn find_suspicious_chars(path: &str) -> Vec<SuspiciousChar> {
+    path.char_indices()
+        .filter_map(|(index, ch)| {
+            suspicious_kind(ch).map(|kind| SuspiciousChar { index, ch, kind })
+        })
+        .collect()
+}
+
+/// Classify a character that can disguise a path when displayed
+fn suspicious_kind(ch: char) -> Option<SuspiciousCharKind> {
+    match ch {
+        '\u{061C}'
+        | '\u{200E}'
+        | '\u{200F}'
+        | '\u{202A}'..='\u{202E}'
+        | '\u{2066}'..='\u{2069}' => Some(SuspiciousCharKind::BidiControl),
+        '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}' => Some(SuspiciousCharKind::ZeroWidth),
+        '\u{00AD}'
+        | '\u{034F}'
+        | '\u{115F}'
+        | '\u{1160}'
+        | '\u{17B4}'
+        | '\u{17B5}'
+        | '\u{180B}'..='\u{180E}'
+        | '\u{2061}'..='\u{2064}'
+        | '\u{206A}'..='\u{206F}'
+        | '\u{3164}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FFA0}'
+        | '\u{FFF9}'..='\u{FFFB}'
+        | '\u{E0000}'..='\u{E007F}'
+        | '\u{E0100}'..='\u{E01EF}' => Some(SuspiciousCharKind::Invisible),
+        _ => None,
+    }
+}
+
+/// Compile dangerous patterns
+fn compile_patterns<S: AsRef<str>>(patterns: &[S]) -> PathResult<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Regex::new(pattern.as_ref()).map_err(|e| {
+                PathError::parse_error(format!("Invalid dangerous pattern: {e}")).with_source(e)
+            })
+        })
+        .collect()
+}
+
+/// Byte range of the stream suffix of a path (`:Zone.Identifier:$DATA`)
+fn stream_span(path: &str) -> Option<Range<usize>> {
+    let start = path.rfind(['\\', '/']).map_or(0, |i| i + 1);
+    let name = &path[start..];
+    // Skip the colon of a drive-relative path (`C:file.txt:stream`)
+    let skip = if start == 0 && name.as_bytes().get(1) == Some(&b':') {
+        2
+    } else {
+        0
+    };
+    let colon = name[skip..].find(':')?;
+    Some(start + skip + colon..path.len())
+}
+
+/// Check if a path string is absolute in either style
+fn is_absolute(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with(['/', '\\'])
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+/// Check if `path` is `root` or inside it, comparing whole components
+///
+/// Both separators are accepted; roots with a drive letter or UNC prefix
+/// are compared case-insensitively.
+fn is_within(path: &str, root: &str) -> bool {
+    let windows = root.starts_with(r"\\") || root.as_bytes().get(1) == Some(&b':');
+    let normalize = |s: &str| {
+        let s = s.replace('\\', "/");
+        if windows { s.to_lowercase() } else { s }
+    };
+    let path = normalize(path);
+    let root = normalize(root);
+    let root = root.trim_end_matches('/');
+    path == root
+        || path
+            .strip_prefix(root)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// System directories rejected by the default policy on this platform
+fn default_system_dirs() -> Vec<&'static str> {
+    #[cfg(target_os = "windows")]
+    {
+        vec![
+            r"C:\Windows",
+            r"C:\System32",
+            r"C:\Program Files",
+            r"C:\ProgramData",
+        ]
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        // Common Unix system directories
+        // Covers Linux, macOS, FreeBSD, OpenBSD, Android, etc.
+        let system_dirs = vec![
+            "/bin",
+            "/sbin",
+            "/usr/bin",
+            "/usr/sbin",
+            "/etc",
+            "/root",
+            "/var",
+            "/lib",
+            "/boot",
+            "/dev",
+            "/proc",
+            "/sys",
+        ];
+
+        // Android specific system directories
+        #[cfg(target_os = "android")]
+        let system_dirs = {
+            let mut dirs = system_dirs;
+            dirs.extend_from_slice(&["/system", "/data", "/cache", "/vendor", "/oem", "/odm"]);
+            dirs
+        };
+
+        // macOS specific system directories
+        #[cfg(target_os = "macos")]
+        let system_dirs = {
+            let mut dirs = system_dirs;
+            dirs.extend_from_slice(&["/System", "/Library", "/private", "/Volumes", "/Network"]);
+            dirs
+        };
+
+        system_dirs
+    }
+}
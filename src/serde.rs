@@ -0,0 +1,125 @@
+//! Serde helpers for [`CrossPath`]
+//!
+//! `CrossPath` serializes as a plain path string: in the configured
+//! [`PathConfig::style`](crate::PathConfig::style), or as written if that is
+//! `Auto`. Deserializing parses the string with the
+//! [current](crate::PathConfig::current) default configuration.
+//!
+//! The modules below pin the style of a single field regardless of its
+//! configuration:
+//!
+//! ```rust
+//! use cross_path::CrossPath;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Job {
+//!     #[serde(with = "cross_path::serde::as_unix")]
+//!     input: CrossPath,
+//!     #[serde(with = "cross_path::serde::as_windows")]
+//!     output: CrossPath,
+//! }
+//! ```
+
+use crate::{CrossPath, PathStyle};
+use ::serde::de::{Deserialize, Deserializer, Error as _};
+use ::serde::ser::{Error as _, Serializer};
+
+impl ::serde::Serialize for CrossPath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_as(self, self.config().style, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CrossPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        CrossPath::new(path).map_err(D::Error::custom)
+    }
+}
+
+/// Serialize a path string in `style` (`Auto` keeps it as written)
+fn serialize_as<S: Serializer>(
+    path: &CrossPath,
+    style: PathStyle,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let text = match style {
+        PathStyle::Auto => path.to_str().map(ToString::to_string).ok_or_else(|| {
+            S::Error::custom(format!(
+                "Path is not valid Unicode: {}",
+                path.as_original().display()
+            ))
+        })?,
+        style => path.to_style(style).map_err(S::Error::custom)?,
+    };
+    serializer.serialize_str(&text)
+}
+
+/// Serialize as a Unix path string
+pub mod as_unix {
+    use super::{CrossPath, Deserializer, PathStyle, Serializer};
+
+    /// Serialize `path` converted to Unix style
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error if the path cannot be converted.
+    pub fn serialize<S: Serializer>(path: &CrossPath, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_as(path, PathStyle::Unix, serializer)
+    }
+
+    /// Deserialize a path string
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error if the string is not a valid path.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<CrossPath, D::Error> {
+        super::Deserialize::deserialize(deserializer)
+    }
+}
+
+/// Serialize as a Windows path string
+pub mod as_windows {
+    use super::{CrossPath, Deserializer, PathStyle, Serializer};
+
+    /// Serialize `path` converted to Windows style
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error if the path cannot be converted.
+    pub fn serialize<S: Serializer>(path: &CrossPath, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_as(path, PathStyle::Windows, serializer)
+    }
+
+    /// Deserialize a path string
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error if the string is not a valid path.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<CrossPath, D::Error> {
+        super::Deserialize::deserialize(deserializer)
+    }
+}
+
+/// Serialize the path as originally written
+pub mod as_original {
+    use super::{CrossPath, Deserializer, PathStyle, Serializer};
+
+    /// Serialize `path` as written
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error if the path is not valid Unicode.
+    pub fn serialize<S: Serializer>(path: &CrossPath, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_as(path, PathStyle::Auto, serializer)
+    }
+
+    /// Deserialize a path string
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error if the string is not a valid path.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<CrossPath, D::Error> {
+        super::Deserialize::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,237 @@
+//! Streaming conversion of path lists
+//!
+//! Converts newline- or NUL-delimited path lists record by record with
+//! bounded memory, so multi-gigabyte manifests can be migrated between
+//! Windows and Unix hosts. Progress is periodically written to a checkpoint
+//! file; rerunning an interrupted conversion with the same checkpoint
+//! resumes where it stopped.
+
+use crate::{CrossPath, PathConfig, PathError, PathResult, PathStyle};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Record delimiter of a path list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Delimiter {
+    /// One path per line (`\n`, a preceding `\r` is stripped)
+    #[default]
+    Newline,
+    /// NUL-terminated paths, as produced by `find -print0`
+    Nul,
+}
+
+impl Delimiter {
+    fn byte(self) -> u8 {
+        match self {
+            Self::Newline => b'\n',
+            Self::Nul => b'\0',
+        }
+    }
+}
+
+/// Options for [`convert_file`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StreamOptions {
+    /// Record delimiter used for input and output
+    pub delimiter: Delimiter,
+    /// Style to convert every path to
+    pub target_style: PathStyle,
+    /// Configuration applied to every path
+    pub config: PathConfig,
+    /// Copy records that fail to convert unchanged instead of aborting
+    pub skip_invalid: bool,
+    /// Checkpoint file used to resume an interrupted conversion
+    pub checkpoint: Option<PathBuf>,
+    /// Number of records between checkpoints
+    pub checkpoint_interval: u64,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: Delimiter::default(),
+            target_style: PathStyle::Auto,
+            config: PathConfig::current(),
+            skip_invalid: false,
+            checkpoint: None,
+            checkpoint_interval: 10_000,
+        }
+    }
+}
+
+/// Summary of a [`convert_file`] run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StreamReport {
+    /// Records processed in this run
+    pub records: u64,
+    /// Records copied unchanged because they failed to convert
+    pub failed: u64,
+    /// Record number the run resumed from (0 for a fresh run)
+    pub resumed_from: u64,
+}
+
+/// Progress saved in a checkpoint file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Checkpoint {
+    input_offset: u64,
+    output_offset: u64,
+    records: u64,
+}
+
+impl Checkpoint {
+    fn load(path: &Path) -> PathResult<Option<Self>> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let fields: Vec<u64> = content
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|_| {
+                PathError::parse_error(format!("Invalid checkpoint: {}", path.display()))
+            })?;
+        let [input_offset, output_offset, records] = fields[..] else {
+            return Err(PathError::parse_error(format!(
+                "Invalid checkpoint: {}",
+                path.display()
+            )));
+        };
+
+        Ok(Some(Self {
+            input_offset,
+            output_offset,
+            records,
+        }))
+    }
+
+    /// Write the checkpoint atomically (temporary file, then rename)
+    fn save(self, path: &Path) -> PathResult<()> {
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        fs::write(
+            &temp,
+            format!(
+                "{} {} {}\n",
+                self.input_offset, self.output_offset, self.records
+            ),
+        )?;
+        fs::rename(&temp, path)?;
+        Ok(())
+    }
+}
+
+/// Convert a path list file
+///
+/// Each record of `input` is converted to `options.target_style` and written
+/// to `output` with the same delimiter. Memory use is bounded by the longest
+/// record. With a checkpoint configured, an existing checkpoint resumes the
+/// conversion (the output is truncated to the last checkpointed position);
+/// the checkpoint is removed once the conversion completes.
+///
+/// # Errors
+///
+/// Returns `PathError` if a file cannot be read or written, the checkpoint
+/// is invalid, or a record fails to convert and `skip_invalid` is not set.
+pub fn convert_file(
+    input: &Path,
+    output: &Path,
+    options: &StreamOptions,
+) -> PathResult<StreamReport> {
+    let checkpoint_path = options.checkpoint.as_deref();
+    let resume = match checkpoint_path {
+        Some(path) => Checkpoint::load(path)?,
+        None => None,
+    };
+
+    let mut reader = BufReader::new(File::open(input)?);
+    let mut output_file = if resume.is_some() {
+        OpenOptions::new().write(true).open(output)?
+    } else {
+        File::create(output)?
+    };
+
+    let mut progress = resume.unwrap_or_default();
+    if resume.is_some() {
+        reader.seek(SeekFrom::Start(progress.input_offset))?;
+        output_file.set_len(progress.output_offset)?;
+        output_file.seek(SeekFrom::Start(progress.output_offset))?;
+    }
+    let mut writer = BufWriter::new(output_file);
+
+    let mut report = StreamReport {
+        resumed_from: progress.records,
+        ..StreamReport::default()
+    };
+    let delimiter = options.delimiter.byte();
+    let mut record = Vec::new();
+
+    loop {
+        record.clear();
+        let read = reader.read_until(delimiter, &mut record)?;
+        if read == 0 {
+            break;
+        }
+
+        let converted = convert_record(&record, options);
+        let bytes = match converted {
+            Ok(converted) => converted.into_bytes(),
+            Err(e) if !options.skip_invalid => {
+                return Err(PathError::parse_error(format!(
+                    "Record {}: {e}",
+                    progress.records + 1
+                )));
+            }
+            Err(_) => {
+                report.failed += 1;
+                strip_delimiter(&record, options.delimiter).to_vec()
+            }
+        };
+        writer.write_all(&bytes)?;
+        writer.write_all(&[delimiter])?;
+
+        progress.input_offset += read as u64;
+        progress.output_offset += bytes.len() as u64 + 1;
+        progress.records += 1;
+        report.records += 1;
+
+        if let Some(path) = checkpoint_path
+            && progress.records % options.checkpoint_interval.max(1) == 0
+        {
+            writer.flush()?;
+            progress.save(path)?;
+        }
+    }
+
+    writer.flush()?;
+    if let Some(path) = checkpoint_path {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+    }
+
+    Ok(report)
+}
+
+/// Convert a single record
+fn convert_record(record: &[u8], options: &StreamOptions) -> PathResult<String> {
+    let text = std::str::from_utf8(strip_delimiter(record, options.delimiter))
+        .map_err(|e| PathError::encoding_error(e.to_string()).with_source(e))?;
+    if text.is_empty() {
+        return Ok(String::new());
+    }
+    CrossPath::with_config(text, options.config.clone())?.to_style(options.target_style)
+}
+
+/// Remove the trailing delimiter (and `\r` for newline-delimited input)
+fn strip_delimiter(record: &[u8], delimiter: Delimiter) -> &[u8] {
+    let record = record.strip_suffix(&[delimiter.byte()]).unwrap_or(record);
+    match delimiter {
+        Delimiter::Newline => record.strip_suffix(b"\r").unwrap_or(record),
+        Delimiter::Nul => record,
+    }
+}
@@ -0,0 +1,185 @@
+//! User-defined path styles
+//!
+//! Notations this crate does not know, such as the `vol0:/data/file` paths
+//! of a proprietary VFS, are added by implementing [`StyleDefinition`] and
+//! registering it with [`registry::register_style`]. The returned
+//! [`PathStyle::Custom`] value is then accepted wherever a style is, and
+//! paths in the style are auto-detected by their root.
+//!
+//! Custom styles convert through Unix paths: the definition maps its roots
+//! to Unix directories and back, and the regular drive mappings take care
+//! of the rest.
+//!
+//! ```rust
+//! use cross_path::style::StyleDefinition;
+//! use cross_path::{PathConverter, PathConfig, PathStyle, registry};
+//! use std::sync::Arc;
+//!
+//! struct Volumes;
+//!
+//! impl StyleDefinition for Volumes {
+//!     fn name(&self) -> &'static str {
+//!         "volumes"
+//!     }
+//!
+//!     fn split_root<'a>(&self, path: &'a str) -> Option<(&'a str, &'a str)> {
+//!         let (root, rest) = path.split_once(':')?;
+//!         root.starts_with("vol").then_some((root, rest))
+//!     }
+//!
+//!     fn format_root(&self, root: &str) -> String {
+//!         format!("{root}:")
+//!     }
+//!
+//!     fn root_to_unix(&self, root: &str) -> Option<String> {
+//!         Some(format!("/volumes/{root}"))
+//!     }
+//!
+//!     fn unix_to_root(&self, path: &str) -> Option<(String, String)> {
+//!         let (root, rest) = path.strip_prefix("/volumes/")?.split_once('/')?;
+//!         Some((root.to_string(), rest.to_string()))
+//!     }
+//! }
+//!
+//! let style = registry::register_style(Arc::new(Volumes));
+//! let converter = PathConverter::new(&PathConfig::default());
+//! assert_eq!(converter.convert("vol0:/data/file", PathStyle::Unix).unwrap(), "/volumes/vol0/data/file");
+//! assert_eq!(converter.convert("/volumes/vol1/logs", style).unwrap(), "vol1:/logs");
+//! ```
+
+use crate::{PathError, PathResult, PathStyle, registry};
+use std::sync::Arc;
+
+/// Name of a registered custom path style
+///
+/// Obtained from [`registry::register_style`] as `PathStyle::Custom`, or
+/// with [`PathStyle::custom`] for a style registered elsewhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomStyle(&'static str);
+
+impl CustomStyle {
+    /// Refer to the style registered under `name`
+    #[must_use]
+    pub const fn new(name: &'static str) -> Self {
+        Self(name)
+    }
+
+    /// Get the style name
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        self.0
+    }
+}
+
+/// Definition of a custom path style
+///
+/// A path in the style is an optional root (`vol0:`) followed by
+/// components joined with [`separator`](Self::separator). Paths without a
+/// root are relative.
+pub trait StyleDefinition: Send + Sync {
+    /// Unique name the style is registered under
+    fn name(&self) -> &'static str;
+
+    /// Component separator (default `/`)
+    fn separator(&self) -> char {
+        '/'
+    }
+
+    /// Split a path into its root name and the remainder
+    ///
+    /// Returns `None` if the path has no root of this style, which also
+    /// means it is not auto-detected as this style. The remainder may start
+    /// with a separator.
+    fn split_root<'a>(&self, path: &'a str) -> Option<(&'a str, &'a str)>;
+
+    /// Format a root name for output, e.g. `vol0` as `vol0:` (default: as is)
+    ///
+    /// The separator and the remaining components are appended.
+    fn format_root(&self, root: &str) -> String {
+        root.to_string()
+    }
+
+    /// Map a root name to the absolute Unix directory it corresponds to
+    ///
+    /// Returns `None` if the root is not mapped, which fails the
+    /// conversion with `PathError::DriveMappingError`.
+    fn root_to_unix(&self, root: &str) -> Option<String>;
+
+    /// Map an absolute Unix path to a root name and the `/`-separated path
+    /// below it
+    ///
+    /// Returns `None` if the path lies under none of the roots, which fails
+    /// the conversion with `PathError::DriveMappingError`.
+    fn unix_to_root(&self, path: &str) -> Option<(String, String)>;
+}
+
+/// Look up the definition of a custom style
+///
+/// # Errors
+///
+/// Returns `PathError::UnsupportedFormat` if no style of that name is
+/// registered.
+pub(crate) fn definition(style: CustomStyle) -> PathResult<Arc<dyn StyleDefinition>> {
+    registry::style_definition(style.name()).ok_or_else(|| {
+        PathError::unsupported_format(format!("Unregistered path style '{}'", style.name()))
+    })
+}
+
+/// Find the registered style a path is written in
+pub(crate) fn detect(path: &str) -> Option<PathStyle> {
+    registry::style_definitions()
+        .into_iter()
+        .find(|definition| definition.split_root(path).is_some())
+        .map(|definition| PathStyle::custom(definition.name()))
+}
+
+/// Convert a path written in a custom style to a Unix path
+pub(crate) fn to_unix(definition: &dyn StyleDefinition, path: &str) -> PathResult<String> {
+    let separator = definition.separator();
+    let join = |rest: &str| {
+        rest.split(separator)
+            .filter(|c| !c.is_empty())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+
+    let Some((root, rest)) = definition.split_root(path) else {
+        return Ok(join(path));
+    };
+    let base = definition.root_to_unix(root).ok_or_else(|| {
+        PathError::drive_mapping_error(format!(
+            "No Unix mapping for {} root '{root}'",
+            definition.name()
+        ))
+    })?;
+    let rest = join(rest);
+    let base = base.trim_end_matches('/');
+    Ok(match (base.is_empty(), rest.is_empty()) {
+        (true, _) => format!("/{rest}"),
+        (false, true) => base.to_string(),
+        (false, false) => format!("{base}/{rest}"),
+    })
+}
+
+/// Convert a Unix path to a custom style
+pub(crate) fn from_unix(definition: &dyn StyleDefinition, path: &str) -> PathResult<String> {
+    let separator = definition.separator().to_string();
+    let join = |rest: &str| {
+        rest.split('/')
+            .filter(|c| !c.is_empty())
+            .collect::<Vec<_>>()
+            .join(&separator)
+    };
+
+    if !path.starts_with('/') {
+        return Ok(join(path));
+    }
+    let (root, rest) = definition.unix_to_root(path).ok_or_else(|| {
+        PathError::drive_mapping_error(format!("{path} is not under a {} root", definition.name()))
+    })?;
+    Ok(format!(
+        "{}{separator}{}",
+        definition.format_root(&root),
+        join(&rest)
+    ))
+}
@@ -0,0 +1,218 @@
+//! Converting the paths embedded in text files
+//!
+//! Rewrites build scripts, project files and logs (`.props`, `.cmake`,
+//! `.sh`, ...) for another platform: every path found by
+//! [`PathScanner`] is converted to the target style and everything else is
+//! copied byte for byte, including line endings. Input is processed line by
+//! line, so memory use is bounded by the longest line.
+//!
+//! Trailing separators are kept. Paths already written in the target style
+//! are left as they are, so a `C:/proj` in a CMake file stays in the
+//! forward-slash form CMake expects when converting to Windows. `file://`
+//! URLs stay URLs and have the path they address converted.
+//!
+//! ```rust
+//! use cross_path::text::{TextOptions, convert_text};
+//! use cross_path::{DriveMap, PathConfig, PathStyle};
+//!
+//! let options = TextOptions {
+//!     target_style: PathStyle::Unix,
+//!     config: PathConfig {
+//!         drive_mappings: DriveMap::from_pairs([("C:", "/mnt/c")])?,
+//!         ..PathConfig::default()
+//!     },
+//!     ..TextOptions::default()
+//! };
+//! let script = r#"set(SRC "C:\proj\src")  # built on /home/me"#;
+//! assert_eq!(
+//!     convert_text(script, &options)?,
+//!     r#"set(SRC "/mnt/c/proj/src")  # built on /home/me"#
+//! );
+//! # Ok::<(), cross_path::PathError>(())
+//! ```
+
+use crate::scan::{PathMatch, PathScanner, ScanKind};
+use crate::{PathConfig, PathConverter, PathError, PathResult, PathStyle, url};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Options for [`convert_text`] and [`convert_stream`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextOptions {
+    /// Style to convert every path to
+    pub target_style: PathStyle,
+    /// Configuration applied to every path
+    pub config: PathConfig,
+    /// Which kinds of paths are converted
+    pub scanner: PathScanner,
+    /// Leave paths that fail to convert unchanged instead of aborting
+    ///
+    /// On by default, since not everything that looks like a path in free
+    /// text is one.
+    pub skip_invalid: bool,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            target_style: PathStyle::Auto,
+            config: PathConfig::current(),
+            scanner: PathScanner::new(),
+            skip_invalid: true,
+        }
+    }
+}
+
+/// Summary of a [`convert_stream`] run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextReport {
+    /// Lines processed
+    pub lines: u64,
+    /// Paths converted
+    pub converted: u64,
+    /// Paths left unchanged because they failed to convert
+    pub failed: u64,
+}
+
+/// Convert the paths in a string
+///
+/// Returns the text borrowed if no path changed.
+///
+/// # Errors
+///
+/// Returns `PathError` if a path fails to convert and `skip_invalid` is not
+/// set.
+pub fn convert_text<'t>(text: &'t str, options: &TextOptions) -> PathResult<Cow<'t, str>> {
+    let converter = TextConverter::new(options);
+    let mut report = TextReport::default();
+    converter.convert_line(text, &mut report)
+}
+
+/// Convert the paths in a text stream
+///
+/// Lines that are not valid UTF-8 are copied unchanged.
+///
+/// # Errors
+///
+/// Returns `PathError` if reading or writing fails, or a path fails to
+/// convert and `skip_invalid` is not set.
+pub fn convert_stream<R: BufRead, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    options: &TextOptions,
+) -> PathResult<TextReport> {
+    let converter = TextConverter::new(options);
+    let mut report = TextReport::default();
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        report.lines += 1;
+        match std::str::from_utf8(&line) {
+            Ok(text) => {
+                let output = converter.convert_line(text, &mut report).map_err(|e| {
+                    PathError::parse_error(format!("Line {}: {e}", report.lines)).with_source(e)
+                })?;
+                writer.write_all(output.as_bytes())?;
+            }
+            Err(_) => writer.write_all(&line)?,
+        }
+    }
+
+    writer.flush()?;
+    Ok(report)
+}
+
+/// Convert the paths in a text file, writing the result to `output`
+///
+/// # Errors
+///
+/// See [`convert_stream`].
+pub fn convert_file(input: &Path, output: &Path, options: &TextOptions) -> PathResult<TextReport> {
+    let reader = BufReader::new(File::open(input)?);
+    let writer = BufWriter::new(File::create(output)?);
+    convert_stream(reader, writer, options)
+}
+
+/// Converter shared by all lines of a run
+struct TextConverter<'o> {
+    options: &'o TextOptions,
+    converter: PathConverter,
+    target_style: PathStyle,
+}
+
+impl<'o> TextConverter<'o> {
+    fn new(options: &'o TextOptions) -> Self {
+        let converter = PathConverter::new(&options.config);
+        let target_style = converter.resolve_target(options.target_style);
+        Self {
+            options,
+            converter,
+            target_style,
+        }
+    }
+
+    fn convert_line<'t>(&self, text: &'t str, report: &mut TextReport) -> PathResult<Cow<'t, str>> {
+        let mut error = None;
+        let converted = self.options.scanner.replace_all(text, |found| {
+            if error.is_some() {
+                return None;
+            }
+            match self.convert_path(found) {
+                Ok(Some(converted)) if converted != found.text => {
+                    report.converted += 1;
+                    Some(converted)
+                }
+                Ok(_) => None,
+                Err(_) if self.options.skip_invalid => {
+                    report.failed += 1;
+                    None
+                }
+                Err(e) => {
+                    error = Some(e);
+                    None
+                }
+            }
+        });
+        match error {
+            Some(e) => Err(e),
+            None => Ok(converted),
+        }
+    }
+
+    /// Convert one path, or return `None` if it is already in the target
+    /// style
+    fn convert_path(&self, found: &PathMatch<'_>) -> PathResult<Option<String>> {
+        let in_target = match found.kind {
+            ScanKind::Windows | ScanKind::Unc => self.target_style == PathStyle::Windows,
+            ScanKind::Unix => self.target_style == PathStyle::Unix,
+            ScanKind::FileUrl => {
+                let path = url::to_path(found.text)?;
+                let target = match self.target_style {
+                    PathStyle::Windows => PathStyle::Windows,
+                    _ => PathStyle::Unix,
+                };
+                let converted = self.converter.convert(&path, target)?;
+                return url::from_path(&converted, target).map(Some);
+            }
+        };
+        if in_target {
+            return Ok(None);
+        }
+        let mut converted = self.converter.convert(found.text, self.target_style)?;
+        // Keep a trailing separator, which build files use to mark
+        // directories (`<OutDir>C:\out\</OutDir>`)
+        if found.text.ends_with(['/', '\\']) && !converted.ends_with(['/', '\\']) {
+            converted.push(match self.target_style {
+                PathStyle::Windows => '\\',
+                _ => '/',
+            });
+        }
+        Ok(Some(converted))
+    }
+}
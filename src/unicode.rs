@@ -1,5 +1,6 @@
 use crate::{PathError, PathResult};
 use encoding_rs::{UTF_8, UTF_16LE, WINDOWS_1252};
+use unicode_normalization::{UnicodeNormalization, is_nfc};
 
 /// Unicode encoding handler for path strings
 #[derive(Debug, Clone, Copy)]
@@ -61,6 +62,21 @@ impl UnicodeHandler {
         Ok(encoded.into_owned())
     }
 
+    /// Check if a string is in Unicode Normalization Form C
+    #[must_use]
+    pub fn is_nfc(text: &str) -> bool {
+        is_nfc(text)
+    }
+
+    /// Convert a string to Unicode Normalization Form C
+    ///
+    /// macOS file systems commonly store names decomposed (NFD), while
+    /// Windows and Linux tools expect composed (NFC) names.
+    #[must_use]
+    pub fn to_nfc(text: &str) -> String {
+        text.nfc().collect()
+    }
+
     /// Normalize Windows path by removing invalid characters
     #[must_use]
     pub fn normalize_windows_path(path: &str) -> String {
@@ -0,0 +1,274 @@
+//! `file://` and `smb://` URL parsing and emission
+//!
+//! Handles percent-encoding, drive letters (`file:///C:/Users`, including
+//! the legacy `file:///C|/Users` form) and UNC hosts
+//! (`file://server/share/dir`). `localhost` is treated as the local machine.
+//!
+//! SMB URLs (`smb://server/share/dir`) map one-to-one to UNC paths
+//! (`\\server\share\dir`) and their Unix form (`//server/share/dir`).
+
+use crate::{PathError, PathResult, PathStyle};
+use std::fmt::Write;
+
+/// Convert a `file://` URL to a path string
+///
+/// Drive and UNC URLs yield Windows paths, all others Unix paths. Query
+/// strings and fragments are ignored.
+///
+/// # Errors
+///
+/// Returns `PathError::InvalidPath` if the URL does not use the `file`
+/// scheme, contains malformed percent-escapes or a segment that decodes to
+/// a separator or NUL, and
+/// `PathError::EncodingError` if the decoded path is not valid UTF-8.
+pub fn to_path(url: &str) -> PathResult<String> {
+    let rest = url
+        .get(..5)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("file:"))
+        .map(|_| &url[5..])
+        .ok_or_else(|| PathError::invalid_path(format!("Not a file URL: {url}")))?;
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+
+    // `file://host/path` carries an authority, `file:/path` does not
+    let (host, path) = match rest.strip_prefix("//") {
+        Some(authority) => match authority.find('/') {
+            Some(index) => authority.split_at(index),
+            None => (authority, "/"),
+        },
+        None => ("", rest),
+    };
+    let host = percent_decode(host)?;
+    let path = path
+        .split('/')
+        .map(decode_segment)
+        .collect::<PathResult<Vec<_>>>()?
+        .join("/");
+
+    if !host.is_empty() && !host.eq_ignore_ascii_case("localhost") {
+        return Ok(format!(r"\\{host}{}", path.replace('/', "\\")));
+    }
+
+    let bytes = path.as_bytes();
+    if bytes.len() >= 3
+        && bytes[0] == b'/'
+        && bytes[1].is_ascii_alphabetic()
+        && matches!(bytes[2], b':' | b'|')
+        && matches!(bytes.get(3), None | Some(b'/'))
+    {
+        let drive = char::from(bytes[1]).to_ascii_uppercase();
+        let rest = path[3..].replace('/', "\\");
+        return Ok(format!(
+            "{drive}:{}",
+            if rest.is_empty() { "\\" } else { &rest }
+        ));
+    }
+
+    if path.is_empty() {
+        return Err(PathError::invalid_path(format!(
+            "File URL has no path: {url}"
+        )));
+    }
+    Ok(path)
+}
+
+/// Convert an absolute path string written in `style` to a `file://` URL
+///
+/// Characters outside the URL path set (including spaces, `%`, `?`, `#`
+/// and all non-ASCII characters) are percent-encoded as UTF-8.
+///
+/// # Errors
+///
+/// Returns `PathError::InvalidPath` if the path is not absolute.
+pub fn from_path(path: &str, style: PathStyle) -> PathResult<String> {
+    let windows = style == PathStyle::Windows;
+    let path = if windows {
+        crate::converter::strip_extended_prefix(path).replace('\\', "/")
+    } else {
+        path.to_string()
+    };
+
+    if let Some(unc) = path.strip_prefix("//") {
+        let (host, rest) = unc.split_once('/').unwrap_or((unc, ""));
+        return Ok(format!(
+            "file://{}/{}",
+            percent_encode(host),
+            percent_encode(rest)
+        ));
+    }
+
+    let bytes = path.as_bytes();
+    let is_drive = windows
+        && bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && bytes[2] == b'/';
+    if is_drive {
+        return Ok(format!("file:///{}", percent_encode(&path)));
+    }
+    if path.starts_with('/') {
+        return Ok(format!("file://{}", percent_encode(&path)));
+    }
+
+    Err(PathError::invalid_path(format!(
+        "File URLs require an absolute path: {path}"
+    )))
+}
+
+/// Check if a string is an `smb://` URL
+#[must_use]
+pub fn is_smb_url(text: &str) -> bool {
+    text.get(..6)
+        .is_some_and(|scheme| scheme.eq_ignore_ascii_case("smb://"))
+}
+
+/// Convert an `smb://` URL to a UNC path
+///
+/// Percent-escapes are decoded; user information and the port are
+/// dropped, since UNC paths cannot carry them, and so are query strings
+/// and fragments. `smb://server` yields the bare server `\\server`.
+///
+/// # Errors
+///
+/// Returns `PathError::InvalidPath` if the URL does not use the `smb`
+/// scheme, has no host, contains malformed percent-escapes or a segment that
+/// decodes to a separator or NUL, and
+/// `PathError::EncodingError` if the decoded path is not valid UTF-8.
+pub fn smb_to_unc(url: &str) -> PathResult<String> {
+    if !is_smb_url(url) {
+        return Err(PathError::invalid_path(format!("Not an SMB URL: {url}")));
+    }
+    let rest = url[6..].split(['?', '#']).next().unwrap_or_default();
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    let host = match host.rfind(':') {
+        // Keep the colons of a bracketed IPv6 literal
+        Some(colon) if !host[colon..].contains(']') => &host[..colon],
+        _ => host,
+    };
+    let host = percent_decode(host)?;
+    if host.is_empty() {
+        return Err(PathError::invalid_path(format!(
+            "SMB URL has no host: {url}"
+        )));
+    }
+
+    let mut unc = format!(r"\\{host}");
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        unc.push('\\');
+        unc.push_str(&decode_segment(segment)?);
+    }
+    Ok(unc)
+}
+
+/// Convert a UNC path to an `smb://` URL
+///
+/// Accepts `\\server\share\dir`, its extended-length form
+/// (`\\?\UNC\server\share\dir`) and the Unix form `//server/share/dir`.
+/// Characters outside the URL path set are percent-encoded as UTF-8.
+///
+/// # Errors
+///
+/// Returns `PathError::InvalidPath` if the path is not a UNC path.
+pub fn unc_to_smb(path: &str) -> PathResult<String> {
+    let path = crate::converter::strip_extended_prefix(path);
+    let unc = path
+        .strip_prefix(r"\\")
+        .or_else(|| path.strip_prefix("//"))
+        .filter(|unc| !unc.starts_with(['\\', '/']) && !unc.is_empty())
+        .ok_or_else(|| PathError::invalid_path(format!("Not a UNC path: {path}")))?;
+
+    let mut segments = unc.split(['\\', '/']).filter(|segment| !segment.is_empty());
+    let host = segments.next().unwrap_or_default();
+    let segments: Vec<&str> = segments.collect();
+    Ok(smb_url(host, &segments))
+}
+
+/// Build an `smb://` URL from a host and path segments
+pub(crate) fn smb_url<S: AsRef<str>>(host: &str, segments: &[S]) -> String {
+    // Brackets of IPv6 literals belong to the host syntax
+    let mut url = format!(
+        "smb://{}",
+        percent_encode(host).replace("%5B", "[").replace("%5D", "]")
+    );
+    for segment in segments {
+        url.push('/');
+        url.push_str(&percent_encode(segment.as_ref()));
+    }
+    url
+}
+
+/// Percent-encode everything except unreserved and path characters
+fn percent_encode(text: &str) -> String {
+    percent_encode_bytes(text.as_bytes())
+}
+
+/// Percent-encode raw bytes, such as a non-UTF-8 Unix path
+pub(crate) fn percent_encode_bytes(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b'/'
+            | b'!'
+            | b'$'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b';'
+            | b'='
+            | b':'
+            | b'@' => encoded.push(char::from(byte)),
+            _ => {
+                let _ = write!(encoded, "%{byte:02X}");
+            }
+        }
+    }
+    encoded
+}
+
+/// Decode one path segment, refusing escapes that would split it
+fn decode_segment(segment: &str) -> PathResult<String> {
+    let decoded = percent_decode(segment)?;
+    if decoded.contains(['/', '\\', '\0']) {
+        return Err(PathError::invalid_path(format!(
+            "URL segment decodes to a separator or NUL: {segment}"
+        )));
+    }
+    Ok(decoded)
+}
+
+/// Decode `%XX` escapes as UTF-8
+pub(crate) fn percent_decode(text: &str) -> PathResult<String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = text
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| {
+                    PathError::invalid_path(format!("Malformed percent-escape in {text}"))
+                })?;
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    String::from_utf8(decoded)
+        .map_err(|_| PathError::encoding_error(format!("URL is not valid UTF-8: {text}")))
+}
@@ -0,0 +1,404 @@
+//! Cross-platform name validation
+//!
+//! Checks path components against the naming rules of a target platform
+//! independent of the host, so paths can be validated on Linux before they
+//! are created on Windows (and vice versa).
+
+use crate::parser::WINDOWS_INVALID_CHARS;
+use crate::{PathError, PathResult, PathStyle};
+use std::ops::Range;
+
+/// Windows reserved device names
+pub(crate) const WINDOWS_RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Platform whose naming rules a path must satisfy
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum ValidationTarget {
+    /// Windows rules (invalid characters, reserved names, trailing dots/spaces)
+    Windows,
+    /// Unix rules (no NUL characters)
+    Unix,
+    /// Both sets of rules
+    #[default]
+    Both,
+}
+
+impl ValidationTarget {
+    /// Check if Windows rules apply
+    #[must_use]
+    pub fn includes_windows(self) -> bool {
+        matches!(self, Self::Windows | Self::Both)
+    }
+
+    /// Check if Unix rules apply
+    #[must_use]
+    pub fn includes_unix(self) -> bool {
+        matches!(self, Self::Unix | Self::Both)
+    }
+}
+
+impl From<PathStyle> for ValidationTarget {
+    fn from(style: PathStyle) -> Self {
+        match style {
+            // Cygwin and MSYS2 store files on Windows file systems
+            PathStyle::Windows | PathStyle::Cygwin | PathStyle::Msys => Self::Windows,
+            PathStyle::Unix | PathStyle::Custom(_) => Self::Unix,
+            PathStyle::Auto => crate::platform::current_style().into(),
+        }
+    }
+}
+
+/// Problem with a single path component
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PortabilityIssue {
+    /// Character not allowed by the target
+    InvalidChar(char),
+    /// Windows reserved device name (`CON`, `NUL`, `COM1`, ...)
+    ReservedName(String),
+    /// Trailing dot or space, which Windows silently strips
+    TrailingDotOrSpace,
+}
+
+/// Proposed fix for a path component
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PortabilityFix {
+    /// Index of the component (drive and UNC server/share excluded)
+    pub index: usize,
+    /// Component as written
+    pub component: String,
+    /// Problems found in the component
+    pub issues: Vec<PortabilityIssue>,
+    /// Proposed replacement component
+    pub replacement: String,
+}
+
+/// Check a single component against the target's rules
+///
+/// Returns the problems found and a fixed name: invalid characters become
+/// `_`, trailing dots/spaces are replaced by `_`, and reserved names get `_`
+/// appended to their stem (`con.txt` → `con_.txt`).
+#[must_use]
+pub fn check_component(
+    component: &str,
+    target: ValidationTarget,
+) -> (Vec<PortabilityIssue>, String) {
+    let mut issues = Vec::new();
+    let mut fixed = String::with_capacity(component.len());
+
+    for c in component.chars() {
+        let invalid = (target.includes_unix() && c == '\0')
+            || (target.includes_windows()
+                && (WINDOWS_INVALID_CHARS.contains(&c) || c == '\\' || c.is_control()));
+        if invalid {
+            if !issues.contains(&PortabilityIssue::InvalidChar(c)) {
+                issues.push(PortabilityIssue::InvalidChar(c));
+            }
+            fixed.push('_');
+        } else {
+            fixed.push(c);
+        }
+    }
+
+    if target.includes_windows() && component != "." && component != ".." {
+        let trimmed_len = fixed.trim_end_matches(['.', ' ']).len();
+        if trimmed_len < fixed.len() {
+            issues.push(PortabilityIssue::TrailingDotOrSpace);
+            fixed.truncate(trimmed_len);
+            fixed.push('_');
+        }
+
+        let stem = fixed.split('.').next().unwrap_or_default();
+        if let Some(reserved) = WINDOWS_RESERVED_NAMES
+            .iter()
+            .find(|r| r.eq_ignore_ascii_case(stem.trim_end()))
+        {
+            issues.push(PortabilityIssue::ReservedName((*reserved).to_string()));
+            fixed.insert(stem.len(), '_');
+        }
+    }
+
+    (issues, fixed)
+}
+
+/// Collect fixes for every offending component
+#[must_use]
+pub fn portability_fixes<S: AsRef<str>>(
+    components: &[S],
+    target: ValidationTarget,
+) -> Vec<PortabilityFix> {
+    components
+        .iter()
+        .enumerate()
+        .filter_map(|(index, component)| {
+            let component = component.as_ref();
+            let (issues, replacement) = check_component(component, target);
+            (!issues.is_empty()).then(|| PortabilityFix {
+                index,
+                component: component.to_string(),
+                issues,
+                replacement,
+            })
+        })
+        .collect()
+}
+
+/// File system whose naming limits a path must satisfy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Filesystem {
+    /// NTFS as used by Windows (Win32 namespace)
+    Ntfs,
+    /// FAT32 with long file names
+    Fat32,
+    /// exFAT
+    ExFat,
+    /// Linux ext4
+    Ext4,
+    /// Apple File System
+    Apfs,
+}
+
+/// Unit a file system measures name lengths in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LengthUnit {
+    Utf16,
+    Bytes,
+    Chars,
+}
+
+impl Filesystem {
+    /// Maximum length of a single component, in the file system's unit
+    ///
+    /// Windows file systems count UTF-16 code units, ext4 counts bytes and
+    /// APFS counts characters.
+    #[must_use]
+    pub fn max_component_len(self) -> usize {
+        255
+    }
+
+    /// Maximum length of a whole path, in the file system's unit
+    ///
+    /// FAT32 is limited by the classic Windows `MAX_PATH`, ext4 and APFS by
+    /// the `PATH_MAX` of Linux and macOS.
+    #[must_use]
+    pub fn max_path_len(self) -> usize {
+        match self {
+            Self::Ntfs => 32_767,
+            Self::Fat32 => 260,
+            Self::ExFat => 32_760,
+            Self::Ext4 => 4096,
+            Self::Apfs => 1024,
+        }
+    }
+
+    /// Check if the file system follows Windows naming rules (invalid
+    /// characters, reserved names, trailing dots and spaces)
+    #[must_use]
+    pub fn is_windows(self) -> bool {
+        matches!(self, Self::Ntfs | Self::Fat32 | Self::ExFat)
+    }
+
+    /// Unit used by the length limits
+    fn length_unit(self) -> LengthUnit {
+        match self {
+            Self::Ntfs | Self::Fat32 | Self::ExFat => LengthUnit::Utf16,
+            Self::Ext4 => LengthUnit::Bytes,
+            Self::Apfs => LengthUnit::Chars,
+        }
+    }
+
+    /// Length of `text` in the file system's unit
+    fn measure(self, text: &str) -> usize {
+        match self.length_unit() {
+            LengthUnit::Utf16 => text.encode_utf16().count(),
+            LengthUnit::Bytes => text.len(),
+            LengthUnit::Chars => text.chars().count(),
+        }
+    }
+}
+
+/// Problem that prevents a path from being created on a file system
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FilesystemIssue {
+    /// Component longer than [`Filesystem::max_component_len`]
+    ComponentTooLong {
+        /// Index of the component (drive and UNC server/share excluded)
+        index: usize,
+        /// Length in the file system's unit
+        length: usize,
+        /// Maximum length
+        max: usize,
+    },
+    /// Character the file system does not allow
+    InvalidChar {
+        /// Index of the component
+        index: usize,
+        /// The offending character
+        ch: char,
+    },
+    /// Windows reserved device name (`CON`, `NUL`, `COM1`, ...)
+    ReservedName {
+        /// Index of the component
+        index: usize,
+        /// The reserved name
+        name: String,
+    },
+    /// Trailing dot or space, which Windows silently strips
+    TrailingDotOrSpace {
+        /// Index of the component
+        index: usize,
+    },
+    /// Whole path longer than [`Filesystem::max_path_len`]
+    PathTooLong {
+        /// Length in the file system's unit
+        length: usize,
+        /// Maximum length
+        max: usize,
+    },
+}
+
+/// Check components and the whole path against a file system's limits
+///
+/// `path` is only measured for the total length; every problem found is
+/// reported, in component order, followed by the path length.
+#[must_use]
+pub fn filesystem_issues<S: AsRef<str>>(
+    components: &[S],
+    path: &str,
+    fs: Filesystem,
+) -> Vec<FilesystemIssue> {
+    let mut issues = Vec::new();
+    let target = if fs.is_windows() {
+        ValidationTarget::Windows
+    } else {
+        ValidationTarget::Unix
+    };
+
+    for (index, component) in components.iter().enumerate() {
+        let component = component.as_ref();
+        let length = fs.measure(component);
+        if length > fs.max_component_len() {
+            issues.push(FilesystemIssue::ComponentTooLong {
+                index,
+                length,
+                max: fs.max_component_len(),
+            });
+        }
+        for issue in check_component(component, target).0 {
+            issues.push(match issue {
+                PortabilityIssue::InvalidChar(ch) => FilesystemIssue::InvalidChar { index, ch },
+                PortabilityIssue::ReservedName(name) => {
+                    FilesystemIssue::ReservedName { index, name }
+                }
+                PortabilityIssue::TrailingDotOrSpace => {
+                    FilesystemIssue::TrailingDotOrSpace { index }
+                }
+            });
+        }
+    }
+
+    let length = fs.measure(path);
+    if length > fs.max_path_len() {
+        issues.push(FilesystemIssue::PathTooLong {
+            length,
+            max: fs.max_path_len(),
+        });
+    }
+    issues
+}
+
+/// Validate a path string against the rules of its style
+///
+/// Rejects characters that are invalid in the style (`<>:"|?*` and control
+/// characters for Windows, NUL for Unix), empty or non-letter drive specs
+/// (`:\dir`, `1:\dir`) and UNC roots missing the server or share.
+///
+/// # Errors
+///
+/// Returns `PathError::InvalidPath` describing the first problem found.
+pub fn validate_strict(path: &str, style: PathStyle) -> PathResult<()> {
+    match style {
+        PathStyle::Windows => validate_windows(path),
+        PathStyle::Unix | PathStyle::Cygwin | PathStyle::Msys | PathStyle::Custom(_) => {
+            match path.find('\0') {
+                Some(pos) => Err(PathError::invalid_path(format!(
+                    "NUL character at byte {pos} in Unix path"
+                ))
+                .with_path(path)
+                .with_span(pos..pos + 1)),
+                None => Ok(()),
+            }
+        }
+        PathStyle::Auto => validate_strict(path, crate::platform::current_style()),
+    }
+}
+
+/// Validate a Windows path string
+fn validate_windows(original: &str) -> PathResult<()> {
+    // Verbatim and device prefixes are followed by an ordinary path; `shift`
+    // is how much longer the original is than the path checked
+    let (unprefixed, shift) = match original
+        .strip_prefix(r"\\?\")
+        .or_else(|| original.strip_prefix(r"\\.\"))
+    {
+        Some(rest) => match rest.strip_prefix(r"UNC\") {
+            Some(unc) => (format!(r"\\{unc}"), 6),
+            None => (rest.to_string(), 4),
+        },
+        None => (original.to_string(), 0),
+    };
+    let path = unprefixed.as_str();
+    let invalid = |msg: String, span: Range<usize>| {
+        Err(PathError::invalid_path(msg)
+            .with_path(original)
+            .with_span(span.start + shift..span.end + shift))
+    };
+
+    let rest = if let Some(unc) = path.strip_prefix(r"\\").or_else(|| path.strip_prefix("//")) {
+        let mut parts = unc.splitn(3, ['\\', '/']);
+        let server = parts.next().unwrap_or_default();
+        let share = parts.next().unwrap_or_default();
+        let rest = parts.next();
+        // A bare server (`\\server`) is valid, an empty share is not
+        if server.is_empty() || (share.is_empty() && rest.is_some()) {
+            let root = 2 + server.len() + 1 + share.len();
+            return invalid(
+                format!("Malformed UNC root (expected \\\\server\\share): {path}"),
+                0..root.min(path.len()),
+            );
+        }
+        rest.unwrap_or_default()
+    } else if let Some(colon) = path.find(':').filter(|&i| i <= 1) {
+        let drive = &path[..colon];
+        if !drive.chars().all(|c| c.is_ascii_alphabetic()) || drive.is_empty() {
+            return invalid(
+                format!("Empty or invalid drive specification: {path}"),
+                0..colon + 1,
+            );
+        }
+        &path[colon + 1..]
+    } else {
+        path
+    };
+
+    // `rest` is a suffix of `path`
+    let mut offset = path.len() - rest.len();
+    for component in rest.split(['\\', '/']) {
+        if let Some((i, c)) = component
+            .char_indices()
+            .find(|&(_, c)| WINDOWS_INVALID_CHARS.contains(&c) || c.is_control())
+        {
+            return invalid(
+                format!("Invalid character {c:?} in Windows path component '{component}'"),
+                offset + i..offset + i + c.len_utf8(),
+            );
+        }
+        offset += component.len() + 1;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,466 @@
+use super::{FileSystem, FileType, Metadata};
+use crate::PathStyle;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{PoisonError, RwLock};
+
+/// Maximum number of symlinks followed while resolving a path
+const MAX_SYMLINK_HOPS: usize = 40;
+
+/// In-memory filesystem
+///
+/// Created with [`new`](Self::new) it behaves like a Unix filesystem
+/// (`/` separators, case-sensitive names). Created with
+/// [`windows`](Self::windows) it accepts drive (`C:\`) and UNC roots, both
+/// separators, and matches names case-insensitively while preserving the
+/// case they were created with. Relative paths are resolved against `/` or
+/// `C:\` respectively.
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+    windows: bool,
+    nodes: RwLock<BTreeMap<String, Node>>,
+}
+
+/// Stored entry
+#[derive(Debug, Clone)]
+struct Node {
+    /// Name the entry was created with
+    name: String,
+    kind: NodeKind,
+}
+
+#[derive(Debug, Clone)]
+enum NodeKind {
+    File(Vec<u8>),
+    Dir,
+    Symlink(PathBuf),
+}
+
+/// Lexically resolved path
+#[derive(Debug, Clone)]
+struct Parts {
+    root: String,
+    components: Vec<String>,
+}
+
+impl MemoryFileSystem {
+    /// Create an empty filesystem with Unix semantics
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty filesystem with Windows semantics
+    #[must_use]
+    pub fn windows() -> Self {
+        Self {
+            windows: true,
+            nodes: RwLock::default(),
+        }
+    }
+
+    /// Create a symbolic link at `link` pointing to `target`
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the parent directory does not exist or the
+    /// link already exists.
+    pub fn symlink<P: AsRef<Path>, Q: AsRef<Path>>(&self, target: P, link: Q) -> io::Result<()> {
+        let parts = self.resolve(link.as_ref(), false)?;
+        self.insert_new(&parts, NodeKind::Symlink(target.as_ref().to_path_buf()))
+    }
+
+    fn separator(&self) -> char {
+        if self.windows { '\\' } else { '/' }
+    }
+
+    /// Split a path into root and lexically normalized components
+    fn parse(&self, path: &Path) -> Parts {
+        let text = path.to_string_lossy();
+        let is_separator = |c: char| c == '/' || (self.windows && c == '\\');
+
+        let (root, rest) = if self.windows {
+            let bytes = text.as_bytes();
+            if text.starts_with(r"\\") || text.starts_with("//") {
+                let mut parts = text[2..].splitn(3, is_separator);
+                let server = parts.next().unwrap_or_default();
+                let share = parts.next().unwrap_or_default();
+                (
+                    format!(r"\\{server}\{share}\"),
+                    parts.next().unwrap_or_default().to_string(),
+                )
+            } else if bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' {
+                (
+                    format!(r"{}:\", char::from(bytes[0]).to_ascii_uppercase()),
+                    text[2..].to_string(),
+                )
+            } else {
+                (r"C:\".to_string(), text.into_owned())
+            }
+        } else {
+            ("/".to_string(), text.into_owned())
+        };
+
+        let mut components: Vec<String> = Vec::new();
+        for component in rest.split(is_separator) {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    components.pop();
+                }
+                name => components.push(name.to_string()),
+            }
+        }
+
+        Parts { root, components }
+    }
+
+    /// Key of the first `len` components
+    fn key(&self, parts: &Parts, len: usize) -> String {
+        let key = format!("{}{}", parts.root, parts.components[..len].join("/"));
+        if self.windows {
+            key.replace('\\', "/").to_lowercase()
+        } else {
+            key
+        }
+    }
+
+    /// Parse a path and follow symlinks in its existing prefix
+    fn resolve(&self, path: &Path, follow_last: bool) -> io::Result<Parts> {
+        let nodes = self.nodes.read().unwrap_or_else(PoisonError::into_inner);
+        let mut parts = self.parse(path);
+        let mut hops = 0;
+
+        let mut i = 0;
+        while i < parts.components.len() {
+            let is_last = i + 1 == parts.components.len();
+            let Some(node) = nodes.get(&self.key(&parts, i + 1)) else {
+                break;
+            };
+            let NodeKind::Symlink(target) = &node.kind else {
+                i += 1;
+                continue;
+            };
+            if is_last && !follow_last {
+                break;
+            }
+
+            hops += 1;
+            if hops > MAX_SYMLINK_HOPS {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "too many levels of symbolic links",
+                ));
+            }
+
+            let base = self.display_with(&nodes, &parts, i);
+            let mut resolved = self.parse(&base.join(target));
+            if self.is_absolute(target) {
+                resolved = self.parse(target);
+            }
+            resolved.components.extend(parts.components.drain(i + 1..));
+            parts = resolved;
+            i = 0;
+        }
+
+        Ok(parts)
+    }
+
+    fn is_absolute(&self, path: &Path) -> bool {
+        let text = path.to_string_lossy();
+        if self.windows {
+            text.starts_with(r"\\")
+                || text.starts_with("//")
+                || (text.as_bytes().get(1) == Some(&b':'))
+        } else {
+            text.starts_with('/')
+        }
+    }
+
+    /// Path of the first `len` components, using stored names where known
+    fn display_with(&self, nodes: &BTreeMap<String, Node>, parts: &Parts, len: usize) -> PathBuf {
+        let mut display = parts.root.clone();
+        for i in 0..len {
+            if i > 0 {
+                display.push(self.separator());
+            }
+            match nodes.get(&self.key(parts, i + 1)) {
+                Some(node) => display.push_str(&node.name),
+                None => display.push_str(&parts.components[i]),
+            }
+        }
+        PathBuf::from(display)
+    }
+
+    /// Node kind of an entry (roots are directories)
+    fn kind(&self, parts: &Parts) -> io::Result<NodeKind> {
+        if parts.components.is_empty() {
+            return Ok(NodeKind::Dir);
+        }
+        self.nodes
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&self.key(parts, parts.components.len()))
+            .map(|node| node.kind.clone())
+            .ok_or_else(|| io::Error::from(ErrorKind::NotFound))
+    }
+
+    /// Check that the parent of an entry is an existing directory
+    fn check_parent(&self, parts: &Parts) -> io::Result<()> {
+        let mut parent = parts.clone();
+        parent.components.pop();
+        match self.kind(&parent)? {
+            NodeKind::Dir => Ok(()),
+            _ => Err(ErrorKind::NotADirectory.into()),
+        }
+    }
+
+    /// Insert an entry that must not exist yet
+    fn insert_new(&self, parts: &Parts, kind: NodeKind) -> io::Result<()> {
+        self.check_parent(parts)?;
+        let key = self.key(parts, parts.components.len());
+        let mut nodes = self.nodes.write().unwrap_or_else(PoisonError::into_inner);
+        if parts.components.is_empty() || nodes.contains_key(&key) {
+            return Err(ErrorKind::AlreadyExists.into());
+        }
+        let name = parts.components.last().cloned().unwrap_or_default();
+        nodes.insert(key, Node { name, kind });
+        Ok(())
+    }
+
+    /// Keys of an entry and all its descendants
+    fn subtree_keys(&self, key: &str) -> Vec<String> {
+        let prefix = format!("{}/", key.trim_end_matches('/'));
+        self.nodes
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .filter(|k| *k == key || k.starts_with(&prefix))
+            .cloned()
+            .collect()
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        let parts = self.resolve(path, true)?;
+        Ok(metadata_of(&self.kind(&parts)?))
+    }
+
+    fn symlink_metadata(&self, path: &Path) -> io::Result<Metadata> {
+        let parts = self.resolve(path, false)?;
+        Ok(metadata_of(&self.kind(&parts)?))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+        let parts = self.resolve(path, true)?;
+        if !matches!(self.kind(&parts)?, NodeKind::Dir) {
+            return Err(ErrorKind::NotADirectory.into());
+        }
+
+        let key = self.key(&parts, parts.components.len());
+        let prefix = if parts.components.is_empty() {
+            key
+        } else {
+            format!("{key}/")
+        };
+        let nodes = self.nodes.read().unwrap_or_else(PoisonError::into_inner);
+        let mut names: Vec<OsString> = nodes
+            .iter()
+            .filter_map(|(k, node)| {
+                let rest = k.strip_prefix(&prefix)?;
+                (!rest.is_empty() && !rest.contains('/')).then(|| node.name.clone().into())
+            })
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let parts = self.resolve(path, true)?;
+        let mut nodes = self.nodes.write().unwrap_or_else(PoisonError::into_inner);
+
+        for (i, name) in parts.components.iter().enumerate() {
+            let key = self.key(&parts, i + 1);
+            match nodes.get(&key).map(|node| &node.kind) {
+                Some(NodeKind::Dir) => {}
+                Some(_) => return Err(ErrorKind::AlreadyExists.into()),
+                None => {
+                    nodes.insert(
+                        key,
+                        Node {
+                            name: name.clone(),
+                            kind: NodeKind::Dir,
+                        },
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let parts = self.resolve(path, true)?;
+        match self.kind(&parts)? {
+            NodeKind::File(data) => Ok(data),
+            _ => Err(ErrorKind::IsADirectory.into()),
+        }
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        let parts = self.resolve(path, true)?;
+        self.check_parent(&parts)?;
+        if parts.components.is_empty() {
+            return Err(ErrorKind::IsADirectory.into());
+        }
+
+        let key = self.key(&parts, parts.components.len());
+        let mut nodes = self.nodes.write().unwrap_or_else(PoisonError::into_inner);
+        match nodes.get_mut(&key) {
+            Some(Node {
+                kind: NodeKind::Dir,
+                ..
+            }) => Err(ErrorKind::IsADirectory.into()),
+            Some(node) => {
+                node.kind = NodeKind::File(contents.to_vec());
+                Ok(())
+            }
+            None => {
+                let name = parts.components.last().cloned().unwrap_or_default();
+                nodes.insert(
+                    key,
+                    Node {
+                        name,
+                        kind: NodeKind::File(contents.to_vec()),
+                    },
+                );
+                Ok(())
+            }
+        }
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        let data = self.read(from)?;
+        self.write(to, &data)?;
+        Ok(data.len() as u64)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let source = self.resolve(from, false)?;
+        let destination = self.resolve(to, false)?;
+        let source_kind = self.kind(&source)?;
+        if source.components.is_empty() {
+            return Err(ErrorKind::InvalidInput.into());
+        }
+        self.check_parent(&destination)?;
+
+        let from_key = self.key(&source, source.components.len());
+        let to_key = self.key(&destination, destination.components.len());
+        if to_key.starts_with(&format!("{from_key}/")) {
+            return Err(ErrorKind::InvalidInput.into());
+        }
+
+        if to_key != from_key {
+            match (self.kind(&destination), &source_kind) {
+                (Ok(NodeKind::Dir), NodeKind::Dir)
+                    if self.subtree_keys(&to_key).iter().any(|k| *k != to_key) =>
+                {
+                    return Err(ErrorKind::DirectoryNotEmpty.into());
+                }
+                (Ok(NodeKind::Dir), NodeKind::Dir) => {}
+                (Ok(NodeKind::Dir), _) => return Err(ErrorKind::IsADirectory.into()),
+                (Ok(_), NodeKind::Dir) => return Err(ErrorKind::NotADirectory.into()),
+                _ => {}
+            }
+        }
+
+        let moved = self.subtree_keys(&from_key);
+        let mut nodes = self.nodes.write().unwrap_or_else(PoisonError::into_inner);
+        nodes.remove(&to_key);
+        let entries: Vec<(String, Node)> = moved
+            .into_iter()
+            .filter_map(|k| nodes.remove(&k).map(|node| (k, node)))
+            .collect();
+        for (key, mut node) in entries {
+            let new_key = format!("{to_key}{}", &key[from_key.len()..]);
+            if key == from_key {
+                node.name = destination.components.last().cloned().unwrap_or_default();
+            }
+            nodes.insert(new_key, node);
+        }
+        Ok(())
+    }
+
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        let parts = self.resolve(path, false)?;
+        if matches!(self.kind(&parts)?, NodeKind::Dir) {
+            return Err(ErrorKind::IsADirectory.into());
+        }
+        let key = self.key(&parts, parts.components.len());
+        self.nodes
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&key);
+        Ok(())
+    }
+
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        let parts = self.resolve(path, false)?;
+        if !matches!(self.kind(&parts)?, NodeKind::Dir) {
+            return Err(ErrorKind::NotADirectory.into());
+        }
+        let keys = self.subtree_keys(&self.key(&parts, parts.components.len()));
+        let mut nodes = self.nodes.write().unwrap_or_else(PoisonError::into_inner);
+        for key in keys {
+            nodes.remove(&key);
+        }
+        Ok(())
+    }
+
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        match self.kind(&self.resolve(path, false)?)? {
+            NodeKind::Symlink(target) => Ok(target),
+            _ => Err(ErrorKind::InvalidInput.into()),
+        }
+    }
+
+    fn symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        Self::symlink(self, target, link)
+    }
+
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        let parts = self.resolve(path, true)?;
+        self.kind(&parts)?;
+        let nodes = self.nodes.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(self.display_with(&nodes, &parts, parts.components.len()))
+    }
+
+    fn current_dir(&self) -> io::Result<PathBuf> {
+        Ok(PathBuf::from(if self.windows { r"C:\" } else { "/" }))
+    }
+
+    fn style(&self) -> PathStyle {
+        if self.windows {
+            PathStyle::Windows
+        } else {
+            PathStyle::Unix
+        }
+    }
+}
+
+/// Metadata for a stored entry
+fn metadata_of(kind: &NodeKind) -> Metadata {
+    let (file_type, len) = match kind {
+        NodeKind::File(data) => (FileType::File, data.len() as u64),
+        NodeKind::Dir => (FileType::Dir, 0),
+        NodeKind::Symlink(target) => (FileType::Symlink, target.as_os_str().len() as u64),
+    };
+    Metadata {
+        file_type,
+        len,
+        readonly: false,
+        modified: None,
+    }
+}
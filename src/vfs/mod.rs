@@ -0,0 +1,329 @@
+//! Virtual filesystem abstraction
+//!
+//! File operations, tree walkers and [`PathExt`](crate::platform::PathExt)
+//! queries run against a [`FileSystem`]. [`OsFileSystem`] forwards to
+//! `std::fs`; [`MemoryFileSystem`] keeps everything in memory and can emulate
+//! Windows semantics (drive roots, `\` separators, case-insensitive names) on
+//! any host, so path-handling logic can be unit-tested on every CI platform.
+
+mod memory;
+mod os;
+
+pub use memory::MemoryFileSystem;
+pub use os::OsFileSystem;
+
+use crate::platform::{
+    AccessMode, DiskInfo, FileAttributes, FileTimes, LinkKind, PathExt, PlatformPath,
+};
+use crate::{PathError, PathStyle, platform};
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Type of a filesystem entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileType {
+    /// Regular file
+    File,
+    /// Directory
+    Dir,
+    /// Symbolic link
+    Symlink,
+}
+
+/// Metadata of a filesystem entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metadata {
+    /// Entry type
+    pub file_type: FileType,
+    /// Size in bytes (0 for directories)
+    pub len: u64,
+    /// Whether the entry is read-only
+    pub readonly: bool,
+    /// Last modification time (if available)
+    pub modified: Option<SystemTime>,
+}
+
+impl From<FileType> for crate::platform::FileType {
+    fn from(file_type: FileType) -> Self {
+        match file_type {
+            FileType::File => Self::Regular,
+            FileType::Dir => Self::Dir,
+            FileType::Symlink => Self::Symlink,
+        }
+    }
+}
+
+impl Metadata {
+    /// Check if the entry is a directory
+    #[must_use]
+    pub fn is_dir(&self) -> bool {
+        self.file_type == FileType::Dir
+    }
+
+    /// Check if the entry is a regular file
+    #[must_use]
+    pub fn is_file(&self) -> bool {
+        self.file_type == FileType::File
+    }
+
+    /// Check if the entry is a symbolic link
+    #[must_use]
+    pub fn is_symlink(&self) -> bool {
+        self.file_type == FileType::Symlink
+    }
+}
+
+/// Filesystem operations used by the crate
+///
+/// Paths are native paths of the filesystem (for [`MemoryFileSystem`] in
+/// Windows mode, `C:\dir\file`).
+pub trait FileSystem: Send + Sync {
+    /// Metadata of an entry, following symlinks
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the entry does not exist.
+    fn metadata(&self, path: &Path) -> io::Result<Metadata>;
+
+    /// Metadata of an entry, without following a final symlink
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the entry does not exist.
+    fn symlink_metadata(&self, path: &Path) -> io::Result<Metadata>;
+
+    /// Names of the entries of a directory, sorted
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the directory cannot be read.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>>;
+
+    /// Create a directory and all missing parents
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if a parent is not a directory.
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+
+    /// Read the contents of a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be read.
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /// Create or replace a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be written.
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
+
+    /// Copy a file, returning the number of bytes copied
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be copied.
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64>;
+
+    /// Rename a file or directory
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the entry cannot be renamed.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+
+    /// Remove a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be removed.
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+
+    /// Remove a directory and its contents
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the directory cannot be removed.
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()>;
+
+    /// Target of a symbolic link, as stored in the link
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the entry is not a symlink. The default
+    /// implementation reports symlinks as unsupported.
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        let _ = path;
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    /// Create a symbolic link at `link` pointing to `target`
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the link cannot be created. The default
+    /// implementation reports symlinks as unsupported.
+    fn symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        let _ = (target, link);
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    /// Resolve a path to its absolute physical form, following symlinks
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the path does not exist.
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf>;
+
+    /// Directory relative paths are resolved against
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the current directory is unavailable.
+    fn current_dir(&self) -> io::Result<PathBuf>;
+
+    /// Style of the paths this filesystem uses
+    fn style(&self) -> PathStyle {
+        platform::current_style()
+    }
+
+    /// Check if an entry exists (following symlinks)
+    fn exists(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok()
+    }
+
+    /// Check if an entry is a directory (following symlinks)
+    fn is_dir(&self, path: &Path) -> bool {
+        self.metadata(path).is_ok_and(|m| m.is_dir())
+    }
+}
+
+/// Path bound to a filesystem, providing [`PathExt`] queries against it
+pub struct VfsPath<'a> {
+    fs: &'a dyn FileSystem,
+    path: PathBuf,
+}
+
+impl<'a> VfsPath<'a> {
+    /// Create a path on the given filesystem
+    pub fn new<P: AsRef<Path>>(fs: &'a dyn FileSystem, path: P) -> Self {
+        Self {
+            fs,
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Get the underlying path
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl PlatformPath for VfsPath<'_> {
+    fn separator(&self) -> char {
+        match self.fs.style() {
+            PathStyle::Windows => '\\',
+            PathStyle::Custom(style) => crate::registry::style_definition(style.name())
+                .map_or('/', |definition| definition.separator()),
+            PathStyle::Unix | PathStyle::Cygwin | PathStyle::Msys | PathStyle::Auto => '/',
+        }
+    }
+
+    fn is_absolute(&self) -> bool {
+        let path = self.path.to_string_lossy();
+        match self.fs.style() {
+            PathStyle::Windows => {
+                path.starts_with(r"\\")
+                    || (path.as_bytes().get(1) == Some(&b':')
+                        && matches!(path.as_bytes().get(2), Some(b'\\' | b'/')))
+            }
+            PathStyle::Custom(style) => crate::registry::style_definition(style.name())
+                .is_some_and(|definition| definition.split_root(&path).is_some()),
+            PathStyle::Unix | PathStyle::Cygwin | PathStyle::Msys | PathStyle::Auto => {
+                path.starts_with('/')
+            }
+        }
+    }
+
+    fn to_platform_specific(&self) -> String {
+        self.path.to_string_lossy().into_owned()
+    }
+}
+
+impl PathExt for VfsPath<'_> {
+    fn get_attributes(&self) -> Option<FileAttributes> {
+        let metadata = self.fs.metadata(&self.path).ok()?;
+
+        Some(FileAttributes {
+            size: metadata.len,
+            is_directory: metadata.is_dir(),
+            is_hidden: self
+                .path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|s| s.starts_with('.')),
+            is_readonly: metadata.readonly,
+            times: FileTimes {
+                modified: metadata.modified,
+                ..FileTimes::default()
+            },
+            link_kind: self
+                .fs
+                .symlink_metadata(&self.path)
+                .is_ok_and(|m| m.is_symlink())
+                .then_some(LinkKind::Symlink),
+            file_type: metadata.file_type.into(),
+            mode: None,
+            uid: None,
+            gid: None,
+            inode: None,
+            nlink: None,
+        })
+    }
+
+    fn is_accessible(&self) -> bool {
+        self.fs.exists(&self.path)
+    }
+
+    fn access(&self, mode: AccessMode) -> bool {
+        // The virtual filesystem has no owners; only read-only entries
+        // restrict access
+        self.fs
+            .metadata(&self.path)
+            .is_ok_and(|metadata| !(mode.write && metadata.readonly))
+    }
+
+    fn xattrs(&self) -> Result<Vec<String>, PathError> {
+        // The virtual filesystem keeps no extended attributes
+        self.fs.metadata(&self.path)?;
+        Ok(Vec::new())
+    }
+
+    fn get_xattr(&self, _name: &str) -> Result<Option<Vec<u8>>, PathError> {
+        self.fs.metadata(&self.path)?;
+        Ok(None)
+    }
+
+    fn set_xattr(&self, _name: &str, _value: &[u8]) -> Result<(), PathError> {
+        Err(PathError::platform_error(
+            "Extended attributes are not supported on virtual filesystems",
+        ))
+    }
+
+    fn remove_xattr(&self, name: &str) -> Result<(), PathError> {
+        self.fs.metadata(&self.path)?;
+        Err(PathError::io_error(format!(
+            "No extended attribute {name} on {}",
+            self.path.display()
+        )))
+    }
+
+    fn get_disk_info(&self) -> Option<DiskInfo> {
+        None
+    }
+}
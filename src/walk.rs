@@ -0,0 +1,285 @@
+//! Recursive directory traversal yielding [`CrossPath`] entries
+//!
+//! [`CrossWalk`] walks a tree depth-first, parents before their children
+//! and siblings in name order, and yields every entry as a `CrossPath` in
+//! the style and configuration of the root: walking `C:\src` gives
+//! `C:\src\main.rs`, walking the same tree as `/mnt/c/src` gives
+//! `/mnt/c/src/main.rs`. Entries carry the same [`FileAttributes`] on every
+//! platform. The walk runs against any [`FileSystem`], so it can be tested
+//! on a [`MemoryFileSystem`](crate::vfs::MemoryFileSystem).
+//!
+//! ```rust,no_run
+//! use cross_path::CrossPath;
+//! use cross_path::walk::CrossWalk;
+//!
+//! let root = CrossPath::new("/mnt/c/src")?;
+//! for entry in CrossWalk::new(&root).max_depth(2) {
+//!     let entry = entry?;
+//!     if !entry.attributes.is_directory {
+//!         println!("{} ({} bytes)", entry.path.to_windows()?, entry.attributes.size);
+//!     }
+//! }
+//! # Ok::<(), cross_path::PathError>(())
+//! ```
+
+use crate::ops::native_path;
+use crate::platform::{FileAttributes, FileTimes, LinkKind};
+use crate::vfs::{FileSystem, Metadata, OsFileSystem};
+use crate::{CrossPath, PathError, PathResult};
+use std::path::{Path, PathBuf};
+
+/// Entry found by a [`CrossWalk`]
+#[derive(Debug, Clone)]
+pub struct WalkEntry {
+    /// Path in the style of the walk's root
+    pub path: CrossPath,
+    /// Depth below the root, which has depth 0
+    pub depth: usize,
+    /// Attributes of the entry, or of its target for followed links
+    pub attributes: FileAttributes,
+    /// Whether the entry is a symbolic link
+    pub is_symlink: bool,
+}
+
+/// Builder and iterator source for a directory walk
+///
+/// Symbolic links are not followed unless enabled; followed links that
+/// lead back to one of their own ancestors are reported as errors instead
+/// of being walked again.
+#[derive(Clone)]
+pub struct CrossWalk<'fs> {
+    root: CrossPath,
+    fs: &'fs dyn FileSystem,
+    max_depth: usize,
+    follow_links: bool,
+}
+
+impl CrossWalk<'static> {
+    /// Walk `root` on the operating system's filesystem
+    #[must_use]
+    pub fn new(root: &CrossPath) -> Self {
+        CrossWalk::new_in(&OsFileSystem, root)
+    }
+}
+
+impl<'fs> CrossWalk<'fs> {
+    /// Walk `root` on the given filesystem
+    #[must_use]
+    pub fn new_in(fs: &'fs dyn FileSystem, root: &CrossPath) -> Self {
+        Self {
+            root: root.clone(),
+            fs,
+            max_depth: usize::MAX,
+            follow_links: false,
+        }
+    }
+
+    /// Descend at most `depth` levels below the root
+    ///
+    /// `0` yields only the root itself.
+    #[must_use]
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Set whether symbolic links to directories are descended into
+    #[must_use]
+    pub fn follow_links(mut self, follow: bool) -> Self {
+        self.follow_links = follow;
+        self
+    }
+
+    /// Walk the tree on the rayon thread pool
+    ///
+    /// Sibling directories are read in parallel. The entries come back in
+    /// the order the sequential walk yields them.
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    pub fn collect_par(self) -> Vec<PathResult<WalkEntry>> {
+        use rayon::prelude::*;
+
+        fn visit(walk: &CrossWalk<'_>, node: Node) -> Vec<PathResult<WalkEntry>> {
+            let (entry, children) = walk.visit(node);
+            let mut results = vec![entry];
+            results.extend(
+                children
+                    .into_par_iter()
+                    .map(|child| visit(walk, child))
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .flatten(),
+            );
+            results
+        }
+
+        match self.root_node() {
+            Ok(root) => visit(&self, root),
+            Err(e) => vec![Err(e)],
+        }
+    }
+
+    fn root_node(&self) -> PathResult<Node> {
+        Ok(Node {
+            native: native_path(self.fs, &self.root)?,
+            path: self.root.clone(),
+            depth: 0,
+            ancestors: Vec::new(),
+        })
+    }
+
+    /// Read one node, returning its entry and the nodes of its children
+    fn visit(&self, node: Node) -> (PathResult<WalkEntry>, Vec<Node>) {
+        let symlink = match self.fs.symlink_metadata(&node.native) {
+            Ok(metadata) => metadata,
+            Err(e) => return (Err(e.into()), Vec::new()),
+        };
+        let is_symlink = symlink.is_symlink();
+        let metadata = if is_symlink && self.follow_links {
+            match self.fs.metadata(&node.native) {
+                Ok(metadata) => metadata,
+                Err(e) => return (Err(e.into()), Vec::new()),
+            }
+        } else {
+            symlink
+        };
+
+        let entry = WalkEntry {
+            attributes: attributes(&node.native, &metadata, is_symlink),
+            path: node.path,
+            depth: node.depth,
+            is_symlink,
+        };
+        if !metadata.is_dir() || node.depth >= self.max_depth {
+            return (Ok(entry), Vec::new());
+        }
+
+        let mut ancestors = node.ancestors;
+        if self.follow_links {
+            let physical = match self.fs.canonicalize(&node.native) {
+                Ok(physical) => physical,
+                Err(e) => return (Err(e.into()), Vec::new()),
+            };
+            if ancestors.contains(&physical) {
+                return (
+                    Err(PathError::io_error(format!(
+                        "Symlink loop at {}",
+                        node.native.display()
+                    ))),
+                    Vec::new(),
+                );
+            }
+            ancestors.push(physical);
+        }
+
+        let names = match self.fs.read_dir(&node.native) {
+            Ok(names) => names,
+            Err(e) => return (Err(e.into()), Vec::new()),
+        };
+        let mut children = Vec::with_capacity(names.len());
+        let mut error = None;
+        for name in names {
+            match entry.path.join(name.to_string_lossy()) {
+                Ok(path) => children.push(Node {
+                    native: node.native.join(&name),
+                    path,
+                    depth: node.depth + 1,
+                    ancestors: ancestors.clone(),
+                }),
+                Err(e) => {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        match error {
+            Some(e) => (Err(e), children),
+            None => (Ok(entry), children),
+        }
+    }
+}
+
+impl std::fmt::Debug for CrossWalk<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CrossWalk")
+            .field("root", &self.root)
+            .field("max_depth", &self.max_depth)
+            .field("follow_links", &self.follow_links)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'fs> IntoIterator for CrossWalk<'fs> {
+    type Item = PathResult<WalkEntry>;
+    type IntoIter = Walk<'fs>;
+
+    fn into_iter(self) -> Walk<'fs> {
+        let (stack, error) = match self.root_node() {
+            Ok(root) => (vec![root], None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        Walk {
+            settings: self,
+            stack,
+            error,
+        }
+    }
+}
+
+/// Iterator over the entries of a [`CrossWalk`]
+///
+/// An entry that cannot be read is yielded as an error and the walk goes
+/// on with its siblings.
+#[derive(Debug)]
+pub struct Walk<'fs> {
+    settings: CrossWalk<'fs>,
+    stack: Vec<Node>,
+    error: Option<PathError>,
+}
+
+impl Iterator for Walk<'_> {
+    type Item = PathResult<WalkEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.error.take() {
+            return Some(Err(e));
+        }
+        let node = self.stack.pop()?;
+        let (entry, children) = self.settings.visit(node);
+        self.stack.extend(children.into_iter().rev());
+        Some(entry)
+    }
+}
+
+/// Entry waiting to be visited
+#[derive(Debug)]
+struct Node {
+    path: CrossPath,
+    native: PathBuf,
+    depth: usize,
+    /// Physical paths of the directories above, when following links
+    ancestors: Vec<PathBuf>,
+}
+
+/// Attributes of an entry from its metadata
+fn attributes(path: &Path, metadata: &Metadata, is_symlink: bool) -> FileAttributes {
+    FileAttributes {
+        size: metadata.len,
+        is_directory: metadata.is_dir(),
+        is_hidden: path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|s| s.starts_with('.')),
+        is_readonly: metadata.readonly,
+        times: FileTimes {
+            modified: metadata.modified,
+            ..FileTimes::default()
+        },
+        link_kind: is_symlink.then_some(LinkKind::Symlink),
+        file_type: metadata.file_type.into(),
+        mode: None,
+        uid: None,
+        gid: None,
+        inode: None,
+        nlink: None,
+    }
+}
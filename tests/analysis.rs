@@ -0,0 +1,96 @@
+use cross_path::CrossPath;
+use cross_path::analysis;
+
+fn paths(items: &[&str]) -> Vec<CrossPath> {
+    items.iter().map(|p| CrossPath::new(p).unwrap()).collect()
+}
+
+#[test]
+fn test_find_duplicates() {
+    let groups = analysis::find_duplicates(paths(&[
+        r"C:\Users\me\project\",
+        "/home/me/a",
+        "/mnt/c/users/me/project",
+        r"C:\Users\me\src\..\project",
+        "/home/me/./a/",
+        "/home/me/A",
+        "/tmp/unique",
+    ]))
+    .unwrap();
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].members.len(), 3);
+    assert_eq!(groups[0].key, "/mnt/c/users/me/project");
+    assert_eq!(groups[1].members.len(), 2);
+    assert_eq!(
+        groups[1].canonical.as_original().to_str(),
+        Some("/home/me/a")
+    );
+}
+
+#[test]
+fn test_normalized_key() {
+    use cross_path::CaseSensitivity;
+    use std::collections::{BTreeSet, HashSet};
+
+    let keys: HashSet<_> = paths(&[r"C:\Foo\Bar", "c:/foo/bar/", "/mnt/c/Foo/Bar"])
+        .iter()
+        .map(|p| p.normalized_key().unwrap())
+        .collect();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys.iter().next().unwrap().as_str(), "/mnt/c/foo/bar");
+
+    let unix = paths(&["/home/me/A", "/home/me/a", "/home/me/./a/"]);
+    let keys: BTreeSet<_> = unix.iter().map(|p| p.normalized_key().unwrap()).collect();
+    assert_eq!(keys.len(), 2);
+    let keys: BTreeSet<_> = unix
+        .iter()
+        .map(|p| p.normalized_key_with(CaseSensitivity::Insensitive).unwrap())
+        .collect();
+    assert_eq!(keys.len(), 1);
+
+    let windows = CrossPath::new(r"C:\Foo").unwrap();
+    assert_eq!(
+        windows
+            .normalized_key_with(CaseSensitivity::Sensitive)
+            .unwrap()
+            .to_string(),
+        "/mnt/c/Foo"
+    );
+}
+
+#[test]
+fn test_length_budget() {
+    use cross_path::PathStyle;
+
+    let base = CrossPath::new(r"C:\projects\app").unwrap();
+    let budget = analysis::length_budget(&base, PathStyle::Windows).unwrap();
+    assert_eq!(budget.base_length, 15);
+    assert_eq!(budget.remaining, 259 - 16);
+    assert!(budget.fits("node_modules/pkg/index.js"));
+    assert!(!budget.fits(&"a".repeat(256)));
+    assert!(!budget.fits(&"abc/".repeat(80)));
+    assert!(budget.overflows.is_empty());
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_length_budget_overflows() {
+    use cross_path::PathStyle;
+    use std::fs;
+
+    let dir = tempfile::tempdir().unwrap();
+    let deep = dir.path().join("a".repeat(100)).join("b".repeat(100));
+    fs::create_dir_all(&deep).unwrap();
+    fs::write(deep.join("c".repeat(100)), "").unwrap();
+    fs::write(dir.path().join("short"), "").unwrap();
+
+    let base = CrossPath::new(dir.path().to_str().unwrap()).unwrap();
+    let budget = analysis::length_budget(&base, PathStyle::Windows).unwrap();
+    assert_eq!(budget.overflows.len(), 1);
+    assert!(budget.overflows[0].relative.ends_with(&"c".repeat(100)));
+    assert!(!budget.overflows[0].component_too_long);
+
+    let budget = analysis::length_budget(&base, PathStyle::Unix).unwrap();
+    assert!(budget.overflows.is_empty());
+}
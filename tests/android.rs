@@ -0,0 +1,157 @@
+use cross_path::PathError;
+use cross_path::platform::android::{
+    AndroidPath, AppLocation, AppStorage, ContentUri, app_dir, app_location, canonical_storage_path,
+};
+
+#[test]
+fn test_android_storage_aliases() {
+    assert_eq!(
+        canonical_storage_path("/sdcard/DCIM/a.jpg"),
+        "/storage/emulated/0/DCIM/a.jpg"
+    );
+    assert_eq!(
+        canonical_storage_path("/storage/self/primary"),
+        "/storage/emulated/0"
+    );
+    assert_eq!(
+        canonical_storage_path("/data/data/com.example/files"),
+        "/data/user/0/com.example/files"
+    );
+    // Only whole components are aliases
+    assert_eq!(canonical_storage_path("/sdcard2/x"), "/sdcard2/x");
+    assert_eq!(
+        canonical_storage_path("/storage/emulated/10/x"),
+        "/storage/emulated/10/x"
+    );
+
+    assert_eq!(
+        app_location("/sdcard/Android/data/com.example.app/files/a.txt"),
+        Some(AppLocation {
+            user: 0,
+            package: "com.example.app".to_string(),
+            storage: AppStorage::External,
+            path: "files/a.txt".to_string(),
+        })
+    );
+    let internal = app_location("/data/user/10/com.example/cache/").unwrap();
+    assert_eq!(internal.user, 10);
+    assert_eq!(internal.storage, AppStorage::Internal);
+    assert_eq!(internal.path, "cache");
+    assert_eq!(
+        app_location("/storage/emulated/0/Android/obb/com.game")
+            .unwrap()
+            .storage,
+        AppStorage::Obb
+    );
+    assert_eq!(app_location("/storage/emulated/0/Download/a.pdf"), None);
+    assert_eq!(app_location("/data/user/0/not a package"), None);
+
+    assert_eq!(
+        app_dir("com.example", AppStorage::Media, 0).unwrap(),
+        "/storage/emulated/0/Android/media/com.example"
+    );
+    assert_eq!(
+        app_dir("com.example", AppStorage::Internal, 10).unwrap(),
+        "/data/user/10/com.example"
+    );
+    assert!(matches!(
+        app_dir("../etc", AppStorage::Internal, 0),
+        Err(PathError::InvalidPath(_))
+    ));
+}
+
+#[test]
+fn test_content_uris() {
+    let uri = ContentUri::parse(
+        "content://com.android.externalstorage.documents/tree/primary%3ADocuments/document/primary%3ADocuments%2Fnotes.txt",
+    )
+    .unwrap();
+    assert_eq!(uri.authority(), "com.android.externalstorage.documents");
+    assert_eq!(uri.tree_document_id(), Some("primary:Documents"));
+    assert_eq!(uri.document_id(), Some("primary:Documents/notes.txt"));
+    assert_eq!(
+        uri.file_path().as_deref(),
+        Some("/storage/emulated/0/Documents/notes.txt")
+    );
+    assert_eq!(uri.to_string().parse::<ContentUri>().unwrap(), uri);
+
+    let removable = ContentUri::for_document("/storage/1A2B-3C4D/Music/song.mp3").unwrap();
+    assert_eq!(
+        removable.to_string(),
+        "content://com.android.externalstorage.documents/document/1A2B-3C4D%3AMusic%2Fsong.mp3"
+    );
+    assert_eq!(
+        removable.file_path().as_deref(),
+        Some("/storage/1A2B-3C4D/Music/song.mp3")
+    );
+    assert_eq!(
+        ContentUri::for_document("/sdcard/Download")
+            .unwrap()
+            .document_id(),
+        Some("primary:Download")
+    );
+    assert!(matches!(
+        ContentUri::for_document("/data/user/0/com.example"),
+        Err(PathError::UnsupportedFormat(_))
+    ));
+
+    let download = ContentUri::parse(
+        "content://com.android.providers.downloads.documents/document/raw%3A%2Fsdcard%2FDownload%2Fa.pdf",
+    )
+    .unwrap();
+    assert_eq!(
+        download.file_path().as_deref(),
+        Some("/storage/emulated/0/Download/a.pdf")
+    );
+
+    // Media store and FileProvider URIs are opaque
+    let media = ContentUri::parse("content://media/external/images/media/42?limit=1#x").unwrap();
+    assert_eq!(media.segments(), ["external", "images", "media", "42"]);
+    assert_eq!(media.query(), Some("limit=1"));
+    assert!(!media.is_convertible());
+    assert_eq!(
+        media.to_string(),
+        "content://media/external/images/media/42?limit=1"
+    );
+    assert!(!ContentUri::new("com.example.fileprovider", ["shared", "a.txt"]).is_convertible());
+
+    assert!(ContentUri::parse("file:///sdcard").is_err());
+    assert!(ContentUri::parse("content:///x").is_err());
+    assert!(ContentUri::parse("content://media/%zz").is_err());
+}
+
+#[test]
+fn test_android_path() {
+    let file = AndroidPath::parse("/sdcard/Download/a b.pdf").unwrap();
+    assert_eq!(
+        file,
+        AndroidPath::File("/storage/emulated/0/Download/a b.pdf".to_string())
+    );
+    assert!(file.is_convertible());
+    assert_eq!(
+        AndroidPath::parse("file:///sdcard/Download/a%20b.pdf").unwrap(),
+        file
+    );
+    assert_eq!(
+        file.to_cross_path().unwrap().to_unix().unwrap(),
+        "/storage/emulated/0/Download/a b.pdf"
+    );
+
+    let document: AndroidPath = "content://com.android.externalstorage.documents/document/primary%3AAndroid%2Fdata%2Fcom.example%2Ffiles"
+        .parse()
+        .unwrap();
+    assert!(document.is_convertible());
+    assert_eq!(document.app_location().unwrap().package, "com.example");
+
+    let opaque = AndroidPath::parse("content://com.example.fileprovider/shared/a.txt").unwrap();
+    assert!(!opaque.is_convertible());
+    assert!(opaque.app_location().is_none());
+    assert_eq!(
+        opaque.to_string(),
+        "content://com.example.fileprovider/shared/a.txt"
+    );
+    assert!(matches!(
+        opaque.to_cross_path(),
+        Err(PathError::UnsupportedFormat(_))
+    ));
+}
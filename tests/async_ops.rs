@@ -0,0 +1,93 @@
+#![cfg(feature = "tokio")]
+
+use cross_path::CrossPath;
+use cross_path::ops::OpOptions;
+use cross_path::ops::r#async::{self as ops, AsyncPathExt};
+use std::fs;
+
+#[tokio::test]
+async fn test_async_file_operations() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| CrossPath::new(dir.path().join(name).to_str().unwrap()).unwrap();
+
+    ops::write_atomic(&path("a.txt"), b"hello").await.unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+        "hello"
+    );
+    assert_eq!(ops::copy(&path("a.txt"), &path("b.txt")).await.unwrap(), 5);
+
+    fs::create_dir_all(dir.path().join("tree/sub")).unwrap();
+    fs::write(dir.path().join("tree/sub/c.txt"), "abc").unwrap();
+    assert_eq!(
+        ops::copy_dir_recursive(&path("tree"), &path("copy"))
+            .await
+            .unwrap(),
+        3
+    );
+    let plan = ops::copy_to(&path("tree"), &path("planned"), &OpOptions::dry_run())
+        .await
+        .unwrap();
+    assert_eq!(plan.len(), 3);
+    assert!(!dir.path().join("planned").exists());
+
+    ops::rename_across_devices(&path("copy"), &path("moved"))
+        .await
+        .unwrap();
+    assert!(dir.path().join("moved/sub/c.txt").exists());
+    ops::remove_dir_all_robust(&path("moved")).await.unwrap();
+    assert!(!dir.path().join("moved").exists());
+    ops::remove_dir_all_robust(&path("moved")).await.unwrap();
+
+    fs::write(dir.path().join("new.txt"), "new").unwrap();
+    ops::replace_file(&path("new.txt"), &path("a.txt"))
+        .await
+        .unwrap();
+    assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "new");
+
+    ops::hard_link(&path("a.txt"), &path("linked.txt"))
+        .await
+        .unwrap();
+    assert!(path("a.txt").is_same_file(&path("linked.txt")).unwrap());
+
+    assert!(
+        ops::copy(&path("missing.txt"), &path("x.txt"))
+            .await
+            .is_err()
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_async_links() {
+    use cross_path::platform::LinkKind;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| CrossPath::new(dir.path().join(name).to_str().unwrap()).unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+
+    ops::symlink(&path("data"), &path("link")).await.unwrap();
+    assert_eq!(ops::read_link(&path("link")).await.unwrap(), path("data"));
+    assert_eq!(
+        ops::link_kind(&path("link")).await.unwrap(),
+        Some(LinkKind::Symlink)
+    );
+    assert!(ops::junction(&path("data"), &path("j")).await.is_err());
+}
+
+#[tokio::test]
+async fn test_async_path_ext() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("file.txt"), "12345").unwrap();
+    let file = CrossPath::new(dir.path().join("file.txt").to_str().unwrap()).unwrap();
+
+    let attributes = file.get_attributes().await.unwrap();
+    assert_eq!(attributes.size, 5);
+    assert!(!attributes.is_directory);
+    assert!(file.is_accessible().await);
+    assert!(file.get_disk_info().await.is_some());
+
+    let missing = CrossPath::new(dir.path().join("missing").to_str().unwrap()).unwrap();
+    assert!(missing.get_attributes().await.is_none());
+    assert!(!missing.is_accessible().await);
+}
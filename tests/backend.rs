@@ -0,0 +1,67 @@
+//! Conversions through a stand-in `cygpath` placed first on `PATH`
+#![cfg(unix)]
+
+use cross_path::{ConversionBackend, CrossPath, PathConfig, PathError, SystemTool};
+use std::os::unix::fs::PermissionsExt;
+
+const FAKE_CYGPATH: &str = r#"#!/bin/sh
+case "$2" in
+    *fail*) exit 1 ;;
+esac
+case "$1" in
+    -u) echo "/tool/unix" ;;
+    -w) printf 'T:\\tool\r\n' ;;
+esac
+"#;
+
+#[test]
+fn test_system_tool_backend() {
+    let dir = tempfile::tempdir().unwrap();
+    let tool = dir.path().join("cygpath");
+    std::fs::write(&tool, FAKE_CYGPATH).unwrap();
+    std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    let mut paths = vec![dir.path().to_path_buf()];
+    paths.extend(std::env::split_paths(&path_var));
+    // SAFETY: this is the only test in the binary, so no other thread reads
+    // the environment concurrently
+    unsafe { std::env::set_var("PATH", std::env::join_paths(paths).unwrap()) };
+
+    let config = |backend| PathConfig {
+        drive_mappings: vec![("C:".to_string(), "/mnt/c".to_string())].into(),
+        backend,
+        ..PathConfig::default()
+    };
+    let path = |path: &str, backend| CrossPath::with_config(path, config(backend)).unwrap();
+    let system = ConversionBackend::System(SystemTool::Cygpath);
+    let verify = ConversionBackend::Verify(SystemTool::Cygpath);
+
+    // The tool's output wins, with the built-in logic as fallback
+    assert_eq!(path(r"C:\data", system).to_unix().unwrap(), "/tool/unix");
+    assert_eq!(
+        path("/mnt/c/data", system).to_windows().unwrap(),
+        r"T:\tool"
+    );
+    assert_eq!(path(r"C:\fail", system).to_unix().unwrap(), "/mnt/c/fail");
+    assert_eq!(path(r"data\file", system).to_unix().unwrap(), "data/file");
+    assert_eq!(
+        path(r"C:\data", ConversionBackend::System(SystemTool::Wslpath))
+            .to_unix()
+            .unwrap(),
+        "/mnt/c/data"
+    );
+    assert_eq!(
+        path(r"C:\data", ConversionBackend::Builtin)
+            .to_unix()
+            .unwrap(),
+        "/mnt/c/data"
+    );
+
+    // Verification reports disagreements and passes agreeing results
+    assert!(matches!(
+        path(r"C:\data", verify).to_unix(),
+        Err(PathError::PlatformError(_))
+    ));
+    assert_eq!(path(r"C:\fail", verify).to_unix().unwrap(), "/mnt/c/fail");
+    assert_eq!(path("/mnt/c/x", verify).to_unix().unwrap(), "/mnt/c/x");
+}
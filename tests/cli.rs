@@ -0,0 +1,76 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn crosspath(args: &[&str], stdin: Option<&str>) -> Output {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("paths.toml");
+    std::fs::write(
+        &config,
+        "unmapped = \"error\"\n[drives]\n\"C:\" = \"/mnt/c\"\n\"D:\" = \"/mnt/d\"\n",
+    )
+    .unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_crosspath"))
+        .arg("--config")
+        .arg(&config)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut input = child.stdin.take().unwrap();
+    input
+        .write_all(stdin.unwrap_or_default().as_bytes())
+        .unwrap();
+    drop(input);
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_cli_convert() {
+    let output = crosspath(&["-u", r"C:\Users\me"], None);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "/mnt/c/Users/me\n");
+
+    let output = crosspath(&["-w", "/mnt/d/data", "/mnt/c"], None);
+    assert_eq!(stdout(&output), "D:\\data\nC:\\\n");
+
+    let output = crosspath(&["-m", "/mnt/c/x/y"], None);
+    assert_eq!(stdout(&output), "C:/x/y\n");
+
+    let output = crosspath(&["--style", "cygwin", r"C:\x"], None);
+    assert_eq!(stdout(&output), "/cygdrive/c/x\n");
+    let output = crosspath(&["--style=msys", r"D:\y"], None);
+    assert_eq!(stdout(&output), "/d/y\n");
+}
+
+#[test]
+fn test_cli_stdin_and_errors() {
+    // Paths from stdin; a failing path does not stop the batch
+    let output = crosspath(&["-w"], Some("/mnt/c/a\r\n\n/srv/unmapped\n/mnt/d/b\n"));
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "C:\\a\nD:\\b\n");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("/srv/unmapped"));
+
+    let output = crosspath(&["--check", "/srv/data/file.txt", "../../etc/passwd"], None);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "/srv/data/file.txt\n");
+    // The rejected `..` is underlined
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("│ ../../etc/passwd"), "{stderr}");
+    assert!(stderr.contains("rejected here"), "{stderr}");
+
+    let output = crosspath(&["--style", "vms", "x"], None);
+    assert_eq!(output.status.code(), Some(2));
+    let output = crosspath(&["--frobnicate"], None);
+    assert_eq!(output.status.code(), Some(2));
+    let output = crosspath(&["--version"], None);
+    assert!(stdout(&output).starts_with("crosspath "));
+}
@@ -0,0 +1,191 @@
+use cross_path::{CrossPath, PathConfig, PathConvert, PathStyle, UnmappedPolicy, registry};
+
+#[test]
+fn test_global_and_scoped_config() {
+    let global = PathConfig {
+        drive_mappings: vec![("W:".to_string(), "/work".to_string())].into(),
+        ..PathConfig::default()
+    };
+    global.clone().set_global();
+    assert_eq!(PathConfig::current(), global);
+    assert_eq!(
+        CrossPath::new("/work/src").unwrap().to_windows().unwrap(),
+        r"W:\src"
+    );
+    assert_eq!("/work/src".to_windows_path().unwrap(), r"W:\src");
+
+    let scoped = PathConfig {
+        style: PathStyle::Unix,
+        unmapped_policy: UnmappedPolicy::DefaultDrive('D'),
+        ..global.clone()
+    };
+    let nested = scoped.clone().scope(|| {
+        assert_eq!(
+            CrossPath::new("/tmp").unwrap().to_windows().unwrap(),
+            r"D:\tmp"
+        );
+        let inner = PathConfig::default();
+        inner
+            .clone()
+            .scope(|| assert_eq!(PathConfig::current(), inner));
+        PathConfig::current()
+    });
+    assert_eq!(nested, scoped);
+
+    // Scopes are per thread and unwound on panic
+    let result = std::panic::catch_unwind(|| {
+        registry::with_config(PathConfig::default(), || panic!("boom"));
+    });
+    assert!(result.is_err());
+    assert_eq!(PathConfig::current(), global);
+    std::thread::spawn(move || scoped.scope(|| ()))
+        .join()
+        .unwrap();
+    assert_eq!(PathConfig::current(), global);
+
+    registry::clear_global_config();
+    assert_eq!(PathConfig::current(), PathConfig::default());
+}
+
+#[cfg(all(feature = "toml", feature = "json"))]
+#[test]
+fn test_load_config_file() {
+    use cross_path::config::ConfigFile;
+    use cross_path::{
+        AdminSharePolicy, CaseSensitivity, ConversionBackend, DataStreamPolicy, DotDotPolicy,
+        NormalizeMode, PathError, SystemTool, TrailingSlash, UnicodeNormalization,
+    };
+
+    let dir = tempfile::tempdir().unwrap();
+    let toml_path = dir.path().join("paths.toml");
+    std::fs::write(
+        &toml_path,
+        r#"
+style = "unix"
+case_sensitivity = "insensitive"
+unicode_normalization = "nfd"
+normalize_mode = "lexical_then_verify"
+dot_dot = "keep"
+data_streams = "strip"
+trailing_slash = "preserve"
+admin_shares = "mount"
+unmapped = "error"
+backend = "verify:cygpath"
+mount_root = "/"
+
+[drives]
+"P:" = "/projects"
+
+[current_dirs]
+d = 'D:\work'
+
+[[rewrite]]
+kind = "prefix"
+from = "/builds/runner"
+to = "/projects"
+
+[security]
+check = false
+max_path_length = 64
+reject_data_streams = true
+"#,
+    )
+    .unwrap();
+
+    let config = PathConfig::from_file(&toml_path).unwrap();
+    assert_eq!(config.style, PathStyle::Unix);
+    assert_eq!(config.case_sensitivity, CaseSensitivity::Insensitive);
+    assert_eq!(config.unicode_normalization, UnicodeNormalization::Nfd);
+    assert_eq!(config.normalize_mode, NormalizeMode::LexicalThenVerify);
+    assert_eq!(config.dot_dot_policy, DotDotPolicy::Keep);
+    assert_eq!(config.data_streams, DataStreamPolicy::Strip);
+    assert_eq!(config.trailing_slash, TrailingSlash::Preserve);
+    assert_eq!(config.admin_shares, AdminSharePolicy::MountPoint);
+    assert!(config.security_policy.rejects_data_streams());
+    assert_eq!(config.unmapped_policy, UnmappedPolicy::Error);
+    assert_eq!(
+        config.backend,
+        ConversionBackend::Verify(SystemTool::Cygpath)
+    );
+    assert!(!config.security_check);
+    assert_eq!(config.security_policy.max_path_length(), Some(64));
+    assert_eq!(
+        config.drive_mappings.as_slice()[0],
+        ("P:".to_string(), "/projects".to_string())
+    );
+
+    assert_eq!(config.drive_current_dirs[&'D'], r"D:\work");
+
+    let path = CrossPath::with_config(r"C:\Users\me", config.clone()).unwrap();
+    assert_eq!(path.to_unix().unwrap(), "/c/Users/me");
+    assert_eq!(config.rewriter.len(), 1);
+    let path = CrossPath::with_config("/builds/runner/app/main.rs", config.clone()).unwrap();
+    assert_eq!(path.to_windows().unwrap(), r"P:\app\main.rs");
+    let path = CrossPath::with_config(r"P:\app", config).unwrap();
+    assert_eq!(path.to_unix().unwrap(), "/projects/app");
+
+    let json_path = dir.path().join("paths.json");
+    std::fs::write(&json_path, r#"{"style": "Windows", "unmapped": "d"}"#).unwrap();
+    let config = PathConfig::from_file(&json_path).unwrap();
+    assert_eq!(config.style, PathStyle::Windows);
+    assert_eq!(config.unmapped_policy, UnmappedPolicy::DefaultDrive('D'));
+
+    assert!(matches!(
+        ConfigFile::from_toml_str("colour = \"blue\""),
+        Err(PathError::ParseError(_))
+    ));
+    assert!(matches!(
+        ConfigFile::from_toml_str("style = \"amiga\"")
+            .unwrap()
+            .into_config(),
+        Err(PathError::ParseError(_))
+    ));
+    assert!(matches!(
+        ConfigFile::from_toml_str("backend = \"builtin:wslpath\"")
+            .unwrap()
+            .into_config(),
+        Err(PathError::ParseError(_))
+    ));
+    assert!(matches!(
+        ConfigFile::from_toml_str("[current_dirs]\nDD = 'x'")
+            .unwrap()
+            .into_config(),
+        Err(PathError::ParseError(_))
+    ));
+    assert!(matches!(
+        ConfigFile::from_toml_str("[[rewrite]]\nkind = 'regex'\npattern = '('\nreplacement = ''")
+            .unwrap()
+            .into_config(),
+        Err(PathError::ParseError(_))
+    ));
+    let yaml_path = dir.path().join("paths.yaml");
+    std::fs::write(&yaml_path, "style: unix").unwrap();
+    assert!(matches!(
+        PathConfig::from_file(&yaml_path),
+        Err(PathError::UnsupportedFormat(_))
+    ));
+}
+
+#[test]
+fn test_config_from_env() {
+    let env = |name: &str| match name {
+        "CROSS_PATH_STYLE" => Some("windows".to_string()),
+        "CROSS_PATH_DRIVES" => Some("W:=$HOME/work; X:=/x".to_string()),
+        "CROSS_PATH_SECURITY_CHECK" => Some("off".to_string()),
+        "CROSS_PATH_BACKEND" => Some("system".to_string()),
+        "HOME" => Some("/home/me".to_string()),
+        _ => None,
+    };
+    let config = PathConfig::from_env_with(&env).unwrap();
+    assert_eq!(config.style, PathStyle::Windows);
+    assert!(!config.security_check);
+    assert_eq!(
+        config.backend,
+        cross_path::ConversionBackend::System(cross_path::SystemTool::Detect)
+    );
+    assert_eq!(config.drive_mappings.get("W:"), Some("/home/me/work"));
+    assert_eq!(config.drive_mappings.get("X:"), Some("/x"));
+
+    let bad = |name: &str| (name == "CROSS_PATH_SECURITY_CHECK").then(|| "maybe".to_string());
+    assert!(PathConfig::from_env_with(&bad).is_err());
+}
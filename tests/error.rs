@@ -0,0 +1,133 @@
+use cross_path::error::ErrorKind;
+#[cfg(feature = "security")]
+use cross_path::security::{PathSecurityChecker, SecurityPolicy};
+use cross_path::validation::validate_strict;
+use cross_path::{PathError, PathStyle};
+use std::error::Error;
+#[cfg(feature = "security")]
+use {cross_path::ValidationTarget, std::path::Path};
+
+#[test]
+fn test_error_kind_and_display() {
+    let error = PathError::parse_error("bad input").with_path("C:\\x");
+    assert_eq!(error.kind(), ErrorKind::Parse);
+    assert!(matches!(error, PathError::ParseError(_)));
+    assert_eq!(error.to_string(), "Parse error: bad input");
+    assert_eq!(error.message(), "bad input");
+    assert_eq!(error.path(), Some("C:\\x"));
+    assert_eq!(error.span(), None);
+    assert!(error.source().is_none());
+
+    assert_eq!(
+        PathError::new(ErrorKind::CrossDrive, "x"),
+        PathError::cross_drive_error("x")
+    );
+    assert_ne!(
+        PathError::invalid_path("x"),
+        PathError::invalid_path("x").with_span(0..1)
+    );
+}
+
+#[test]
+fn test_io_error_source() {
+    let io = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+    let error = PathError::from(io);
+    assert_eq!(error.kind(), ErrorKind::Io);
+    assert_eq!(
+        error.io_error_kind(),
+        Some(std::io::ErrorKind::PermissionDenied)
+    );
+    assert_eq!(error.source().unwrap().to_string(), "denied");
+    assert_eq!(error.clone().to_string(), "IO error: denied");
+
+    let missing = std::fs::read("/nonexistent/cross-path").map_err(PathError::from);
+    assert_eq!(
+        missing.unwrap_err().io_error_kind(),
+        Some(std::io::ErrorKind::NotFound)
+    );
+    assert_eq!(PathError::io_error("plain").io_error_kind(), None);
+
+    // Wrapped errors keep the original in the chain
+    let wrapped = PathError::parse_error("Line 3: bad")
+        .with_source(PathError::invalid_path("bad").with_span(1..2));
+    let inner = wrapped
+        .source()
+        .unwrap()
+        .downcast_ref::<PathError>()
+        .unwrap();
+    assert_eq!(inner.span(), Some(1..2));
+}
+
+#[cfg(feature = "security")]
+#[test]
+fn test_security_error_spans() {
+    let checker = PathSecurityChecker::new();
+    let error = checker
+        .check(Path::new("docs/../../etc/passwd"))
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Security);
+    assert_eq!(error.path(), Some("docs/../../etc/passwd"));
+    assert_eq!(error.span(), Some(5..7));
+
+    let policy = SecurityPolicy::builder()
+        .validation_target(ValidationTarget::Windows)
+        .build()
+        .unwrap();
+    let checker = PathSecurityChecker::with_policy(&policy).unwrap();
+    let error = checker.check(Path::new("data/nul.txt/x")).unwrap_err();
+    assert_eq!(error.span(), Some(5..12));
+}
+
+#[test]
+fn test_error_spans() {
+    let error = validate_strict(r"C:\dir\a|b.txt", PathStyle::Windows).unwrap_err();
+    assert_eq!(error.span(), Some(8..9));
+    let error = validate_strict(r"\\?\C:\dir\a|b", PathStyle::Windows).unwrap_err();
+    assert_eq!(error.path(), Some(r"\\?\C:\dir\a|b"));
+    assert_eq!(error.span(), Some(12..13));
+    let error = validate_strict(r"1:\dir", PathStyle::Windows).unwrap_err();
+    assert_eq!(error.span(), Some(0..2));
+    let error = validate_strict("a/b\0c", PathStyle::Unix).unwrap_err();
+    assert_eq!(error.span(), Some(3..4));
+}
+
+#[cfg(all(feature = "diagnostics", feature = "security"))]
+#[test]
+fn test_render_diagnostics() {
+    let error = PathSecurityChecker::new()
+        .check(Path::new("docs/../../etc/passwd"))
+        .unwrap_err();
+    let rendered = error.render(false);
+    assert!(rendered.contains("cross_path::security"), "{rendered}");
+    assert!(
+        rendered.contains("Security error: Path traversal attack detected"),
+        "{rendered}"
+    );
+    assert!(rendered.contains("│ docs/../../etc/passwd"), "{rendered}");
+    // The underline starts under the first `..`
+    let line = rendered.lines().find(|line| line.contains("─┬")).unwrap();
+    let source = rendered
+        .lines()
+        .find(|line| line.contains("docs/"))
+        .unwrap();
+    assert_eq!(
+        line.chars().position(|c| c == '─'),
+        source.chars().position(|c| c == '.')
+    );
+    assert!(rendered.contains("rejected here"), "{rendered}");
+    assert!(!error.render(false).contains('\u{1b}'));
+
+    // Underlying errors are listed after the snippet
+    let wrapped = PathError::parse_error("Line 3: bad path")
+        .with_path("C:\\a|b")
+        .with_span(4..5)
+        .with_source(std::io::Error::other("disk on fire"));
+    let rendered = wrapped.render(false);
+    assert!(rendered.contains("invalid here"), "{rendered}");
+    assert!(rendered.contains("disk on fire"), "{rendered}");
+
+    // Errors without a path are just their message
+    let plain = PathError::drive_mapping_error("No drive mapping for /srv").render(false);
+    assert!(plain.contains("Drive mapping error: No drive mapping for /srv"));
+    assert!(!plain.contains('│'));
+}
@@ -0,0 +1,112 @@
+use cross_path::{
+    CaseSensitivity, CrossGlob, CrossPath, DriveMap, PathConfig, PathError, PathStyle,
+};
+
+fn config() -> PathConfig {
+    PathConfig {
+        drive_mappings: DriveMap::from_pairs([("C:", "/mnt/c"), ("D:", "/mnt/d")]).unwrap(),
+        ..PathConfig::default()
+    }
+}
+
+fn glob(pattern: &str) -> CrossGlob {
+    CrossGlob::with_config(pattern, config()).unwrap()
+}
+
+fn path(path: &str) -> CrossPath {
+    CrossPath::with_config(path, config()).unwrap()
+}
+
+#[test]
+fn test_convert_glob_patterns() {
+    let windows = glob(r"C:\src\**\*.rs");
+    assert_eq!(windows.style(), PathStyle::Windows);
+    assert_eq!(windows.pattern(), r"C:\src\**\*.rs");
+    assert_eq!(windows.to_unix().unwrap(), "/mnt/c/src/**/*.rs");
+    assert_eq!(windows.to_windows().unwrap(), r"C:\src\**\*.rs");
+    assert_eq!(
+        windows.to_style(PathStyle::Cygwin).unwrap(),
+        "/cygdrive/c/src/**/*.rs"
+    );
+    assert_eq!(windows.to_string(), r"C:\src\**\*.rs");
+
+    let unix = glob("/mnt/d/data/{logs,tmp}/20??-[0-9]*.txt");
+    assert_eq!(
+        unix.to_windows().unwrap(),
+        r"D:\data\{logs,tmp}\20??-[0-9]*.txt"
+    );
+
+    // Separators inside alternatives are converted too
+    assert_eq!(
+        glob("/mnt/c/{a/b,c}/*").to_windows().unwrap(),
+        r"C:\{a\b,c}\*"
+    );
+
+    // Escapes become one-character classes on Windows
+    assert_eq!(
+        glob(r"/mnt/c/notes/\*draft\*.md").to_windows().unwrap(),
+        r"C:\notes\[*]draft[*].md"
+    );
+    assert_eq!(
+        glob(r"/mnt/c/notes/\*draft.md").to_unix().unwrap(),
+        r"/mnt/c/notes/\*draft.md"
+    );
+
+    // Relative patterns only have their separators swapped
+    assert_eq!(glob(r"src\**\*.rs").to_unix().unwrap(), "src/**/*.rs");
+    assert_eq!(
+        CrossGlob::with_style("**/*.rs", PathStyle::Unix)
+            .unwrap()
+            .to_windows()
+            .unwrap(),
+        r"**\*.rs"
+    );
+
+    // Patterns without wildcards convert like paths
+    assert_eq!(glob(r"C:\Users\me").to_unix().unwrap(), "/mnt/c/Users/me");
+
+    assert!(matches!(
+        CrossGlob::with_config("/src/{a,b", config()),
+        Err(PathError::ParseError(_))
+    ));
+}
+
+#[test]
+fn test_glob_matches() {
+    let rust = glob(r"C:\src\**\*.{rs,toml}");
+    assert!(rust.matches(&path("/mnt/c/src/main.rs")).unwrap());
+    assert!(rust.matches(&path(r"C:\src\app\lib\mod.rs")).unwrap());
+    assert!(rust.matches(&path("/mnt/c/src/Cargo.toml")).unwrap());
+    assert!(!rust.matches(&path("/mnt/c/src/README.md")).unwrap());
+    assert!(!rust.matches(&path("/mnt/d/src/main.rs")).unwrap());
+
+    // `*` and `?` stay within one directory
+    let logs = glob("/var/log/*.lo?");
+    assert!(logs.matches(&path("/var/log/syslog.log")).unwrap());
+    assert!(!logs.matches(&path("/var/log/nginx/access.log")).unwrap());
+
+    let classes = glob("/data/[!.]*/file[0-9]");
+    assert!(classes.matches(&path("/data/x/file1")).unwrap());
+    assert!(!classes.matches(&path("/data/.git/file1")).unwrap());
+
+    // Trailing separators and `./` do not matter
+    let relative = glob("./src/*/");
+    assert!(relative.matches(&path("src/bin")).unwrap());
+    assert!(relative.matches(&path(r"src\bin\")).unwrap());
+
+    // Case sensitivity follows the configuration unless overridden
+    let upper = glob(r"C:\SRC\*.RS");
+    assert!(!upper.matches(&path("/mnt/c/src/main.rs")).unwrap());
+    let upper = upper.case_sensitivity(CaseSensitivity::Insensitive);
+    assert!(upper.matches(&path("/mnt/c/src/main.rs")).unwrap());
+
+    let insensitive = CrossGlob::with_config(
+        "/mnt/c/src/*.rs",
+        PathConfig {
+            case_sensitivity: CaseSensitivity::Insensitive,
+            ..config()
+        },
+    )
+    .unwrap();
+    assert!(insensitive.matches(&path(r"C:\Src\MAIN.RS")).unwrap());
+}
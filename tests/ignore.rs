@@ -0,0 +1,99 @@
+#![cfg(feature = "gitignore")]
+
+use cross_path::CrossPath;
+use cross_path::ignore::IgnoreMatcher;
+
+fn ignored(matcher: &IgnoreMatcher, path: &str, is_dir: bool) -> bool {
+    matcher
+        .is_ignored(&CrossPath::new(path).unwrap(), is_dir)
+        .unwrap()
+}
+
+#[test]
+fn test_ignore_patterns() {
+    let matcher = IgnoreMatcher::parse(
+        "# build output\n\
+         target/\n\
+         *.log\n\
+         !keep.log\n\
+         /docs/*.pdf\n\
+         **/cache/**\n\
+         src/**/gen_*.rs\n",
+    )
+    .unwrap();
+
+    assert!(ignored(&matcher, r"target\debug\app.exe", false));
+    assert!(ignored(&matcher, "crates/x/target", true));
+    assert!(!ignored(&matcher, "crates/x/target", false));
+    assert!(ignored(&matcher, r"logs\build.log", false));
+    assert!(!ignored(&matcher, "logs/keep.log", false));
+    assert!(ignored(&matcher, r"docs\manual.pdf", false));
+    assert!(!ignored(&matcher, "sub/docs/manual.pdf", false));
+    assert!(ignored(&matcher, "a/cache/b/c.bin", false));
+    assert!(ignored(&matcher, r"src\x\y\gen_api.rs", false));
+    assert!(ignored(&matcher, "src/gen_api.rs", false));
+    assert!(!ignored(&matcher, "src/main.rs", false));
+}
+
+#[test]
+fn test_ignore_parent_cannot_be_reincluded() {
+    let matcher = IgnoreMatcher::parse("build/\n!build/keep.txt\n").unwrap();
+    assert!(ignored(&matcher, "build/keep.txt", false));
+
+    let matcher = IgnoreMatcher::parse("build/*\n!build/keep.txt\n").unwrap();
+    assert!(!ignored(&matcher, "build/keep.txt", false));
+    assert!(ignored(&matcher, "build/other.txt", false));
+}
+
+#[test]
+fn test_ignore_root_and_case() {
+    let mut matcher = IgnoreMatcher::parse("/Dist\n[Tt]emp?\n").unwrap();
+    matcher
+        .set_root(&CrossPath::new(r"C:\work\proj").unwrap())
+        .unwrap();
+
+    assert!(ignored(&matcher, "/mnt/c/work/proj/Dist", true));
+    assert!(ignored(&matcher, r"C:\work\proj\a\temp1", false));
+    assert!(!ignored(&matcher, r"C:\work\other\Dist", true));
+    assert!(!ignored(&matcher, r"C:\work\proj\dist", true));
+
+    matcher.set_case_insensitive(true).unwrap();
+    assert!(ignored(&matcher, r"C:\work\proj\dist", true));
+}
+
+#[test]
+fn test_ignore_borrowed_paths() {
+    let matcher = IgnoreMatcher::parse("target/\n*.log\n").unwrap();
+    assert!(matcher.is_ignored(r"target\debug\app", false).unwrap());
+    assert!(matcher.is_ignored("logs/app.log", false).unwrap());
+    assert!(!matcher.is_ignored("src/main.rs", false).unwrap());
+}
+
+#[test]
+fn test_dockerignore_rules() {
+    use cross_path::ignore::IgnoreSyntax;
+
+    let matcher = IgnoreMatcher::parse_with(
+        "# context\n\
+         *.log\n\
+         /build/\n\
+         ./node_modules\n\
+         **/*.tmp\n\
+         !build/keep.txt\n",
+        IgnoreSyntax::Docker,
+    )
+    .unwrap();
+    assert_eq!(matcher.syntax(), IgnoreSyntax::Docker);
+
+    // Patterns are anchored at the root
+    assert!(ignored(&matcher, "app.log", false));
+    assert!(!ignored(&matcher, r"logs\app.log", false));
+    assert!(ignored(&matcher, r"node_modules\x\index.js", false));
+    assert!(ignored(&matcher, r"a\b\c.tmp", false));
+
+    // A trailing `/` does not restrict to directories, and negation reaches
+    // inside an ignored directory
+    assert!(ignored(&matcher, "build", false));
+    assert!(ignored(&matcher, "build/out.o", false));
+    assert!(!ignored(&matcher, r"build\keep.txt", false));
+}
@@ -0,0 +1,90 @@
+use cross_path::list::{self, PathList};
+use cross_path::{DriveMap, PathConfig, PathError, PathStyle, UnmappedPolicy};
+
+fn config() -> PathConfig {
+    PathConfig {
+        drive_mappings: DriveMap::from_pairs([("C:", "/mnt/c"), ("D:", "/mnt/d")]).unwrap(),
+        unmapped_policy: UnmappedPolicy::WslUnc {
+            distro: "Ubuntu".to_string(),
+        },
+        ..PathConfig::default()
+    }
+}
+
+#[test]
+fn test_parse_path_lists() {
+    let windows = PathList::parse(
+        r#"C:\Windows\system32;;"C:\Program Files\a;b";D:\bin;"#,
+        PathStyle::Auto,
+    );
+    assert_eq!(windows.style(), PathStyle::Windows);
+    assert_eq!(
+        windows.entries(),
+        [r"C:\Windows\system32", r"C:\Program Files\a;b", r"D:\bin"]
+    );
+    assert_eq!(
+        windows.join().unwrap(),
+        r#"C:\Windows\system32;"C:\Program Files\a;b";D:\bin"#
+    );
+
+    let unix = PathList::parse("/usr/local/bin::/usr/bin:/bin", PathStyle::Auto);
+    assert_eq!(unix.style(), PathStyle::Unix);
+    assert_eq!(
+        unix.iter().collect::<Vec<_>>(),
+        ["/usr/local/bin", "/usr/bin", "/bin"]
+    );
+
+    assert_eq!(PathList::detect_style(r"C:\bin"), PathStyle::Windows);
+    assert_eq!(PathList::detect_style("/bin"), PathStyle::Unix);
+    assert_eq!(PathList::detect_style("a:b"), PathStyle::Unix);
+    assert!(PathList::parse("", PathStyle::Auto).is_empty());
+
+    let mut built = PathList::new(PathStyle::Unix);
+    built.extend(["/opt/bin", "", "/srv/my:app"]);
+    assert_eq!(built.len(), 2);
+    assert!(matches!(built.join(), Err(PathError::InvalidPath(_))));
+}
+
+#[test]
+fn test_convert_path_lists() {
+    let config = config();
+    assert_eq!(
+        list::convert(
+            r#"C:\Windows;"D:\a;b\bin";C:\Tools"#,
+            PathStyle::Unix,
+            &config
+        )
+        .unwrap(),
+        "/mnt/c/Windows:/mnt/d/a;b/bin:/mnt/c/Tools"
+    );
+    assert_eq!(
+        list::convert(r"C:\Windows;D:\bin", PathStyle::Unix, &config).unwrap(),
+        "/mnt/c/Windows:/mnt/d/bin"
+    );
+    assert_eq!(
+        list::convert("/mnt/c/Tools:/usr/bin", PathStyle::Windows, &config).unwrap(),
+        r"C:\Tools;\\wsl$\Ubuntu\usr\bin"
+    );
+    assert_eq!(
+        list::convert(r"C:\Tools;D:\bin", PathStyle::Cygwin, &config).unwrap(),
+        "/cygdrive/c/Tools:/cygdrive/d/bin"
+    );
+    let msys = PathList::parse("/c/Tools:/d/bin", PathStyle::Msys);
+    assert_eq!(
+        msys.convert(PathStyle::Windows, &config)
+            .unwrap()
+            .join()
+            .unwrap(),
+        r"C:\Tools;D:\bin"
+    );
+
+    let strict = PathConfig {
+        unmapped_policy: UnmappedPolicy::Error,
+        ..config
+    };
+    assert!(matches!(
+        list::convert("/mnt/c/x:/srv/y", PathStyle::Windows, &strict),
+        Err(PathError::DriveMappingError(detail))
+            if detail.message() == "Entry 2: No drive mapping for /srv/y"
+    ));
+}
@@ -0,0 +1,618 @@
+use cross_path::mapping::devcontainer::{parse_compose_volumes, parse_volume_spec};
+use cross_path::mapping::{MappingSet, PathMapping};
+
+#[test]
+fn test_mapping_set_translation() {
+    let set: MappingSet = vec![
+        PathMapping::new(r"C:\proj", "/workspace"),
+        PathMapping::new(r"C:\proj\cache", "/cache"),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(
+        set.to_guest(r"c:\Proj\src\main.rs").as_deref(),
+        Some("/workspace/src/main.rs")
+    );
+    // Longest prefix wins
+    assert_eq!(
+        set.to_guest(r"C:\proj\cache\a.bin").as_deref(),
+        Some("/cache/a.bin")
+    );
+    assert_eq!(
+        set.to_host("/workspace/src/main.rs").as_deref(),
+        Some(r"C:\proj\src\main.rs")
+    );
+    // Component boundaries are respected
+    assert_eq!(set.to_host("/workspaces/other"), None);
+}
+
+#[test]
+fn test_parse_volume_spec() {
+    let mapping = parse_volume_spec(r"C:\proj:/app:ro", "/").unwrap();
+    assert_eq!(mapping, PathMapping::new(r"C:\proj", "/app"));
+
+    let mapping = parse_volume_spec("../src:/workspace:cached", r"C:\proj\.devcontainer").unwrap();
+    assert_eq!(mapping, PathMapping::new(r"C:\proj\src", "/workspace"));
+
+    // Named volumes have no host path
+    assert_eq!(
+        parse_volume_spec("node_modules:/app/node_modules", "/"),
+        None
+    );
+}
+
+#[test]
+fn test_parse_compose_volumes() {
+    let compose = r#"
+services:
+  app:
+    image: rust
+    volumes:
+      - ..:/workspaces/proj:cached
+      - cargo-cache:/usr/local/cargo
+      - type: bind
+        source: ./data
+        target: /data
+  db:
+    volumes:
+      - ./db:/var/lib/db
+volumes:
+  cargo-cache:
+"#;
+
+    let set = parse_compose_volumes(compose, Some("app"), "/home/me/proj/.devcontainer").unwrap();
+    assert_eq!(set.len(), 2);
+    assert_eq!(
+        set.to_guest("/home/me/proj/src/lib.rs").as_deref(),
+        Some("/workspaces/proj/src/lib.rs")
+    );
+    assert_eq!(
+        set.to_host("/data/x.csv").as_deref(),
+        Some("/home/me/proj/.devcontainer/data/x.csv")
+    );
+
+    let all = parse_compose_volumes(compose, None, "/srv").unwrap();
+    assert_eq!(all.len(), 3);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_parse_devcontainer() {
+    use cross_path::mapping::devcontainer::parse_devcontainer;
+
+    let json = r#"{
+        // Comments and trailing commas are allowed
+        "name": "proj",
+        "mounts": [
+            "source=${localWorkspaceFolder}/../shared,target=/shared,type=bind",
+            "source=vol,target=/vol,type=volume",
+        ],
+    }"#;
+
+    let set = parse_devcontainer(json, r"C:\src\proj").unwrap();
+    assert_eq!(
+        set.to_guest(r"C:\src\proj\main.rs").as_deref(),
+        Some("/workspaces/proj/main.rs")
+    );
+    assert_eq!(
+        set.to_host("/shared/readme.md").as_deref(),
+        Some(r"C:\src\shared\readme.md")
+    );
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_parse_vagrantfile() {
+    use cross_path::mapping::vagrant::parse_vagrantfile;
+
+    let vagrantfile = r#"
+Vagrant.configure("2") do |config|
+  config.vm.box = "ubuntu/jammy64"
+  config.vm.synced_folder "../data", "/srv/data"
+  # config.vm.synced_folder "ignored", "/ignored"
+  config.vm.synced_folder ".", "/home/vagrant/app", type: "rsync"
+end
+"#;
+
+    let set = parse_vagrantfile(vagrantfile, r"C:\vm\box");
+    assert_eq!(set.len(), 3);
+    assert_eq!(
+        set.to_guest(r"C:\vm\box\Vagrantfile").as_deref(),
+        Some("/home/vagrant/app/Vagrantfile")
+    );
+    assert_eq!(
+        set.to_host("/srv/data/db.sqlite").as_deref(),
+        Some(r"C:\vm\data\db.sqlite")
+    );
+    assert_eq!(
+        set.to_host("/vagrant/notes.txt").as_deref(),
+        Some(r"C:\vm\box\notes.txt")
+    );
+
+    let disabled = r#"config.vm.synced_folder ".", "/vagrant", disabled: true"#;
+    assert!(parse_vagrantfile(disabled, "/home/me/vm").is_empty());
+}
+
+#[test]
+fn test_virtualbox_windows_guest() {
+    use cross_path::mapping::vagrant::{GuestOs, virtualbox_share};
+
+    let set: MappingSet = [virtualbox_share(
+        "/home/me/shared",
+        "shared",
+        GuestOs::Windows,
+    )]
+    .into_iter()
+    .collect();
+    assert_eq!(
+        set.to_host(r"\\VBOXSVR\shared\docs\a.txt").as_deref(),
+        Some("/home/me/shared/docs/a.txt")
+    );
+    assert_eq!(
+        set.to_guest("/home/me/shared/docs").as_deref(),
+        Some(r"\\vboxsvr\shared\docs")
+    );
+}
+
+#[test]
+fn test_parse_smb_conf() {
+    use cross_path::mapping::samba::parse_smb_conf;
+
+    let conf = r"
+[global]
+   workgroup = WORKGROUP
+
+[Data]
+   path = /srv/data
+   read only = no
+
+[homes]
+   path = /home/%U
+
+; disabled share
+[old]
+   path = /srv/old
+   available = no
+";
+
+    let set = parse_smb_conf(conf, "fileserver").unwrap();
+    assert_eq!(set.len(), 1);
+    assert_eq!(
+        set.to_host(r"\\FILESERVER\data\reports\q1.xlsx").as_deref(),
+        Some("/srv/data/reports/q1.xlsx")
+    );
+    assert_eq!(
+        set.to_guest("/srv/data/reports").as_deref(),
+        Some(r"\\fileserver\Data\reports")
+    );
+}
+
+#[test]
+fn test_mapping_change_apply() {
+    use cross_path::mapping::watcher::MappingChange;
+
+    let old = vec![("C:".to_string(), "/mnt/c".to_string())];
+    let new = vec![
+        ("C:".to_string(), "/mnt/c".to_string()),
+        (r"\\nas\media".to_string(), "/media/nas".to_string()),
+    ];
+    let change = MappingChange::between(&old, &new);
+    assert_eq!(change.added.len(), 1);
+    assert!(change.removed.is_empty());
+
+    let mut mappings = vec![
+        ("C:".to_string(), "/mnt/c".to_string()),
+        ("Z:".to_string(), "/network".to_string()),
+    ];
+    change.apply(&mut mappings);
+    assert_eq!(mappings.len(), 3);
+
+    MappingChange::between(&new, &old).apply(&mut mappings);
+    assert_eq!(
+        mappings,
+        vec![
+            ("C:".to_string(), "/mnt/c".to_string()),
+            ("Z:".to_string(), "/network".to_string()),
+        ]
+    );
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_discover_drive_mappings() {
+    use cross_path::mapping::drives::{DriveMappings, automount_root};
+
+    let mounts = "\
+/dev/sdc / ext4 rw,relatime 0 0
+C:\\134 /c 9p rw,noatime,aname=drvfs;path=C:\\134;uid=1000 0 0
+D:\\134 /c/d drvfs rw,noatime 0 0
+//nas/media /srv/media cifs rw,vers=3.0 0 0
+//guest@mac-nas/photos /Volumes/photos smbfs rw 0 0
+";
+    let wsl_conf =
+        "[boot]\nsystemd=true\n\n[automount]\nenabled = true\nroot = \"/\" # drives at /c\n";
+    assert_eq!(automount_root(wsl_conf).as_deref(), Some("/"));
+    assert_eq!(automount_root("[network]\nroot=/x\n"), None);
+
+    let mappings = DriveMappings::from_sources(mounts, Some(wsl_conf));
+    assert_eq!(
+        mappings.as_slice(),
+        [
+            ("C:".to_string(), "/c".to_string()),
+            ("D:".to_string(), "/c/d".to_string()),
+            (r"\\nas\media".to_string(), "/srv/media".to_string()),
+            (
+                r"\\mac-nas\photos".to_string(),
+                "/Volumes/photos".to_string()
+            ),
+            ("E:".to_string(), "/e".to_string()),
+        ]
+    );
+
+    // Without any drvfs mounts the built-in layout is kept
+    assert_eq!(
+        DriveMappings::from_sources("/dev/sda1 / ext4 rw 0 0\n", None),
+        DriveMappings::builtin()
+    );
+    assert_eq!(DriveMappings::builtin().as_slice()[0].1, "/mnt/c");
+
+    let config = cross_path::PathConfig {
+        drive_mappings: mappings.into(),
+        ..cross_path::PathConfig::default()
+    };
+    let path = cross_path::CrossPath::with_config(r"E:\data", config).unwrap();
+    assert_eq!(path.to_unix().unwrap(), "/e/data");
+}
+
+#[test]
+fn test_drive_mappings_from_drives() {
+    use cross_path::mapping::drives::DriveMappings;
+    use cross_path::platform::{DriveType, LogicalDrive};
+
+    let drives = [
+        LogicalDrive {
+            letter: 'C',
+            drive_type: DriveType::Fixed,
+        },
+        LogicalDrive {
+            letter: 'F',
+            drive_type: DriveType::Removable,
+        },
+        LogicalDrive {
+            letter: 'Z',
+            drive_type: DriveType::Network,
+        },
+    ];
+    let mappings = DriveMappings::from_drives(&drives);
+    assert_eq!(
+        mappings.as_slice(),
+        [
+            ("C:".to_string(), "/mnt/c".to_string()),
+            ("F:".to_string(), "/mnt/f".to_string()),
+            ("Z:".to_string(), "/mnt/z".to_string()),
+            ("D:".to_string(), "/mnt/d".to_string()),
+            ("E:".to_string(), "/mnt/e".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_detect_environment() {
+    use cross_path::mapping::drives::DriveMappings;
+    use cross_path::platform::{RuntimeEnvironment, detect_environment};
+    use std::collections::HashMap;
+
+    let classify = |version: &str, cgroup: &str, vars: &[(&str, &str)]| {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let env = move |name: &str| vars.get(name).cloned();
+        RuntimeEnvironment::from_sources(Some(version), Some(cgroup), &env)
+    };
+    let linux = "Linux version 6.8.0-45-generic (buildd@lcy02-amd64-075)";
+    assert_eq!(
+        classify(linux, "0::/init.scope\n", &[]),
+        RuntimeEnvironment::Native
+    );
+    assert_eq!(
+        classify(
+            "Linux version 4.4.0-19041-Microsoft (Microsoft@Microsoft.com)",
+            "",
+            &[]
+        ),
+        RuntimeEnvironment::Wsl1
+    );
+    assert_eq!(
+        classify(
+            "Linux version 5.15.153.1-microsoft-standard-WSL2 (root@941d701f84f1)",
+            "",
+            &[("WSL_DISTRO_NAME", "Ubuntu")]
+        ),
+        RuntimeEnvironment::Wsl2
+    );
+    assert_eq!(
+        classify(linux, "", &[("WSL_DISTRO_NAME", "Ubuntu")]),
+        RuntimeEnvironment::Wsl2
+    );
+    assert_eq!(
+        classify(linux, "12:pids:/docker/3f2a9c\n", &[]),
+        RuntimeEnvironment::Container
+    );
+    assert_eq!(
+        classify(linux, "", &[("container", "podman")]),
+        RuntimeEnvironment::Container
+    );
+    assert_eq!(
+        classify("MSYS_NT-10.0-22631 version 3.5.4", "", &[]),
+        RuntimeEnvironment::Msys
+    );
+    assert_eq!(
+        classify("", "", &[("MSYSTEM", "MINGW64")]),
+        RuntimeEnvironment::Msys
+    );
+    assert_eq!(
+        classify("CYGWIN_NT-10.0-22631 version 3.5.3", "", &[]),
+        RuntimeEnvironment::Cygwin
+    );
+    assert!(RuntimeEnvironment::Wsl1.is_wsl());
+    assert!(!RuntimeEnvironment::Container.is_wsl());
+    assert_eq!(detect_environment(), detect_environment());
+
+    // Drives mounted under `/mnt` move to where Cygwin and MSYS2 mount them
+    let discovered = DriveMappings::cached();
+    let cygwin = DriveMappings::for_environment(RuntimeEnvironment::Cygwin);
+    let msys = DriveMappings::for_environment(RuntimeEnvironment::Msys);
+    for (i, (windows, unix)) in discovered.as_slice().iter().enumerate() {
+        assert_eq!(&cygwin.as_slice()[i].0, windows);
+        if let Some(letter) = unix.strip_prefix("/mnt/").filter(|l| l.len() == 1) {
+            assert_eq!(cygwin.as_slice()[i].1, format!("/cygdrive/{letter}"));
+            assert_eq!(msys.as_slice()[i].1, format!("/{letter}"));
+        } else {
+            assert_eq!(&cygwin.as_slice()[i].1, unix);
+        }
+    }
+    assert_eq!(
+        DriveMappings::for_environment(RuntimeEnvironment::Native),
+        discovered
+    );
+}
+
+#[test]
+fn test_drive_map_lookup() {
+    use cross_path::{CrossPath, DriveMap, PathConfig, PathError};
+
+    // Later, more specific mappings win regardless of order
+    let map: DriveMap = vec![
+        ("C:".to_string(), "/mnt/c".to_string()),
+        ("U:".to_string(), "/mnt/c/Users".to_string()),
+        (r"\\nas\media\".to_string(), "/srv/media/".to_string()),
+    ]
+    .into();
+    assert_eq!(map.find_unix("/mnt/c/Users/me"), Some(("U:", "/me")));
+    assert_eq!(map.find_unix("/mnt/c/Windows"), Some(("C:", "/Windows")));
+    assert_eq!(map.find_unix("/mnt/c"), Some(("C:", "")));
+    assert_eq!(map.find_unix("/mnt/cd/x"), None);
+    assert_eq!(
+        map.find_windows(r"\\NAS\Media\film.mkv"),
+        Some(("/srv/media/", r"\film.mkv"))
+    );
+    assert_eq!(map.get("c:/"), Some("/mnt/c"));
+
+    let config = PathConfig {
+        drive_mappings: map.clone(),
+        ..PathConfig::default()
+    };
+    let path = CrossPath::with_config("/mnt/c/Users/me/notes.txt", config).unwrap();
+    assert_eq!(path.to_windows().unwrap(), r"U:\me\notes.txt");
+
+    // Duplicates are rejected when inserting, and keep the first when collecting
+    let mut strict = map.clone();
+    assert!(matches!(
+        strict.insert("c:", "/c"),
+        Err(PathError::DriveMappingError(_))
+    ));
+    assert!(matches!(
+        strict.insert("Z:", "/mnt/c/"),
+        Err(PathError::DriveMappingError(_))
+    ));
+    assert!(DriveMap::from_pairs([("C:", "/a"), ("c:\\", "/b")]).is_err());
+    let lenient: DriveMap = vec![
+        ("C:".to_string(), "/a".to_string()),
+        ("c:".to_string(), "/b".to_string()),
+    ]
+    .into();
+    assert_eq!(lenient.len(), 1);
+    assert_eq!(lenient.get("C:"), Some("/a"));
+
+    strict.insert("D:", "/mnt/d").unwrap();
+    assert_eq!(strict.remove("u:"), Some("/mnt/c/Users".to_string()));
+    assert_eq!(
+        strict.find_unix("/mnt/c/Users/me"),
+        Some(("C:", "/Users/me"))
+    );
+    assert_eq!(strict.get("D:"), Some("/mnt/d"));
+}
+
+#[test]
+fn test_volume_map() {
+    use cross_path::mapping::volume::VolumeMap;
+    use cross_path::{DriveMap, PathConfig, PathError, registry};
+
+    let mut volumes = VolumeMap::from_specs(
+        [
+            r"C:\proj:/workspace",
+            r"C:\proj\target:/cache:delegated",
+            "node_modules:/workspace/node_modules",
+            "../shared:/shared:ro",
+        ],
+        "/home/me/proj",
+    )
+    .unwrap();
+    assert_eq!(volumes.len(), 3);
+    assert_eq!(volumes.host_of("/shared/"), Some("/home/me/shared"));
+
+    assert_eq!(
+        volumes.to_container(r"c:\PROJ\src\main.rs").unwrap(),
+        "/workspace/src/main.rs"
+    );
+    // The most specific mount wins in both directions
+    assert_eq!(
+        volumes.to_container(r"C:\proj\target\debug").unwrap(),
+        "/cache/debug"
+    );
+    assert_eq!(
+        volumes.to_host("/cache/debug/app").unwrap(),
+        r"C:\proj\target\debug\app"
+    );
+    assert_eq!(
+        volumes.to_host(r"\shared\a.txt").unwrap(),
+        "/home/me/shared/a.txt"
+    );
+    assert_eq!(volumes.to_host("/workspace").unwrap(), r"C:\proj");
+    assert!(matches!(
+        volumes.to_host("/workspaces/other"),
+        Err(PathError::DriveMappingError(_))
+    ));
+    assert!(matches!(
+        volumes.to_container("/home/me/proj"),
+        Err(PathError::DriveMappingError(_))
+    ));
+
+    // Host paths written in the other style go through the drive mappings
+    let config = PathConfig {
+        drive_mappings: DriveMap::from_pairs([("C:", "/mnt/c")]).unwrap(),
+        ..PathConfig::default()
+    };
+    registry::with_config(config, || {
+        assert_eq!(
+            volumes.to_container("/mnt/c/proj/README.md").unwrap(),
+            "/workspace/README.md"
+        );
+        assert!(volumes.to_container("/srv/proj").is_err());
+    });
+
+    assert!(matches!(
+        volumes.insert(r"D:\other", "/workspace/"),
+        Err(PathError::DriveMappingError(_))
+    ));
+    assert!(matches!(
+        volumes.insert("relative", "/x"),
+        Err(PathError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        volumes.insert(r"D:\x", "x"),
+        Err(PathError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        volumes.insert_spec("cargo:/usr/local/cargo", "/"),
+        Err(PathError::ParseError(_))
+    ));
+    volumes.insert_spec("./data:/data", r"D:\proj").unwrap();
+    assert_eq!(
+        volumes.to_host("/data/x.csv").unwrap(),
+        r"D:\proj\data\x.csv"
+    );
+    assert_eq!(
+        volumes.remove("/cache"),
+        Some(r"C:\proj\target".to_string())
+    );
+    assert_eq!(
+        volumes.to_container(r"C:\proj\target\debug").unwrap(),
+        "/workspace/target/debug"
+    );
+
+    let compose = r"
+services:
+  app:
+    volumes:
+      - ..:/workspace
+  worker:
+    volumes:
+      - ./other:/workspace
+";
+    let volumes = VolumeMap::from_compose(compose, None, "/home/me/proj/.devcontainer").unwrap();
+    assert_eq!(volumes.len(), 1);
+    assert_eq!(volumes.host_of("/workspace"), Some("/home/me/proj"));
+}
+
+#[test]
+fn test_sandbox_map() {
+    use cross_path::PathError;
+    use cross_path::mapping::sandbox::{Sandbox, SandboxMap, document_id};
+    use cross_path::platform::RuntimeEnvironment;
+
+    let env = |name: &str| (name == "SNAP_NAME").then(|| "editor".to_string());
+    assert_eq!(
+        Sandbox::from_sources(false, &env, RuntimeEnvironment::Native),
+        Some(Sandbox::Snap)
+    );
+    let none = |_: &str| None;
+    assert_eq!(
+        Sandbox::from_sources(true, &none, RuntimeEnvironment::Container),
+        Some(Sandbox::Flatpak)
+    );
+    assert_eq!(
+        Sandbox::from_sources(false, &none, RuntimeEnvironment::Container),
+        Some(Sandbox::Container)
+    );
+    assert_eq!(
+        Sandbox::from_sources(false, &none, RuntimeEnvironment::Wsl2),
+        None
+    );
+
+    let flatpak = SandboxMap::for_sandbox(Sandbox::Flatpak);
+    assert_eq!(
+        flatpak.to_sandbox("/usr/share/icons/a.png"),
+        "/run/host/usr/share/icons/a.png"
+    );
+    assert_eq!(flatpak.to_host("/run/host/etc/fonts"), "/etc/fonts");
+    // Uncovered paths are shared unchanged
+    assert_eq!(flatpak.to_host("/home/me/a.txt"), "/home/me/a.txt");
+    assert_eq!(flatpak.to_host("/run/hostile"), "/run/hostile");
+
+    let snap = SandboxMap::for_sandbox(Sandbox::Snap);
+    assert_eq!(snap.to_sandbox("/"), "/var/lib/snapd/hostfs");
+    assert_eq!(snap.to_sandbox("/media/usb/x"), "/media/usb/x");
+    assert_eq!(snap.to_host("/var/lib/snapd/hostfs/opt/x"), "/opt/x");
+
+    let mut container = SandboxMap::for_sandbox(Sandbox::Container);
+    assert!(container.is_empty());
+    container.insert("/", "/host").unwrap();
+    container
+        .insert_document("/run/user/1000", "a1b2c3", "/home/me/Documents/report.pdf")
+        .unwrap();
+    assert_eq!(container.len(), 2);
+    assert_eq!(container.to_host("/host/var/log/syslog"), "/var/log/syslog");
+    assert_eq!(
+        container.to_host("/run/user/1000/doc/a1b2c3/report.pdf"),
+        "/home/me/Documents/report.pdf"
+    );
+    assert_eq!(
+        container.to_sandbox("/home/me/Documents/report.pdf"),
+        "/run/user/1000/doc/a1b2c3/report.pdf"
+    );
+    assert!(matches!(
+        container.insert("relative", "/x"),
+        Err(PathError::InvalidPath(_))
+    ));
+    assert!(
+        container
+            .insert_document("/run/user/1000", "a/b", "/x")
+            .is_err()
+    );
+    assert!(
+        container
+            .insert_document("/run/user/1000", "id", "/")
+            .is_err()
+    );
+
+    assert_eq!(
+        document_id("/run/user/1000/doc/a1b2c3/report.pdf"),
+        Some("a1b2c3")
+    );
+    assert_eq!(document_id("/run/flatpak/doc/ff00/x"), Some("ff00"));
+    assert_eq!(document_id("/run/user/me/doc/a1b2c3"), None);
+    assert_eq!(document_id("/home/me/doc/a1b2c3"), None);
+}
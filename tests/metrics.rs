@@ -0,0 +1,55 @@
+use cross_path::metrics::{ConversionStats, LATENCY_BUCKETS, MetricsSink};
+use cross_path::{CrossPath, PathConfig, PathStyle, registry};
+use std::sync::Arc;
+
+#[test]
+fn test_conversion_stats() {
+    let stats = Arc::new(ConversionStats::new());
+    registry::set_metrics(stats.clone());
+
+    let cp = CrossPath::new(r"C:\Users\test\file.txt").unwrap();
+    cp.to_unix().unwrap();
+    cp.to_unix().unwrap();
+
+    // Unmapped absolute Unix paths fall back to the default drive
+    CrossPath::new("/var/log/syslog")
+        .unwrap()
+        .to_windows()
+        .unwrap();
+
+    #[cfg(feature = "security")]
+    assert!(CrossPath::new("../../secret").unwrap().is_safe().is_err());
+
+    registry::clear_metrics();
+    CrossPath::new("/srv").unwrap().to_windows().unwrap();
+
+    let snapshot = stats.snapshot();
+    assert_eq!(
+        snapshot.conversions[&(PathStyle::Windows, PathStyle::Unix)],
+        2
+    );
+    assert_eq!(snapshot.total_conversions(), 3);
+    assert_eq!(snapshot.lossy_conversions, 1);
+    #[cfg(feature = "security")]
+    assert_eq!(snapshot.security_rejections, 1);
+}
+
+#[test]
+fn test_config_metrics() {
+    let stats = Arc::new(ConversionStats::new());
+    let config = PathConfig {
+        metrics: MetricsSink::new(stats.clone()),
+        ..PathConfig::default()
+    };
+    assert_ne!(config, PathConfig::default());
+    assert_eq!(config, config.clone());
+
+    let cp = CrossPath::with_config(r"D:\data\report.txt", config).unwrap();
+    cp.to_unix().unwrap();
+    cp.to_windows().unwrap();
+
+    let snapshot = stats.snapshot();
+    assert_eq!(snapshot.total_conversions(), 2);
+    assert_eq!(snapshot.latency_histogram.len(), LATENCY_BUCKETS.len() + 1);
+    assert_eq!(snapshot.latency_histogram.iter().sum::<u64>(), 2);
+}
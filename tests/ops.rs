@@ -0,0 +1,571 @@
+use cross_path::CrossPath;
+use cross_path::ops::{self, Conflict, OpKind, OpOptions};
+use cross_path::vfs::{FileSystem, Metadata};
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[test]
+fn test_copy_dry_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir_all(src.join("sub")).unwrap();
+    fs::write(src.join("a.txt"), "a").unwrap();
+    fs::write(src.join("sub").join("b.txt"), "b").unwrap();
+    let dst = dir.path().join("dst");
+
+    let source = CrossPath::new(src.to_str().unwrap()).unwrap();
+    let destination = CrossPath::new(dst.to_str().unwrap()).unwrap();
+
+    let plan = ops::copy_to(&source, &destination, &OpOptions::dry_run()).unwrap();
+    let kinds: Vec<_> = plan.iter().map(|op| op.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            OpKind::CreateDir,
+            OpKind::Copy,
+            OpKind::CreateDir,
+            OpKind::Copy
+        ]
+    );
+    assert_eq!(
+        plan[1].destination.as_deref(),
+        Some(dst.join("a.txt").as_path())
+    );
+    assert!(plan.iter().all(|op| op.conflict.is_none()));
+    assert!(!dst.exists());
+
+    ops::copy_to(&source, &destination, &OpOptions::default()).unwrap();
+    assert_eq!(
+        fs::read_to_string(dst.join("sub").join("b.txt")).unwrap(),
+        "b"
+    );
+
+    let plan = ops::copy_to(&source, &destination, &OpOptions::dry_run()).unwrap();
+    assert_eq!(plan[1].conflict, Some(Conflict::DestinationExists));
+    assert!(ops::copy_to(&source, &destination, &OpOptions::default()).is_err());
+}
+
+#[test]
+fn test_copy_symlink_loop() {
+    use cross_path::vfs::MemoryFileSystem;
+
+    let memory = MemoryFileSystem::new();
+    memory.create_dir_all(Path::new("/src/sub")).unwrap();
+    memory.write(Path::new("/src/sub/a.txt"), b"a").unwrap();
+    memory.symlink("/src", "/src/sub/up").unwrap();
+
+    let source = CrossPath::new("/src").unwrap();
+    let destination = CrossPath::new("/dst").unwrap();
+    let error = ops::copy_to_in(&memory, &source, &destination, &OpOptions::dry_run()).unwrap_err();
+    assert!(error.to_string().contains("Symlink loop"), "{error}");
+
+    // Links to directories outside the tree are copied as directories
+    memory.remove_file(Path::new("/src/sub/up")).unwrap();
+    memory.create_dir_all(Path::new("/other")).unwrap();
+    memory.symlink("/other", "/src/sub/other").unwrap();
+    let plan = ops::copy_to_in(&memory, &source, &destination, &OpOptions::dry_run()).unwrap();
+    assert_eq!(plan.len(), 4);
+}
+
+/// In-memory filesystem whose renames always cross devices
+struct CrossDevice(cross_path::vfs::MemoryFileSystem);
+
+impl cross_path::vfs::FileSystem for CrossDevice {
+    fn metadata(&self, path: &Path) -> io::Result<Metadata> {
+        self.0.metadata(path)
+    }
+    fn symlink_metadata(&self, path: &Path) -> io::Result<Metadata> {
+        self.0.symlink_metadata(path)
+    }
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<OsString>> {
+        self.0.read_dir(path)
+    }
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.0.create_dir_all(path)
+    }
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.0.read(path)
+    }
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.0.write(path, contents)
+    }
+    fn copy(&self, from: &Path, to: &Path) -> io::Result<u64> {
+        self.0.copy(from, to)
+    }
+    fn rename(&self, _from: &Path, _to: &Path) -> io::Result<()> {
+        Err(io::ErrorKind::CrossesDevices.into())
+    }
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.0.remove_file(path)
+    }
+    fn remove_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.0.remove_dir_all(path)
+    }
+    fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+        FileSystem::read_link(&self.0, path)
+    }
+    fn symlink(&self, target: &Path, link: &Path) -> io::Result<()> {
+        self.0.symlink(target, link)
+    }
+    fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+        self.0.canonicalize(path)
+    }
+    fn current_dir(&self) -> io::Result<PathBuf> {
+        self.0.current_dir()
+    }
+}
+
+impl CrossDevice {
+    /// Tree at `/src` with a link to `/`, which would copy everything if it
+    /// were followed, and a relative link to a file
+    fn with_linked_tree() -> Self {
+        let fs = Self(cross_path::vfs::MemoryFileSystem::new());
+        fs.create_dir_all(Path::new("/src/sub")).unwrap();
+        fs.write(Path::new("/src/sub/file.txt"), b"data").unwrap();
+        fs.symlink(Path::new("/"), Path::new("/src/root")).unwrap();
+        fs.symlink(Path::new("sub/file.txt"), Path::new("/src/rel"))
+            .unwrap();
+        fs
+    }
+
+    /// Check that the tree was moved from `/src` to `/dst` with its links
+    fn assert_moved(&self) {
+        assert!(!self.exists(Path::new("/src")));
+        assert!(
+            self.symlink_metadata(Path::new("/dst/root"))
+                .unwrap()
+                .is_symlink()
+        );
+        assert_eq!(
+            self.read_link(Path::new("/dst/root")).unwrap(),
+            Path::new("/")
+        );
+        assert_eq!(
+            self.read_link(Path::new("/dst/rel")).unwrap(),
+            Path::new("sub/file.txt")
+        );
+        assert_eq!(self.read(Path::new("/dst/rel")).unwrap(), b"data");
+    }
+}
+
+#[test]
+fn test_move_across_devices_keeps_symlinks() {
+    let fs = CrossDevice::with_linked_tree();
+    let plan = ops::move_to_in(
+        &fs,
+        &CrossPath::new("/src").unwrap(),
+        &CrossPath::new("/dst").unwrap(),
+        &OpOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(plan[0].kind, OpKind::Move);
+    fs.assert_moved();
+}
+
+#[test]
+fn test_rename_across_devices_keeps_symlinks() {
+    let fs = CrossDevice::with_linked_tree();
+    ops::rename_across_devices_in(
+        &fs,
+        &CrossPath::new("/src").unwrap(),
+        &CrossPath::new("/dst").unwrap(),
+    )
+    .unwrap();
+    fs.assert_moved();
+}
+
+// macOS file systems only accept UTF-8 names
+#[cfg(target_os = "linux")]
+#[test]
+fn test_copy_keeps_non_utf8_names() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir(&src).unwrap();
+    let name = OsStr::from_bytes(b"caf\xe9.txt");
+    fs::write(src.join(name), "menu").unwrap();
+    let dst = dir.path().join("dst");
+
+    let source = CrossPath::new(src.to_str().unwrap()).unwrap();
+    let destination = CrossPath::new(dst.to_str().unwrap()).unwrap();
+    ops::copy_to(&source, &destination, &OpOptions::default()).unwrap();
+    assert_eq!(fs::read_to_string(dst.join(name)).unwrap(), "menu");
+}
+
+#[test]
+fn test_move_to() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("a.txt");
+    let dst = dir.path().join("b.txt");
+    fs::write(&src, "a").unwrap();
+
+    let source = CrossPath::new(src.to_str().unwrap()).unwrap();
+    let destination = CrossPath::new(dst.to_str().unwrap()).unwrap();
+
+    let plan = ops::move_to(&source, &destination, &OpOptions::dry_run()).unwrap();
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan[0].kind, OpKind::Move);
+    assert!(src.exists());
+
+    ops::move_to(&source, &destination, &OpOptions::default()).unwrap();
+    assert!(!src.exists());
+    assert_eq!(fs::read_to_string(&dst).unwrap(), "a");
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn test_convert_tree_renames() {
+    use cross_path::PathStyle;
+
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("what?.txt"), "").unwrap();
+    fs::write(src.join("con.txt"), "").unwrap();
+    fs::write(src.join("Readme"), "").unwrap();
+    fs::write(src.join("README"), "").unwrap();
+    let dst = dir.path().join("dst");
+
+    let source = CrossPath::new(src.to_str().unwrap()).unwrap();
+    let destination = CrossPath::new(dst.to_str().unwrap()).unwrap();
+    let options = OpOptions {
+        target_style: PathStyle::Windows,
+        ..OpOptions::dry_run()
+    };
+
+    let plan = ops::convert_tree(&source, &destination, &options).unwrap();
+    let renamed = plan
+        .iter()
+        .find(|op| op.renamed_from.as_deref() == Some("what?.txt"))
+        .unwrap();
+    assert_eq!(
+        renamed.destination.as_deref(),
+        Some(dst.join("what_.txt").as_path())
+    );
+    let reserved = plan
+        .iter()
+        .find(|op| op.renamed_from.as_deref() == Some("con.txt"))
+        .unwrap();
+    assert_eq!(
+        reserved.destination.as_deref(),
+        Some(dst.join("con_.txt").as_path())
+    );
+    assert!(
+        plan.iter()
+            .any(|op| op.conflict == Some(Conflict::DuplicateDestination))
+    );
+    assert!(
+        ops::convert_tree(
+            &source,
+            &destination,
+            &OpOptions {
+                dry_run: false,
+                ..options
+            }
+        )
+        .is_err()
+    );
+}
+
+#[cfg(all(feature = "unicode", not(target_os = "windows")))]
+#[test]
+fn test_fix_name_encoding() {
+    use cross_path::ops::encoding::{self, CollisionStrategy, RenameReason};
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("cafe\u{301}.txt"), "nfd").unwrap();
+    fs::write(src.join(OsStr::from_bytes(b"na\xefve.txt")), "latin1").unwrap();
+    fs::write(src.join("café.txt"), "nfc").unwrap();
+    let dst = dir.path().join("dst");
+
+    let source = CrossPath::new(src.to_str().unwrap()).unwrap();
+    let destination = CrossPath::new(dst.to_str().unwrap()).unwrap();
+
+    let report = encoding::fix_name_encoding(
+        &source,
+        &destination,
+        CollisionStrategy::Suffix,
+        &OpOptions::dry_run(),
+    )
+    .unwrap();
+    let renames: Vec<_> = report
+        .renames
+        .iter()
+        .map(|r| (r.to.as_str(), r.reason))
+        .collect();
+    assert_eq!(
+        renames,
+        [
+            ("café (1).txt", RenameReason::Collision),
+            ("naïve.txt", RenameReason::LegacyEncoding),
+        ]
+    );
+    assert!(!dst.exists());
+
+    let report = encoding::fix_name_encoding(
+        &source,
+        &destination,
+        CollisionStrategy::Skip,
+        &OpOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(report.skipped, [src.join("cafe\u{301}.txt")]);
+    assert_eq!(fs::read_to_string(dst.join("café.txt")).unwrap(), "nfc");
+    assert_eq!(fs::read_to_string(dst.join("naïve.txt")).unwrap(), "latin1");
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn test_fix_name_encoding_symlink_loop() {
+    use cross_path::ops::encoding::{self, CollisionStrategy};
+    use cross_path::vfs::MemoryFileSystem;
+
+    let memory = MemoryFileSystem::new();
+    memory.create_dir_all(Path::new("/src/sub")).unwrap();
+    memory.write(Path::new("/src/sub/a.txt"), b"a").unwrap();
+    memory.symlink("/src", "/src/sub/up").unwrap();
+
+    let error = encoding::fix_name_encoding_in(
+        &memory,
+        &CrossPath::new("/src").unwrap(),
+        &CrossPath::new("/dst").unwrap(),
+        CollisionStrategy::Suffix,
+        &OpOptions::dry_run(),
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("Symlink loop"), "{error}");
+}
+
+#[test]
+fn test_file_operations() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("src").join("sub")).unwrap();
+    fs::write(root.join("src").join("a.txt"), "aaa").unwrap();
+    fs::write(root.join("src").join("sub").join("b.txt"), "bb").unwrap();
+    let path = |name: &str| CrossPath::new(root.join(name).to_str().unwrap()).unwrap();
+
+    assert_eq!(ops::copy(&path("src/a.txt"), &path("a.txt")).unwrap(), 3);
+    assert_eq!(fs::read_to_string(root.join("a.txt")).unwrap(), "aaa");
+
+    // Copying into an existing tree merges and replaces
+    fs::create_dir_all(root.join("dst").join("sub")).unwrap();
+    fs::write(root.join("dst").join("a.txt"), "old").unwrap();
+    assert_eq!(
+        ops::copy_dir_recursive(&path("src"), &path("dst")).unwrap(),
+        5
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("dst").join("a.txt")).unwrap(),
+        "aaa"
+    );
+    assert!(root.join("dst").join("sub").join("b.txt").exists());
+
+    ops::rename_across_devices(&path("dst"), &path("moved")).unwrap();
+    assert!(!root.join("dst").exists());
+    assert!(root.join("moved").join("sub").join("b.txt").exists());
+
+    ops::remove_dir_all_robust(&path("moved")).unwrap();
+    assert!(!root.join("moved").exists());
+    // Removing what is already gone succeeds
+    ops::remove_dir_all_robust(&path("moved")).unwrap();
+    assert!(ops::copy(&path("missing.txt"), &path("x.txt")).is_err());
+}
+
+#[test]
+fn test_file_operations_in_memory() {
+    use cross_path::vfs::MemoryFileSystem;
+
+    let memory = MemoryFileSystem::windows();
+    memory.create_dir_all(Path::new(r"C:\src\sub")).unwrap();
+    memory
+        .write(Path::new(r"C:\src\sub\a.txt"), b"abcd")
+        .unwrap();
+
+    // Unix paths are converted to the filesystem's native Windows form
+    let src = CrossPath::new("/mnt/c/src").unwrap();
+    let dst = CrossPath::new("/mnt/c/dst").unwrap();
+    assert_eq!(ops::copy_dir_recursive_in(&memory, &src, &dst).unwrap(), 4);
+    assert_eq!(
+        memory.read(Path::new(r"C:\dst\sub\a.txt")).unwrap(),
+        b"abcd"
+    );
+
+    ops::remove_dir_all_robust_in(&memory, &src).unwrap();
+    assert!(!memory.exists(Path::new(r"C:\src")));
+}
+
+#[test]
+fn test_atomic_write_and_replace() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| CrossPath::new(dir.path().join(name).to_str().unwrap()).unwrap();
+
+    ops::write_atomic(&path("config.toml"), b"a = 1\n").unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("config.toml")).unwrap(),
+        "a = 1\n"
+    );
+    ops::write_atomic(&path("config.toml"), b"a = 2\n").unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("config.toml")).unwrap(),
+        "a = 2\n"
+    );
+    // No temporary files are left behind
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    fs::write(dir.path().join("new.toml"), "a = 3\n").unwrap();
+    ops::replace_file(&path("new.toml"), &path("config.toml")).unwrap();
+    assert!(!dir.path().join("new.toml").exists());
+    assert_eq!(
+        fs::read_to_string(dir.path().join("config.toml")).unwrap(),
+        "a = 3\n"
+    );
+
+    assert!(ops::write_atomic(&path("missing/x.toml"), b"").is_err());
+    assert!(ops::replace_file(&path("missing.toml"), &path("config.toml")).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_links() {
+    use cross_path::PathError;
+    use cross_path::platform::unix::UnixPathExt;
+    use cross_path::platform::{LinkKind, PathExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| CrossPath::new(dir.path().join(name).to_str().unwrap()).unwrap();
+    fs::create_dir(dir.path().join("data")).unwrap();
+    fs::write(dir.path().join("data").join("file.txt"), "x").unwrap();
+
+    ops::symlink(&path("data"), &path("data-link")).unwrap();
+    assert!(dir.path().join("data-link").join("file.txt").exists());
+    assert_eq!(ops::read_link(&path("data-link")).unwrap(), path("data"));
+    assert_eq!(
+        ops::link_kind(&path("data-link")).unwrap(),
+        Some(LinkKind::Symlink)
+    );
+    assert_eq!(ops::link_kind(&path("data")).unwrap(), None);
+    assert_eq!(
+        UnixPathExt::new(dir.path().join("data-link"))
+            .get_attributes()
+            .unwrap()
+            .link_kind,
+        Some(LinkKind::Symlink)
+    );
+
+    ops::hard_link(&path("data/file.txt"), &path("copy.txt")).unwrap();
+    assert_eq!(
+        ops::link_kind(&path("copy.txt")).unwrap(),
+        Some(LinkKind::HardLink)
+    );
+
+    assert!(ops::read_link(&path("data")).is_err());
+    assert!(ops::link_kind(&path("missing")).is_err());
+    assert!(matches!(
+        ops::junction(&path("data"), &path("junction")),
+        Err(PathError::PlatformError(_))
+    ));
+}
+
+#[test]
+fn test_is_same_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = |name: &str| CrossPath::new(dir.path().join(name).to_str().unwrap()).unwrap();
+    fs::create_dir(dir.path().join("sub")).unwrap();
+    fs::write(dir.path().join("a.txt"), "same").unwrap();
+    fs::write(dir.path().join("b.txt"), "same").unwrap();
+
+    let original = path("a.txt");
+    assert!(original.is_same_file(&path("sub/../a.txt")).unwrap());
+    assert!(!original.is_same_file(&path("b.txt")).unwrap());
+
+    ops::hard_link(&original, &path("linked.txt")).unwrap();
+    assert!(original.is_same_file(&path("linked.txt")).unwrap());
+    assert!(path("sub").is_same_file(&path("sub/.")).unwrap());
+
+    assert!(original.is_same_file(&path("missing.txt")).is_err());
+}
+
+#[cfg(all(feature = "trash", target_os = "linux"))]
+#[test]
+fn test_move_to_trash() {
+    let dir = tempfile::tempdir().unwrap();
+    let data_home = dir.path().join("share");
+    // SAFETY: no other test reads XDG_DATA_HOME
+    unsafe { std::env::set_var("XDG_DATA_HOME", &data_home) };
+    let path = |name: &str| CrossPath::new(dir.path().join(name).to_str().unwrap()).unwrap();
+
+    fs::write(dir.path().join("old report.txt"), "v1").unwrap();
+    let plan = ops::move_to_trash_with(&path("old report.txt"), &OpOptions::dry_run()).unwrap();
+    assert_eq!(plan.len(), 1);
+    assert_eq!(plan[0].kind, OpKind::Trash);
+    assert_eq!(plan[0].destination, None);
+    assert!(dir.path().join("old report.txt").exists());
+    assert!(!data_home.exists());
+
+    ops::move_to_trash(&path("old report.txt")).unwrap();
+    fs::write(dir.path().join("old report.txt"), "v2").unwrap();
+    ops::move_to_trash(&path("old report.txt")).unwrap();
+    assert!(!dir.path().join("old report.txt").exists());
+
+    let trash = data_home.join("Trash");
+    assert_eq!(
+        fs::read_to_string(trash.join("files/old report.txt")).unwrap(),
+        "v1"
+    );
+    assert_eq!(
+        fs::read_to_string(trash.join("files/old report.txt.2")).unwrap(),
+        "v2"
+    );
+    let info = fs::read_to_string(trash.join("info/old report.txt.trashinfo")).unwrap();
+    assert!(info.starts_with("[Trash Info]\nPath=/"), "{info}");
+    assert!(info.contains("/old%20report.txt\nDeletionDate="), "{info}");
+
+    assert!(ops::move_to_trash(&path("missing.txt")).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_file_times() {
+    use cross_path::platform::{FileTimes, PathExt, UnixPathExt};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("synced.txt");
+    fs::write(&file, "x").unwrap();
+    let path = CrossPath::new(file.to_str().unwrap()).unwrap();
+
+    // Sub-second precision survives the round trip
+    let modified = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_700);
+    let accessed = UNIX_EPOCH + Duration::new(1_600_000_000, 500_000_000);
+    let times = FileTimes::default()
+        .with_modified(modified)
+        .with_accessed(accessed);
+    ops::set_times(&path, &times).unwrap();
+
+    let read = FileTimes::from_metadata(&fs::metadata(&file).unwrap());
+    assert_eq!(read.modified, Some(modified));
+    assert_eq!(read.accessed, Some(accessed));
+    assert!(
+        read.changed
+            .is_some_and(|t| t > SystemTime::now() - Duration::from_secs(60))
+    );
+
+    // Unset times stay as they are
+    let later = modified + Duration::from_secs(10);
+    ops::set_times(&path, &FileTimes::default().with_modified(later)).unwrap();
+    let attributes = UnixPathExt::new(&file).get_attributes().unwrap();
+    assert_eq!(attributes.times.modified, Some(later));
+    assert_eq!(attributes.times.accessed, Some(accessed));
+
+    let missing = CrossPath::new(dir.path().join("missing").to_str().unwrap()).unwrap();
+    assert!(ops::set_times(&missing, &times).is_err());
+}
@@ -0,0 +1,147 @@
+use cross_path::rewrite::{PathRewriter, RewriteRule};
+use cross_path::{CrossPath, DriveMap, PathConfig, PathConverter, PathError, PathStyle};
+use std::borrow::Cow;
+
+#[test]
+fn test_prefix_and_regex_rules() {
+    let rewriter = PathRewriter::from_rules([
+        RewriteRule::prefix("/builds/runner/", r"D:\src"),
+        RewriteRule::prefix("/builds/runner/vendor", r"E:\vendor"),
+        RewriteRule::regex(r"/rustc/[0-9a-f]{40}", r"C:\rust"),
+        RewriteRule::prefix(r"c:\Agent\_work\1\s", "/home/me/app"),
+    ])
+    .unwrap();
+    assert_eq!(rewriter.len(), 4);
+
+    assert_eq!(
+        rewriter.rewrite("/builds/runner/app/src/main.rs").unwrap(),
+        r"D:\src\app\src\main.rs"
+    );
+    // The longest matching prefix wins, regardless of rule order
+    assert_eq!(
+        rewriter.rewrite("/builds/runner/vendor/lib.rs").unwrap(),
+        r"E:\vendor\lib.rs"
+    );
+    assert_eq!(
+        rewriter
+            .rewrite("/rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library/std/src/rt.rs")
+            .unwrap(),
+        r"C:\rust\library\std\src\rt.rs"
+    );
+    // Windows prefixes ignore case and accept either separator
+    assert_eq!(
+        rewriter.rewrite("C:/agent/_work/1/s/src/lib.rs").unwrap(),
+        "/home/me/app/src/lib.rs"
+    );
+    assert_eq!(rewriter.rewrite("/builds/runner").unwrap(), r"D:\src");
+
+    // Whole components only; unmatched paths are borrowed
+    assert!(matches!(
+        rewriter.rewrite("/builds/runner2/x").unwrap(),
+        Cow::Borrowed("/builds/runner2/x")
+    ));
+    assert!(!rewriter.matches("/builds/runner2/x"));
+    assert!(rewriter.matches("/builds/runner/x"));
+
+    let named = PathRewriter::from_rules([RewriteRule::regex(
+        r"/home/(?<user>[^/]+)/\.cargo",
+        r"C:\Users\$user\.cargo",
+    )])
+    .unwrap();
+    assert_eq!(
+        named.rewrite("/home/ci/.cargo/registry").unwrap(),
+        r"C:\Users\ci\.cargo\registry"
+    );
+
+    assert!(matches!(
+        PathRewriter::from_rules([RewriteRule::regex("(", "")]),
+        Err(PathError::ParseError(_))
+    ));
+}
+
+#[test]
+fn test_rule_chains_and_loops() {
+    // The output of one rule can be rewritten by another
+    let chained = PathRewriter::from_rules([
+        RewriteRule::prefix("/ci", "/builds"),
+        RewriteRule::prefix("/builds", r"D:\src"),
+    ])
+    .unwrap();
+    assert_eq!(chained.rewrite("/ci/app").unwrap(), r"D:\src\app");
+
+    // A rule whose output it matches again fires only once
+    let growing = PathRewriter::from_rules([RewriteRule::prefix("/src", "/src/app")]).unwrap();
+    assert_eq!(growing.rewrite("/src/main.rs").unwrap(), "/src/app/main.rs");
+
+    let looping = PathRewriter::from_rules([
+        RewriteRule::prefix("/a", "/b"),
+        RewriteRule::prefix("/b", "/a"),
+    ])
+    .unwrap();
+    assert!(matches!(
+        looping.rewrite("/a/x"),
+        Err(PathError::InvalidPath(_))
+    ));
+}
+
+#[test]
+fn test_rewriter_config_hook() {
+    let config = PathConfig {
+        drive_mappings: DriveMap::from_pairs([("D:", "/mnt/d")]).unwrap(),
+        rewriter: PathRewriter::from_rules([RewriteRule::prefix("/builds/runner", r"D:\src")])
+            .unwrap(),
+        ..PathConfig::default()
+    };
+
+    let converter = PathConverter::new(&config);
+    assert_eq!(
+        converter
+            .convert("/builds/runner/app/main.rs", PathStyle::Windows)
+            .unwrap(),
+        r"D:\src\app\main.rs"
+    );
+    // The rewritten path is in another style, which conversion follows
+    assert_eq!(
+        converter
+            .convert("/builds/runner/app/main.rs", PathStyle::Unix)
+            .unwrap(),
+        "/mnt/d/src/app/main.rs"
+    );
+    assert_eq!(
+        converter
+            .convert_cow("/builds/runner/app", PathStyle::Unix)
+            .unwrap(),
+        "/mnt/d/src/app"
+    );
+    assert_eq!(
+        converter.convert("/home/me", PathStyle::Unix).unwrap(),
+        "/home/me"
+    );
+
+    let path = CrossPath::with_config("/builds/runner/lib.rs", config).unwrap();
+    assert_eq!(path.to_windows().unwrap(), r"D:\src\lib.rs");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_rewriter_serde() {
+    let rewriter = PathRewriter::from_rules([
+        RewriteRule::prefix("/builds", r"D:\src"),
+        RewriteRule::regex("/tmp/[^/]+", "/tmp/x"),
+    ])
+    .unwrap();
+    let json = serde_json::to_string(&rewriter).unwrap();
+    assert_eq!(
+        json,
+        r#"[{"kind":"prefix","from":"/builds","to":"D:\\src"},{"kind":"regex","pattern":"/tmp/[^/]+","replacement":"/tmp/x"}]"#
+    );
+    let back: PathRewriter = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, rewriter);
+    assert_eq!(back.rewrite("/tmp/abc/f").unwrap(), "/tmp/x/f");
+    assert!(
+        serde_json::from_str::<PathRewriter>(
+            r#"[{"kind":"regex","pattern":"(","replacement":""}]"#
+        )
+        .is_err()
+    );
+}
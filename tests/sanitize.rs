@@ -0,0 +1,56 @@
+use cross_path::ValidationTarget;
+use cross_path::sanitize::{NonAscii, Sanitizer};
+
+#[test]
+fn test_sanitizer_preserves_structure() {
+    let sanitizer = Sanitizer::new();
+    assert_eq!(
+        sanitizer.sanitize("../uploads/./what?/con.txt/notes. "),
+        "uploads/what_/con_.txt/notes"
+    );
+    assert_eq!(
+        sanitizer.sanitize(r"C:\Users\me\a|b.txt"),
+        r"C_\Users\me\a_b.txt"
+    );
+    assert_eq!(sanitizer.sanitize("/etc/passwd"), "etc/passwd");
+    assert_eq!(sanitizer.sanitize("Köln/日本.txt"), "Köln/日本.txt");
+
+    let flat = Sanitizer::new().preserve_separators(false).replacement('-');
+    assert_eq!(flat.sanitize("reports/2024:q1.pdf"), "reports-2024-q1.pdf");
+
+    let unix = Sanitizer::new().target(ValidationTarget::Unix);
+    assert_eq!(unix.sanitize("notes./con:1.txt"), "notes./con:1.txt");
+}
+
+#[test]
+fn test_sanitizer_non_ascii() {
+    let replace = Sanitizer::new().non_ascii(NonAscii::Replace);
+    assert_eq!(replace.sanitize("Straße/日本.txt"), "Stra_e/__.txt");
+
+    let transliterate = Sanitizer::new().non_ascii(NonAscii::Transliterate);
+    assert_eq!(
+        transliterate.sanitize("Straße – Æther.txt"),
+        "Strasse - AEther.txt"
+    );
+    #[cfg(feature = "unicode")]
+    assert_eq!(
+        transliterate.sanitize("Crème brûlée.txt"),
+        "Creme brulee.txt"
+    );
+    assert_eq!(transliterate.sanitize("日本.txt"), "__.txt");
+}
+
+#[test]
+fn test_sanitizer_length_limits() {
+    let sanitizer = Sanitizer::new().max_component_len(10);
+    // Multi-byte characters are never split and the extension is kept
+    assert_eq!(sanitizer.sanitize("ééééééé.txt"), "ééé.txt");
+    assert_eq!(sanitizer.sanitize("abcdefghijklmnop"), "abcdefghij");
+
+    // Truncation never produces a reserved device name
+    let sanitizer = Sanitizer::new().max_component_len(7);
+    assert_eq!(sanitizer.sanitize("conx.txt"), "co_.txt");
+
+    let sanitizer = Sanitizer::new().max_path_len(12);
+    assert_eq!(sanitizer.sanitize("docs/reports/q1.pdf"), "docs/reports");
+}
@@ -0,0 +1,95 @@
+use cross_path::scan::{PathMatch, PathScanner, ScanKind};
+
+fn found(text: &str) -> Vec<(ScanKind, &str)> {
+    PathScanner::new()
+        .scan(text)
+        .into_iter()
+        .map(|m| (m.kind, m.text))
+        .collect()
+}
+
+#[test]
+fn test_scan_compiler_output() {
+    let rustc = "error[E0425]: cannot find value\n  --> /builds/app/src/main.rs:10:5\n";
+    assert_eq!(found(rustc), [(ScanKind::Unix, "/builds/app/src/main.rs")]);
+
+    let msvc = r"C:\src\app\main.cpp(12,5): error C2065: see \\nas\share\logs\build.txt.";
+    assert_eq!(
+        found(msvc),
+        [
+            (ScanKind::Windows, r"C:\src\app\main.cpp"),
+            (ScanKind::Unc, r"\\nas\share\logs\build.txt"),
+        ]
+    );
+
+    // Ranges point into the scanned text
+    let matches = PathScanner::new().scan(rustc);
+    assert_eq!(&rustc[matches[0].range.clone()], "/builds/app/src/main.rs");
+}
+
+#[test]
+fn test_scan_boundaries() {
+    // Quoted paths may contain spaces
+    assert_eq!(
+        found(r#"running "C:\Program Files (x86)\tool.exe" and '/opt/my app/run'"#),
+        [
+            (ScanKind::Windows, r"C:\Program Files (x86)\tool.exe"),
+            (ScanKind::Unix, "/opt/my app/run"),
+        ]
+    );
+    assert_eq!(
+        found("PATH=/usr/bin:/bin:~/bin (see /etc/profile)."),
+        [
+            (ScanKind::Unix, "/usr/bin"),
+            (ScanKind::Unix, "/bin"),
+            (ScanKind::Unix, "~/bin"),
+            (ScanKind::Unix, "/etc/profile"),
+        ]
+    );
+    assert_eq!(
+        found("open file:///C:/Users/me/a%20b.txt, then C:/work/../x"),
+        [
+            (ScanKind::FileUrl, "file:///C:/Users/me/a%20b.txt"),
+            (ScanKind::Windows, "C:/work/../x"),
+        ]
+    );
+
+    // Not paths: fractions, prose, URLs and command switches
+    assert!(found("and/or 1/2 http://example.com/a/b dir /s").is_empty());
+}
+
+#[test]
+fn test_scan_kinds_and_replace() {
+    let text = r"copy C:\a\b.txt to /mnt/d/b.txt";
+    let unix_only = PathScanner::new().windows(false);
+    assert!(!unix_only.finds(ScanKind::Windows));
+    // A disabled kind is skipped whole
+    assert_eq!(
+        unix_only.scan(text),
+        [PathMatch {
+            kind: ScanKind::Unix,
+            range: 19..31,
+            text: "/mnt/d/b.txt",
+        }]
+    );
+    assert!(PathScanner::new().unix(false).scan("cd /tmp").is_empty());
+    assert!(
+        PathScanner::new()
+            .unc(false)
+            .scan(r"\\nas\share")
+            .is_empty()
+    );
+    assert!(
+        PathScanner::new()
+            .file_urls(false)
+            .scan("file:///tmp/x")
+            .is_empty()
+    );
+
+    let replaced = PathScanner::new().replace_all(text, |m| {
+        (m.kind == ScanKind::Windows).then(|| m.text.replace('\\', "/"))
+    });
+    assert_eq!(replaced, "copy C:/a/b.txt to /mnt/d/b.txt");
+    let untouched = PathScanner::new().replace_all("no paths here", |_| Some(String::new()));
+    assert!(matches!(untouched, std::borrow::Cow::Borrowed(_)));
+}
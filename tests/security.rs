@@ -0,0 +1,262 @@
+#![cfg(feature = "security")]
+
+use cross_path::security::{PathSecurityChecker, SecurityPolicy};
+use cross_path::{CrossPath, PathConfig, PathError, PathStyle};
+use std::path::Path;
+
+#[test]
+fn test_security_policy_builder() {
+    let policy = SecurityPolicy::builder()
+        .remove_dangerous_pattern(r"(?i)\.(exe|bat|cmd|sh|php|py|js)$")
+        .add_dangerous_pattern(r"(?i)\.dll$")
+        .allow_root("/srv/uploads")
+        .reserved_names(PathStyle::Windows, true)
+        .reserved_names(PathStyle::Unix, true)
+        .max_path_length(32)
+        .build()
+        .unwrap();
+    let checker = PathSecurityChecker::with_policy(&policy).unwrap();
+    let check = |path: &str| checker.check(Path::new(path)).is_ok();
+
+    assert!(check("/srv/uploads/script.sh"));
+    assert!(!check("/srv/uploads/hook.DLL"));
+    assert!(!check("/srv/uploads/NUL.txt"));
+    assert!(!check("/srv/uploads-old/file.txt"));
+    assert!(!check("/home/user/file.txt"));
+    assert!(check("relative/file.txt"));
+    assert!(!check("/srv/uploads/a/very/long/path/name.txt"));
+    assert!(!check("../escape.txt"));
+
+    // Paths inside an allowed root skip the system directory check
+    let policy = SecurityPolicy::builder()
+        .allow_root("/var/www")
+        .build()
+        .unwrap();
+    let checker = PathSecurityChecker::with_policy(&policy).unwrap();
+    assert!(checker.check(Path::new("/var/www/index.html")).is_ok());
+
+    let invalid = SecurityPolicy::builder().add_dangerous_pattern("(").build();
+    assert!(matches!(invalid, Err(PathError::ParseError(_))));
+}
+
+#[test]
+fn test_is_safe_uses_configured_policy() {
+    let path = CrossPath::new("/srv/data/run.sh").unwrap();
+    assert!(path.is_safe().is_err());
+
+    let config = PathConfig {
+        security_policy: SecurityPolicy::builder()
+            .clear_dangerous_patterns()
+            .build()
+            .unwrap(),
+        ..PathConfig::default()
+    };
+    let path = CrossPath::with_config("/srv/data/run.sh", config).unwrap();
+    assert!(path.is_safe().unwrap());
+}
+
+#[test]
+fn test_safe_join() {
+    let root = CrossPath::new("/srv/uploads").unwrap();
+    let joined = root.safe_join("user/./docs/../report.pdf").unwrap();
+    assert_eq!(
+        joined.as_original(),
+        Path::new("/srv/uploads/user/report.pdf")
+    );
+    assert_eq!(
+        root.safe_join("").unwrap().as_original(),
+        Path::new("/srv/uploads")
+    );
+
+    let windows = CrossPath::new(r"C:\data").unwrap();
+    assert_eq!(
+        windows.safe_join("a/b.txt").unwrap().to_windows().unwrap(),
+        r"C:\data\a\b.txt"
+    );
+
+    for escape in [
+        "../etc/passwd",
+        "a/../../b",
+        r"..\..\windows",
+        "/etc/passwd",
+        r"C:\Windows",
+        "C:relative",
+        r"\\server\share",
+    ] {
+        assert!(
+            matches!(root.safe_join(escape), Err(PathError::SecurityError(_))),
+            "{escape}"
+        );
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_safe_join_resolves_symlinks() {
+    use cross_path::NormalizeMode;
+
+    let dir = tempfile::tempdir().unwrap();
+    let root_dir = dir.path().join("root");
+    std::fs::create_dir(&root_dir).unwrap();
+    std::os::unix::fs::symlink(dir.path(), root_dir.join("link")).unwrap();
+
+    let config = PathConfig {
+        normalize_mode: NormalizeMode::Physical,
+        ..PathConfig::default()
+    };
+    let root = CrossPath::with_config(root_dir.to_str().unwrap(), config).unwrap();
+    assert!(root.safe_join("inside.txt").is_ok());
+    assert!(matches!(
+        root.safe_join("link/secret.txt"),
+        Err(PathError::SecurityError(_))
+    ));
+
+    // Lexical joins do not consult the filesystem
+    let lexical = CrossPath::new(root_dir.to_str().unwrap()).unwrap();
+    assert!(lexical.safe_join("link/secret.txt").is_ok());
+}
+
+#[cfg(all(unix, feature = "fs-security"))]
+#[test]
+fn test_symlink_escape_detection() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base");
+    std::fs::create_dir(&base).unwrap();
+    std::fs::write(dir.path().join("secret.txt"), "secret").unwrap();
+    std::os::unix::fs::symlink(dir.path(), base.join("link")).unwrap();
+
+    let policy = SecurityPolicy::builder()
+        .allow_root(dir.path().to_str().unwrap())
+        .base_dir(base.to_str().unwrap())
+        .build()
+        .unwrap();
+    let checker = PathSecurityChecker::with_policy(&policy).unwrap();
+
+    assert!(checker.check(Path::new("new/file.txt")).is_ok());
+    assert!(checker.check(&base.join("file.txt")).is_ok());
+    assert!(matches!(
+        checker.check(Path::new("link/secret.txt")),
+        Err(PathError::SecurityError(_))
+    ));
+    assert!(
+        checker
+            .check(&base.join("link").join("secret.txt"))
+            .is_err()
+    );
+    assert!(checker.check(&dir.path().join("secret.txt")).is_err());
+}
+
+#[test]
+fn test_invisible_characters() {
+    use cross_path::metrics::ConversionStats;
+    use cross_path::registry;
+    use cross_path::security::{InvisibleCharAction, SuspiciousCharKind, find_suspicious_chars};
+    use std::sync::Arc;
+
+    let spoofed = "uploads/invoice\u{202E}fdp.exe";
+    let found = find_suspicious_chars(spoofed);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].index, 15);
+    assert_eq!(found[0].kind, SuspiciousCharKind::BidiControl);
+    assert_eq!(
+        find_suspicious_chars("a\u{200D}b\u{FEFF}")[1].kind,
+        SuspiciousCharKind::ZeroWidth
+    );
+    assert!(find_suspicious_chars("résumé.pdf").is_empty());
+
+    let checker = PathSecurityChecker::new();
+    assert!(matches!(
+        checker.check(Path::new("docs/report\u{200B}.pdf")),
+        Err(PathError::SecurityError(_))
+    ));
+
+    // Stripping exposes the real extension to the remaining checks
+    let policy = SecurityPolicy::builder()
+        .invisible_chars(InvisibleCharAction::Strip)
+        .build()
+        .unwrap();
+    let checker = PathSecurityChecker::with_policy(&policy).unwrap();
+    assert_eq!(
+        checker.check_unicode(spoofed).unwrap(),
+        "uploads/invoicefdp.exe"
+    );
+    assert!(checker.check(Path::new(spoofed)).is_err());
+    assert!(checker.check(Path::new("docs/report\u{200B}.pdf")).is_ok());
+
+    let stats = Arc::new(ConversionStats::new());
+    registry::set_metrics(stats.clone());
+    let policy = SecurityPolicy::builder()
+        .invisible_chars(InvisibleCharAction::Warn)
+        .build()
+        .unwrap();
+    let checker = PathSecurityChecker::with_policy(&policy).unwrap();
+    assert!(
+        checker
+            .check(Path::new("docs/re\u{200C}port\u{2066}.pdf"))
+            .is_ok()
+    );
+    registry::clear_metrics();
+    assert_eq!(stats.snapshot().security_warnings, 2);
+}
+
+#[test]
+fn test_validation_target() {
+    use cross_path::ValidationTarget;
+
+    let policy = SecurityPolicy::builder()
+        .validation_target(ValidationTarget::Windows)
+        .build()
+        .unwrap();
+    let checker = PathSecurityChecker::with_policy(&policy).unwrap();
+    let rejected = |path: &str| {
+        matches!(
+            checker.check(Path::new(path)),
+            Err(PathError::SecurityError(_))
+        )
+    };
+
+    // Checked in every component, whatever the host
+    assert!(rejected("/srv/data/con.txt"));
+    assert!(rejected("/srv/NUL/readme.md"));
+    assert!(rejected("/srv/notes./readme.md"));
+    assert!(rejected("/srv/data/name "));
+    assert!(rejected("/srv/data/what?.txt"));
+    assert!(rejected(r"C:\data\aux\file.txt"));
+    assert!(!rejected(r"C:\data\console\file.txt"));
+    assert!(!rejected("/srv/data/readme.md"));
+
+    let policy = SecurityPolicy::builder()
+        .validation_target(ValidationTarget::Unix)
+        .build()
+        .unwrap();
+    let checker = PathSecurityChecker::with_policy(&policy).unwrap();
+    assert!(checker.check(Path::new("/srv/data/con.txt")).is_ok());
+}
+
+#[test]
+fn test_reject_data_streams() {
+    let check = |policy: &SecurityPolicy, path: &str| {
+        PathSecurityChecker::with_policy(policy)
+            .unwrap()
+            .check(Path::new(path))
+            .is_ok()
+    };
+
+    let default = SecurityPolicy::default();
+    assert!(!default.rejects_data_streams());
+    assert!(check(&default, r"C:\docs\report.pdf:payload"));
+
+    let policy = SecurityPolicy::builder()
+        .reject_data_streams(true)
+        .build()
+        .unwrap();
+    assert!(policy.rejects_data_streams());
+    assert!(!check(&policy, r"C:\docs\report.pdf:payload"));
+    assert!(!check(
+        &policy,
+        r"\\server\share\a.txt:Zone.Identifier:$DATA"
+    ));
+    assert!(!check(&policy, "report.pdf:payload"));
+    assert!(check(&policy, r"C:\docs\report.pdf"));
+    assert!(check(&policy, "C:notes.txt"));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "json")]
+
+use cross_path::{CrossPath, PathConfig, PathStyle};
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Job {
+    #[serde(with = "cross_path::serde::as_unix")]
+    input: CrossPath,
+    #[serde(with = "cross_path::serde::as_windows")]
+    output: CrossPath,
+    #[serde(with = "cross_path::serde::as_original")]
+    log: CrossPath,
+}
+
+#[test]
+fn test_serialize_as_plain_string() {
+    let path = CrossPath::new(r"C:\data\in.csv").unwrap();
+    assert_eq!(
+        serde_json::to_string(&path).unwrap(),
+        r#""C:\\data\\in.csv""#
+    );
+
+    let config = PathConfig {
+        style: PathStyle::Unix,
+        ..PathConfig::default()
+    };
+    let path = CrossPath::with_config(r"C:\data\in.csv", config).unwrap();
+    assert_eq!(
+        serde_json::to_string(&path).unwrap(),
+        r#""/mnt/c/data/in.csv""#
+    );
+
+    let parsed: CrossPath = serde_json::from_str(r#""/mnt/c/data/in.csv""#).unwrap();
+    assert_eq!(parsed.to_windows().unwrap(), r"C:\data\in.csv");
+    assert!(serde_json::from_str::<CrossPath>("42").is_err());
+}
+
+#[test]
+fn test_serde_style_helpers() {
+    let job = Job {
+        input: CrossPath::new(r"C:\data\in.csv").unwrap(),
+        output: CrossPath::new("/mnt/d/out/result.csv").unwrap(),
+        log: CrossPath::new("logs/run.log").unwrap(),
+    };
+    let json = serde_json::to_string(&job).unwrap();
+    assert_eq!(
+        json,
+        r#"{"input":"/mnt/c/data/in.csv","output":"D:\\out\\result.csv","log":"logs/run.log"}"#
+    );
+
+    let back: Job = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.input.to_unix().unwrap(), "/mnt/c/data/in.csv");
+    assert_eq!(back.output.to_windows().unwrap(), r"D:\out\result.csv");
+    assert_eq!(back.log.to_str(), Some("logs/run.log"));
+}
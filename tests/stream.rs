@@ -0,0 +1,66 @@
+use cross_path::PathStyle;
+use cross_path::stream::{self, Delimiter, StreamOptions};
+use std::fs;
+
+#[test]
+fn test_convert_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("manifest.txt");
+    let output = dir.path().join("converted.txt");
+    fs::write(&input, "C:\\src\\main.rs\r\nD:\\data\\x.bin\r\n\r\n").unwrap();
+
+    let options = StreamOptions {
+        target_style: PathStyle::Unix,
+        ..StreamOptions::default()
+    };
+    let report = stream::convert_file(&input, &output, &options).unwrap();
+    assert_eq!(report.records, 3);
+    assert_eq!(report.failed, 0);
+    assert_eq!(
+        fs::read_to_string(&output).unwrap(),
+        "/mnt/c/src/main.rs\n/mnt/d/data/x.bin\n\n"
+    );
+}
+
+#[test]
+fn test_convert_file_nul_delimited() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("list0");
+    let output = dir.path().join("out0");
+    fs::write(&input, b"/mnt/c/a b\0/mnt/e/x\n y\0").unwrap();
+
+    let options = StreamOptions {
+        delimiter: Delimiter::Nul,
+        target_style: PathStyle::Windows,
+        ..StreamOptions::default()
+    };
+    stream::convert_file(&input, &output, &options).unwrap();
+    assert_eq!(fs::read(&output).unwrap(), b"C:\\a b\0E:\\x\n y\0");
+}
+
+#[test]
+fn test_convert_file_resume() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("manifest.txt");
+    let output = dir.path().join("converted.txt");
+    let checkpoint = dir.path().join("manifest.ckpt");
+    fs::write(&input, "C:\\a\nC:\\b\nC:\\c\n").unwrap();
+
+    // Simulate a run interrupted after two records, with a partial third
+    fs::write(&output, "/mnt/c/a\n/mnt/c/b\n/mnt/c/").unwrap();
+    fs::write(&checkpoint, "10 18 2\n").unwrap();
+
+    let options = StreamOptions {
+        target_style: PathStyle::Unix,
+        checkpoint: Some(checkpoint.clone()),
+        ..StreamOptions::default()
+    };
+    let report = stream::convert_file(&input, &output, &options).unwrap();
+    assert_eq!(report.resumed_from, 2);
+    assert_eq!(report.records, 1);
+    assert_eq!(
+        fs::read_to_string(&output).unwrap(),
+        "/mnt/c/a\n/mnt/c/b\n/mnt/c/c\n"
+    );
+    assert!(!checkpoint.exists());
+}
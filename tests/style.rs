@@ -0,0 +1,92 @@
+use cross_path::formatter::PathFormatter;
+use cross_path::style::StyleDefinition;
+use cross_path::{
+    CrossPath, PathConfig, PathConverter, PathError, PathParser, PathStyle, registry,
+};
+use std::sync::Arc;
+
+/// `vol0:/data/file` notation, with volumes mounted under `/vfs`
+struct Vfs;
+
+impl StyleDefinition for Vfs {
+    fn name(&self) -> &'static str {
+        "vfs"
+    }
+
+    fn split_root<'a>(&self, path: &'a str) -> Option<(&'a str, &'a str)> {
+        let (root, rest) = path.split_once(':')?;
+        let index = root.strip_prefix("vol")?;
+        (!index.is_empty() && index.bytes().all(|b| b.is_ascii_digit())).then_some((root, rest))
+    }
+
+    fn format_root(&self, root: &str) -> String {
+        format!("{root}:")
+    }
+
+    fn root_to_unix(&self, root: &str) -> Option<String> {
+        (root != "vol9").then(|| format!("/vfs/{root}"))
+    }
+
+    fn unix_to_root(&self, path: &str) -> Option<(String, String)> {
+        let rest = path.strip_prefix("/vfs/")?;
+        let (root, rest) = rest.split_once('/').unwrap_or((rest, ""));
+        Some((root.to_string(), rest.to_string()))
+    }
+}
+
+#[test]
+fn test_custom_style() {
+    let style = registry::register_style(Arc::new(Vfs));
+    assert_eq!(style, PathStyle::custom("vfs"));
+
+    let converter = PathConverter::new(&PathConfig::default());
+    assert_eq!(converter.detect_style("vol0:/data/file").unwrap(), style);
+    assert_eq!(
+        converter
+            .convert("vol0:/data/file", PathStyle::Unix)
+            .unwrap(),
+        "/vfs/vol0/data/file"
+    );
+    assert_eq!(
+        converter.convert("vol2:/logs", PathStyle::Windows).unwrap(),
+        r"C:\vfs\vol2\logs"
+    );
+    assert_eq!(
+        converter.convert("/vfs/vol1/a/b.txt", style).unwrap(),
+        "vol1:/a/b.txt"
+    );
+    assert_eq!(converter.convert("/vfs/vol1", style).unwrap(), "vol1:/");
+    assert_eq!(
+        converter.convert(r"docs\a.txt", style).unwrap(),
+        "docs/a.txt"
+    );
+    assert!(matches!(
+        converter.convert("vol9:/x", PathStyle::Unix),
+        Err(PathError::DriveMappingError(_))
+    ));
+    assert!(matches!(
+        converter.convert("/home/user", style),
+        Err(PathError::DriveMappingError(_))
+    ));
+    assert!(matches!(
+        converter.convert("/vfs/vol0", PathStyle::custom("missing")),
+        Err(PathError::UnsupportedFormat(_))
+    ));
+
+    let formatter = PathFormatter::new(&PathConfig::default());
+    let parsed = PathParser::parse("/vfs/vol3/x/y").unwrap();
+    assert_eq!(formatter.format(&parsed, style).unwrap(), "vol3:/x/y");
+
+    let path = CrossPath::new("vol0:/data/file").unwrap();
+    assert_eq!(path.to_unix().unwrap(), "/vfs/vol0/data/file");
+    assert_eq!(
+        CrossPath::new("/vfs/vol0/data")
+            .unwrap()
+            .to_style(style)
+            .unwrap(),
+        "vol0:/data"
+    );
+
+    registry::unregister_style("vfs");
+    assert!(registry::style_definition("vfs").is_none());
+}
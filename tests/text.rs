@@ -0,0 +1,92 @@
+#![cfg(feature = "text")]
+
+use cross_path::text::{self, TextOptions, TextReport};
+use cross_path::{DriveMap, PathConfig, PathError, PathStyle, UnmappedPolicy};
+use std::fs;
+
+fn options(target_style: PathStyle) -> TextOptions {
+    TextOptions {
+        target_style,
+        config: PathConfig {
+            drive_mappings: DriveMap::from_pairs([("C:", "/mnt/c"), ("D:", "/mnt/d")]).unwrap(),
+            ..PathConfig::default()
+        },
+        ..TextOptions::default()
+    }
+}
+
+#[test]
+fn test_convert_text() {
+    let props = r#"<OutDir>C:\build\out\</OutDir> <Include>"D:\SDK 10\include";/opt/x</Include>"#;
+    assert_eq!(
+        text::convert_text(props, &options(PathStyle::Unix)).unwrap(),
+        r#"<OutDir>/mnt/c/build/out/</OutDir> <Include>"/mnt/d/SDK 10/include";/opt/x</Include>"#
+    );
+
+    // Paths already in the target style keep their form
+    let cmake = r#"set(ROOT "C:/proj") # mirrored at /mnt/d/cache"#;
+    assert_eq!(
+        text::convert_text(cmake, &options(PathStyle::Windows)).unwrap(),
+        r#"set(ROOT "C:/proj") # mirrored at D:\cache"#
+    );
+
+    // URLs keep being URLs
+    assert_eq!(
+        text::convert_text(
+            "see file:///mnt/c/docs/a%20b.html",
+            &options(PathStyle::Windows)
+        )
+        .unwrap(),
+        "see file:///C:/docs/a%20b.html"
+    );
+
+    let unchanged = text::convert_text("no paths, only and/or", &options(PathStyle::Unix)).unwrap();
+    assert!(matches!(unchanged, std::borrow::Cow::Borrowed(_)));
+}
+
+#[test]
+fn test_convert_stream_and_file() {
+    let script = "#!/bin/sh\r\ncd /mnt/c/proj && make\r\ncp /srv/a.txt /mnt/d/b.txt\n";
+    let mut output = Vec::new();
+    let mut strict = options(PathStyle::Windows);
+    strict.config.unmapped_policy = UnmappedPolicy::Error;
+
+    // Unmapped paths are left as they are by default
+    let lenient = TextOptions {
+        skip_invalid: true,
+        ..strict.clone()
+    };
+    let report = text::convert_stream(script.as_bytes(), &mut output, &lenient).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "#!/bin/sh\r\ncd C:\\proj && make\r\ncp /srv/a.txt D:\\b.txt\n"
+    );
+    assert_eq!(
+        report,
+        TextReport {
+            lines: 3,
+            converted: 2,
+            failed: 1,
+        }
+    );
+
+    strict.skip_invalid = false;
+    assert!(matches!(
+        text::convert_stream(script.as_bytes(), Vec::new(), &strict),
+        Err(PathError::ParseError(detail)) if detail.message().starts_with("Line 3:")
+    ));
+
+    let dir = tempfile::tempdir().unwrap();
+    let input = dir.path().join("build.sh");
+    let output = dir.path().join("build.cmd");
+    let mut bytes = b"copy C:\\a.txt \xff\n".to_vec();
+    bytes.extend_from_slice(b"type C:\\b.txt\n");
+    fs::write(&input, &bytes).unwrap();
+    let report = text::convert_file(&input, &output, &options(PathStyle::Unix)).unwrap();
+    assert_eq!(report.converted, 1);
+    // Lines that are not UTF-8 are copied as is
+    assert_eq!(
+        fs::read(&output).unwrap(),
+        b"copy C:\\a.txt \xff\ntype /mnt/c/b.txt\n"
+    );
+}
@@ -0,0 +1,127 @@
+use cross_path::ops::{self, Conflict, OpOptions};
+use cross_path::platform::{AccessMode, PathExt};
+use cross_path::vfs::{FileSystem, MemoryFileSystem, VfsPath};
+use cross_path::{CrossPath, PathParser};
+use std::path::Path;
+
+#[test]
+fn test_memory_file_system() {
+    let fs = MemoryFileSystem::new();
+    fs.create_dir_all(Path::new("/data/logs")).unwrap();
+    fs.write(Path::new("/data/logs/a.log"), b"hello").unwrap();
+    fs.write(Path::new("/data/b.txt"), b"b").unwrap();
+
+    assert_eq!(fs.read_dir(Path::new("/data")).unwrap(), ["b.txt", "logs"]);
+    assert_eq!(fs.metadata(Path::new("/data/logs/a.log")).unwrap().len, 5);
+    assert!(!fs.exists(Path::new("/data/LOGS")));
+
+    fs.rename(Path::new("/data/logs"), Path::new("/archive"))
+        .unwrap();
+    assert_eq!(fs.read(Path::new("/archive/a.log")).unwrap(), b"hello");
+    assert!(!fs.exists(Path::new("/data/logs/a.log")));
+
+    fs.symlink("/archive", "/data/link").unwrap();
+    assert!(
+        fs.symlink_metadata(Path::new("/data/link"))
+            .unwrap()
+            .is_symlink()
+    );
+    assert_eq!(
+        fs.canonicalize(Path::new("/data/link/a.log")).unwrap(),
+        Path::new("/archive/a.log")
+    );
+
+    let path = VfsPath::new(&fs, "/data/b.txt");
+    assert!(path.is_accessible());
+    assert!(path.access(AccessMode::READ | AccessMode::WRITE));
+    assert!(!VfsPath::new(&fs, "/data/missing").access(AccessMode::EXISTS));
+    assert_eq!(path.get_attributes().unwrap().size, 1);
+}
+
+#[test]
+fn test_memory_file_system_windows() {
+    let fs = MemoryFileSystem::windows();
+    fs.create_dir_all(Path::new(r"C:\Users\Me")).unwrap();
+    fs.write(Path::new(r"c:\users\me\Notes.TXT"), b"x").unwrap();
+
+    assert!(fs.exists(Path::new("C:/USERS/ME/notes.txt")));
+    assert_eq!(
+        fs.read_dir(Path::new(r"C:\Users\Me")).unwrap(),
+        ["Notes.TXT"]
+    );
+    assert_eq!(
+        fs.canonicalize(Path::new(r"c:\users\me\notes.txt"))
+            .unwrap(),
+        Path::new(r"C:\Users\Me\Notes.TXT")
+    );
+}
+
+#[test]
+fn test_ops_on_memory_file_system() {
+    let fs = MemoryFileSystem::windows();
+    fs.create_dir_all(Path::new(r"C:\src")).unwrap();
+    fs.write(Path::new(r"C:\src\a.txt"), b"a").unwrap();
+    fs.create_dir_all(Path::new(r"C:\dst")).unwrap();
+    fs.write(Path::new(r"C:\DST\A.TXT"), b"old").unwrap();
+
+    let source = CrossPath::new(r"C:\src").unwrap();
+    let destination = CrossPath::new(r"C:\dst").unwrap();
+
+    let plan = ops::copy_to_in(&fs, &source, &destination, &OpOptions::dry_run()).unwrap();
+    assert_eq!(plan[1].conflict, Some(Conflict::DestinationExists));
+
+    let options = OpOptions {
+        overwrite: true,
+        ..OpOptions::default()
+    };
+    ops::copy_to_in(&fs, &source, &destination, &options).unwrap();
+    assert_eq!(fs.read(Path::new(r"C:\dst\a.txt")).unwrap(), b"a");
+}
+
+#[test]
+fn test_physical_normalization_on_memory_file_system() {
+    let fs = MemoryFileSystem::new();
+    fs.create_dir_all(Path::new("/real/inner")).unwrap();
+    fs.create_dir_all(Path::new("/other")).unwrap();
+    fs.symlink("/real/inner", "/other/link").unwrap();
+
+    let normalized = PathParser::normalize_physical_in(&fs, Path::new("/other/link/../x")).unwrap();
+    assert_eq!(normalized, Path::new("/real/x"));
+}
+
+#[test]
+fn test_canonicalize_on_memory_file_system() {
+    let fs = MemoryFileSystem::windows();
+    fs.create_dir_all(Path::new(r"C:\Data\Real")).unwrap();
+    fs.write(Path::new(r"C:\Data\Real\a.txt"), b"a").unwrap();
+    fs.symlink(r"C:\Data\Real", r"C:\Link").unwrap();
+
+    let canonical = CrossPath::new("/mnt/c/link/./A.TXT")
+        .unwrap()
+        .canonicalize_in(&fs)
+        .unwrap();
+    assert_eq!(canonical.to_windows().unwrap(), r"C:\Data\Real\a.txt");
+    assert_eq!(canonical.to_unix().unwrap(), "/mnt/c/Data/Real/a.txt");
+
+    assert!(
+        CrossPath::new(r"C:\missing")
+            .unwrap()
+            .canonicalize_in(&fs)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_canonicalize() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("file.txt");
+    std::fs::write(&file, "").unwrap();
+
+    let canonical = CrossPath::from(file.as_path()).canonicalize().unwrap();
+    let native = canonical.to_platform().unwrap();
+    assert!(!native.starts_with(r"\\?\"), "{native}");
+    assert_eq!(
+        std::fs::canonicalize(&native).unwrap(),
+        std::fs::canonicalize(&file).unwrap()
+    );
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "walk")]
+
+use cross_path::vfs::{FileSystem, MemoryFileSystem};
+use cross_path::walk::CrossWalk;
+use cross_path::{CrossPath, DriveMap, PathConfig};
+use std::path::Path;
+
+fn tree() -> MemoryFileSystem {
+    let fs = MemoryFileSystem::windows();
+    fs.create_dir_all(Path::new(r"C:\src\app\lib")).unwrap();
+    fs.create_dir_all(Path::new(r"C:\src\docs")).unwrap();
+    fs.write(Path::new(r"C:\src\app\main.rs"), b"fn main() {}")
+        .unwrap();
+    fs.write(Path::new(r"C:\src\app\lib\mod.rs"), b"").unwrap();
+    fs.write(Path::new(r"C:\src\.hidden"), b"x").unwrap();
+    fs
+}
+
+fn walked(walk: CrossWalk<'_>) -> Vec<(String, usize)> {
+    walk.into_iter()
+        .map(|entry| {
+            let entry = entry.unwrap();
+            (entry.path.to_string_lossy().into_owned(), entry.depth)
+        })
+        .collect()
+}
+
+#[test]
+fn test_walk_keeps_root_style() {
+    let fs = tree();
+
+    let windows = CrossPath::new(r"C:\src").unwrap();
+    assert_eq!(
+        walked(CrossWalk::new_in(&fs, &windows)),
+        [
+            (r"C:\src", 0),
+            (r"C:\src\.hidden", 1),
+            (r"C:\src\app", 1),
+            (r"C:\src\app\lib", 2),
+            (r"C:\src\app\lib\mod.rs", 3),
+            (r"C:\src\app\main.rs", 2),
+            (r"C:\src\docs", 1),
+        ]
+        .map(|(path, depth)| (path.to_string(), depth))
+    );
+
+    // The same tree walked from its WSL path yields WSL paths
+    let config = PathConfig {
+        drive_mappings: DriveMap::from_pairs([("C:", "/mnt/c")]).unwrap(),
+        ..PathConfig::default()
+    };
+    let unix = CrossPath::with_config("/mnt/c/src", config).unwrap();
+    let entries: Vec<_> = CrossWalk::new_in(&fs, &unix)
+        .max_depth(1)
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[2].path.to_string_lossy(), "/mnt/c/src/app");
+    assert!(entries[2].attributes.is_directory);
+    assert!(entries[1].attributes.is_hidden);
+    assert_eq!(entries[1].attributes.size, 1);
+    assert_eq!(entries[3].path.to_windows().unwrap(), r"C:\src\docs");
+}
+
+#[test]
+fn test_walk_depth_links_and_errors() {
+    let fs = MemoryFileSystem::new();
+    fs.create_dir_all(Path::new("/data/a")).unwrap();
+    fs.write(Path::new("/data/a/file"), b"abc").unwrap();
+    fs.symlink("/data", "/data/a/loop").unwrap();
+    let root = CrossPath::new("/data").unwrap();
+
+    assert_eq!(
+        walked(CrossWalk::new_in(&fs, &root).max_depth(0)),
+        [("/data".to_string(), 0)]
+    );
+
+    // Links are reported but not followed by default
+    let entries: Vec<_> = CrossWalk::new_in(&fs, &root)
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(entries.len(), 4);
+    assert!(entries[3].is_symlink);
+    assert!(!entries[3].attributes.is_directory);
+
+    // Followed links that loop back are errors, not endless walks
+    let results: Vec<_> = CrossWalk::new_in(&fs, &root)
+        .follow_links(true)
+        .into_iter()
+        .collect();
+    assert_eq!(results.len(), 4);
+    assert!(results[3].is_err());
+
+    let missing = CrossPath::new("/missing").unwrap();
+    let results: Vec<_> = CrossWalk::new_in(&fs, &missing).into_iter().collect();
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+}
+
+#[test]
+fn test_walk_os_file_system() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("sub")).unwrap();
+    std::fs::write(dir.path().join("sub").join("x.txt"), b"hello").unwrap();
+
+    let root = CrossPath::from(dir.path().to_path_buf());
+    let entries: Vec<_> = CrossWalk::new(&root)
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[2].path.file_name(), Some("x.txt"));
+    assert_eq!(entries[2].attributes.size, 5);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_walk_parallel_matches_sequential() {
+    let fs = tree();
+    let root = CrossPath::new(r"C:\src").unwrap();
+    let sequential = walked(CrossWalk::new_in(&fs, &root));
+    let parallel: Vec<_> = CrossWalk::new_in(&fs, &root)
+        .collect_par()
+        .into_iter()
+        .map(|entry| {
+            let entry = entry.unwrap();
+            (entry.path.to_string_lossy().into_owned(), entry.depth)
+        })
+        .collect();
+    assert_eq!(parallel, sequential);
+    assert!(!fs.read_dir(Path::new(r"C:\src")).unwrap().is_empty());
+}
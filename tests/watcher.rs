@@ -0,0 +1,34 @@
+use cross_path::mapping::watcher::{MappingChange, MappingWatcher, mounted_drive_mappings};
+use cross_path::registry;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[test]
+fn test_watcher_reconciles_registered_mappings() {
+    let stale = ("Q:".to_string(), "/mnt/cross-path-not-mounted".to_string());
+    registry::set_drive_mappings(vec![stale.clone()]);
+
+    let changes: Arc<Mutex<Vec<MappingChange>>> = Arc::default();
+    let recorded = Arc::clone(&changes);
+    let watcher = MappingWatcher::start(Duration::from_secs(60), move |change| {
+        recorded.lock().unwrap().push(change.clone());
+    })
+    .unwrap();
+
+    // Unmounted entries are dropped and reported before `start` returns
+    let registered = registry::drive_mappings().unwrap();
+    assert!(!registered.contains(&stale));
+    for mapping in mounted_drive_mappings().unwrap() {
+        assert!(
+            registered
+                .iter()
+                .any(|(w, _)| w.eq_ignore_ascii_case(&mapping.0))
+        );
+    }
+    let changes = changes.lock().unwrap().clone();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].removed, vec![stale]);
+
+    watcher.stop();
+    registry::clear_drive_mappings();
+}